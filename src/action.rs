@@ -18,24 +18,29 @@
 //!
 //! ## System Actions
 //! - **Lifecycle**: `Tick`, `Render`, `Quit`, `Shutdown`, `Suspend`, `Resume`
-//! - **UI**: `Resize`, `Refresh`, `Error`
+//! - **UI**: `Resize`, `Refresh`, `Error`, `Status`, `CaptureUnavailable`, `CaptureStats`, `CaptureComplete`
 //!
 //! ## Navigation Actions
-//! - **Movement**: `Up`, `Down`, `Left`, `Right`
+//! - **Movement**: `Up`, `Down`, `Left`, `Right`, `Home`, `End`, `PageUp`, `PageDown`
 //! - **Tabs**: `Tab`, `TabChange`
-//! - **Modes**: `AppModeChange`, `ModeChange`
+//! - **Modes**: `AppModeChange`, `ModeChange`, `Submit`
 //!
 //! ## Network Actions
 //! - **Discovery**: `ScanCidr`, `PingIp`, `CountIp`, `CidrError`
 //! - **Ports**: `PortScan`, `PortScanDone`
-//! - **Packets**: `PacketDump`, `ArpRecieve`
+//! - **Packets**: `PacketDump`, `ArpRecieve`, `Ipv6NeighborDiscovered`, `SaveFilter`, `RecallFilter`, `TcpdumpCommand`,
+//!   `ShowDetail`, `DisableProtocol`, `EnableProtocols`
 //! - **WiFi**: `Scan`
-//! - **DNS**: `DnsResolved`
+//! - **DNS**: `DnsResolved`, `HostnameAnnounced`
+//! - **Anomalies**: `Anomaly`
 //!
 //! ## Data Actions
 //! - **Export**: `Export`, `ExportData`
-//! - **Interface**: `ActiveInterface`, `InterfaceSwitch`
-//! - **Toggles**: `GraphToggle`, `DumpToggle`, `Clear`
+//! - **Sessions**: `SaveSession`, `LoadSession`, `SessionLoaded`, `ReadPcap`
+//! - **Clipboard**: `CopySelection`, `Copy`
+//! - **Interface**: `ActiveInterface`, `InterfaceSwitch`, `InterfaceSwitchAll`, `InterfaceMultiToggle`,
+//!   `InterfaceCaptureToggle`, `AddInterface`, `RemoveInterface`, `ExtraInterfaces`
+//! - **Toggles**: `GraphToggle`, `DumpToggle`, `WrapToggle`, `FlowsToggle`, `FlowOrderCycle`, `TalkersToggle`, `FollowToggle`, `FlowColorsToggle`, `RelativeSeqToggle`, `TimeFormatCycle`, `TimeDeltaToggle`, `PresetCycle`, `HistoryGrow`, `HistoryShrink`, `Clear`
 //!
 //! # Message Flow Example
 //!
@@ -75,11 +80,17 @@ use serde::{
     Deserialize,
 };
 use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use crate::{
-    components::{packetdump::ArpPacketData, wifi_scan::WifiInfo},
-    enums::{ExportData, PacketTypeEnum, PacketsInfoTypesEnum, TabsEnum},
+    components::{
+        packetdump::{ArpPacketData, CaptureSummary, Ipv6NeighborData},
+        wifi_scan::WifiInfo,
+    },
+    enums::{Anomaly, ExportData, PacketTypeEnum, PacketsInfoTypesEnum, TabsEnum},
     mode::Mode,
+    session::Session,
 };
 
 /// Actions represent all possible messages that can flow through the application.
@@ -110,10 +121,24 @@ pub enum Action {
     Shutdown,
     /// Refresh UI (currently unused)
     Refresh,
-    /// Fatal error occurred, display message and quit
+    /// Non-fatal error, shown on the status line until it times out or a key dismisses it
     Error(String),
-    /// Show help information (currently unused)
+    /// Short-lived message on the status line, e.g. after copying
+    Status(String),
+    /// Raw packet capture can't be opened, capture features run degraded
+    CaptureUnavailable(String),
+    /// Capture thread stopped at one of the `capture.max_*` limits
+    CaptureComplete(CaptureSummary),
+    /// Kernel receive counters of the capture interface since capture start
+    CaptureStats {
+        received: u64,
+        dropped: u64,
+        iface: String,
+    },
+    /// Toggle the keybinding help overlay
     Help,
+    /// A detector flagged something, collected on the Anomalies tab
+    Anomaly(Anomaly),
 
     // -- Navigation and UI actions
     /// Move selection up in lists
@@ -124,6 +149,18 @@ pub enum Action {
     Left,
     /// Navigate right (currently unused)
     Right,
+    /// Copy the selected row, or the open packet detail, to the clipboard
+    CopySelection,
+    /// Put text on the clipboard through the terminal
+    Copy(String),
+    /// Select the first row, the newest packet in the packet log
+    Home,
+    /// Select the last row, the oldest packet in the packet log
+    End,
+    /// Move the selection up by the rows the table shows
+    PageUp,
+    /// Move the selection down by the rows the table shows
+    PageDown,
     /// Cycle to next tab
     Tab,
     /// Jump to specific tab
@@ -132,16 +169,67 @@ pub enum Action {
     GraphToggle,
     /// Toggle packet dump display
     DumpToggle,
+    /// Toggle line wrapping of long packet log rows
+    WrapToggle,
+    /// Switch the Packets tab between the packet log and the flows view
+    FlowsToggle,
+    /// Sort the flows view by recent activity or by bytes
+    FlowOrderCycle,
+    /// Switch the Packets tab to the traffic per source address
+    TalkersToggle,
+    /// Keep the newest packet selected as packets arrive
+    FollowToggle,
+    /// Color packet log badges by conversation instead of by protocol
+    FlowColorsToggle,
+    /// Switch TCP rows between relative and absolute sequence numbers
+    RelativeSeqToggle,
+    /// Show packet times with milliseconds, with the date or as epoch seconds
+    TimeFormatCycle,
+    /// Show the gap to the previous packet log row instead of the time
+    TimeDeltaToggle,
+    /// Cycle through the packet filter presets
+    PresetCycle,
+    /// Double the number of packets kept per packet type
+    HistoryGrow,
+    /// Halve the number of packets kept per packet type, dropping the oldest
+    HistoryShrink,
+    /// Save the current packet filter under a name
+    SaveFilter,
+    /// Pick a saved packet filter from a list
+    RecallFilter,
+    /// Show the packet filter as an equivalent tcpdump command
+    TcpdumpCommand,
+    /// Show the decoded fields and bytes of the selected packet
+    ShowDetail,
+    /// Stop decoding and storing the selected packet type
+    DisableProtocol,
+    /// Decode and store every packet type again
+    EnableProtocols,
     /// Switch to next network interface
     InterfaceSwitch,
+    /// Switch to next network interface, including down and loopback ones
+    InterfaceSwitchAll,
+    /// Pick interfaces to capture on next to the active one
+    InterfaceMultiToggle,
+    /// Capture on the picked interface too, or stop capturing on it
+    InterfaceCaptureToggle,
 
     // -- Network discovery and scanning
     /// Start CIDR network scan (triggered by 's' key)
     ScanCidr,
     /// Set the active network interface for capture
     ActiveInterface(NetworkInterface),
+    /// Also capture on an interface, next to the active one
+    AddInterface(NetworkInterface),
+    /// Stop capturing on an interface added next to the active one
+    RemoveInterface(String),
+    /// Interfaces captured on next to the active one, sent by the packet dump
+    /// whenever a capture starts or stops
+    ExtraInterfaces(Vec<String>),
     /// ARP packet received (from packet capture)
     ArpRecieve(ArpPacketData),
+    /// ICMPv6 Neighbor Discovery binding received (from packet capture)
+    Ipv6NeighborDiscovered(Ipv6NeighborData),
     /// WiFi scan results ready
     Scan(Vec<WifiInfo>),
 
@@ -150,6 +238,8 @@ pub enum Action {
     AppModeChange(Mode),
     /// Change component-specific mode
     ModeChange(Mode),
+    /// Submit the value of the focused input field
+    Submit,
 
     // -- Host discovery
     /// Ping response received for IP address
@@ -160,6 +250,8 @@ pub enum Action {
     CidrError,
     /// DNS reverse lookup completed (IP, Hostname)
     DnsResolved(String, String),
+    /// Hostname announced over mDNS in the packet capture (IP, Hostname)
+    HostnameAnnounced(String, String),
     /// MAC address discovered for IP (IP, MAC)
     UpdateMac(String, String),
 
@@ -180,6 +272,14 @@ pub enum Action {
     Export,
     /// Export data ready for writing
     ExportData(ExportData),
+    /// Save all captured and scanned data to a session file
+    SaveSession(PathBuf),
+    /// Load a session file for offline inspection
+    LoadSession(PathBuf),
+    /// Session file read, components replace their data with it
+    SessionLoaded(Arc<Session>),
+    /// Replay a pcap file in the packets tab instead of capturing live
+    ReadPcap(PathBuf),
 }
 
 impl<'de> Deserialize<'de> for Action {
@@ -204,21 +304,51 @@ impl<'de> Deserialize<'de> for Action {
                     // -- custom actions
                     "InputMode" => Ok(Action::ModeChange(Mode::Input)),
                     "NormalMode" => Ok(Action::ModeChange(Mode::Normal)),
+                    "Submit" => Ok(Action::Submit),
                     "Graph" => Ok(Action::GraphToggle),
                     "Dump" => Ok(Action::DumpToggle),
+                    "Wrap" => Ok(Action::WrapToggle),
+                    "Flows" => Ok(Action::FlowsToggle),
+                    "FlowOrder" => Ok(Action::FlowOrderCycle),
+                    "Talkers" => Ok(Action::TalkersToggle),
+                    "Follow" => Ok(Action::FollowToggle),
+                    "FlowColors" => Ok(Action::FlowColorsToggle),
+                    "RelativeSeq" => Ok(Action::RelativeSeqToggle),
+                    "TimeFormat" => Ok(Action::TimeFormatCycle),
+                    "TimeDelta" => Ok(Action::TimeDeltaToggle),
+                    "Preset" => Ok(Action::PresetCycle),
+                    "HistoryGrow" => Ok(Action::HistoryGrow),
+                    "HistoryShrink" => Ok(Action::HistoryShrink),
+                    "SaveFilter" => Ok(Action::SaveFilter),
+                    "RecallFilter" => Ok(Action::RecallFilter),
+                    "Tcpdump" => Ok(Action::TcpdumpCommand),
+                    "Detail" => Ok(Action::ShowDetail),
+                    "DisableProtocol" => Ok(Action::DisableProtocol),
+                    "EnableProtocols" => Ok(Action::EnableProtocols),
                     "Interface" => Ok(Action::InterfaceSwitch),
+                    "InterfaceAll" => Ok(Action::InterfaceSwitchAll),
+                    "InterfaceMulti" => Ok(Action::InterfaceMultiToggle),
+                    "InterfaceCapture" => Ok(Action::InterfaceCaptureToggle),
                     "Scan" => Ok(Action::ScanCidr),
                     "Clear" => Ok(Action::Clear),
                     "Up" => Ok(Action::Up),
                     "Down" => Ok(Action::Down),
                     "Left" => Ok(Action::Left),
                     "Right" => Ok(Action::Right),
+                    "Home" => Ok(Action::Home),
+                    "Copy" => Ok(Action::CopySelection),
+                    "End" => Ok(Action::End),
+                    "PageUp" => Ok(Action::PageUp),
+                    "PageDown" => Ok(Action::PageDown),
                     "Tab" => Ok(Action::Tab),
                     "Export" => Ok(Action::Export),
+                    "SaveSession" => Ok(Action::SaveSession(Session::default_path())),
+                    "LoadSession" => Ok(Action::LoadSession(Session::default_path())),
                     "JumpDiscovery" => Ok(Action::TabChange(TabsEnum::Discovery)),
                     "JumpPackets" => Ok(Action::TabChange(TabsEnum::Packets)),
                     "JumpPorts" => Ok(Action::TabChange(TabsEnum::Ports)),
                     "JumpSniffer" => Ok(Action::TabChange(TabsEnum::Traffic)),
+                    "JumpAnomalies" => Ok(Action::TabChange(TabsEnum::Anomalies)),
 
                     // -- default actions
                     "Tick" => Ok(Action::Tick),
@@ -232,6 +362,14 @@ impl<'de> Deserialize<'de> for Action {
                         let error_msg = data.trim_start_matches("Error(").trim_end_matches(')');
                         Ok(Action::Error(error_msg.to_string()))
                     }
+                    data if data.starts_with("SaveSession(") => {
+                        let path = data.trim_start_matches("SaveSession(").trim_end_matches(')');
+                        Ok(Action::SaveSession(PathBuf::from(path)))
+                    }
+                    data if data.starts_with("LoadSession(") => {
+                        let path = data.trim_start_matches("LoadSession(").trim_end_matches(')');
+                        Ok(Action::LoadSession(PathBuf::from(path)))
+                    }
                     data if data.starts_with("Resize(") => {
                         let parts: Vec<&str> = data
                             .trim_start_matches("Resize(")
@@ -45,33 +45,39 @@
 //!
 //! 1. **Event Collection**: Wait for terminal events (keyboard, resize, ticks)
 //! 2. **Action Generation**: Convert events to actions via keybindings
-//! 3. **Action Distribution**: Route actions to all components
+//! 3. **Action Distribution**: Route actions to all components; actions from the
+//!    terminal and the components go first, then what queued up in the channel
 //! 4. **State Update**: Components update their state based on actions
 //! 5. **Rendering**: Components draw themselves to the terminal
 //!
 //! # Memory Management
 //!
 //! The application uses **bounded channels** (capacity 1000) for action messages
-//! to prevent memory exhaustion. If consumers are slow, senders will block
-//! rather than accumulating unbounded messages.
+//! to prevent memory exhaustion. If consumers are slow, messages are dropped
+//! rather than accumulating unbounded; drops are counted in [`ChannelPressure`]
+//! and shown as a throttled warning on the status line.
 //!
 //! For data export, [`Arc`] is used to share large datasets (scanned IPs, packets)
 //! without cloning, significantly reducing memory usage during export operations.
 
 use chrono::{DateTime, Local};
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Result};
 use crossterm::event::KeyEvent;
-use ratatui::prelude::Rect;
+use ratatui::prelude::{Rect, Size};
 use std::sync::Arc;
-use tokio::sync::mpsc::{self, Receiver, Sender};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
 
 use crate::{
     action::Action,
     components::{
+        anomalies::Anomalies,
         discovery::{Discovery, ScannedIp},
         export::Export,
-        interfaces::Interfaces,
-        packetdump::PacketDump,
+        help::Help,
+        interfaces::{InterfaceSelect, Interfaces},
+        packetdump::{PacketDump, TimeFormat},
         ports::{Ports, ScannedIpPorts},
         tabs::Tabs,
         title::Title,
@@ -84,9 +90,39 @@ use crate::{
     config::Config,
     enums::{ExportData, PacketTypeEnum, PacketsInfoTypesEnum},
     mode::Mode,
-    tui,
+    privilege,
+    session::{Session, SESSION_VERSION},
+    tui, utils,
 };
 
+/// A full channel is reported at most this often.
+const CHANNEL_FULL_WARN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Counts actions and terminal events lost to full channels, so a packet storm
+/// slowing the UI down gets one throttled warning instead of passing silently.
+#[derive(Default)]
+pub struct ChannelPressure {
+    dropped: u64,
+    last_warning: Option<Instant>,
+}
+
+impl ChannelPressure {
+    pub fn record(&mut self, dropped: u64) {
+        self.dropped += dropped;
+    }
+
+    /// Drops since the last warning, at most once per `CHANNEL_FULL_WARN_INTERVAL`.
+    pub fn take_warning(&mut self) -> Option<u64> {
+        if self.dropped == 0
+            || self.last_warning.is_some_and(|t| t.elapsed() < CHANNEL_FULL_WARN_INTERVAL)
+        {
+            return None;
+        }
+        self.last_warning = Some(Instant::now());
+        Some(std::mem::take(&mut self.dropped))
+    }
+}
+
 /// The main application coordinator.
 ///
 /// This struct owns all components and manages the application lifecycle,
@@ -95,8 +131,8 @@ use crate::{
 /// # Fields
 ///
 /// * `config` - Application configuration loaded from config files
-/// * `tick_rate` - Logic update rate in Hz (currently fixed at 1.0)
-/// * `frame_rate` - UI render rate in Hz (currently fixed at 10.0)
+/// * `tick_rate` - Logic update rate in Hz
+/// * `frame_rate` - UI render rate in Hz
 /// * `components` - All UI components implementing the Component trait
 /// * `should_quit` - Signal to exit the main loop
 /// * `should_suspend` - Signal to suspend the application (Unix SIGTSTP)
@@ -105,6 +141,9 @@ use crate::{
 /// * `action_tx` - Sender half of the action channel
 /// * `action_rx` - Receiver half of the action channel
 /// * `post_exist_msg` - Optional error message to display after exit
+/// * `capture_available` - Whether a raw capture handle could be opened at startup
+/// * `last_activity` - Last key press or captured packet, drives the low-power frame rate
+/// * `channel_pressure` - Actions and events dropped because a channel was full
 pub struct App {
     pub config: Config,
     pub tick_rate: f64,
@@ -117,6 +156,9 @@ pub struct App {
     pub action_tx: Sender<Action>,
     pub action_rx: Receiver<Action>,
     pub post_exist_msg: Option<String>,
+    pub capture_available: bool,
+    pub last_activity: Instant,
+    pub channel_pressure: ChannelPressure,
 }
 
 impl App {
@@ -128,8 +170,12 @@ impl App {
     ///
     /// # Arguments
     ///
-    /// * `_tick_rate` - Requested logic update rate (currently unused, fixed at 1.0 Hz)
-    /// * `_frame_rate` - Requested render rate (currently unused, fixed at 10.0 Hz)
+    /// * `tick_rate` - Logic update rate, `ui.tick_rate` from the config when `None`
+    /// * `frame_rate` - Render rate, `ui.frame_rate` from the config when `None`
+    /// * `interface_select` - How the capture interface is picked at startup
+    /// * `filter` - Packet filter to start with
+    /// * `stream_json` - Write captured packets to stdout as JSON lines, only
+    ///   with [`App::run_headless`] as the TUI draws to stdout too
     ///
     /// # Returns
     ///
@@ -141,22 +187,31 @@ impl App {
     ///
     /// ```no_run
     /// use netscanner::app::App;
+    /// use netscanner::components::interfaces::InterfaceSelect;
     ///
-    /// let app = App::new(2.0, 30.0)?;
+    /// let app = App::new(Some(2.0), Some(30.0), InterfaceSelect::Auto, None, false)?;
     /// # Ok::<(), color_eyre::eyre::Error>(())
     /// ```
-    pub fn new(_tick_rate: f64, _frame_rate: f64) -> Result<Self> {
+    pub fn new(
+        tick_rate: Option<f64>,
+        frame_rate: Option<f64>,
+        interface_select: InterfaceSelect,
+        filter: Option<String>,
+        stream_json: bool,
+    ) -> Result<Self> {
         let title = Title::new();
-        let interfaces = Interfaces::default();
+        let interfaces = Interfaces::default().with_select(interface_select);
         let wifiscan = WifiScan::default();
         let wifi_interface = WifiInterface::default();
         let wifi_chart = WifiChart::default();
         let tabs = Tabs::default();
         let discovery = Discovery::default();
-        let packetdump = PacketDump::default();
+        let packetdump = PacketDump::default().with_filter(filter).with_stream_json(stream_json);
         let ports = Ports::default();
         let sniff = Sniffer::default();
+        let anomalies = Anomalies::default();
         let export = Export::default();
+        let help = Help::new();
         let config = Config::new()?;
 
         let mode = Mode::Normal;
@@ -165,8 +220,8 @@ impl App {
         let (action_tx, action_rx) = mpsc::channel(1000);
 
         Ok(Self {
-            tick_rate: 1.0,
-            frame_rate: 10.0,
+            tick_rate: tick_rate.unwrap_or(config.ui.tick_rate),
+            frame_rate: frame_rate.unwrap_or(config.ui.frame_rate),
             components: vec![
                 Box::new(title),
                 Box::new(interfaces),
@@ -178,7 +233,9 @@ impl App {
                 Box::new(packetdump),
                 Box::new(ports),
                 Box::new(sniff),
+                Box::new(anomalies),
                 Box::new(export),
+                Box::new(help),
             ],
             should_quit: false,
             should_suspend: false,
@@ -188,9 +245,100 @@ impl App {
             action_tx,
             action_rx,
             post_exist_msg: None,
+            capture_available: privilege::can_capture(),
+            last_activity: Instant::now(),
+            channel_pressure: ChannelPressure::default(),
         })
     }
 
+    /// Gathers scan results and captured packets into a session snapshot, using
+    /// the same component downcasting as the export handler.
+    fn collect_session(components: &[Box<dyn Component>]) -> Session {
+        let mut session = Session {
+            version: SESSION_VERSION,
+            saved_at: Local::now(),
+            interface: None,
+            scanned_ips: Vec::new(),
+            scanned_ports: Vec::new(),
+            packets: Vec::new(),
+        };
+        for component in components {
+            if let Some(d) = component.as_any().downcast_ref::<Discovery>() {
+                session.scanned_ips = d.get_scanned_ips().to_vec();
+            } else if let Some(pd) = component.as_any().downcast_ref::<PacketDump>() {
+                session.interface = pd.get_active_interface().map(|i| i.name.clone());
+                session.packets = pd.session_packets();
+            } else if let Some(p) = component.as_any().downcast_ref::<Ports>() {
+                session.scanned_ports = p.get_scanned_ports().to_vec();
+            }
+        }
+        session
+    }
+
+    /// `try_send` that counts an action lost to a full channel instead of
+    /// failing, only a closed channel is an error.
+    fn send_action(
+        action_tx: &Sender<Action>,
+        pressure: &mut ChannelPressure,
+        action: Action,
+    ) -> Result<()> {
+        match action_tx.try_send(action) {
+            Err(TrySendError::Full(_)) => {
+                pressure.record(1);
+                Ok(())
+            }
+            result => Ok(result?),
+        }
+    }
+
+    /// Calls `shutdown()` on each component, giving up on the rest once five
+    /// seconds have passed.
+    fn shutdown_components(components: &mut [Box<dyn Component>]) {
+        // Shutdown each component with timeout
+        let shutdown_start = std::time::Instant::now();
+        let total_timeout = std::time::Duration::from_secs(5);
+
+        for (idx, component) in components.iter_mut().enumerate() {
+            let elapsed = shutdown_start.elapsed();
+            if elapsed >= total_timeout {
+                log::warn!(
+                    "Shutdown timeout reached, forcing termination for remaining components"
+                );
+                break;
+            }
+
+            log::debug!("Shutting down component {}", idx);
+
+            // Shutdown with timeout
+            let shutdown_result = std::panic::catch_unwind(
+                std::panic::AssertUnwindSafe(|| component.shutdown())
+            );
+
+            match shutdown_result {
+                Ok(Ok(())) => {
+                    log::debug!("Component {} shutdown successfully", idx);
+                }
+                Ok(Err(e)) => {
+                    log::error!("Component {} shutdown failed: {:?}", idx, e);
+                }
+                Err(_) => {
+                    log::error!("Component {} panicked during shutdown", idx);
+                }
+            }
+        }
+
+        log::info!("All components shutdown complete");
+    }
+
+    /// Render rate while idle; the normal rate when low-power mode is off.
+    fn idle_frame_rate(config: &Config, frame_rate: f64) -> f64 {
+        if config.ui.low_power {
+            config.ui.idle_frame_rate.min(frame_rate)
+        } else {
+            frame_rate
+        }
+    }
+
     /// Runs the main application event loop.
     ///
     /// This is the heart of the application, coordinating all components through
@@ -211,6 +359,7 @@ impl App {
     /// - **Action Processing**: Route actions to all components via `update()`
     /// - **Special Actions**:
     ///   - `Action::Export`: Collect data from all components using Arc for efficiency
+    ///   - `Action::SaveSession` / `Action::LoadSession`: Write or read a session snapshot
     ///   - `Action::Resize`: Trigger re-render with new terminal dimensions
     ///   - `Action::Render`: Draw all components to the terminal
     ///   - `Action::Quit`: Initiate graceful shutdown sequence
@@ -234,10 +383,13 @@ impl App {
     ///
     /// # Error Handling
     ///
-    /// Render errors are caught and converted to `Action::Error`, which:
-    /// - Sets `should_quit` to true
-    /// - Stores an error message in `post_exist_msg`
-    /// - Allows graceful shutdown and error reporting
+    /// Render errors are fatal, they:
+    /// - Set `should_quit` to true
+    /// - Store an error message in `post_exist_msg`
+    /// - Allow graceful shutdown and error reporting
+    ///
+    /// `Action::Error` from components (e.g. a capture thread failing to open its
+    /// channel) is not fatal and is shown on the status line by the `Title` component.
     ///
     /// # Errors
     ///
@@ -251,10 +403,11 @@ impl App {
     ///
     /// ```no_run
     /// use netscanner::app::App;
+    /// use netscanner::components::interfaces::InterfaceSelect;
     ///
     /// #[tokio::main]
     /// async fn main() -> color_eyre::eyre::Result<()> {
-    ///     let mut app = App::new(1.0, 10.0)?;
+    ///     let mut app = App::new(None, None, InterfaceSelect::Default, None, false)?;
     ///     app.run().await?;
     ///     Ok(())
     /// }
@@ -266,7 +419,8 @@ impl App {
 
         let mut tui = tui::Tui::new()?
             .tick_rate(self.tick_rate)
-            .frame_rate(self.frame_rate);
+            .frame_rate(self.frame_rate)
+            .idle_frame_rate(Self::idle_frame_rate(&self.config, self.frame_rate));
         // tui.mouse(true);
         tui.enter()?;
 
@@ -282,18 +436,29 @@ impl App {
             component.init(tui.size()?)?;
         }
 
+        // -- run without packet capture rather than failing once a capture thread starts
+        if !self.capture_available {
+            action_tx.try_send(Action::CaptureUnavailable(privilege::get_privilege_error_message()))?;
+        }
+
+        // -- actions from the terminal and the components reacting to them, handled
+        // ahead of the action channel so ticks, renders and keys aren't stuck
+        // behind packets or lost when it is full
+        let mut ui_actions: VecDeque<Action> = VecDeque::new();
         loop {
             if let Some(e) = tui.next().await {
                 match e {
-                    tui::Event::Quit => action_tx.try_send(Action::Quit)?,
-                    tui::Event::Tick => action_tx.try_send(Action::Tick)?,
-                    tui::Event::Render => action_tx.try_send(Action::Render)?,
-                    tui::Event::Resize(x, y) => action_tx.try_send(Action::Resize(x, y))?,
+                    tui::Event::Quit => ui_actions.push_back(Action::Quit),
+                    tui::Event::Tick => ui_actions.push_back(Action::Tick),
+                    tui::Event::Render => ui_actions.push_back(Action::Render),
+                    tui::Event::Resize(x, y) => ui_actions.push_back(Action::Resize(x, y)),
                     tui::Event::Key(key) => {
+                        self.last_activity = Instant::now();
+                        tui.set_idle(false);
                         if let Some(keymap) = self.config.keybindings.get(&self.mode) {
                             if let Some(action) = keymap.get(&vec![key]) {
                                 log::info!("Got action: {action:?}");
-                                action_tx.try_send(action.clone())?;
+                                ui_actions.push_back(action.clone());
                             } else {
                                 // If the key was not handled as a single key action,
                                 // then consider it for multi-key combinations.
@@ -302,7 +467,7 @@ impl App {
                                 // Check for multi-key combinations
                                 if let Some(action) = keymap.get(&self.last_tick_key_events) {
                                     log::info!("Got action: {action:?}");
-                                    action_tx.try_send(action.clone())?;
+                                    ui_actions.push_back(action.clone());
                                 }
                             }
                         };
@@ -311,12 +476,26 @@ impl App {
                 }
                 for component in self.components.iter_mut() {
                     if let Some(action) = component.handle_events(Some(e.clone()))? {
-                        action_tx.try_send(action)?;
+                        ui_actions.push_back(action);
                     }
                 }
             }
 
-            while let Ok(action) = action_rx.try_recv() {
+            // -- only what was queued by now, a packet storm refilling the channel
+            // would otherwise keep the loop from getting back to the terminal
+            let mut backlog = action_rx.len();
+            loop {
+                let action = match ui_actions.pop_front() {
+                    Some(action) => action,
+                    None if backlog > 0 => {
+                        backlog -= 1;
+                        match action_rx.try_recv() {
+                            Ok(action) => action,
+                            Err(_) => break,
+                        }
+                    }
+                    None => break,
+                };
                 if action != Action::Tick && action != Action::Render {
                     log::debug!("{action:?}");
                 }
@@ -325,11 +504,6 @@ impl App {
                         self.mode = mode;
                     }
 
-                    Action::Error(ref err_msg) => {
-                        self.post_exist_msg = Some(err_msg.to_string());
-                        self.should_quit = true;
-                    }
-
                     Action::Export => {
                         // Collect data from components using Arc for memory-efficient sharing.
                         // Only Arc pointers are cloned, not the actual data, significantly
@@ -341,6 +515,14 @@ impl App {
                         let mut tcp_packets = Arc::new(Vec::new());
                         let mut icmp_packets = Arc::new(Vec::new());
                         let mut icmp6_packets = Arc::new(Vec::new());
+                        let mut igmp_packets = Arc::new(Vec::new());
+                        let mut other_packets = Arc::new(Vec::new());
+                        let mut anomalies = Arc::new(Vec::new());
+                        let mut flows = Arc::new(Vec::new());
+                        let mut interface = None;
+                        let mut time_format = TimeFormat::default();
+                        let mut dropped = 0;
+                        let mut kernel_dropped = None;
 
                         // Note: Component downcasting pattern used here for data aggregation.
                         // While this creates coupling between App and specific component types,
@@ -361,8 +543,16 @@ impl App {
                                 tcp_packets = Arc::new(pd.clone_array_by_packet_type(PacketTypeEnum::Tcp));
                                 icmp_packets = Arc::new(pd.clone_array_by_packet_type(PacketTypeEnum::Icmp));
                                 icmp6_packets = Arc::new(pd.clone_array_by_packet_type(PacketTypeEnum::Icmp6));
+                                igmp_packets = Arc::new(pd.clone_array_by_packet_type(PacketTypeEnum::Igmp));
+                                other_packets = Arc::new(pd.clone_array_by_packet_type(PacketTypeEnum::Other));
+                                flows = Arc::new(pd.get_flows());
+                                interface = pd.get_active_interface().map(|i| i.name.clone());
+                                time_format = pd.get_time_format();
+                                (dropped, kernel_dropped) = pd.get_capture_drops();
                             } else if let Some(p) = component.as_any().downcast_ref::<Ports>() {
                                 scanned_ports = Arc::new(p.get_scanned_ports().to_vec());
+                            } else if let Some(a) = component.as_any().downcast_ref::<Anomalies>() {
+                                anomalies = Arc::new(a.get_anomalies());
                             }
                         }
                         if let Err(e) = action_tx.try_send(Action::ExportData(ExportData {
@@ -373,117 +563,167 @@ impl App {
                             tcp_packets,
                             icmp_packets,
                             icmp6_packets,
+                            igmp_packets,
+                            other_packets,
+                            anomalies,
+                            flows,
+                            interface,
+                            time_format,
+                            dropped,
+                            kernel_dropped,
                         })) {
                             log::error!("Failed to send export data action: {:?}", e);
                         }
                     }
 
+                    Action::SaveSession(ref path) => {
+                        let session = Self::collect_session(&self.components);
+                        match session.save(path) {
+                            Ok(()) => log::info!("Session saved to {}", path.display()),
+                            Err(e) => {
+                                let _ = action_tx.try_send(Action::Error(format!(
+                                    "Failed to save session to {}: {}",
+                                    path.display(),
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                    Action::LoadSession(ref path) => match Session::load(path) {
+                        Ok(session) => {
+                            let _ = action_tx.try_send(Action::SessionLoaded(Arc::new(session)));
+                        }
+                        Err(e) => {
+                            let _ = action_tx.try_send(Action::Error(format!(
+                                "Failed to load session from {}: {}",
+                                path.display(),
+                                e
+                            )));
+                        }
+                    },
+
+                    Action::Copy(ref text) => match tui.copy(text) {
+                        Ok(tool) => {
+                            let mut status = format!("copied {} characters", text.chars().count());
+                            if let Some(tool) = tool {
+                                status.push_str(&format!(" with {}", tool));
+                            }
+                            ui_actions.push_back(Action::Status(status));
+                        }
+                        Err(e) => {
+                            let _ = action_tx.try_send(Action::Error(format!(
+                                "Failed to copy to the clipboard: {}",
+                                e
+                            )));
+                        }
+                    },
+
                     Action::Tick => {
                         self.last_tick_key_events.drain(..);
+                        let idle_after = Duration::from_secs(self.config.ui.idle_after_secs);
+                        tui.set_idle(self.last_activity.elapsed() > idle_after);
+                    }
+                    Action::PacketDump(..) => {
+                        self.last_activity = Instant::now();
+                        tui.set_idle(false);
                     }
                     Action::Quit => self.should_quit = true,
                     Action::Suspend => self.should_suspend = true,
                     Action::Resume => self.should_suspend = false,
                     Action::Resize(w, h) => {
                         tui.resize(Rect::new(0, 0, w, h))?;
+                        let mut render_error = None;
                         tui.draw(|f| {
                             for (idx, component) in self.components.iter_mut().enumerate() {
                                 let r = component.draw(f, f.area());
                                 if let Err(e) = r {
-                                    let _ = action_tx.try_send(Action::Error(format!(
+                                    render_error = Some(format!(
                                         "Failed to render component {} during terminal resize ({}x{}).\n\
                                         \n\
                                         Error: {:?}\n\
                                         \n\
                                         The application will now exit to prevent further issues.",
                                         idx, w, h, e
-                                    )));
+                                    ));
                                 }
                             }
+                            if !*utils::COLOR_ENABLED {
+                                utils::strip_colors(f.buffer_mut());
+                            }
                         })?;
+                        if render_error.is_some() {
+                            self.post_exist_msg = render_error;
+                            self.should_quit = true;
+                        }
                     }
                     Action::Render => {
+                        let mut render_error = None;
                         tui.draw(|f| {
                             for (idx, component) in self.components.iter_mut().enumerate() {
                                 let r = component.draw(f, f.area());
                                 if let Err(e) = r {
-                                    let _ = action_tx.try_send(Action::Error(format!(
+                                    render_error = Some(format!(
                                         "Failed to render component {} during frame update.\n\
                                         \n\
                                         Error: {:?}\n\
                                         \n\
                                         The application will now exit to prevent further issues.",
                                         idx, e
-                                    )));
+                                    ));
                                 }
                             }
+                            if !*utils::COLOR_ENABLED {
+                                utils::strip_colors(f.buffer_mut());
+                            }
                         })?;
+                        if render_error.is_some() {
+                            self.post_exist_msg = render_error;
+                            self.should_quit = true;
+                        }
                     }
                     _ => {}
                 }
                 for component in self.components.iter_mut() {
                     if let Some(action) = component.update(action.clone())? {
-                        action_tx.try_send(action)?
+                        ui_actions.push_back(action);
                     };
                 }
             }
+
+            self.channel_pressure.record(tui.take_dropped());
+            if let Some(dropped) = self.channel_pressure.take_warning() {
+                log::warn!("{} actions/events dropped, the event loop can't keep up", dropped);
+                ui_actions.push_back(Action::Error(format!(
+                    "UI falling behind: {} events dropped, packet capture may be too busy",
+                    dropped
+                )));
+            }
+
             if self.should_suspend {
                 tui.suspend()?;
-                action_tx.try_send(Action::Resume)?;
+                ui_actions.push_back(Action::Resume);
                 tui = tui::Tui::new()?
                     .tick_rate(self.tick_rate)
-                    .frame_rate(self.frame_rate);
+                    .frame_rate(self.frame_rate)
+                    .idle_frame_rate(Self::idle_frame_rate(&self.config, self.frame_rate));
                 // tui.mouse(true);
                 tui.enter()?;
             } else if self.should_quit {
                 log::info!("Application shutting down, initiating graceful shutdown sequence");
 
-                // Send shutdown action to all components
-                action_tx.try_send(Action::Shutdown)?;
-
-                // Process any pending actions
-                while let Ok(action) = action_rx.try_recv() {
+                // Process any pending actions, the shutdown first; a channel
+                // full of packets must not keep it from the components
+                let pending = std::iter::once(Action::Shutdown)
+                    .chain(std::iter::from_fn(|| action_rx.try_recv().ok()));
+                for action in pending {
                     for component in self.components.iter_mut() {
                         if let Some(action) = component.update(action.clone())? {
-                            action_tx.try_send(action)?;
-                        }
-                    }
-                }
-
-                // Shutdown each component with timeout
-                let shutdown_start = std::time::Instant::now();
-                let total_timeout = std::time::Duration::from_secs(5);
-
-                for (idx, component) in self.components.iter_mut().enumerate() {
-                    let elapsed = shutdown_start.elapsed();
-                    if elapsed >= total_timeout {
-                        log::warn!(
-                            "Shutdown timeout reached, forcing termination for remaining components"
-                        );
-                        break;
-                    }
-
-                    log::debug!("Shutting down component {}", idx);
-
-                    // Shutdown with timeout
-                    let shutdown_result = std::panic::catch_unwind(
-                        std::panic::AssertUnwindSafe(|| component.shutdown())
-                    );
-
-                    match shutdown_result {
-                        Ok(Ok(())) => {
-                            log::debug!("Component {} shutdown successfully", idx);
-                        }
-                        Ok(Err(e)) => {
-                            log::error!("Component {} shutdown failed: {:?}", idx, e);
-                        }
-                        Err(_) => {
-                            log::error!("Component {} panicked during shutdown", idx);
+                            Self::send_action(action_tx, &mut self.channel_pressure, action)?;
                         }
                     }
                 }
 
-                log::info!("All components shutdown complete");
+                Self::shutdown_components(&mut self.components);
 
                 tui.stop()?;
                 break;
@@ -498,4 +738,90 @@ impl App {
 
         Ok(())
     }
+
+    /// Runs without the terminal UI, for `--headless`.
+    ///
+    /// Components get the same actions as under [`App::run`] minus keys and
+    /// rendering: ticks come from a timer, Ctrl-C or a capture limit quits. Stdout carries only
+    /// the packet stream, errors the status line would show go to stderr.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if packets can't be captured, there would be nothing
+    /// to stream.
+    pub async fn run_headless(&mut self) -> Result<()> {
+        if !self.capture_available {
+            return Err(eyre!(privilege::get_privilege_error_message()));
+        }
+        let action_tx = self.action_tx.clone();
+
+        for component in self.components.iter_mut() {
+            component.register_action_handler(action_tx.clone())?;
+        }
+
+        for component in self.components.iter_mut() {
+            component.register_config_handler(self.config.clone())?;
+        }
+
+        // -- nothing is drawn, components only get a nominal size
+        for component in self.components.iter_mut() {
+            component.init(Size::new(80, 24))?;
+        }
+
+        let mut tick = tokio::time::interval(Duration::from_secs_f64(1.0 / self.tick_rate));
+        let ctrl_c = tokio::signal::ctrl_c();
+        tokio::pin!(ctrl_c);
+        loop {
+            let action = tokio::select! {
+                _ = tick.tick() => Action::Tick,
+                _ = &mut ctrl_c => Action::Quit,
+                action = self.action_rx.recv() => match action {
+                    Some(action) => action,
+                    None => break,
+                },
+            };
+            match action {
+                Action::Error(ref msg) => eprintln!("{}", msg),
+                Action::CaptureComplete(ref summary) => {
+                    eprintln!("capture complete: {}", summary);
+                    self.should_quit = true;
+                }
+                Action::Quit => self.should_quit = true,
+                _ => {}
+            }
+            for component in self.components.iter_mut() {
+                if let Some(action) = component.update(action.clone())? {
+                    Self::send_action(&action_tx, &mut self.channel_pressure, action)?;
+                };
+            }
+            if let Some(dropped) = self.channel_pressure.take_warning() {
+                log::warn!("{} actions dropped, the event loop can't keep up", dropped);
+            }
+            if self.should_quit {
+                log::info!("Stream stopped, shutting down");
+                Self::shutdown_components(&mut self.components);
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_channel_drops_instead_of_failing() {
+        let (action_tx, mut action_rx) = mpsc::channel(1);
+        let mut pressure = ChannelPressure::default();
+        App::send_action(&action_tx, &mut pressure, Action::Tick).unwrap();
+        App::send_action(&action_tx, &mut pressure, Action::Render).unwrap();
+        App::send_action(&action_tx, &mut pressure, Action::Render).unwrap();
+        assert_eq!(pressure.take_warning(), Some(2));
+        assert_eq!(action_rx.try_recv().unwrap(), Action::Tick);
+
+        drop(action_rx);
+        assert!(App::send_action(&action_tx, &mut pressure, Action::Tick).is_err());
+    }
 }
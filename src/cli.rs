@@ -1,6 +1,11 @@
 
+use std::path::PathBuf;
+
 use clap::Parser;
+use ipnetwork::IpNetwork;
 
+use crate::components::interfaces::InterfaceSelect;
+use crate::config::CaptureConfig;
 use crate::utils::version;
 
 #[derive(Parser, Debug)]
@@ -10,17 +15,120 @@ pub struct Cli {
         short,
         long,
         value_name = "FLOAT",
-        help = "Tick rate, i.e. number of ticks per second",
-        default_value_t = 1.0
+        help = "Tick rate, i.e. number of ticks per second [default: ui.tick_rate from config, 1.0]"
     )]
-    pub tick_rate: f64,
+    pub tick_rate: Option<f64>,
 
     #[arg(
         short,
         long,
         value_name = "FLOAT",
-        help = "Frame rate, i.e. number of frames per second",
-        default_value_t = 10.0
+        help = "Frame rate, i.e. number of frames per second [default: ui.frame_rate from config, 10.0]"
+    )]
+    pub frame_rate: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "CIDR",
+        help = "Start on the interface with an address in this subnet, e.g. 192.168.1.0/24"
+    )]
+    pub subnet: Option<IpNetwork>,
+
+    #[arg(
+        long,
+        conflicts_with = "subnet",
+        help = "Start on the first up, non-loopback interface with an IPv4 address"
+    )]
+    pub auto: bool,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Open a saved session for offline inspection, capture starts paused"
+    )]
+    pub session: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "session",
+        help = "Replay a pcap file in the packets tab instead of capturing live"
+    )]
+    pub read_pcap: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILTER",
+        help = "Packet filter to start with, same syntax as the filter input of the packets tab"
     )]
-    pub frame_rate: f64,
+    pub filter: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "session",
+        help = "Run without the TUI, capturing until interrupted with Ctrl-C"
+    )]
+    pub headless: bool,
+
+    #[arg(
+        long,
+        requires = "headless",
+        help = "Write each captured packet as a JSON line to stdout, needs --headless"
+    )]
+    pub json: bool,
+
+    #[arg(long, conflicts_with = "session", help = "Shorthand for --headless --json")]
+    pub stream_json: bool,
+
+    #[arg(
+        long,
+        value_name = "COUNT",
+        help = "Stop capturing after this many packets, 0 is unlimited [default: capture.max_packets from config, 0]"
+    )]
+    pub max_packets: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "BYTES",
+        help = "Stop capturing after this many bytes, 0 is unlimited [default: capture.max_bytes from config, 0]"
+    )]
+    pub max_bytes: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "SECS",
+        help = "Stop capturing after this many seconds, 0 is unlimited [default: capture.max_duration_secs from config, 0]"
+    )]
+    pub duration: Option<u64>,
+}
+
+impl Cli {
+    pub fn headless(&self) -> bool {
+        self.headless || self.stream_json
+    }
+
+    pub fn json(&self) -> bool {
+        self.json || self.stream_json
+    }
+
+    /// Override the capture limits of the config with the ones given on the command line.
+    pub fn apply_capture_limits(&self, capture: &mut CaptureConfig) {
+        if let Some(max_packets) = self.max_packets {
+            capture.max_packets = max_packets;
+        }
+        if let Some(max_bytes) = self.max_bytes {
+            capture.max_bytes = max_bytes;
+        }
+        if let Some(duration) = self.duration {
+            capture.max_duration_secs = duration;
+        }
+    }
+
+    pub fn interface_select(&self) -> InterfaceSelect {
+        match (self.subnet, self.auto) {
+            (Some(net), _) => InterfaceSelect::Subnet(net),
+            (None, true) => InterfaceSelect::Auto,
+            (None, false) => InterfaceSelect::Default,
+        }
+    }
 }
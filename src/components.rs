@@ -52,6 +52,7 @@
 //! - **[`wifi_interface`]**: WiFi connection information
 //! - **[`interfaces`]**: Network interface selection
 //! - **[`export`]**: Data export functionality
+//! - **[`help`]**: Keybinding overlay for the active tab
 //! - **[`tabs`]**: Tab navigation UI
 //! - **[`title`]**: Application title bar
 //!
@@ -82,8 +83,10 @@ use crate::{
     tui::{Event, Frame},
 };
 
+pub mod anomalies;
 pub mod discovery;
 pub mod export;
+pub mod help;
 pub mod interfaces;
 pub mod packetdump;
 pub mod ports;
@@ -0,0 +1,246 @@
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+    action::Action,
+    config::DEFAULT_BORDER_STYLE,
+    enums::{Anomaly, Severity, TabsEnum},
+    layout::get_vertical_layout,
+    tui::Frame,
+    utils::MaxSizeVec,
+};
+
+const MAX_ANOMALIES: usize = 1000;
+
+/// Tab collecting what the detectors of the other components flag (ARP
+/// spoofing, IP conflicts, retransmissions, low TTLs, oversized frames, drops),
+/// newest first.
+pub struct Anomalies {
+    active_tab: TabsEnum,
+    anomalies: MaxSizeVec<Anomaly>,
+    table_state: TableState,
+    scrollbar_state: ScrollbarState,
+    // -- table rows visible at the last draw, for paging
+    page_rows: usize,
+}
+
+impl Default for Anomalies {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn severity_style(severity: Severity) -> Style {
+    match severity {
+        Severity::Info => Style::default().fg(Color::Blue),
+        Severity::Warning => Style::default().fg(Color::Yellow),
+        Severity::Critical => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+    }
+}
+
+impl Anomalies {
+    pub fn new() -> Self {
+        Self {
+            active_tab: TabsEnum::Discovery,
+            anomalies: MaxSizeVec::new(MAX_ANOMALIES),
+            table_state: TableState::default().with_selected(0),
+            scrollbar_state: ScrollbarState::new(0),
+            page_rows: 0,
+        }
+    }
+
+    pub fn get_anomalies(&self) -> Vec<Anomaly> {
+        self.anomalies.get_vec()
+    }
+
+    fn previous_in_table(&mut self) {
+        let len = self.anomalies.get_deque().len();
+        let index = match self.table_state.selected() {
+            Some(0) | None => len.saturating_sub(1),
+            Some(index) => index - 1,
+        };
+        self.table_state.select(Some(index));
+        self.scrollbar_state = self.scrollbar_state.position(index);
+    }
+
+    fn next_in_table(&mut self) {
+        let len = self.anomalies.get_deque().len();
+        let index = match self.table_state.selected() {
+            Some(index) if index + 1 < len => index + 1,
+            _ => 0,
+        };
+        self.table_state.select(Some(index));
+        self.scrollbar_state = self.scrollbar_state.position(index);
+    }
+
+    fn first_in_table(&mut self) {
+        self.table_state.select(Some(0));
+        self.scrollbar_state = self.scrollbar_state.position(0);
+    }
+
+    fn last_in_table(&mut self) {
+        let index = self.anomalies.get_deque().len().saturating_sub(1);
+        self.table_state.select(Some(index));
+        self.scrollbar_state = self.scrollbar_state.position(index);
+    }
+
+    fn page_in_table(&mut self, down: bool) {
+        let selected = self.table_state.selected().unwrap_or(0);
+        let rows = self.page_rows.max(1);
+        let index = if down {
+            (selected + rows).min(self.anomalies.get_deque().len().saturating_sub(1))
+        } else {
+            selected.saturating_sub(rows)
+        };
+        self.table_state.select(Some(index));
+        self.scrollbar_state = self.scrollbar_state.position(index);
+    }
+
+    fn make_table(anomalies: &MaxSizeVec<Anomaly>) -> Table<'static> {
+        let header = Row::new(vec!["time", "severity", "category", "detail"])
+            .style(Style::default().fg(Color::Yellow))
+            .top_margin(1)
+            .bottom_margin(1);
+        let rows: Vec<Row> = anomalies
+            .get_deque()
+            .iter()
+            .map(|a| {
+                Row::new(vec![
+                    Cell::from(a.time.format("%H:%M:%S").to_string().light_magenta()),
+                    Cell::from(Span::styled(a.severity.to_string(), severity_style(a.severity))),
+                    Cell::from(a.category.to_string().green()),
+                    Cell::from(a.detail.clone()),
+                ])
+            })
+            .collect();
+        let critical = anomalies
+            .get_deque()
+            .iter()
+            .filter(|a| a.severity == Severity::Critical)
+            .count();
+
+        let mut count_title = vec![
+            Span::styled("|", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("{}", anomalies.get_deque().len()),
+                Style::default().fg(Color::Red),
+            ),
+        ];
+        if critical > 0 {
+            count_title.push(Span::styled(
+                format!(" ({} critical)", critical),
+                severity_style(Severity::Critical),
+            ));
+        }
+        count_title.push(Span::styled("|", Style::default().fg(Color::Yellow)));
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(15),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::new()
+                .title(
+                    ratatui::widgets::block::Title::from("|Anomalies|".yellow())
+                        .position(ratatui::widgets::block::Position::Top)
+                        .alignment(Alignment::Right),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Line::from(count_title))
+                        .position(ratatui::widgets::block::Position::Top)
+                        .alignment(Alignment::Left),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Line::from(vec![
+                        Span::styled("|", Style::default().fg(Color::Yellow)),
+                        String::from(char::from_u32(0x25b2).unwrap_or('>')).red(),
+                        String::from(char::from_u32(0x25bc).unwrap_or('>')).red(),
+                        Span::styled("select|", Style::default().fg(Color::Yellow)),
+                    ]))
+                    .position(ratatui::widgets::block::Position::Bottom)
+                    .alignment(Alignment::Right),
+                )
+                .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+                .borders(Borders::ALL)
+                .border_type(DEFAULT_BORDER_STYLE),
+        )
+        .highlight_symbol(String::from(char::from_u32(0x25b6).unwrap_or('>')).red())
+        .column_spacing(1)
+    }
+
+    pub fn make_scrollbar<'a>() -> Scrollbar<'a> {
+        Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .style(Style::default().fg(Color::Rgb(100, 100, 100)))
+            .begin_symbol(None)
+            .end_symbol(None)
+    }
+}
+
+impl Component for Anomalies {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn tab_changed(&mut self, tab: TabsEnum) -> Result<()> {
+        self.active_tab = tab;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::TabChange(tab) => self.tab_changed(tab)?,
+            Action::Anomaly(anomaly) => {
+                log::info!("anomaly [{}] {}: {}", anomaly.severity, anomaly.category, anomaly.detail);
+                self.anomalies.push(anomaly);
+                let len = self.anomalies.get_deque().len();
+                self.scrollbar_state = self.scrollbar_state.content_length(len);
+            }
+            Action::Up if self.active_tab == TabsEnum::Anomalies => self.previous_in_table(),
+            Action::Down if self.active_tab == TabsEnum::Anomalies => self.next_in_table(),
+            Action::Home if self.active_tab == TabsEnum::Anomalies => self.first_in_table(),
+            Action::End if self.active_tab == TabsEnum::Anomalies => self.last_in_table(),
+            Action::PageUp if self.active_tab == TabsEnum::Anomalies => self.page_in_table(false),
+            Action::PageDown if self.active_tab == TabsEnum::Anomalies => self.page_in_table(true),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        if self.active_tab == TabsEnum::Anomalies {
+            let layout = get_vertical_layout(area);
+
+            let mut table_rect = layout.bottom;
+            table_rect.y += 1;
+            table_rect.height = table_rect.height.saturating_sub(1);
+            // -- borders and the header with its margins
+            self.page_rows = table_rect.height.saturating_sub(5) as usize;
+
+            let table = Self::make_table(&self.anomalies);
+            f.render_stateful_widget(table, table_rect, &mut self.table_state);
+
+            let scrollbar = Self::make_scrollbar();
+            let mut scroll_rect = table_rect;
+            scroll_rect.y += 3;
+            scroll_rect.height = scroll_rect.height.saturating_sub(3);
+            f.render_stateful_widget(
+                scrollbar,
+                scroll_rect.inner(Margin {
+                    vertical: 1,
+                    horizontal: 1,
+                }),
+                &mut self.scrollbar_state,
+            );
+        }
+
+        Ok(())
+    }
+}
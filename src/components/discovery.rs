@@ -1,3 +1,4 @@
+use chrono::{DateTime, Local};
 use cidr::Ipv4Cidr;
 use color_eyre::eyre::Result;
 use ipnetwork::IpNetwork;
@@ -14,6 +15,7 @@ use tokio::sync::Semaphore;
 use core::str;
 use ratatui::layout::Position;
 use ratatui::{prelude::*, widgets::*};
+use std::collections::HashSet;
 use std::net::{IpAddr, Ipv6Addr};
 use std::sync::Arc;
 use std::time::Duration;
@@ -23,22 +25,27 @@ use tokio::{
     task::JoinHandle,
 };
 
+mod roles;
+
 use super::Component;
+use roles::HostRoles;
+pub use roles::HostRole;
 use crate::{
     action::Action,
-    components::packetdump::ArpPacketData,
-    config::DEFAULT_BORDER_STYLE,
+    components::packetdump::{ArpPacketData, Ipv6NeighborData},
+    config::{DiscoveryConfig, DEFAULT_BORDER_STYLE},
     dns_cache::DnsCache,
-    enums::TabsEnum,
-    layout::get_vertical_layout,
+    enums::{Anomaly, AnomalyCategory, Severity, TabsEnum},
+    layout::{get_input_rect, get_vertical_layout},
     mode::Mode,
     tui::Frame,
     utils::{count_ipv4_net_length, count_ipv6_net_length, get_ips4_from_cidr, get_ips6_from_cidr},
+    vendors,
 };
 use crossterm::event::Event;
-use crossterm::event::{KeyCode, KeyEvent};
-use mac_oui::Oui;
+use crossterm::event::KeyEvent;
 use rand::random;
+use serde::{Deserialize, Serialize};
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
@@ -49,21 +56,63 @@ const PING_TIMEOUT_SECS: u64 = 2;
 const INPUT_SIZE: usize = 30;
 const DEFAULT_IP: &str = "192.168.1.0/24";
 const SPINNER_SYMBOLS: [&str; 6] = ["⠷", "⠯", "⠟", "⠻", "⠽", "⠾"];
+// -- hosts added from Neighbor Discovery alone, the least recently seen go first
+const MAX_NDP_HOSTS: usize = 256;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ScannedIp {
     pub ip: String,
     pub ip_addr: IpAddr,
     pub mac: String,
     pub hostname: String,
     pub vendor: String,
+    /// Gateway or DNS server of the active interface.
+    #[serde(default)]
+    pub role: Option<HostRole>,
+    #[serde(default = "Local::now")]
+    pub first_seen: DateTime<Local>,
+    /// Last ping reply, ARP or Neighbor Discovery message from the host.
+    #[serde(default = "Local::now")]
+    pub last_seen: DateTime<Local>,
+}
+
+/// Marker of hosts that recently appeared or went quiet.
+#[derive(Clone, Copy, PartialEq)]
+enum Freshness {
+    /// First seen within `discovery.new_host_secs`, `fading` in the second half.
+    New { fading: bool },
+    /// Not seen for `discovery.stale_host_secs`.
+    Stale,
+}
+
+impl Freshness {
+    fn tag(self) -> &'static str {
+        match self {
+            Freshness::New { .. } => "new",
+            Freshness::Stale => "stale",
+        }
+    }
+
+    fn style(self) -> Style {
+        match self {
+            Freshness::New { fading: false } => {
+                Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD)
+            }
+            Freshness::New { fading: true } => Style::default().fg(Color::Green),
+            Freshness::Stale => Style::default().fg(Color::DarkGray),
+        }
+    }
 }
 
 pub struct Discovery {
     active_tab: TabsEnum,
+    config: crate::config::Config,
     active_interface: Option<NetworkInterface>,
+    host_roles: HostRoles,
     action_tx: Option<Sender<Action>>,
     scanned_ips: Vec<ScannedIp>,
+    // -- hosts that are in the list only because of Neighbor Discovery
+    ndp_hosts: HashSet<IpAddr>,
     ip_num: i32,
     input: Input,
     cidr: Option<IpNetwork>,
@@ -71,9 +120,10 @@ pub struct Discovery {
     is_scanning: bool,
     mode: Mode,
     task: JoinHandle<()>,
-    oui: Option<Oui>,
     table_state: TableState,
     scrollbar_state: ScrollbarState,
+    // -- rows the table showed when last drawn
+    page_rows: usize,
     spinner_index: usize,
     dns_cache: DnsCache,
 }
@@ -88,19 +138,22 @@ impl Discovery {
     pub fn new() -> Self {
         Self {
             active_tab: TabsEnum::Discovery,
+            config: crate::config::Config::default(),
             active_interface: None,
+            host_roles: HostRoles::default(),
             task: tokio::spawn(async {}),
             action_tx: None,
             scanned_ips: Vec::new(),
+            ndp_hosts: HashSet::new(),
             ip_num: 0,
             input: Input::default().with_value(String::from(DEFAULT_IP)),
             cidr: None,
             cidr_error: false,
             is_scanning: false,
             mode: Mode::Normal,
-            oui: None,
             table_state: TableState::default().with_selected(0),
             scrollbar_state: ScrollbarState::new(0),
+            page_rows: 0,
             spinner_index: 0,
             dns_cache: DnsCache::new(),
         }
@@ -586,6 +639,7 @@ impl Discovery {
 
     fn reset_scan(&mut self) {
         self.scanned_ips.clear();
+        self.ndp_hosts.clear();
         self.ip_num = 0;
     }
 
@@ -808,19 +862,62 @@ impl Discovery {
     }
 
     fn process_mac(&mut self, arp_data: ArpPacketData) {
-        if let Some(n) = self
-            .scanned_ips
-            .iter_mut()
-            .find(|item| item.ip == arp_data.sender_ip.to_string())
-        {
-            n.mac = arp_data.sender_mac.to_string();
+        self.set_mac(&arp_data.sender_ip.to_string(), arp_data.sender_mac);
+    }
 
-            if let Some(oui) = &self.oui {
-                let oui_res = oui.lookup_by_mac(&n.mac);
-                if let Ok(Some(oui_res)) = oui_res {
-                    let cn = oui_res.company_name.clone();
-                    n.vendor = cn;
+    // IPv6 hosts are rarely found by scanning, so neighbors seen passively
+    // are added to the list rather than only updating known ones, up to
+    // MAX_NDP_HOSTS of them
+    fn process_ipv6_neighbor(&mut self, neighbor: Ipv6NeighborData) {
+        if neighbor.ip.is_unspecified() || neighbor.ip.is_multicast() {
+            return;
+        }
+        let ip = neighbor.ip.to_string();
+        match self.scanned_ips.iter().find(|item| item.ip == ip) {
+            // -- another MAC answering for a known IP hints at spoofing or an address
+            // conflict; IPv4 conflicts are reported by the packet dump's ARP tracker
+            Some(known) if !known.mac.is_empty() && known.mac != neighbor.mac.to_string() => {
+                if let Some(tx) = &self.action_tx {
+                    let _ = tx.try_send(Action::Anomaly(Anomaly::new(
+                        AnomalyCategory::NdpSpoofing,
+                        Severity::Critical,
+                        format!("{} moved from {} to {}", ip, known.mac, neighbor.mac),
+                    )));
+                }
+            }
+            Some(_) => {}
+            None => {
+                if self.ndp_hosts.len() >= MAX_NDP_HOSTS {
+                    self.evict_ndp_host();
                 }
+                self.ndp_hosts.insert(IpAddr::V6(neighbor.ip));
+                self.process_ip(&ip);
+            }
+        }
+        self.set_mac(&ip, neighbor.mac);
+    }
+
+    fn evict_ndp_host(&mut self) {
+        let oldest = self
+            .scanned_ips
+            .iter()
+            .enumerate()
+            .filter(|(_, host)| self.ndp_hosts.contains(&host.ip_addr))
+            .min_by_key(|(_, host)| host.last_seen)
+            .map(|(index, _)| index);
+        if let Some(index) = oldest {
+            let host = self.scanned_ips.remove(index);
+            self.ndp_hosts.remove(&host.ip_addr);
+        }
+    }
+
+    fn set_mac(&mut self, ip: &str, mac: MacAddr) {
+        if let Some(n) = self.scanned_ips.iter_mut().find(|item| item.ip == ip) {
+            n.mac = mac.to_string();
+            n.last_seen = Local::now();
+
+            if let Some(vendor) = vendors::vendor(&n.mac) {
+                n.vendor = vendor.to_string();
             }
         }
     }
@@ -830,9 +927,11 @@ impl Discovery {
             return;
         };
 
+        let now = Local::now();
         if let Some(n) = self.scanned_ips.iter_mut().find(|item| item.ip == ip) {
             n.ip = ip.to_string();
             n.ip_addr = hip;
+            n.last_seen = now;
         } else {
             let new_ip = ScannedIp {
                 ip: ip.to_string(),
@@ -840,6 +939,9 @@ impl Discovery {
                 mac: String::new(),
                 hostname: String::new(),
                 vendor: String::new(),
+                role: self.host_roles.role(hip),
+                first_seen: now,
+                last_seen: now,
             };
 
             let insert_pos = self.scanned_ips
@@ -896,6 +998,13 @@ impl Discovery {
         }
     }
 
+    fn set_host_roles(&mut self, interface: &NetworkInterface) {
+        self.host_roles = HostRoles::load(&interface.name);
+        for sip in &mut self.scanned_ips {
+            sip.role = self.host_roles.role(sip.ip_addr);
+        }
+    }
+
     fn set_scrollbar_height(&mut self) {
         let mut ip_len = 0;
         if !self.scanned_ips.is_empty() {
@@ -923,6 +1032,29 @@ impl Discovery {
         self.scrollbar_state = self.scrollbar_state.position(index);
     }
 
+    fn first_in_table(&mut self) {
+        self.table_state.select(Some(0));
+        self.scrollbar_state = self.scrollbar_state.position(0);
+    }
+
+    fn last_in_table(&mut self) {
+        let index = self.scanned_ips.len().saturating_sub(1);
+        self.table_state.select(Some(index));
+        self.scrollbar_state = self.scrollbar_state.position(index);
+    }
+
+    fn page_in_table(&mut self, down: bool) {
+        let selected = self.table_state.selected().unwrap_or(0);
+        let rows = self.page_rows.max(1);
+        let index = if down {
+            (selected + rows).min(self.scanned_ips.len().saturating_sub(1))
+        } else {
+            selected.saturating_sub(rows)
+        };
+        self.table_state.select(Some(index));
+        self.scrollbar_state = self.scrollbar_state.position(index);
+    }
+
     fn next_in_table(&mut self) {
         let index = match self.table_state.selected() {
             Some(index) => {
@@ -942,12 +1074,29 @@ impl Discovery {
         self.scrollbar_state = self.scrollbar_state.position(index);
     }
 
-    fn make_table(
-        scanned_ips: &Vec<ScannedIp>,
+    fn host_freshness(
+        sip: &ScannedIp,
+        hosts: &DiscoveryConfig,
+        now: DateTime<Local>,
+    ) -> Option<Freshness> {
+        let since_first = (now - sip.first_seen).num_seconds().max(0) as u64;
+        let since_last = (now - sip.last_seen).num_seconds().max(0) as u64;
+        if hosts.stale_host_secs > 0 && since_last >= hosts.stale_host_secs {
+            Some(Freshness::Stale)
+        } else if since_first < hosts.new_host_secs {
+            Some(Freshness::New { fading: since_first >= hosts.new_host_secs / 2 })
+        } else {
+            None
+        }
+    }
+
+    fn make_table<'a>(
+        scanned_ips: &'a Vec<ScannedIp>,
         cidr: Option<IpNetwork>,
         ip_num: i32,
         is_scanning: bool,
-    ) -> Table<'_> {
+        hosts: &DiscoveryConfig,
+    ) -> Table<'a> {
         let header = Row::new(vec!["ip", "mac", "hostname", "vendor"])
             .style(Style::default().fg(Color::Yellow))
             .top_margin(1)
@@ -959,17 +1108,32 @@ impl Discovery {
             None => 0,
         };
 
+        let now = Local::now();
         for sip in scanned_ips {
             let ip = &sip.ip;
-            rows.push(Row::new(vec![
-                Cell::from(Span::styled(
-                    format!("{ip:<2}"),
-                    Style::default().fg(Color::Blue),
-                )),
+            let freshness = Self::host_freshness(sip, hosts, now);
+            let ip_style = freshness.map_or(Style::default().fg(Color::Blue), |f| f.style());
+            let mut ip_line = vec![Span::styled(format!("{ip:<2}"), ip_style)];
+            if let Some(role) = sip.role {
+                ip_line.push(Span::styled(
+                    format!(" {}", role.tag()),
+                    Style::default().fg(Color::Magenta),
+                ));
+            }
+            if let Some(freshness) = freshness {
+                ip_line.push(Span::styled(format!(" {}", freshness.tag()), freshness.style()));
+            }
+            let mut row = Row::new(vec![
+                Cell::from(Line::from(ip_line)),
                 Cell::from(sip.mac.as_str().green()),
                 Cell::from(sip.hostname.as_str()),
                 Cell::from(sip.vendor.as_str().yellow()),
-            ]));
+            ]);
+            // -- stale rows are dimmed as a whole, the cell colors would stand out otherwise
+            if freshness == Some(Freshness::Stale) {
+                row = row.style(Style::default().add_modifier(Modifier::DIM));
+            }
+            rows.push(row);
         }
 
         let mut scan_title = vec![
@@ -1124,10 +1288,7 @@ impl Component for Discovery {
         if self.cidr.is_none() {
             self.set_cidr(String::from(DEFAULT_IP), false);
         }
-        match Oui::default() {
-            Ok(s) => self.oui = Some(s),
-            Err(_) => self.oui = None,
-        }
+        vendors::load();
         Ok(())
     }
 
@@ -1140,27 +1301,20 @@ impl Component for Discovery {
         Ok(())
     }
 
+    fn register_config_handler(&mut self, config: crate::config::Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
-        if self.active_tab == TabsEnum::Discovery {
-            let action = match self.mode {
-                Mode::Normal => return Ok(None),
-                Mode::Input => match key.code {
-                    KeyCode::Enter => {
-                        if let Some(_sender) = &self.action_tx {
-                            self.set_cidr(self.input.value().to_string(), true);
-                        }
-                        Action::ModeChange(Mode::Normal)
-                    }
-                    _ => {
-                        self.input.handle_event(&Event::Key(key));
-                        return Ok(None);
-                    }
-                },
-            };
-            Ok(Some(action))
-        } else {
-            Ok(None)
+        // -- keys bound in input mode (submit, cancel) are dispatched by the app
+        if self.active_tab == TabsEnum::Discovery
+            && self.mode == Mode::Input
+            && !self.config.keybindings.is_bound(Mode::Input, &key)
+        {
+            self.input.handle_event(&Event::Key(key));
         }
+        Ok(None)
     }
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
@@ -1178,6 +1332,10 @@ impl Component for Discovery {
         }
 
         if let Action::PingIp(ref ip) = action {
+            // -- a host answering the scan is no longer evicted with the NDP ones
+            if let Ok(ip_addr) = ip.parse() {
+                self.ndp_hosts.remove(&ip_addr);
+            }
             self.process_ip(ip);
         }
         if let Action::DnsResolved(ref ip, ref hostname) = action {
@@ -1185,13 +1343,19 @@ impl Component for Discovery {
                 entry.hostname = hostname.clone();
             }
         }
+        // -- reverse DNS wins, an announced name only fills the gap
+        if let Action::HostnameAnnounced(ref ip, ref hostname) = action {
+            if let Some(entry) = self.scanned_ips.iter_mut().find(|item| item.ip == *ip) {
+                if entry.hostname.is_empty() {
+                    entry.hostname = hostname.clone();
+                }
+            }
+        }
         if let Action::UpdateMac(ref ip, ref mac) = action {
             if let Some(entry) = self.scanned_ips.iter_mut().find(|item| item.ip == *ip) {
                 entry.mac = mac.clone();
-                if let Some(oui) = &self.oui {
-                    if let Ok(Some(oui_res)) = oui.lookup_by_mac(mac) {
-                        entry.vendor = oui_res.company_name.clone();
-                    }
+                if let Some(vendor) = vendors::vendor(mac) {
+                    entry.vendor = vendor.to_string();
                 }
             }
         }
@@ -1218,9 +1382,22 @@ impl Component for Discovery {
         if let Action::CidrError = action {
             self.cidr_error = true;
         }
+        if let Action::SessionLoaded(ref session) = action {
+            // -- a running scan would add its hosts to the loaded ones
+            if self.is_scanning {
+                self.task.abort();
+                self.is_scanning = false;
+            }
+            self.scanned_ips = session.scanned_ips.clone();
+            self.ndp_hosts.clear();
+            self.set_scrollbar_height();
+        }
         if let Action::ArpRecieve(ref arp_data) = action {
             self.process_mac(arp_data.clone());
         }
+        if let Action::Ipv6NeighborDiscovered(ref neighbor) = action {
+            self.process_ipv6_neighbor(neighbor.clone());
+        }
         if let Action::ScanCidr = action {
             if self.active_interface.is_some()
                 && !self.is_scanning
@@ -1233,6 +1410,7 @@ impl Component for Discovery {
             if self.active_interface.is_none() {
                 self.set_active_subnet(interface);
             }
+            self.set_host_roles(interface);
             self.active_interface = Some(interface.clone());
         }
 
@@ -1243,6 +1421,31 @@ impl Component for Discovery {
             if let Action::Up = action {
                 self.previous_in_table();
             }
+            if let Action::Home = action {
+                self.first_in_table();
+            }
+            if let Action::End = action {
+                self.last_in_table();
+            }
+            if let Action::PageUp = action {
+                self.page_in_table(false);
+            }
+            if let Action::CopySelection = action {
+                let selected = self.table_state.selected().unwrap_or(0);
+                if let Some(sip) = self.scanned_ips.get(selected) {
+                    return Ok(Some(Action::Copy(format!("{} {}", sip.ip, sip.mac))));
+                }
+            }
+            if let Action::PageDown = action {
+                self.page_in_table(true);
+            }
+
+            if let Action::Submit = action {
+                if self.mode == Mode::Input {
+                    self.set_cidr(self.input.value().to_string(), true);
+                    return Ok(Some(Action::ModeChange(Mode::Normal)));
+                }
+            }
 
             if let Action::ModeChange(mode) = action {
                 if self.is_scanning && mode == Mode::Input {
@@ -1288,16 +1491,23 @@ impl Component for Discovery {
 
             let mut table_rect = layout.bottom;
             table_rect.y += 1;
-            table_rect.height -= 1;
-
-            let table =
-                Self::make_table(&self.scanned_ips, self.cidr, self.ip_num, self.is_scanning);
+            table_rect.height = table_rect.height.saturating_sub(1);
+            // -- borders and the header with its margins
+            self.page_rows = table_rect.height.saturating_sub(5) as usize;
+
+            let table = Self::make_table(
+                &self.scanned_ips,
+                self.cidr,
+                self.ip_num,
+                self.is_scanning,
+                &self.config.discovery,
+            );
             f.render_stateful_widget(table, table_rect, &mut self.table_state);
 
             let scrollbar = Self::make_scrollbar();
             let mut scroll_rect = table_rect;
             scroll_rect.y += 3;
-            scroll_rect.height -= 3;
+            scroll_rect.height = scroll_rect.height.saturating_sub(3);
             f.render_stateful_widget(
                 scrollbar,
                 scroll_rect.inner(Margin {
@@ -1307,21 +1517,15 @@ impl Component for Discovery {
                 &mut self.scrollbar_state,
             );
 
+            let input_rect = get_input_rect(table_rect, INPUT_SIZE as u16);
+
             if self.cidr_error {
-                let error_rect = Rect::new(table_rect.width - (19 + 41), table_rect.y + 1, 18, 3);
+                let error_rect = Rect::new(input_rect.x.saturating_sub(29), table_rect.y + 1, 18, 3);
                 let block = self.make_error();
                 f.render_widget(block, error_rect);
             }
 
-            let input_size: u16 = INPUT_SIZE as u16;
-            let input_rect = Rect::new(
-                table_rect.width - (input_size + 1),
-                table_rect.y + 1,
-                input_size,
-                3,
-            );
-
-            let scroll = self.input.visual_scroll(INPUT_SIZE - 3);
+            let scroll = self.input.visual_scroll((input_rect.width as usize).saturating_sub(3));
             let mut block = self.make_input(scroll);
             if self.is_scanning {
                 block = block.add_modifier(Modifier::DIM);
@@ -1329,20 +1533,21 @@ impl Component for Discovery {
             f.render_widget(block, input_rect);
 
             match self.mode {
-                Mode::Input => {
+                // -- no room for a cursor inside the borders
+                Mode::Input if input_rect.width >= 3 => {
+                    let offset = ((self.input.visual_cursor()).max(scroll) - scroll) as u16;
                     f.set_cursor_position(Position {
-                        x: input_rect.x
-                            + ((self.input.visual_cursor()).max(scroll) - scroll) as u16
-                            + 1,
+                        x: input_rect.x + offset.min(input_rect.width - 3) + 1,
                         y: input_rect.y + 1,
                     });
                 }
-                Mode::Normal => {}
+                _ => {}
             }
 
             if self.is_scanning {
                 let throbber = self.make_spinner();
-                let throbber_rect = Rect::new(input_rect.x + 1, input_rect.y, 12, 1);
+                let throbber_width = input_rect.width.saturating_sub(2).min(12);
+                let throbber_rect = Rect::new(input_rect.x + 1, input_rect.y, throbber_width, 1);
                 f.render_widget(throbber, throbber_rect);
             }
         }
@@ -1350,3 +1555,39 @@ impl Component for Discovery {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn neighbor(index: u16) -> Ipv6NeighborData {
+        Ipv6NeighborData {
+            ip: Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, index + 1),
+            mac: MacAddr::new(2, 0, 0, 0, (index >> 8) as u8, index as u8),
+        }
+    }
+
+    #[tokio::test]
+    async fn ndp_hosts_are_bounded() {
+        let mut discovery = Discovery::new();
+        discovery.process_ip("192.168.1.1");
+        for index in 0..MAX_NDP_HOSTS as u16 + 10 {
+            discovery.process_ipv6_neighbor(neighbor(index));
+        }
+        assert_eq!(discovery.scanned_ips.len(), MAX_NDP_HOSTS + 1);
+        assert_eq!(discovery.ndp_hosts.len(), MAX_NDP_HOSTS);
+        // -- the scanned host stays, the neighbors seen first went
+        assert!(discovery.scanned_ips.iter().any(|host| host.ip == "192.168.1.1"));
+        assert!(!discovery.scanned_ips.iter().any(|host| host.ip == "fe80::1"));
+        assert!(discovery.scanned_ips.iter().any(|host| host.ip == "fe80::10a"));
+    }
+
+    #[tokio::test]
+    async fn known_neighbors_are_updated_in_place() {
+        let mut discovery = Discovery::new();
+        discovery.process_ipv6_neighbor(neighbor(0));
+        discovery.process_ipv6_neighbor(neighbor(0));
+        assert_eq!(discovery.scanned_ips.len(), 1);
+        assert_eq!(discovery.scanned_ips[0].mac, "02:00:00:00:00:00");
+    }
+}
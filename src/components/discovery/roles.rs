@@ -0,0 +1,266 @@
+//! Roles of discovered hosts taken from the system network configuration.
+//!
+//! The default gateway is looked up for the active interface only, so on
+//! multi-homed hosts the router of another link is not tagged:
+//!
+//! - **Linux**: `/proc/net/route` and `/proc/net/ipv6_route`
+//! - **macOS**: `route -n get default` (`-inet6` for IPv6)
+//!
+//! DNS servers come from the `nameserver` lines of `/etc/resolv.conf`.
+//! Anything that can't be read simply leaves the hosts without a role.
+
+use std::net::IpAddr;
+#[cfg(target_os = "linux")]
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use serde::{Deserialize, Serialize};
+
+const RESOLV_CONF: &str = "/etc/resolv.conf";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HostRole {
+    Gateway,
+    Dns,
+    GatewayDns,
+}
+
+impl HostRole {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            HostRole::Gateway => "[gateway]",
+            HostRole::Dns => "[dns]",
+            HostRole::GatewayDns => "[gateway][dns]",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct HostRoles {
+    gateways: Vec<IpAddr>,
+    dns_servers: Vec<IpAddr>,
+}
+
+impl HostRoles {
+    /// Read the gateways of `interface_name` and the system DNS servers.
+    pub fn load(interface_name: &str) -> Self {
+        Self {
+            gateways: default_gateways(interface_name),
+            dns_servers: std::fs::read_to_string(RESOLV_CONF)
+                .map(|conf| parse_resolv_conf(&conf))
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn role(&self, ip: IpAddr) -> Option<HostRole> {
+        match (self.gateways.contains(&ip), self.dns_servers.contains(&ip)) {
+            (true, true) => Some(HostRole::GatewayDns),
+            (true, false) => Some(HostRole::Gateway),
+            (false, true) => Some(HostRole::Dns),
+            (false, false) => None,
+        }
+    }
+}
+
+fn parse_resolv_conf(conf: &str) -> Vec<IpAddr> {
+    conf.lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            if words.next() != Some("nameserver") {
+                return None;
+            }
+            // -- drop a zone index, e.g. fe80::1%eth0
+            let addr = words.next()?.split('%').next()?;
+            addr.parse().ok()
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn default_gateways(interface_name: &str) -> Vec<IpAddr> {
+    let mut gateways = Vec::new();
+    if let Ok(route) = std::fs::read_to_string("/proc/net/route") {
+        gateways.extend(parse_proc_route(&route, interface_name).map(IpAddr::V4));
+    }
+    if let Ok(route) = std::fs::read_to_string("/proc/net/ipv6_route") {
+        gateways.extend(parse_proc_ipv6_route(&route, interface_name).map(IpAddr::V6));
+    }
+    gateways
+}
+
+/// Gateway of the IPv4 default route, columns are
+/// `Iface Destination Gateway Flags ...` with the addresses printed as a hex
+/// `u32` in host byte order, so its native bytes are the network order octets.
+#[cfg(target_os = "linux")]
+fn parse_proc_route(route: &str, interface_name: &str) -> Option<Ipv4Addr> {
+    route.lines().skip(1).find_map(|line| {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        if cols.len() < 3 || cols[0] != interface_name || cols[1] != "00000000" {
+            return None;
+        }
+        let gateway = u32::from_str_radix(cols[2], 16).ok()?;
+        (gateway != 0).then(|| Ipv4Addr::from(gateway.to_ne_bytes()))
+    })
+}
+
+/// Gateway of the IPv6 default route, columns are
+/// `dest dest_len src src_len next_hop metric refcnt use flags iface` with
+/// the addresses as 32 hex digits in network order.
+#[cfg(target_os = "linux")]
+fn parse_proc_ipv6_route(route: &str, interface_name: &str) -> Option<Ipv6Addr> {
+    route.lines().find_map(|line| {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        if cols.len() < 10 || cols[9] != interface_name || cols[1] != "00" {
+            return None;
+        }
+        let gateway = Ipv6Addr::from(u128::from_str_radix(cols[4], 16).ok()?.to_be_bytes());
+        (!gateway.is_unspecified()).then_some(gateway)
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn default_gateways(interface_name: &str) -> Vec<IpAddr> {
+    ["-inet", "-inet6"]
+        .iter()
+        .filter_map(|family| {
+            let output = std::process::Command::new("route")
+                .args(["-n", "get", family, "default"])
+                .output()
+                .ok()?;
+            parse_route_get(&String::from_utf8_lossy(&output.stdout), interface_name)
+        })
+        .collect()
+}
+
+/// `gateway:` of `route -n get default` when its `interface:` matches.
+#[cfg(target_os = "macos")]
+fn parse_route_get(output: &str, interface_name: &str) -> Option<IpAddr> {
+    let field = |name: &str| {
+        output.lines().find_map(|line| {
+            let (key, value) = line.trim().split_once(':')?;
+            (key == name).then(|| value.trim().to_string())
+        })
+    };
+    if field("interface")? != interface_name {
+        return None;
+    }
+    field("gateway")?.split('%').next()?.parse().ok()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn default_gateways(_interface_name: &str) -> Vec<IpAddr> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolv_conf_nameservers() {
+        let conf = "# Generated by NetworkManager\n\
+            search lan\n\
+            nameserver 192.168.1.1\n\
+            nameserver fe80::1%wlan0\n\
+            ;nameserver 10.0.0.1\n\
+            nameserver not-an-address\n\
+            options edns0\n";
+        let servers = parse_resolv_conf(conf);
+        assert_eq!(
+            servers,
+            vec![
+                IpAddr::from([192, 168, 1, 1]),
+                "fe80::1".parse::<IpAddr>().unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn roles_combine() {
+        let roles = HostRoles {
+            gateways: vec!["192.168.1.1".parse().unwrap()],
+            dns_servers: vec!["192.168.1.1".parse().unwrap(), "1.1.1.1".parse().unwrap()],
+        };
+        assert_eq!(
+            roles.role("192.168.1.1".parse().unwrap()),
+            Some(HostRole::GatewayDns)
+        );
+        assert_eq!(roles.role("1.1.1.1".parse().unwrap()), Some(HostRole::Dns));
+        assert_eq!(roles.role("192.168.1.2".parse().unwrap()), None);
+    }
+
+    // -- /proc/net/route prints the __be32 address as a native u32
+    #[cfg(target_os = "linux")]
+    fn proc_route_hex(ip: [u8; 4]) -> String {
+        format!("{:08X}", u32::from_ne_bytes(ip))
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn proc_route_default_gateway() {
+        let route = format!(
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n\
+             wlan0\t00000000\t{}\t0003\t0\t0\t600\t00000000\t0\t0\t0\n\
+             eth0\t00000000\t{}\t0003\t0\t0\t100\t00000000\t0\t0\t0\n\
+             eth0\t{}\t00000000\t0001\t0\t0\t100\t{}\t0\t0\t0\n",
+            proc_route_hex([10, 0, 0, 1]),
+            proc_route_hex([192, 168, 1, 254]),
+            proc_route_hex([192, 168, 1, 0]),
+            proc_route_hex([255, 255, 255, 0]),
+        );
+        assert_eq!(
+            parse_proc_route(&route, "eth0"),
+            Some(Ipv4Addr::new(192, 168, 1, 254))
+        );
+        assert_eq!(
+            parse_proc_route(&route, "wlan0"),
+            Some(Ipv4Addr::new(10, 0, 0, 1))
+        );
+        assert_eq!(parse_proc_route(&route, "eth1"), None);
+        if cfg!(target_endian = "little") {
+            // -- as captured on x86_64
+            let captured = "Iface\tDestination\tGateway\n\
+                            eth0\t00000000\t0101A8C0\t0003\n";
+            assert_eq!(
+                parse_proc_route(captured, "eth0"),
+                Some(Ipv4Addr::new(192, 168, 1, 1))
+            );
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn proc_ipv6_route_default_gateway() {
+        let route = "\
+            fe800000000000000000000000000000 40 00000000000000000000000000000000 00 \
+            00000000000000000000000000000000 00000100 00000001 00000000 00000001 eth0\n\
+            00000000000000000000000000000000 00 00000000000000000000000000000000 00 \
+            fe80000000000000022233fffe445566 00000400 00000001 00000000 00000003 eth0\n\
+            00000000000000000000000000000000 00 00000000000000000000000000000000 00 \
+            00000000000000000000000000000000 ffffffff 00000001 00000000 00200200 lo\n";
+        let gateway = "fe80::222:33ff:fe44:5566".parse::<Ipv6Addr>().unwrap();
+        assert_eq!(parse_proc_ipv6_route(route, "eth0"), Some(gateway));
+        assert_eq!(parse_proc_ipv6_route(route, "lo"), None);
+        assert_eq!(parse_proc_ipv6_route(route, "wlan0"), None);
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn route_get_default_gateway() {
+        let output = "   route to: default\n\
+                      destination: default\n\
+                             mask: default\n\
+                          gateway: 192.168.1.1\n\
+                        interface: en0\n\
+                            flags: <UP,GATEWAY,DONE,STATIC,PRCLONING>\n";
+        assert_eq!(
+            parse_route_get(output, "en0"),
+            Some(IpAddr::from([192, 168, 1, 1]))
+        );
+        assert_eq!(parse_route_get(output, "en1"), None);
+        let inet6 = "gateway: fe80::1%en0\ninterface: en0\n";
+        assert_eq!(
+            parse_route_get(inet6, "en0"),
+            Some("fe80::1".parse().unwrap())
+        );
+    }
+}
@@ -2,18 +2,111 @@ use chrono::{DateTime, Local};
 use color_eyre::eyre::Result;
 use csv::Writer;
 use ratatui::prelude::*;
-use std::env;
+use std::fs::File;
+use std::io::BufWriter;
 use std::sync::Arc;
 use tokio::sync::mpsc::Sender;
 
-use super::{discovery::ScannedIp, ports::ScannedIpPorts, Component, Frame};
-use crate::{action::Action, enums::PacketsInfoTypesEnum};
+use super::{
+    discovery::ScannedIp,
+    packetdump::{FlowStats, TimeFormat},
+    ports::ScannedIpPorts,
+    Component, Frame,
+};
+use crate::{
+    action::Action,
+    config::{Config, ExportConfig},
+    enums::{Anomaly, ExportData, PacketsInfoTypesEnum},
+    services,
+    utils::get_netscanner_dir,
+};
+
+mod pcap;
+mod report;
+
+pub use pcap::PcapWriter;
+use report::Report;
+
+/// CSV writer that rolls over to `<base>.001.csv`, `<base>.002.csv`, ... once the
+/// configured size or row limit is crossed. Without limits it writes `<base>.csv`.
+struct RotatingWriter {
+    base: String,
+    header: &'static [&'static str],
+    max_bytes: Option<u64>,
+    max_rows: Option<usize>,
+    index: usize,
+    writer: Writer<File>,
+    bytes: u64,
+    rows: usize,
+}
+
+impl RotatingWriter {
+    fn new(base: String, header: &'static [&'static str], export: &ExportConfig) -> Result<Self> {
+        let index = if export.rotation_enabled() { 1 } else { 0 };
+        let mut rw = Self {
+            writer: Writer::from_path(Self::path(&base, index))?,
+            base,
+            header,
+            max_bytes: export.max_file_bytes,
+            max_rows: export.max_file_rows,
+            index,
+            bytes: 0,
+            rows: 0,
+        };
+        rw.write_header()?;
+        Ok(rw)
+    }
+
+    fn path(base: &str, index: usize) -> String {
+        if index == 0 {
+            format!("{}.csv", base)
+        } else {
+            format!("{}.{:03}.csv", base, index)
+        }
+    }
+
+    /// Approximate on-disk size of a record: fields, separators and newline.
+    fn record_len(fields: &[String]) -> u64 {
+        fields.iter().map(|f| f.len() as u64 + 1).sum()
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        let header: Vec<String> = self.header.iter().map(|h| h.to_string()).collect();
+        self.bytes += Self::record_len(&header);
+        self.writer.write_record(&header)?;
+        Ok(())
+    }
+
+    fn write_record(&mut self, fields: &[String]) -> Result<()> {
+        let bytes_full = self.max_bytes.is_some_and(|max| self.bytes >= max);
+        let rows_full = self.max_rows.is_some_and(|max| self.rows >= max);
+        if bytes_full || rows_full {
+            self.writer.flush()?;
+            self.index += 1;
+            self.writer = Writer::from_path(Self::path(&self.base, self.index))?;
+            self.bytes = 0;
+            self.rows = 0;
+            self.write_header()?;
+        }
+        self.bytes += Self::record_len(fields);
+        self.rows += 1;
+        self.writer.write_record(fields)?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
 
 #[derive(Default)]
 pub struct Export {
     action_tx: Option<Sender<Action>>,
+    config: Config,
     home_dir: String,
     export_done: bool,
+    export_summary: String,
     _export_failed: bool,
 }
 
@@ -21,113 +114,223 @@ impl Export {
     pub fn new() -> Self {
         Self {
             action_tx: None,
+            config: Config::default(),
             home_dir: String::new(),
             export_done: false,
+            export_summary: String::new(),
             _export_failed: false,
         }
     }
 
-    #[cfg(target_os = "linux")]
     fn get_user_home_dir(&mut self) {
-        let mut home_dir = String::from("/root");
-        if let Some(h_dir) = env::var_os("HOME") {
-            if let Some(dir_str) = h_dir.to_str() {
-                home_dir = String::from(dir_str);
-            }
-        }
-        if let Some(sudo_user) = env::var_os("SUDO_USER") {
-            if let Some(user_str) = sudo_user.to_str() {
-                home_dir = format!("/home/{}", user_str);
-            }
-        }
-        self.home_dir = format!("{}/.netscanner", home_dir);
+        self.home_dir = get_netscanner_dir();
 
         // -- create dot folder
         if std::fs::metadata(&self.home_dir).is_err()
             && std::fs::create_dir_all(&self.home_dir).is_err()
         {
+            log::error!("Failed to create export directory: {}", self.home_dir);
             self._export_failed = true;
         }
     }
 
-    #[cfg(target_os = "macos")]
-    fn get_user_home_dir(&mut self) {
-        let mut home_dir = String::from("/root");
-        if let Some(h_dir) = env::var_os("HOME") {
-            if let Some(dir_str) = h_dir.to_str() {
-                home_dir = String::from(dir_str);
+
+    /// Collapse a sorted port list into comma separated ranges,
+    /// e.g. `[22, 80, 8080, 8081, 8082]` becomes `22,80,8080-8082`. With
+    /// `names`, single ports get their service name, `22(ssh)`.
+    pub fn compact_port_ranges(ports: &[u16], names: bool) -> String {
+        let mut ranges: Vec<String> = Vec::new();
+        let mut iter = ports.iter().copied().peekable();
+        while let Some(start) = iter.next() {
+            let mut end = start;
+            while let Some(&next) = iter.peek() {
+                if end == u16::MAX || next != end + 1 {
+                    break;
+                }
+                end = next;
+                iter.next();
             }
-        }
-        if let Some(sudo_user) = env::var_os("SUDO_USER") {
-            if let Some(user_str) = sudo_user.to_str() {
-                home_dir = format!("/Users/{}", user_str);
+            if start == end {
+                let name = services::tcp_service(start).filter(|_| names);
+                ranges.push(services::port_label(start, name));
+            } else {
+                ranges.push(format!("{}-{}", start, end));
             }
         }
-        self.home_dir = format!("{}/.netscanner", home_dir);
+        ranges.join(",")
+    }
 
-        // -- create dot folder
-        if std::fs::metadata(&self.home_dir).is_err()
-            && std::fs::create_dir_all(&self.home_dir).is_err()
-        {
-            log::error!("Failed to create export directory: {}", self.home_dir);
+    /// Exports started within the same second share a timestamp, so add a counter
+    /// suffix rather than overwrite the files of the previous export.
+    fn unique_export_stamp(&self, timestamp: String) -> String {
+        let stamp_taken = |stamp: &str| {
+            // -- rotated files continue as `.{stamp}.001.csv`
+            let plain = format!(".{}.csv", stamp);
+            let rotated = format!(".{}.", stamp);
+            std::fs::read_dir(&self.home_dir)
+                .map(|entries| {
+                    entries.flatten().any(|entry| {
+                        let name = entry.file_name().to_string_lossy().to_string();
+                        name.ends_with(&plain) || name.contains(&rotated)
+                    })
+                })
+                .unwrap_or(false)
+        };
+        let mut stamp = timestamp.clone();
+        let mut counter = 1;
+        while stamp_taken(&stamp) {
+            stamp = format!("{}-{}", timestamp, counter);
+            counter += 1;
         }
+        stamp
     }
 
-    #[cfg(target_os = "windows")]
-    fn get_user_home_dir(&mut self) {
-        let mut home_dir = String::from("C:\\Users\\Administrator");
-        if let Some(h_dir) = env::var_os("USERPROFILE") {
-            if let Some(dir_str) = h_dir.to_str() {
-                home_dir = String::from(dir_str);
-            }
-        }
-        if let Some(sudo_user) = env::var_os("SUDO_USER") {
-            if let Some(user_str) = sudo_user.to_str() {
-                home_dir = format!("C:\\Users\\{}", user_str);
-            }
-        }
-        self.home_dir = format!("{}\\.netscanner", home_dir);
+    pub fn write_discovery(&mut self, data: Arc<Vec<ScannedIp>>, timestamp: &String) -> Result<usize> {
+        let mut w = RotatingWriter::new(
+            format!("{}/scanned_ips.{}", self.home_dir, timestamp),
+            &["ip", "mac", "hostname", "vendor"],
+            &self.config.export,
+        )?;
 
-        // -- create .netscanner folder if it doesn't exist
-        if std::fs::metadata(&self.home_dir).is_err() {
-            if std::fs::create_dir_all(&self.home_dir).is_err() {
-                self._export_failed = true;
-            }
+        for s_ip in data.iter() {
+            w.write_record(&[
+                s_ip.ip.clone(),
+                s_ip.mac.clone(),
+                s_ip.hostname.clone(),
+                s_ip.vendor.clone(),
+            ])?;
         }
-    }
+        w.flush()?;
 
+        Ok(data.len())
+    }
 
-    pub fn write_discovery(&mut self, data: Arc<Vec<ScannedIp>>, timestamp: &String) -> Result<()> {
-        let mut w = Writer::from_path(format!("{}/scanned_ips.{}.csv", self.home_dir, timestamp))?;
+    pub fn write_ports(&mut self, data: Arc<Vec<ScannedIpPorts>>, timestamp: &String) -> Result<usize> {
+        let mut w = RotatingWriter::new(
+            format!("{}/scanned_ports.{}", self.home_dir, timestamp),
+            &["ip", "ports"],
+            &self.config.export,
+        )?;
 
-        // -- header
-        w.write_record(["ip", "mac", "hostname", "vendor"])?;
+        let names = self.config.export.port_names;
         for s_ip in data.iter() {
-            w.write_record([&s_ip.ip, &s_ip.mac, &s_ip.hostname, &s_ip.vendor])?;
+            let ports: String = if self.config.export.compact_port_ranges {
+                let mut sorted = s_ip.ports.clone();
+                sorted.sort_unstable();
+                sorted.dedup();
+                Self::compact_port_ranges(&sorted, names)
+            } else {
+                s_ip.ports
+                    .iter()
+                    .map(|n| services::port_label(*n, services::tcp_service(*n).filter(|_| names)))
+                    .collect::<Vec<String>>()
+                    .join(":")
+            };
+            w.write_record(&[s_ip.ip.clone(), ports])?;
         }
         w.flush()?;
 
-        Ok(())
+        Ok(data.len())
     }
 
-    pub fn write_ports(&mut self, data: Arc<Vec<ScannedIpPorts>>, timestamp: &String) -> Result<()> {
-        let mut w =
-            Writer::from_path(format!("{}/scanned_ports.{}.csv", self.home_dir, timestamp))?;
+    pub fn write_anomalies(&mut self, data: Arc<Vec<Anomaly>>, timestamp: &String) -> Result<usize> {
+        let mut w = RotatingWriter::new(
+            format!("{}/anomalies.{}", self.home_dir, timestamp),
+            &["time", "severity", "category", "detail"],
+            &self.config.export,
+        )?;
 
-        // -- header
-        w.write_record(["ip", "ports"])?;
-        for s_ip in data.iter() {
-            let ports: String = s_ip
-                .ports
-                .iter()
-                .map(|n| n.to_string())
-                .collect::<Vec<String>>()
-                .join(":");
-            w.write_record([&s_ip.ip, &ports])?;
+        for a in data.iter() {
+            w.write_record(&[
+                a.time.to_string(),
+                a.severity.to_string(),
+                a.category.to_string(),
+                a.detail.clone(),
+            ])?;
         }
         w.flush()?;
 
-        Ok(())
+        Ok(data.len())
+    }
+
+    pub fn write_flows(&mut self, data: Arc<Vec<FlowStats>>, timestamp: &String) -> Result<usize> {
+        let mut w = RotatingWriter::new(
+            format!("{}/flows.{}", self.home_dir, timestamp),
+            &[
+                "protocol",
+                "source",
+                "destination",
+                "packets_out",
+                "bytes_out",
+                "packets_in",
+                "bytes_in",
+                "first_seen",
+                "last_seen",
+                "state",
+            ],
+            &self.config.export,
+        )?;
+
+        for f in data.iter() {
+            w.write_record(&[
+                f.protocol.to_string(),
+                format!("{}:{}", f.source.0, f.source.1),
+                format!("{}:{}", f.destination.0, f.destination.1),
+                f.packets_out.to_string(),
+                f.bytes_out.to_string(),
+                f.packets_in.to_string(),
+                f.bytes_in.to_string(),
+                f.first_seen.to_string(),
+                f.last_seen.to_string(),
+                f.tcp_state.map(|s| s.to_string()).unwrap_or_default(),
+            ])?;
+        }
+        w.flush()?;
+
+        Ok(data.len())
+    }
+
+    /// Summary of the whole export as `report.<timestamp>.md`, plus `.html`
+    /// when `export.report_html` is set. Returns the number of files written.
+    pub fn write_report(&mut self, data: &ExportData, timestamp: &String) -> Result<usize> {
+        let report = Report::new(data);
+        let base = format!("{}/report.{}", self.home_dir, timestamp);
+        std::fs::write(format!("{}.md", base), report.to_markdown())?;
+        if !self.config.export.report_html {
+            return Ok(1);
+        }
+        std::fs::write(format!("{}.html", base), report.to_html())?;
+        Ok(2)
+    }
+
+    /// Frames of every packet type as `capture.<timestamp>.pcap`, oldest first.
+    pub fn write_pcap(&mut self, data: &ExportData, timestamp: &String) -> Result<usize> {
+        let mut packets: Vec<&(DateTime<Local>, PacketsInfoTypesEnum)> = [
+            &data.arp_packets,
+            &data.tcp_packets,
+            &data.udp_packets,
+            &data.icmp_packets,
+            &data.icmp6_packets,
+            &data.igmp_packets,
+            &data.other_packets,
+        ]
+        .into_iter()
+        .flat_map(|p| p.iter())
+        .filter(|(_, p)| !p.raw_bytes().is_empty())
+        .collect();
+        packets.sort_by_key(|(t, _)| *t);
+
+        let file = File::create(format!("{}/capture.{}.pcap", self.home_dir, timestamp))?;
+        // -- frames of a loaded session may have been captured with a larger snaplen
+        let longest = packets.iter().map(|(_, p)| p.raw_bytes().len()).max().unwrap_or(0);
+        let snaplen = self.config.capture.snaplen.max(longest);
+        let mut w = PcapWriter::new(BufWriter::new(file), snaplen)?;
+        for (t, p) in &packets {
+            w.write_packet(t, p)?;
+        }
+        w.finish()?;
+
+        Ok(packets.len())
     }
 
     pub fn write_packets(
@@ -135,14 +338,14 @@ impl Export {
         data: Arc<Vec<(DateTime<Local>, PacketsInfoTypesEnum)>>,
         timestamp: &String,
         name: &str,
-    ) -> Result<()> {
-        let mut w = Writer::from_path(format!(
-            "{}/{}_packets.{}.csv",
-            self.home_dir, name, timestamp
-        ))?;
-
-        // -- header
-        w.write_record(["time", "log"])?;
+        time_format: TimeFormat,
+    ) -> Result<usize> {
+        let mut w = RotatingWriter::new(
+            format!("{}/{}_packets.{}", self.home_dir, name, timestamp),
+            &["time", "log", "payload"],
+            &self.config.export,
+        )?;
+
         for (t, p) in data.iter() {
             let log_str = match p {
                 PacketsInfoTypesEnum::Icmp(log) => log.raw_str.clone(),
@@ -150,12 +353,14 @@ impl Export {
                 PacketsInfoTypesEnum::Icmp6(log) => log.raw_str.clone(),
                 PacketsInfoTypesEnum::Udp(log) => log.raw_str.clone(),
                 PacketsInfoTypesEnum::Tcp(log) => log.raw_str.clone(),
+                PacketsInfoTypesEnum::Igmp(log) => log.raw_str.clone(),
+                PacketsInfoTypesEnum::Other(log) => log.raw_str.clone(),
             };
-            w.write_record([t.to_string(), log_str])?;
+            w.write_record(&[time_format.format(t), log_str, p.payload_hex()])?;
         }
         w.flush()?;
 
-        Ok(())
+        Ok(data.len())
     }
 }
 
@@ -170,6 +375,11 @@ impl Component for Export {
         Ok(())
     }
 
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -180,14 +390,41 @@ impl Component for Export {
             Action::ExportData(data) => {
                 let now = Local::now();
                 // let now_str = now.format("%Y-%m-%d-%H-%M-%S").to_string();
-                let now_str = now.timestamp().to_string();
-                let _ = self.write_discovery(data.scanned_ips, &now_str);
-                let _ = self.write_ports(data.scanned_ports, &now_str);
-                let _ = self.write_packets(data.arp_packets, &now_str, "arp");
-                let _ = self.write_packets(data.tcp_packets, &now_str, "tcp");
-                let _ = self.write_packets(data.udp_packets, &now_str, "udp");
-                let _ = self.write_packets(data.icmp_packets, &now_str, "icmp");
-                let _ = self.write_packets(data.icmp6_packets, &now_str, "icmp6");
+                let now_str = self.unique_export_stamp(now.timestamp().to_string());
+
+                // -- record counts per file for the status line
+                let time_format = data.time_format;
+                let counts = [
+                    ("report", self.write_report(&data, &now_str)),
+                    ("pcap frames", self.write_pcap(&data, &now_str)),
+                    ("IPs", self.write_discovery(data.scanned_ips, &now_str)),
+                    ("port hosts", self.write_ports(data.scanned_ports, &now_str)),
+                    ("ARP", self.write_packets(data.arp_packets, &now_str, "arp", time_format)),
+                    ("TCP", self.write_packets(data.tcp_packets, &now_str, "tcp", time_format)),
+                    ("UDP", self.write_packets(data.udp_packets, &now_str, "udp", time_format)),
+                    ("ICMP", self.write_packets(data.icmp_packets, &now_str, "icmp", time_format)),
+                    ("ICMP6", self.write_packets(data.icmp6_packets, &now_str, "icmp6", time_format)),
+                    ("IGMP", self.write_packets(data.igmp_packets, &now_str, "igmp", time_format)),
+                    ("other", self.write_packets(data.other_packets, &now_str, "other", time_format)),
+                    ("flows", self.write_flows(data.flows, &now_str)),
+                    ("anomalies", self.write_anomalies(data.anomalies, &now_str)),
+                ];
+                let summary: Vec<String> = counts
+                    .into_iter()
+                    .filter_map(|(name, count)| match count {
+                        Ok(0) => None,
+                        Ok(n) => Some(format!("{} {}", n, name)),
+                        Err(e) => {
+                            log::error!("Failed to export {}: {}", name, e);
+                            Some(format!("{} failed", name))
+                        }
+                    })
+                    .collect();
+                self.export_summary = if summary.is_empty() {
+                    String::from("no records")
+                } else {
+                    summary.join(", ")
+                };
 
                 self.export_done = true;
             }
@@ -211,6 +448,10 @@ impl Component for Export {
                     format!("{}/*", self.home_dir),
                     Style::default().fg(Color::Green),
                 ),
+                Span::styled(
+                    format!(" ({})", self.export_summary),
+                    Style::default().fg(Color::Yellow),
+                ),
                 Span::styled("|", Style::default().fg(Color::Yellow)),
             ]);
             f.render_widget(line, l_area);
@@ -219,3 +460,32 @@ impl Component for Export {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compact(ports: &[u16]) -> String {
+        Export::compact_port_ranges(ports, false)
+    }
+
+    #[test]
+    fn port_ranges() {
+        assert_eq!(compact(&[]), "");
+        assert_eq!(compact(&[22]), "22");
+        assert_eq!(compact(&[22, 80, 8080, 8081, 8082]), "22,80,8080-8082");
+        assert_eq!(compact(&[1, 2, 3, 5, 6]), "1-3,5-6");
+        assert_eq!(compact(&[65535]), "65535");
+        assert_eq!(compact(&[65533, 65534, 65535]), "65533-65535");
+        assert_eq!(compact(&[0, 1, 65535]), "0-1,65535");
+    }
+
+    #[test]
+    fn port_ranges_with_names() {
+        assert_eq!(Export::compact_port_ranges(&[22, 80, 81], true), "22(ssh),80-81");
+        assert_eq!(Export::compact_port_ranges(&[22, 80, 81], false), "22,80-81");
+        assert_eq!(Export::compact_port_ranges(&[443], true), "443(https)");
+        // -- a port without a known service stays a number
+        assert_eq!(Export::compact_port_ranges(&[49999], true), "49999");
+    }
+}
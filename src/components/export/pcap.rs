@@ -0,0 +1,132 @@
+//! Captured frames as a classic libpcap file, for Wireshark and tcpdump.
+//!
+//! Frames are written as stored by the packet log: Ethernet framed (loopback
+//! and TUN captures carry the header faked at capture time) and truncated to
+//! the capture snap length, with the length on the wire kept in each record.
+
+use std::io::Write;
+
+use chrono::{DateTime, Local};
+
+use crate::enums::PacketsInfoTypesEnum;
+
+/// Microsecond resolution timestamps.
+const MAGIC: u32 = 0xa1b2_c3d4;
+const VERSION_MAJOR: u16 = 2;
+const VERSION_MINOR: u16 = 4;
+const LINKTYPE_ETHERNET: u32 = 1;
+
+pub struct PcapWriter<W: Write> {
+    out: W,
+    snaplen: usize,
+}
+
+impl<W: Write> PcapWriter<W> {
+    pub fn new(mut out: W, snaplen: usize) -> std::io::Result<Self> {
+        let mut header = Vec::with_capacity(24);
+        header.extend(MAGIC.to_le_bytes());
+        header.extend(VERSION_MAJOR.to_le_bytes());
+        header.extend(VERSION_MINOR.to_le_bytes());
+        // -- timezone offset and timestamp accuracy, always zero
+        header.extend(0i32.to_le_bytes());
+        header.extend(0u32.to_le_bytes());
+        header.extend((snaplen as u32).to_le_bytes());
+        header.extend(LINKTYPE_ETHERNET.to_le_bytes());
+        out.write_all(&header)?;
+        Ok(Self { out, snaplen })
+    }
+
+    pub fn write_packet(
+        &mut self,
+        time: &DateTime<Local>,
+        packet: &PacketsInfoTypesEnum,
+    ) -> std::io::Result<()> {
+        let bytes = packet.raw_bytes();
+        let bytes = &bytes[..bytes.len().min(self.snaplen)];
+        let mut record = Vec::with_capacity(16 + bytes.len());
+        record.extend((time.timestamp() as u32).to_le_bytes());
+        record.extend(time.timestamp_subsec_micros().min(999_999).to_le_bytes());
+        record.extend((bytes.len() as u32).to_le_bytes());
+        record.extend((packet.orig_len().max(bytes.len()) as u32).to_le_bytes());
+        record.extend_from_slice(bytes);
+        self.out.write_all(&record)
+    }
+
+    pub fn finish(mut self) -> std::io::Result<()> {
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use chrono::TimeZone;
+    use pnet::packet::arp::ArpOperations;
+    use pnet::util::MacAddr;
+
+    use super::*;
+    use crate::enums::ARPPacketInfo;
+
+    fn frame(len: usize, orig_len: usize) -> PacketsInfoTypesEnum {
+        PacketsInfoTypesEnum::Arp(ARPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source_mac: MacAddr::new(2, 0, 0, 0, 0, 1),
+            source_ip: Ipv4Addr::new(10, 0, 0, 1),
+            destination_mac: MacAddr::zero(),
+            destination_ip: Ipv4Addr::new(10, 0, 0, 2),
+            operation: ArpOperations::Request,
+            source_vendor: None,
+            vlan: None,
+            raw_str: String::new(),
+            raw_bytes: vec![0xaa; len],
+            orig_len,
+        })
+    }
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn global_header() {
+        let mut out = vec![];
+        PcapWriter::new(&mut out, 262_144).unwrap().finish().unwrap();
+        assert_eq!(
+            out,
+            [
+                0xd4, 0xc3, 0xb2, 0xa1, 2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x00, 0x00, 0x04, 0x00,
+                1, 0, 0, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn record_header() {
+        let time = Local.timestamp_opt(1_700_000_000, 123_456_789).unwrap();
+        let mut out = vec![];
+        let mut writer = PcapWriter::new(&mut out, 65535).unwrap();
+        writer.write_packet(&time, &frame(42, 60)).unwrap();
+        writer.finish().unwrap();
+        let record = &out[24..];
+        assert_eq!(record.len(), 16 + 42);
+        assert_eq!(u32_at(record, 0), 1_700_000_000);
+        assert_eq!(u32_at(record, 4), 123_456);
+        assert_eq!(u32_at(record, 8), 42);
+        assert_eq!(u32_at(record, 12), 60);
+        assert!(record[16..].iter().all(|b| *b == 0xaa));
+    }
+
+    #[test]
+    fn frames_are_cut_to_the_snaplen() {
+        let time = Local.timestamp_opt(1_700_000_000, 0).unwrap();
+        let mut out = vec![];
+        let mut writer = PcapWriter::new(&mut out, 64).unwrap();
+        writer.write_packet(&time, &frame(100, 100)).unwrap();
+        writer.finish().unwrap();
+        let record = &out[24..];
+        assert_eq!(u32_at(&out, 16), 64);
+        assert_eq!((u32_at(record, 8), u32_at(record, 12)), (64, 100));
+        assert_eq!(record.len(), 16 + 64);
+    }
+}
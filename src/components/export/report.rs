@@ -0,0 +1,535 @@
+//! Human-readable session summary written next to the CSV files.
+//!
+//! The report is built from the same [`ExportData`] as the CSVs and rendered
+//! as Markdown, optionally also as HTML. It holds no generation time and every
+//! table has a fixed sort order, so reports of two runs diff cleanly.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Local};
+
+use super::Export;
+use crate::enums::{ExportData, PacketsInfoTypesEnum};
+use crate::utils::bytes_convert;
+
+const TOP_TALKERS: usize = 10;
+const TOP_PORTS: usize = 10;
+
+// -- protocol and service port
+type PortKey = (&'static str, u16);
+
+struct Section {
+    title: &'static str,
+    header: &'static [&'static str],
+    rows: Vec<Vec<String>>,
+    empty: &'static str,
+}
+
+pub struct Report {
+    facts: Vec<(&'static str, String)>,
+    sections: Vec<Section>,
+}
+
+fn md_escape(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Report {
+    pub fn new(data: &ExportData) -> Self {
+        let protocols = [
+            ("ARP", &data.arp_packets),
+            ("TCP", &data.tcp_packets),
+            ("UDP", &data.udp_packets),
+            ("ICMP", &data.icmp_packets),
+            ("ICMP6", &data.icmp6_packets),
+            ("IGMP", &data.igmp_packets),
+            ("other", &data.other_packets),
+        ];
+        let packets: Vec<&(DateTime<Local>, PacketsInfoTypesEnum)> =
+            protocols.iter().flat_map(|(_, p)| p.iter()).collect();
+
+        let mut facts = vec![(
+            "Interface",
+            data.interface.clone().unwrap_or_else(|| "-".to_string()),
+        )];
+        let first = packets.iter().map(|(t, _)| *t).min();
+        let last = packets.iter().map(|(t, _)| *t).max();
+        if let (Some(first), Some(last)) = (first, last) {
+            let secs = (last - first).num_seconds();
+            facts.push((
+                "Capture window",
+                format!(
+                    "{} - {} ({:02}:{:02}:{:02})",
+                    first.format("%Y-%m-%d %H:%M:%S"),
+                    last.format("%Y-%m-%d %H:%M:%S"),
+                    secs / 3600,
+                    (secs / 60) % 60,
+                    secs % 60
+                ),
+            ));
+        }
+        facts.push(("Packets", packets.len().to_string()));
+        // -- a lossy capture undercounts everything below
+        let kernel = match data.kernel_dropped {
+            Some(dropped) => dropped.to_string(),
+            None => "unknown".to_string(),
+        };
+        facts.push((
+            "Dropped",
+            format!("{} before the UI, {} by the kernel", data.dropped, kernel),
+        ));
+        facts.push(("Hosts", data.scanned_ips.len().to_string()));
+
+        let per_protocol = protocols
+            .iter()
+            .map(|(name, p)| {
+                let bytes: usize = p.iter().map(|(_, p)| p.orig_len()).sum();
+                vec![name.to_string(), p.len().to_string(), bytes_convert(bytes as f64)]
+            })
+            .collect();
+
+        Self {
+            facts,
+            sections: vec![
+                Section {
+                    title: "Packets per protocol",
+                    header: &["protocol", "packets", "bytes"],
+                    rows: per_protocol,
+                    empty: "",
+                },
+                Section {
+                    title: "Top talkers",
+                    header: &["address", "packets", "bytes"],
+                    rows: Self::top_talkers(&packets),
+                    empty: "No IP traffic captured.",
+                },
+                Section {
+                    title: "Top ports",
+                    header: &["port", "packets", "bytes"],
+                    rows: Self::top_ports(&packets),
+                    empty: "No TCP or UDP traffic captured.",
+                },
+                Section {
+                    title: "Discovered hosts",
+                    header: &["ip", "mac", "hostname", "vendor", "open ports"],
+                    rows: Self::hosts(data),
+                    empty: "No hosts discovered.",
+                },
+                Section {
+                    title: "Anomalies",
+                    header: &["finding"],
+                    rows: Self::anomalies(data),
+                    empty: "None detected.",
+                },
+            ],
+        }
+    }
+
+    // -- both endpoints of a packet are credited, ordered by bytes then address
+    fn top_talkers(packets: &[&(DateTime<Local>, PacketsInfoTypesEnum)]) -> Vec<Vec<String>> {
+        let mut talkers: HashMap<IpAddr, (usize, usize)> = HashMap::new();
+        for (_, packet) in packets {
+            let Some((source, destination)) = packet.addresses() else {
+                continue;
+            };
+            for ip in [source, destination] {
+                let entry = talkers.entry(ip).or_default();
+                entry.0 += 1;
+                entry.1 += packet.orig_len();
+            }
+        }
+        let mut talkers: Vec<(IpAddr, (usize, usize))> = talkers.into_iter().collect();
+        talkers.sort_by(|a, b| b.1 .1.cmp(&a.1 .1).then(a.0.cmp(&b.0)));
+        talkers
+            .into_iter()
+            .take(TOP_TALKERS)
+            .map(|(ip, (count, bytes))| {
+                vec![ip.to_string(), count.to_string(), bytes_convert(bytes as f64)]
+            })
+            .collect()
+    }
+
+    // -- the lower port of a packet is taken as the service, the other side is
+    // usually ephemeral; ordered by packets then protocol and port
+    fn top_ports(packets: &[&(DateTime<Local>, PacketsInfoTypesEnum)]) -> Vec<Vec<String>> {
+        let mut ports: HashMap<PortKey, (usize, usize)> = HashMap::new();
+        for (_, packet) in packets {
+            let key = match packet {
+                PacketsInfoTypesEnum::Tcp(p) => ("TCP", p.source_port.min(p.destination_port)),
+                PacketsInfoTypesEnum::Udp(p) => ("UDP", p.source_port.min(p.destination_port)),
+                _ => continue,
+            };
+            let entry = ports.entry(key).or_default();
+            entry.0 += 1;
+            entry.1 += packet.orig_len();
+        }
+        let mut ports: Vec<(PortKey, (usize, usize))> = ports.into_iter().collect();
+        ports.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.0.cmp(&b.0)));
+        ports
+            .into_iter()
+            .take(TOP_PORTS)
+            .map(|((protocol, port), (count, bytes))| {
+                vec![
+                    format!("{} {}", protocol, port),
+                    count.to_string(),
+                    bytes_convert(bytes as f64),
+                ]
+            })
+            .collect()
+    }
+
+    fn hosts(data: &ExportData) -> Vec<Vec<String>> {
+        let mut hosts: Vec<_> = data.scanned_ips.iter().collect();
+        hosts.sort_by_key(|h| h.ip_addr);
+        hosts
+            .into_iter()
+            .map(|h| {
+                let ports = data
+                    .scanned_ports
+                    .iter()
+                    .find(|p| p.ip == h.ip)
+                    .map(|p| {
+                        let mut ports = p.ports.clone();
+                        ports.sort_unstable();
+                        ports.dedup();
+                        Export::compact_port_ranges(&ports, false)
+                    })
+                    .unwrap_or_default();
+                let mut ip = h.ip.clone();
+                if let Some(role) = h.role {
+                    ip = format!("{} {}", ip, role.tag());
+                }
+                vec![ip, h.mac.clone(), h.hostname.clone(), h.vendor.clone(), ports]
+            })
+            .collect()
+    }
+
+    // -- what the Anomalies tab collected, oldest first; its detectors already
+    // cover retransmissions, low TTLs and addresses claimed by several MACs
+    fn anomalies(data: &ExportData) -> Vec<Vec<String>> {
+        data.anomalies
+            .iter()
+            .rev()
+            .map(|anomaly| {
+                vec![format!(
+                    "{} {} {}: {}",
+                    anomaly.time.format("%Y-%m-%d %H:%M:%S"),
+                    anomaly.severity,
+                    anomaly.category,
+                    anomaly.detail
+                )]
+            })
+            .collect()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# netscanner report\n\n");
+        for (name, value) in &self.facts {
+            out.push_str(&format!("- **{}**: {}\n", name, md_escape(value)));
+        }
+        for section in &self.sections {
+            out.push_str(&format!("\n## {}\n\n", section.title));
+            if section.rows.is_empty() {
+                out.push_str(&format!("{}\n", section.empty));
+                continue;
+            }
+            out.push_str(&format!("| {} |\n", section.header.join(" | ")));
+            out.push_str(&format!("|{}\n", " --- |".repeat(section.header.len())));
+            for row in &section.rows {
+                let cells: Vec<String> = row.iter().map(|c| md_escape(c)).collect();
+                out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+        }
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>netscanner report</title>\n</head>\n<body>\n<h1>netscanner report</h1>\n<ul>\n",
+        );
+        for (name, value) in &self.facts {
+            out.push_str(&format!("<li><b>{}</b>: {}</li>\n", name, html_escape(value)));
+        }
+        out.push_str("</ul>\n");
+        for section in &self.sections {
+            out.push_str(&format!("<h2>{}</h2>\n", section.title));
+            if section.rows.is_empty() {
+                out.push_str(&format!("<p>{}</p>\n", section.empty));
+                continue;
+            }
+            out.push_str("<table border=\"1\">\n<tr>");
+            for h in section.header {
+                out.push_str(&format!("<th>{}</th>", h));
+            }
+            out.push_str("</tr>\n");
+            for row in &section.rows {
+                out.push_str("<tr>");
+                for c in row {
+                    out.push_str(&format!("<td>{}</td>", html_escape(c)));
+                }
+                out.push_str("</tr>\n");
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::sync::Arc;
+
+    use chrono::TimeZone;
+    use pnet::packet::arp::ArpOperations;
+    use pnet::util::MacAddr;
+
+    use super::*;
+    use crate::components::discovery::ScannedIp;
+    use crate::components::packetdump::TimeFormat;
+    use crate::enums::{ARPPacketInfo, Anomaly, AnomalyCategory, IpMeta, Severity, TCPPacketInfo};
+
+    fn at(secs: i64) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap() + chrono::Duration::seconds(secs)
+    }
+
+    fn tcp(
+        source: &str,
+        source_port: u16,
+        destination: &str,
+        destination_port: u16,
+    ) -> PacketsInfoTypesEnum {
+        PacketsInfoTypesEnum::Tcp(TCPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source: source.parse().unwrap(),
+            source_port,
+            destination: destination.parse().unwrap(),
+            destination_port,
+            length: 20,
+            flags: 0x10,
+            sequence: 0,
+            acknowledgement: 0,
+            relative_sequence: None,
+            relative_acknowledgement: None,
+            payload_len: 0,
+            retransmission: false,
+            http_info: None,
+            payload: vec![],
+            ip: IpMeta::default(),
+            vlan: None,
+            raw_str: String::new(),
+            raw_bytes: vec![],
+            orig_len: 1000,
+        })
+    }
+
+    fn data() -> ExportData {
+        // -- flagged packets must only show up through the anomalies below
+        let mut retransmitted = tcp("192.168.1.10", 50000, "1.1.1.1", 443);
+        if let PacketsInfoTypesEnum::Tcp(ref mut tcp) = retransmitted {
+            tcp.retransmission = true;
+            tcp.ip.low_ttl = true;
+        }
+        let arp = |mac: u8| {
+            PacketsInfoTypesEnum::Arp(ARPPacketInfo {
+                interface_name: "eth0".to_string(),
+                source_mac: MacAddr::new(2, 0, 0, 0, 0, mac),
+                source_ip: Ipv4Addr::new(192, 168, 1, 1),
+                destination_mac: MacAddr::zero(),
+                destination_ip: Ipv4Addr::new(192, 168, 1, 10),
+                operation: ArpOperations::Reply,
+                source_vendor: None,
+                vlan: None,
+                raw_str: String::new(),
+                raw_bytes: vec![],
+                orig_len: 60,
+            })
+        };
+        let host = ScannedIp {
+            ip: "192.168.1.1".to_string(),
+            ip_addr: "192.168.1.1".parse().unwrap(),
+            mac: "02:00:00:00:00:01".to_string(),
+            hostname: "router|lan".to_string(),
+            vendor: "<none>".to_string(),
+            role: None,
+            first_seen: at(0),
+            last_seen: at(5),
+        };
+        let ports = serde_json::from_str(
+            r#"{"ip": "192.168.1.1", "state": "Done", "hostname": "", "ports": [443, 22, 80, 81, 82]}"#,
+        )
+        .unwrap();
+        let anomaly = |time, category, severity, detail: &str| Anomaly {
+            time,
+            category,
+            severity,
+            detail: detail.to_string(),
+        };
+        ExportData {
+            scanned_ips: Arc::new(vec![host]),
+            scanned_ports: Arc::new(vec![ports]),
+            arp_packets: Arc::new(vec![(at(1), arp(1)), (at(2), arp(2))]),
+            udp_packets: Arc::new(vec![]),
+            tcp_packets: Arc::new(vec![
+                (at(3), tcp("192.168.1.10", 50000, "1.1.1.1", 443)),
+                (at(4), retransmitted),
+                (at(65), tcp("192.168.1.10", 50001, "192.168.1.1", 22)),
+            ]),
+            icmp_packets: Arc::new(vec![]),
+            icmp6_packets: Arc::new(vec![]),
+            igmp_packets: Arc::new(vec![]),
+            other_packets: Arc::new(vec![]),
+            // -- newest first, like the Anomalies tab
+            anomalies: Arc::new(vec![
+                anomaly(
+                    at(9),
+                    AnomalyCategory::LowTtl,
+                    Severity::Warning,
+                    "1 routed packets with a TTL of 1 or less",
+                ),
+                anomaly(
+                    at(8),
+                    AnomalyCategory::Retransmission,
+                    Severity::Info,
+                    "1 likely TCP retransmissions",
+                ),
+                anomaly(
+                    at(2),
+                    AnomalyCategory::IpConflict,
+                    Severity::Critical,
+                    "192.168.1.1 claimed by 02:00:00:00:00:01 and 02:00:00:00:00:02",
+                ),
+            ]),
+            flows: Arc::new(vec![]),
+            interface: Some("eth0".to_string()),
+            time_format: TimeFormat::default(),
+            dropped: 3,
+            kernel_dropped: None,
+        }
+    }
+
+    #[test]
+    fn markdown() {
+        assert_eq!(Report::new(&data()).to_markdown(), MARKDOWN);
+    }
+
+    #[test]
+    fn html() {
+        assert_eq!(Report::new(&data()).to_html(), HTML);
+    }
+
+    const MARKDOWN: &str = r#"# netscanner report
+
+- **Interface**: eth0
+- **Capture window**: 2024-01-15 10:00:01 - 2024-01-15 10:01:05 (00:01:04)
+- **Packets**: 5
+- **Dropped**: 3 before the UI, unknown by the kernel
+- **Hosts**: 1
+
+## Packets per protocol
+
+| protocol | packets | bytes |
+| --- | --- | --- |
+| ARP | 2 | 120B |
+| TCP | 3 | 3kB |
+| UDP | 0 | 0B |
+| ICMP | 0 | 0B |
+| ICMP6 | 0 | 0B |
+| IGMP | 0 | 0B |
+| other | 0 | 0B |
+
+## Top talkers
+
+| address | packets | bytes |
+| --- | --- | --- |
+| 192.168.1.10 | 5 | 3.12kB |
+| 1.1.1.1 | 2 | 2kB |
+| 192.168.1.1 | 3 | 1.12kB |
+
+## Top ports
+
+| port | packets | bytes |
+| --- | --- | --- |
+| TCP 443 | 2 | 2kB |
+| TCP 22 | 1 | 1kB |
+
+## Discovered hosts
+
+| ip | mac | hostname | vendor | open ports |
+| --- | --- | --- | --- | --- |
+| 192.168.1.1 | 02:00:00:00:00:01 | router\|lan | <none> | 22,80-82,443 |
+
+## Anomalies
+
+| finding |
+| --- |
+| 2024-01-15 10:00:02 critical IP conflict: 192.168.1.1 claimed by 02:00:00:00:00:01 and 02:00:00:00:00:02 |
+| 2024-01-15 10:00:08 info retransmission: 1 likely TCP retransmissions |
+| 2024-01-15 10:00:09 warning low TTL: 1 routed packets with a TTL of 1 or less |
+"#;
+
+    const HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>netscanner report</title>
+</head>
+<body>
+<h1>netscanner report</h1>
+<ul>
+<li><b>Interface</b>: eth0</li>
+<li><b>Capture window</b>: 2024-01-15 10:00:01 - 2024-01-15 10:01:05 (00:01:04)</li>
+<li><b>Packets</b>: 5</li>
+<li><b>Dropped</b>: 3 before the UI, unknown by the kernel</li>
+<li><b>Hosts</b>: 1</li>
+</ul>
+<h2>Packets per protocol</h2>
+<table border="1">
+<tr><th>protocol</th><th>packets</th><th>bytes</th></tr>
+<tr><td>ARP</td><td>2</td><td>120B</td></tr>
+<tr><td>TCP</td><td>3</td><td>3kB</td></tr>
+<tr><td>UDP</td><td>0</td><td>0B</td></tr>
+<tr><td>ICMP</td><td>0</td><td>0B</td></tr>
+<tr><td>ICMP6</td><td>0</td><td>0B</td></tr>
+<tr><td>IGMP</td><td>0</td><td>0B</td></tr>
+<tr><td>other</td><td>0</td><td>0B</td></tr>
+</table>
+<h2>Top talkers</h2>
+<table border="1">
+<tr><th>address</th><th>packets</th><th>bytes</th></tr>
+<tr><td>192.168.1.10</td><td>5</td><td>3.12kB</td></tr>
+<tr><td>1.1.1.1</td><td>2</td><td>2kB</td></tr>
+<tr><td>192.168.1.1</td><td>3</td><td>1.12kB</td></tr>
+</table>
+<h2>Top ports</h2>
+<table border="1">
+<tr><th>port</th><th>packets</th><th>bytes</th></tr>
+<tr><td>TCP 443</td><td>2</td><td>2kB</td></tr>
+<tr><td>TCP 22</td><td>1</td><td>1kB</td></tr>
+</table>
+<h2>Discovered hosts</h2>
+<table border="1">
+<tr><th>ip</th><th>mac</th><th>hostname</th><th>vendor</th><th>open ports</th></tr>
+<tr><td>192.168.1.1</td><td>02:00:00:00:00:01</td><td>router|lan</td><td>&lt;none&gt;</td><td>22,80-82,443</td></tr>
+</table>
+<h2>Anomalies</h2>
+<table border="1">
+<tr><th>finding</th></tr>
+<tr><td>2024-01-15 10:00:02 critical IP conflict: 192.168.1.1 claimed by 02:00:00:00:00:01 and 02:00:00:00:00:02</td></tr>
+<tr><td>2024-01-15 10:00:08 info retransmission: 1 likely TCP retransmissions</td></tr>
+<tr><td>2024-01-15 10:00:09 warning low TTL: 1 routed packets with a TTL of 1 or less</td></tr>
+</table>
+</body>
+</html>
+"#;
+}
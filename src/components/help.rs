@@ -0,0 +1,206 @@
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::{Component, Frame};
+use crate::{
+    action::Action,
+    config::{Config, DEFAULT_BORDER_STYLE},
+    enums::TabsEnum,
+    mode::Mode,
+    session::Session,
+};
+
+const HELP_WIDTH: u16 = 64;
+
+/// Overlay listing the keys of the active tab, toggled with `Action::Help`.
+/// Keys are looked up in the configured keybindings, so remapped keys show
+/// as remapped and unbound actions are left out.
+#[derive(Default)]
+pub struct Help {
+    config: Config,
+    active_tab: TabsEnum,
+    visible: bool,
+}
+
+impl Help {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tab_actions(tab: TabsEnum) -> Vec<(Mode, Action, &'static str)> {
+        match tab {
+            TabsEnum::Discovery => vec![
+                (Mode::Normal, Action::ModeChange(Mode::Input), "edit the CIDR to scan"),
+                (Mode::Normal, Action::ScanCidr, "scan the CIDR"),
+                (Mode::Normal, Action::Up, "previous host"),
+                (Mode::Normal, Action::Down, "next host"),
+                (Mode::Normal, Action::Home, "first host"),
+                (Mode::Normal, Action::End, "last host"),
+                (Mode::Normal, Action::PageUp, "page up"),
+                (Mode::Normal, Action::PageDown, "page down"),
+                (Mode::Normal, Action::CopySelection, "copy IP and MAC"),
+            ],
+            TabsEnum::Packets => vec![
+                (Mode::Normal, Action::ModeChange(Mode::Input), "edit the filter, bpf: for kernel BPF"),
+                (Mode::Normal, Action::DumpToggle, "pause/resume capture"),
+                (Mode::Normal, Action::Left, "previous packet type"),
+                (Mode::Normal, Action::Right, "next packet type"),
+                (Mode::Normal, Action::Up, "previous packet"),
+                (Mode::Normal, Action::Down, "next packet"),
+                (Mode::Normal, Action::Home, "newest packet, follow again"),
+                (Mode::Normal, Action::End, "oldest packet"),
+                (Mode::Normal, Action::PageUp, "page up"),
+                (Mode::Normal, Action::PageDown, "page down"),
+                (Mode::Normal, Action::FollowToggle, "follow new packets"),
+                (Mode::Normal, Action::ShowDetail, "packet detail and hex dump"),
+                (Mode::Normal, Action::CopySelection, "copy packet or detail"),
+                (Mode::Normal, Action::WrapToggle, "wrap long rows"),
+                (Mode::Normal, Action::FlowsToggle, "flows/groups view"),
+                (Mode::Normal, Action::FlowOrderCycle, "sort flows by recency/bytes"),
+                (Mode::Normal, Action::TalkersToggle, "top talkers, Enter filters one"),
+                (Mode::Normal, Action::FlowColorsToggle, "color by conversation"),
+                (Mode::Normal, Action::RelativeSeqToggle, "relative/absolute TCP seq"),
+                (Mode::Normal, Action::TimeFormatCycle, "time, date or epoch"),
+                (Mode::Normal, Action::TimeDeltaToggle, "time since previous row"),
+                (Mode::Normal, Action::PresetCycle, "cycle filter presets"),
+                (Mode::Normal, Action::HistoryGrow, "keep twice the packets"),
+                (Mode::Normal, Action::HistoryShrink, "keep half the packets"),
+                (Mode::Normal, Action::SaveFilter, "save the filter"),
+                (Mode::Normal, Action::RecallFilter, "recall a saved filter"),
+                (Mode::Normal, Action::TcpdumpCommand, "filter as tcpdump command"),
+                (Mode::Normal, Action::DisableProtocol, "stop capturing this type"),
+                (Mode::Normal, Action::EnableProtocols, "capture all types again"),
+                (Mode::Normal, Action::Clear, "clear the filter"),
+            ],
+            TabsEnum::Ports => vec![
+                (Mode::Normal, Action::ScanCidr, "scan ports of discovered hosts"),
+                (Mode::Normal, Action::Up, "previous host"),
+                (Mode::Normal, Action::Down, "next host"),
+                (Mode::Normal, Action::Home, "first host"),
+                (Mode::Normal, Action::End, "last host"),
+                (Mode::Normal, Action::PageUp, "page up"),
+                (Mode::Normal, Action::PageDown, "page down"),
+            ],
+            TabsEnum::Traffic => vec![
+                (Mode::Normal, Action::Up, "scroll up"),
+                (Mode::Normal, Action::Down, "scroll down"),
+            ],
+            TabsEnum::Anomalies => vec![
+                (Mode::Normal, Action::Up, "previous anomaly"),
+                (Mode::Normal, Action::Down, "next anomaly"),
+                (Mode::Normal, Action::Home, "newest anomaly"),
+                (Mode::Normal, Action::End, "oldest anomaly"),
+                (Mode::Normal, Action::PageUp, "page up"),
+                (Mode::Normal, Action::PageDown, "page down"),
+            ],
+        }
+    }
+
+    fn global_actions() -> Vec<(Mode, Action, &'static str)> {
+        vec![
+            (Mode::Input, Action::Submit, "apply input"),
+            (Mode::Input, Action::ModeChange(Mode::Normal), "leave input"),
+            (Mode::Normal, Action::Tab, "next tab"),
+            (Mode::Normal, Action::TabChange(TabsEnum::Discovery), "discovery tab"),
+            (Mode::Normal, Action::TabChange(TabsEnum::Packets), "packets tab"),
+            (Mode::Normal, Action::TabChange(TabsEnum::Ports), "ports tab"),
+            (Mode::Normal, Action::TabChange(TabsEnum::Traffic), "traffic tab"),
+            (Mode::Normal, Action::TabChange(TabsEnum::Anomalies), "anomalies tab"),
+            (Mode::Normal, Action::InterfaceSwitch, "next interface"),
+            (Mode::Normal, Action::InterfaceSwitchAll, "next interface, incl. down"),
+            (Mode::Normal, Action::InterfaceMultiToggle, "pick more interfaces"),
+            (Mode::Normal, Action::InterfaceCaptureToggle, "capture on picked one too"),
+            (Mode::Normal, Action::GraphToggle, "wifi graph"),
+            (Mode::Normal, Action::Export, "export to CSV and pcap"),
+            (Mode::Normal, Action::SaveSession(Session::default_path()), "save session"),
+            (Mode::Normal, Action::LoadSession(Session::default_path()), "load session"),
+            (Mode::Normal, Action::Help, "toggle this help"),
+            (Mode::Normal, Action::Suspend, "suspend"),
+            (Mode::Normal, Action::Quit, "quit"),
+        ]
+    }
+
+    fn make_rows(&self) -> Vec<Row<'static>> {
+        let tab = Self::tab_actions(self.active_tab);
+        let global = Self::global_actions();
+        let mut rows = vec![];
+        for (section, actions) in [(self.active_tab.to_string(), tab), ("Global".to_string(), global)] {
+            let bound: Vec<(String, &'static str)> = actions
+                .iter()
+                .filter_map(|(mode, action, desc)| {
+                    let keys = self.config.keybindings.keys_for(*mode, action);
+                    (!keys.is_empty()).then(|| (keys.join(" "), *desc))
+                })
+                .collect();
+            if bound.is_empty() {
+                continue;
+            }
+            rows.push(Row::new(vec![Cell::from(Span::styled(
+                section,
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ))]));
+            for (keys, desc) in bound {
+                rows.push(Row::new(vec![
+                    Cell::from(Span::styled(keys, Style::default().fg(Color::Red))),
+                    Cell::from(Span::styled(desc, Style::default().fg(Color::Green))),
+                ]));
+            }
+        }
+        rows
+    }
+
+    fn make_table(rows: Vec<Row<'static>>) -> Table<'static> {
+        Table::new(rows, [Constraint::Length(22), Constraint::Fill(1)]).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+                .border_type(DEFAULT_BORDER_STYLE)
+                .title(ratatui::widgets::block::Title::from(Line::from(vec![
+                    Span::styled("|", Style::default().fg(Color::Yellow)),
+                    Span::styled(
+                        "?",
+                        Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
+                    ),
+                    Span::styled(" keys|", Style::default().fg(Color::Yellow)),
+                ]))),
+        )
+    }
+}
+
+impl Component for Help {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Help => self.visible = !self.visible,
+            Action::TabChange(tab) => self.active_tab = tab,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let rows = self.make_rows();
+        let width = HELP_WIDTH.min(area.width);
+        let height = (rows.len() as u16 + 2).min(area.height);
+        let rect = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        f.render_widget(Clear, rect);
+        f.render_widget(Self::make_table(rows), rect);
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use ipnetwork::IpNetwork;
 use pnet::{
     datalink::{self, NetworkInterface},
     util::MacAddr,
@@ -17,12 +18,30 @@ use crate::{
     tui::Frame,
 };
 
+/// How the interface to capture on is picked at startup.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum InterfaceSelect {
+    /// First active interface with a private IPv4 address.
+    #[default]
+    Default,
+    /// Interface with an address inside the given subnet.
+    Subnet(IpNetwork),
+    /// First up, non-loopback interface with any IPv4 address.
+    Auto,
+}
+
 pub struct Interfaces {
     action_tx: Option<Sender<Action>>,
+    select: InterfaceSelect,
     interfaces: Vec<NetworkInterface>,
     last_update_time: Instant,
     active_interfaces: Vec<NetworkInterface>,
     active_interface_index: usize,
+    forced_interface: Option<NetworkInterface>,
+    // -- picking interfaces to capture on next to the active one
+    multi: bool,
+    cursor: usize,
+    captured: Vec<String>,
 }
 
 impl Default for Interfaces {
@@ -35,10 +54,71 @@ impl Interfaces {
     pub fn new() -> Self {
         Self {
             action_tx: None,
+            select: InterfaceSelect::Default,
             interfaces: Vec::new(),
             last_update_time: Instant::now(),
             active_interfaces: Vec::new(),
             active_interface_index: 0,
+            forced_interface: None,
+            multi: false,
+            cursor: 0,
+            captured: Vec::new(),
+        }
+    }
+
+    pub fn with_select(mut self, select: InterfaceSelect) -> Self {
+        self.select = select;
+        self
+    }
+
+    fn find_interface(&self, select: InterfaceSelect) -> Option<NetworkInterface> {
+        let found = match select {
+            InterfaceSelect::Default => return None,
+            InterfaceSelect::Subnet(net) => self
+                .interfaces
+                .iter()
+                .find(|i| i.ips.iter().any(|ip| net.contains(ip.ip()))),
+            InterfaceSelect::Auto => self.interfaces.iter().find(|i| {
+                (cfg!(windows) || i.is_up())
+                    && !i.is_loopback()
+                    && i.ips.iter().any(|ip| ip.is_ipv4())
+            }),
+        };
+        found.cloned()
+    }
+
+    /// Apply the startup selection, falling back to the first active interface
+    /// with a note on the status line when nothing matches.
+    fn select_startup_interface(&mut self) {
+        if self.select == InterfaceSelect::Default {
+            self.send_active_interface();
+            return;
+        }
+        let Some(interface) = self.find_interface(self.select) else {
+            let note = match self.select {
+                InterfaceSelect::Subnet(net) => format!("No interface in {}", net),
+                _ => String::from("No up interface with an IPv4 address"),
+            };
+            if let Some(tx) = &self.action_tx {
+                let _ = tx.try_send(Action::Error(format!("{}, using the default", note)));
+            }
+            self.send_active_interface();
+            return;
+        };
+
+        match self.active_interfaces.iter().position(|i| i.name == interface.name) {
+            Some(idx) => {
+                self.active_interface_index = idx;
+                self.send_active_interface();
+            }
+            None => {
+                let Some(tx) = self.action_tx.clone() else {
+                    log::error!("Cannot send active interface: action channel not initialized");
+                    return;
+                };
+                let _ = tx.try_send(Action::ActiveInterface(interface.clone()));
+                self.forced_interface = Some(interface);
+            }
         }
     }
 
@@ -68,6 +148,11 @@ impl Interfaces {
     }
     
     fn next_active_interface(&mut self) {
+        // -- back from a forced interface, resend the active one even if unchanged
+        if self.forced_interface.take().is_some() {
+            self.send_active_interface();
+            return;
+        }
         let mut new_index = self.active_interface_index + 1;
         if new_index >= self.active_interfaces.len() {
             new_index = 0;
@@ -78,6 +163,32 @@ impl Interfaces {
         }
     }
 
+    /// Cycle through every interface, not only the active ones,
+    /// so down and loopback interfaces can be captured on as well.
+    fn next_any_interface(&mut self) {
+        if self.interfaces.is_empty() {
+            return;
+        }
+        let current = self
+            .forced_interface
+            .as_ref()
+            .or(self.active_interfaces.get(self.active_interface_index));
+        let new_index = match current {
+            Some(ci) => match self.interfaces.iter().position(|i| i.name == ci.name) {
+                Some(idx) => (idx + 1) % self.interfaces.len(),
+                None => 0,
+            },
+            None => 0,
+        };
+        let interface = self.interfaces[new_index].clone();
+        let Some(tx) = self.action_tx.clone() else {
+            log::error!("Cannot send active interface: action channel not initialized");
+            return;
+        };
+        let _ = tx.try_send(Action::ActiveInterface(interface.clone()));
+        self.forced_interface = Some(interface);
+    }
+
     fn send_active_interface(&mut self) {
         if !self.active_interfaces.is_empty() {
             let Some(tx) = self.action_tx.clone() else {
@@ -89,6 +200,52 @@ impl Interfaces {
         }
     }
 
+    /// The interface packets are captured on, forced or one of the active ones.
+    fn current_interface(&self) -> Option<&NetworkInterface> {
+        self.forced_interface
+            .as_ref()
+            .or(self.active_interfaces.get(self.active_interface_index))
+    }
+
+    fn toggle_multi(&mut self) {
+        self.multi = !self.multi;
+        if self.multi {
+            let current = self.current_interface().map(|i| i.name.clone());
+            self.cursor = self
+                .interfaces
+                .iter()
+                .position(|i| Some(&i.name) == current.as_ref())
+                .unwrap_or(0);
+        }
+    }
+
+    fn next_cursor(&mut self) {
+        if !self.interfaces.is_empty() {
+            self.cursor = (self.cursor + 1) % self.interfaces.len();
+        }
+    }
+
+    /// Capture on the interface under the cursor as well, or stop doing so.
+    fn toggle_capture(&mut self) {
+        let Some(interface) = self.interfaces.get(self.cursor).cloned() else {
+            return;
+        };
+        // -- already captured on
+        if self.current_interface().is_some_and(|i| i.name == interface.name) {
+            return;
+        }
+        let Some(tx) = self.action_tx.clone() else {
+            log::error!("Cannot send interface: action channel not initialized");
+            return;
+        };
+        // -- marked once the packet dump reports the capture running
+        if self.captured.contains(&interface.name) {
+            let _ = tx.try_send(Action::RemoveInterface(interface.name));
+        } else {
+            let _ = tx.try_send(Action::AddInterface(interface));
+        }
+    }
+
     fn app_tick(&mut self) -> Result<()> {
         let now = Instant::now();
         let elapsed = (now - self.last_update_time).as_secs_f64();
@@ -100,21 +257,21 @@ impl Interfaces {
     }
 
     fn make_table(&mut self) -> Table<'_> {
-        let mut active_interface: Option<&NetworkInterface> = None;
-        if !self.active_interfaces.is_empty() {
-            active_interface = Some(&self.active_interfaces[self.active_interface_index]);
-        }
+        let active_interface = self.current_interface();
         let header = Row::new(vec!["", "name", "mac", "ipv4", "ipv6"])
             .style(Style::default().fg(Color::Yellow))
             .height(1);
         let mut rows = Vec::new();
-        for w in &self.interfaces {
+        for (idx, w) in self.interfaces.iter().enumerate() {
             let mut active = String::from("");
             if let Some(ai) = active_interface {
-                if ai == w {
+                if ai.name == w.name {
                     active = String::from("*");
                 }
             }
+            if self.captured.contains(&w.name) {
+                active = String::from("+");
+            }
             let name = if cfg!(windows) {
                 w.description.clone()
             } else {
@@ -159,10 +316,25 @@ impl Interfaces {
                     Cell::from(ipv4.clone()),
                     Cell::from(vec![Line::from(ipv6)]),
                 ])
-                .height(row_height), // .bottom_margin((ipv4.len()) as u16)
+                .height(row_height) // .bottom_margin((ipv4.len()) as u16)
+                .style(if self.multi && idx == self.cursor {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                }),
             );
         }
 
+        let mut title = vec![
+            Span::styled("|Inter", Style::default().fg(Color::Yellow)),
+            Span::styled("f", Style::default().fg(Color::Red)),
+            Span::styled("aces", Style::default().fg(Color::Yellow)),
+        ];
+        if self.multi {
+            title.push(Span::styled(" pick", Style::default().fg(Color::Magenta)));
+        }
+        title.push(Span::styled("|", Style::default().fg(Color::Yellow)));
+
         let table = Table::new(
             rows,
             [
@@ -176,11 +348,7 @@ impl Interfaces {
         .header(header)
         .block(
             Block::default()
-                .title(Line::from(vec![
-                    Span::styled("|Inter", Style::default().fg(Color::Yellow)),
-                    Span::styled("f", Style::default().fg(Color::Red)),
-                    Span::styled("aces|", Style::default().fg(Color::Yellow)),
-                ]))
+                .title(Line::from(title))
                 .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
                 .title_style(Style::default().fg(Color::Yellow))
                 .title_alignment(Alignment::Right)
@@ -196,7 +364,7 @@ impl Interfaces {
 impl Component for Interfaces {
     fn init(&mut self, _area: Size) -> Result<()> {
         self.get_interfaces();
-        self.send_active_interface();
+        self.select_startup_interface();
         Ok(())
     }
 
@@ -213,8 +381,26 @@ impl Component for Interfaces {
         if let Action::Tick = action {
             self.app_tick()?
         }
-        if let Action::InterfaceSwitch = action {
-            self.next_active_interface();
+        if let Action::InterfaceMultiToggle = action {
+            self.toggle_multi();
+        }
+        // -- while picking, the switch keys move the cursor instead
+        if let Action::InterfaceSwitch | Action::InterfaceSwitchAll = action {
+            if self.multi {
+                self.next_cursor();
+            } else if action == Action::InterfaceSwitch {
+                self.next_active_interface();
+            } else {
+                self.next_any_interface();
+            }
+        }
+        if let Action::InterfaceCaptureToggle = action {
+            if self.multi {
+                self.toggle_capture();
+            }
+        }
+        if let Action::ExtraInterfaces(names) = action {
+            self.captured = names;
         }
 
         Ok(None)
@@ -1,6 +1,7 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveTime};
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
+use ipnetwork::IpNetwork;
 
 use pnet::datalink::{Channel, ChannelType, NetworkInterface};
 use pnet::packet::icmpv6::Icmpv6Types;
@@ -12,7 +13,7 @@ use pnet::packet::{
     ip::{IpNextHeaderProtocol, IpNextHeaderProtocols},
     ipv4::Ipv4Packet,
     ipv6::Ipv6Packet,
-    tcp::TcpPacket,
+    tcp::{TcpFlags, TcpPacket},
     udp::UdpPacket, Packet,
 };
 use pnet::util::MacAddr;
@@ -21,46 +22,537 @@ use ratatui::layout::Position;
 use ratatui::style::Stylize;
 use ratatui::{prelude::*, widgets::*};
 use std::{
-    net::{IpAddr, Ipv4Addr},
+    collections::BTreeMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    path::PathBuf,
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicU16, AtomicU64, AtomicU8, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Arc, Mutex,
     },
     thread::{self, JoinHandle},
-    time::Duration,
+    time::{Duration, Instant},
 };
-use tokio::sync::mpsc::Sender;
+use tokio::sync::mpsc::{error::TrySendError, Sender};
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
 use super::{Component, Frame};
 use crate::{
     action::Action,
-    config::DEFAULT_BORDER_STYLE,
+    config::{
+        CaptureConfig, Config, PortProtocol, DEFAULT_BORDER_STYLE, MAX_PACKET_HISTORY_SIZE,
+    },
     enums::{
-        ARPPacketInfo, ICMP6PacketInfo, ICMPPacketInfo, PacketTypeEnum, PacketsInfoTypesEnum,
-        TCPPacketInfo, TabsEnum, UDPPacketInfo,
+        ARPPacketInfo, Anomaly, AnomalyCategory, ICMP6PacketInfo, ICMPPacketInfo, IGMPPacketInfo,
+        IpMeta, OtherPacketInfo, PacketTypeEnum, PacketsInfoTypesEnum, Severity, TCPPacketInfo,
+        TabsEnum, UDPPacketInfo, Vlan,
     },
-    layout::get_vertical_layout,
+    layout::{get_input_rect, get_vertical_layout},
+    session::Session,
     mode::Mode,
-    privilege,
-    utils::MaxSizeVec,
+    privilege, services,
+    utils::{bytes_convert, MaxSizeVec},
+    vendors,
 };
-use strum::{EnumCount, IntoEnumIterator};
+use strum::IntoEnumIterator;
+
+mod bounded;
+mod bpf;
+mod color_rules;
+mod detail;
+mod dhcp;
+mod dns;
+mod echo;
+mod ethertypes;
+mod field_filter;
+mod filter_history;
+mod flows;
+mod groups;
+mod http;
+mod icmp;
+mod igmp;
+mod ip_conflicts;
+mod ipv6_ext;
+mod kernel_stats;
+mod loopback;
+mod mdns;
+mod ndp;
+mod pcap;
+mod presets;
+mod ssdp;
+mod tcp_flags;
+mod rate;
+mod retransmit;
+mod seqnums;
+mod saved_filters;
+mod stream;
+mod talkers;
+mod tcpdump;
+mod text_filter;
+mod time_format;
+
+use bpf::{BpfExpr, BpfInsn};
+use color_rules::ColorRules;
+use detail::{hex_dump, PacketDetail};
+use echo::{EchoReply, EchoTracker};
+use field_filter::FieldFilter;
+use filter_history::FilterHistory;
+use flows::{FlowOrder, FlowTable};
+pub use flows::FlowStats;
+use groups::GroupTable;
+use kernel_stats::KernelStats;
+use pcap::PcapReader;
+use presets::{FilterPreset, DNS_PORTS};
+use rate::{count_str, ByteRate, Rate, RATE_WINDOW_SECS};
+use ip_conflicts::{Conflict, ConflictTracker};
+use retransmit::RetransmitTracker;
+use seqnums::SequenceBases;
+use saved_filters::SavedFilters;
+use talkers::TalkerTable;
+use tcpdump::TcpdumpCommand;
+use text_filter::TextFilter;
+use time_format::{format_delta, DELTA_WIDTH};
+pub use time_format::TimeFormat;
 
 const INPUT_SIZE: usize = 30;
 
+// Background of every other packet log row when `ui.zebra_rows` is on
+const ZEBRA_ROW_BG: Color = Color::Rgb(40, 40, 40);
+
+// Badge colors of conversations when flow coloring is on, all readable with black text
+const FLOW_PALETTE: [Color; 12] = [
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::Rgb(255, 165, 0),
+    Color::Rgb(180, 140, 255),
+    Color::Rgb(0, 200, 170),
+    Color::Rgb(255, 130, 180),
+    Color::Rgb(200, 200, 90),
+    Color::Rgb(130, 200, 255),
+];
+
+// -- sparkline is hidden when narrower than this, and keeps this much room on its left
+const SPARKLINE_MIN_WIDTH: u16 = 12;
+const SPARKLINE_MIN_LEFT: u16 = 24;
+// Fits the hex/ASCII dump lines of the packet detail popup
+const DETAIL_WIDTH: u16 = 80;
+
 // Network packet capture buffer size
 // Standard Ethernet MTU is 1500 bytes + 14 bytes Ethernet header = 1514 bytes
 // Jumbo frames can be up to 9000 bytes + headers = 9018 bytes
 // We use 9100 to support jumbo frames with overhead for VLAN tags and extensions
 const MAX_PACKET_BUFFER_SIZE: usize = 9100;
 
-// Maximum number of packets to keep in history per packet type
-// Limits memory usage to approximately 1000 packets * average packet size
-// This provides sufficient history for analysis while preventing unbounded growth
+// Packets kept in history per packet type until `capture.packet_history_size`
+// is applied, limits memory usage to that many packets * average packet size
 const MAX_PACKET_HISTORY: usize = 1000;
 
+// Smallest history the runtime resize goes down to
+const MIN_PACKET_HISTORY: usize = 100;
+
+// Maximum number of flows tracked by the flows view, least recently seen are evicted
+const MAX_FLOWS: usize = 1000;
+
+// Maximum number of sources tracked by the top talkers view
+const MAX_TALKERS: usize = 1000;
+
+/// Connection directions tracked for retransmissions, and how long an idle one
+/// is kept.
+const MAX_RETRANSMIT_FLOWS: usize = 1000;
+const RETRANSMIT_IDLE_SECS: i64 = 60;
+
+/// Echo requests awaiting a reply, and how long one is waited for.
+const MAX_PENDING_ECHOES: usize = 1000;
+const ECHO_TIMEOUT_SECS: i64 = 10;
+
+/// How often the capture thread reports the interface's kernel counters.
+const KERNEL_STATS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// TTLs at or below this are flagged on routed traffic.
+const LOW_TTL: u8 = 2;
+
+/// Per-packet detections are summed up and reported as one anomaly this often.
+const ANOMALY_INTERVAL: Duration = Duration::from_secs(5);
+
+// -- detections not yet reported to the Anomalies tab, and the drop counters
+// as of the last report so only growth is reported
+#[derive(Default)]
+struct PendingAnomalies {
+    retransmissions: usize,
+    low_ttl: usize,
+    dropped: u64,
+    kernel_dropped: u64,
+    oversized: u64,
+}
+
+// Packet types excluded from decoding and storage, one bit per `PacketTypeEnum`
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct DisabledTypes(u16);
+
+impl DisabledTypes {
+    fn from_types(types: &[PacketTypeEnum]) -> Self {
+        types.iter().fold(Self::default(), |mask, t| mask.with(*t))
+    }
+
+    fn with(self, packet_type: PacketTypeEnum) -> Self {
+        Self(self.0 | 1 << packet_type as u16)
+    }
+
+    fn contains(self, packet_type: PacketTypeEnum) -> bool {
+        self.0 & 1 << packet_type as u16 != 0
+    }
+}
+
+// What the capture thread decodes, updated from the UI while it runs
+#[derive(Debug, Default)]
+struct DecodeFilter {
+    // -- packet type being viewed, for `capture.selected_type_only`
+    selected: AtomicU8,
+    disabled: AtomicU16,
+    // -- BPF program attached when the channel opens, changing it needs a restart
+    kernel: Mutex<Option<Vec<BpfInsn>>>,
+}
+
+impl DecodeFilter {
+    fn disabled(&self) -> DisabledTypes {
+        DisabledTypes(self.disabled.load(Ordering::Relaxed))
+    }
+
+    fn kernel_program(&self) -> Option<Vec<BpfInsn>> {
+        self.kernel.lock().ok()?.clone()
+    }
+
+    /// Returns whether the program changed.
+    fn set_kernel_program(&self, program: Option<Vec<BpfInsn>>) -> bool {
+        let Ok(mut kernel) = self.kernel.lock() else {
+            return false;
+        };
+        if *kernel == program {
+            return false;
+        }
+        *kernel = program;
+        true
+    }
+}
+
+// Frames the capture threads lost or could not decode, shown in the dump title
+#[derive(Debug, Default)]
+struct CaptureCounters {
+    // -- actions lost to a full channel
+    dropped: AtomicU64,
+    // -- frames with a truncated or invalid IP header, skipped
+    malformed: AtomicU64,
+    // -- frames longer than the decode buffer, reported as one anomaly a flush
+    oversized: AtomicU64,
+    // -- frames and bytes read on all interfaces since the active capture
+    // started, checked against capture.max_* by its thread
+    packets: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl CaptureCounters {
+    fn reset(&self) {
+        self.dropped.store(0, Ordering::Relaxed);
+        self.malformed.store(0, Ordering::Relaxed);
+        self.oversized.store(0, Ordering::Relaxed);
+    }
+}
+
+// A frame being decoded: its leading bytes, truncated to the configured snap
+// length, plus the capture settings the protocol handlers depend on
+struct CapturedFrame<'a> {
+    bytes: Vec<u8>,
+    orig_len: usize,
+    // -- when the frame was read, from the file when replaying
+    time: DateTime<Local>,
+    // -- replayed frames wait for room in the channel instead of being dropped
+    replayed: bool,
+    capture: &'a CaptureConfig,
+    counters: &'a CaptureCounters,
+    // -- packet type being viewed, `All` unless `capture.selected_type_only` is on
+    wanted: PacketTypeEnum,
+    disabled: DisabledTypes,
+    // -- set by the IP layer handlers, read by the transport handlers
+    ip: std::cell::Cell<IpMeta>,
+    // -- 802.1Q tag stripped before the inner frame was decoded
+    vlan: std::cell::Cell<Option<Vlan>>,
+}
+
+impl<'a> CapturedFrame<'a> {
+    fn new(
+        packet: &[u8],
+        capture: &'a CaptureConfig,
+        counters: &'a CaptureCounters,
+        wanted: PacketTypeEnum,
+        disabled: DisabledTypes,
+    ) -> Self {
+        Self {
+            bytes: packet[..packet.len().min(capture.snaplen)].to_vec(),
+            orig_len: packet.len(),
+            time: Local::now(),
+            replayed: false,
+            capture,
+            counters,
+            wanted,
+            disabled,
+            ip: std::cell::Cell::new(IpMeta::default()),
+            vlan: std::cell::Cell::new(None),
+        }
+    }
+
+    fn replayed(mut self, time: DateTime<Local>, orig_len: usize) -> Self {
+        self.time = time;
+        self.orig_len = orig_len;
+        self.replayed = true;
+        self
+    }
+
+    // -- frames read without a real Ethernet header are kept with the fake one,
+    // so they export like any other Ethernet frame
+    fn rewrap(&mut self, frame: &[u8]) {
+        self.bytes = frame[..frame.len().min(self.capture.snaplen)].to_vec();
+        self.orig_len = frame.len();
+    }
+
+    // -- leading payload bytes kept with a TCP or UDP packet
+    fn snippet(&self, payload: &[u8]) -> Vec<u8> {
+        payload[..payload.len().min(self.capture.payload_snippet)].to_vec()
+    }
+
+    // Whether packets of this type are decoded at all
+    fn wants(&self, packet_type: PacketTypeEnum) -> bool {
+        !self.disabled.contains(packet_type)
+            && (self.wanted == PacketTypeEnum::All || self.wanted == packet_type)
+    }
+
+    fn send(&self, action_tx: &Sender<Action>, mut action: Action) {
+        if let (Action::PacketDump(_, packet, _), Some(vlan)) = (&mut action, self.vlan.get()) {
+            packet.tag_vlan(vlan);
+        }
+        if self.replayed {
+            let _ = action_tx.blocking_send(action);
+            return;
+        }
+        if let Err(TrySendError::Full(_)) = action_tx.try_send(action) {
+            self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn malformed(&self) {
+        self.counters.malformed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// Optional time window applied to the packet list together with the text filter.
+// Parsed out of the filter input from `from:HH:MM[:SS]`, `to:HH:MM[:SS]` and
+// `last:<n>[s|m|h]` tokens.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct TimeRange {
+    from: Option<NaiveTime>,
+    to: Option<NaiveTime>,
+    last: Option<chrono::Duration>,
+}
+
+impl TimeRange {
+    /// Split the time bound tokens out of `filter`, returning the range and the
+    /// remaining substring filter.
+    fn parse(filter: &str) -> (Self, String) {
+        let mut range = TimeRange::default();
+        let mut rest: Vec<&str> = vec![];
+        for token in filter.split_whitespace() {
+            if let Some(t) = token.strip_prefix("from:").and_then(Self::parse_time) {
+                range.from = Some(t);
+            } else if let Some(t) = token.strip_prefix("to:").and_then(Self::parse_time) {
+                range.to = Some(t);
+            } else if let Some(d) = token.strip_prefix("last:").and_then(Self::parse_duration) {
+                range.last = Some(d);
+            } else {
+                rest.push(token);
+            }
+        }
+        if range.is_empty() {
+            // -- no time bounds, keep the text filter untouched
+            return (range, filter.to_string());
+        }
+        (range, rest.join(" "))
+    }
+
+    fn parse_time(s: &str) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(s, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(s, "%H:%M"))
+            .ok()
+    }
+
+    fn parse_duration(s: &str) -> Option<chrono::Duration> {
+        let (value, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+            Some(idx) => s.split_at(idx),
+            None => (s, "s"),
+        };
+        let value: i64 = value.parse().ok()?;
+        match unit {
+            "s" => chrono::Duration::try_seconds(value),
+            "m" => chrono::Duration::try_minutes(value),
+            "h" => chrono::Duration::try_hours(value),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.from.is_none() && self.to.is_none() && self.last.is_none()
+    }
+
+    fn contains(&self, time: &DateTime<Local>, now: &DateTime<Local>) -> bool {
+        if let Some(from) = self.from {
+            if time.time() < from {
+                return false;
+            }
+        }
+        if let Some(to) = self.to {
+            if time.time() > to {
+                return false;
+            }
+        }
+        if let Some(last) = self.last {
+            if *now - *time > last {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// Capture start time and running duration shown in the table title. The
+// duration only advances while the dump is running.
+#[derive(Debug, Default, Clone, Copy)]
+struct CaptureClock {
+    started: Option<DateTime<Local>>,
+    elapsed: Duration,
+    running_since: Option<Instant>,
+}
+
+impl CaptureClock {
+    fn resume(&mut self) {
+        if self.started.is_none() {
+            self.started = Some(Local::now());
+        }
+        if self.running_since.is_none() {
+            self.running_since = Some(Instant::now());
+        }
+    }
+
+    fn pause(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.elapsed += since.elapsed();
+        }
+    }
+
+    fn duration(&self) -> Duration {
+        self.elapsed + self.running_since.map(|s| s.elapsed()).unwrap_or_default()
+    }
+
+    fn duration_str(&self) -> String {
+        let secs = self.duration().as_secs();
+        format!("{:02}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+    }
+}
+
+// Subnets from `net:<cidr>` filter tokens. A packet matches when its source or
+// destination falls in any of them; invalid CIDRs are dropped and flagged.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct NetFilter {
+    nets: Vec<IpNetwork>,
+    invalid: bool,
+}
+
+impl NetFilter {
+    /// Split the `net:` tokens out of `filter`, returning the subnets and the
+    /// remaining filter.
+    fn parse(filter: &str) -> (Self, String) {
+        let mut net_filter = NetFilter::default();
+        let mut rest: Vec<&str> = vec![];
+        let mut found = false;
+        for token in filter.split_whitespace() {
+            match token.strip_prefix("net:") {
+                Some(cidr) => {
+                    found = true;
+                    match cidr.parse::<IpNetwork>() {
+                        Ok(net) => net_filter.nets.push(net),
+                        Err(_) => net_filter.invalid = true,
+                    }
+                }
+                None => rest.push(token),
+            }
+        }
+        if !found {
+            return (net_filter, filter.to_string());
+        }
+        (net_filter, rest.join(" "))
+    }
+
+    fn matches(&self, packet: &PacketsInfoTypesEnum) -> bool {
+        if self.nets.is_empty() {
+            return true;
+        }
+        let Some((source, destination)) = packet.addresses() else {
+            return false;
+        };
+        self.nets.iter().any(|net| net.contains(source) || net.contains(destination))
+    }
+}
+
+// Interfaces from `iface:<name>` filter tokens. A packet matches when it was
+// captured on any of them; without tokens every interface matches.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct IfaceFilter {
+    names: Vec<String>,
+}
+
+impl IfaceFilter {
+    /// Split the `iface:` tokens out of `filter`, returning the interface
+    /// names and the remaining filter.
+    fn parse(filter: &str) -> (Self, String) {
+        let mut iface_filter = IfaceFilter::default();
+        let mut rest: Vec<&str> = vec![];
+        let mut found = false;
+        for token in filter.split_whitespace() {
+            match token.strip_prefix("iface:") {
+                Some(name) => {
+                    found = true;
+                    if !name.is_empty() {
+                        iface_filter.names.push(name.to_string());
+                    }
+                }
+                None => rest.push(token),
+            }
+        }
+        if !found {
+            return (iface_filter, filter.to_string());
+        }
+        (iface_filter, rest.join(" "))
+    }
+
+    fn matches(&self, packet: &PacketsInfoTypesEnum) -> bool {
+        self.names.is_empty() || self.names.iter().any(|name| name == packet.interface_name())
+    }
+}
+
+// Popup shown over the packet table while naming or recalling a saved filter,
+// showing the filter as a tcpdump command or the selected packet in detail. All
+// run in input mode so Enter submits and Esc cancels.
+enum FilterPrompt {
+    None,
+    Name { input: Input, overwrite: bool },
+    Recall { filters: SavedFilters, selected: usize },
+    Tcpdump(TcpdumpCommand),
+    Detail(Box<PacketDetail>),
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ArpPacketData {
     pub sender_mac: MacAddr,
@@ -69,27 +561,138 @@ pub struct ArpPacketData {
     pub target_ip: Ipv4Addr,
 }
 
+/// IPv6 to MAC binding from Neighbor Discovery, the IPv6 counterpart of
+/// [`ArpPacketData`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ipv6NeighborData {
+    pub ip: Ipv6Addr,
+    pub mac: MacAddr,
+}
+
+/// Totals of a capture stopped by one of the `capture.max_*` limits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureSummary {
+    pub packets: u64,
+    pub bytes: u64,
+    pub duration: Duration,
+}
+
+impl std::fmt::Display for CaptureSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} packets / {} / {}s",
+            self.packets,
+            bytes_convert(self.bytes as f64),
+            self.duration.as_secs()
+        )
+    }
+}
+
+// What the packet log shows, for the dump title
+enum DumpSource {
+    Live,
+    // -- save time of a loaded session
+    Session(DateTime<Local>),
+    // -- name of a replayed pcap file
+    File(String),
+}
+
+/// Capture thread on an interface added next to the active one.
+struct ExtraCapture {
+    interface: NetworkInterface,
+    handle: JoinHandle<()>,
+    stop: Arc<AtomicBool>,
+    done: Receiver<()>,
+}
+
 pub struct PacketDump {
     active_tab: TabsEnum,
     action_tx: Option<Sender<Action>>,
+    config: Config,
     loop_thread: Option<JoinHandle<()>>,
+    // -- the capture thread sends (or drops the sender) when it exits
+    loop_done: Option<Receiver<()>>,
     _should_quit: bool,
     dump_paused: Arc<AtomicBool>,
     dump_stop: Arc<AtomicBool>,
+    counters: Arc<CaptureCounters>,
+    decode: Arc<DecodeFilter>,
     active_interface: Option<NetworkInterface>,
+    // -- captures on more interfaces, by name, their packets join the log
+    extra_captures: BTreeMap<String, ExtraCapture>,
     table_state: TableState,
     scrollbar_state: ScrollbarState,
     packet_type: PacketTypeEnum,
     input: Input,
     mode: Mode,
     filter_str: String,
+    // -- `filter_str` compiled for the substring match
+    text_filter: TextFilter,
+    // -- `src:`, `dst:`, `port:`, `proto:` and `flags:` tokens of the filter input
+    field_filter: FieldFilter,
+    // -- `filter_str` when it parses as a BPF expression, replaces the substring match
+    bpf_filter: Option<BpfExpr>,
+    // -- `bpf:` text that isn't an expression
+    bpf_invalid: bool,
+    preset: FilterPreset,
+    time_range: TimeRange,
+    net_filter: NetFilter,
+    iface_filter: IfaceFilter,
+    // -- well-known DNS ports plus the ones mapped in capture.port_protocols
+    dns_ports: Vec<u16>,
+    filter_prompt: FilterPrompt,
+    // -- filters applied from the input box, Up/Down in input mode
+    filter_history: FilterHistory,
+    // -- `packet_colors` from the config
+    color_rules: ColorRules,
     changed_interface: bool,
+    wrap_rows: bool,
+    // -- the selection stays on the newest row instead of on its packet
+    follow: bool,
+    // -- protocol badges colored by conversation instead of by protocol
+    flow_colors: bool,
+    log_width: usize,
+    row_heights: Vec<usize>,
+    // -- rows the table showed when last drawn
+    page_rows: usize,
+    show_flows: bool,
+    flows: FlowTable,
+    flow_order: FlowOrder,
+    show_talkers: bool,
+    talkers: TalkerTable,
+    retransmits: RetransmitTracker,
+    sequence_bases: SequenceBases,
+    ip_conflicts: ConflictTracker,
+    // -- TCP rows show sequence numbers relative to each direction's first one
+    relative_seq: bool,
+    time_format: TimeFormat,
+    // -- rows show the gap to the previous row instead of the time
+    time_delta: bool,
+    echoes: EchoTracker,
+    groups: GroupTable,
+    clock: CaptureClock,
+    // -- save time of a loaded session, set while it is shown instead of live capture
+    snapshot: Option<DateTime<Local>>,
+    // -- pcap file replayed instead of capturing live, from `--read-pcap`
+    replay: Option<PathBuf>,
+    byte_rate: ByteRate,
+    kernel_stats: Option<KernelStats>,
+    anomalies: PendingAnomalies,
+    anomalies_reported: Instant,
+    // -- set when the capture thread stopped at a capture.max_* limit
+    complete: Option<CaptureSummary>,
+    capture_unavailable: Option<String>,
+    // -- write packets passing the filter to stdout as JSON lines
+    stream_json: bool,
 
     arp_packets: MaxSizeVec<(DateTime<Local>, PacketsInfoTypesEnum)>,
     udp_packets: MaxSizeVec<(DateTime<Local>, PacketsInfoTypesEnum)>,
     tcp_packets: MaxSizeVec<(DateTime<Local>, PacketsInfoTypesEnum)>,
     icmp_packets: MaxSizeVec<(DateTime<Local>, PacketsInfoTypesEnum)>,
     icmp6_packets: MaxSizeVec<(DateTime<Local>, PacketsInfoTypesEnum)>,
+    igmp_packets: MaxSizeVec<(DateTime<Local>, PacketsInfoTypesEnum)>,
+    other_packets: MaxSizeVec<(DateTime<Local>, PacketsInfoTypesEnum)>,
     all_packets: MaxSizeVec<(DateTime<Local>, PacketsInfoTypesEnum)>,
 }
 
@@ -104,49 +707,155 @@ impl PacketDump {
         Self {
             active_tab: TabsEnum::Discovery,
             action_tx: None,
+            config: Config::default(),
             loop_thread: None,
+            loop_done: None,
             _should_quit: false,
             dump_paused: Arc::new(AtomicBool::new(false)),
             dump_stop: Arc::new(AtomicBool::new(false)),
+            counters: Arc::new(CaptureCounters::default()),
+            decode: Arc::new(DecodeFilter::default()),
             active_interface: None,
+            extra_captures: BTreeMap::new(),
             table_state: TableState::default().with_selected(0),
             scrollbar_state: ScrollbarState::new(0),
             packet_type: PacketTypeEnum::All,
             input: Input::default().with_value(String::from("")),
             mode: Mode::Normal,
             filter_str: String::from(""),
+            text_filter: TextFilter::default(),
+            field_filter: FieldFilter::default(),
+            bpf_filter: None,
+            bpf_invalid: false,
+            preset: FilterPreset::None,
+            time_range: TimeRange::default(),
+            net_filter: NetFilter::default(),
+            iface_filter: IfaceFilter::default(),
+            dns_ports: DNS_PORTS.to_vec(),
+            filter_prompt: FilterPrompt::None,
+            filter_history: FilterHistory::default(),
+            color_rules: ColorRules::default(),
             changed_interface: false,
+            wrap_rows: false,
+            follow: true,
+            flow_colors: false,
+            log_width: 0,
+            row_heights: Vec::new(),
+            page_rows: 0,
+            show_flows: false,
+            flows: FlowTable::new(MAX_FLOWS),
+            flow_order: FlowOrder::default(),
+            show_talkers: false,
+            talkers: TalkerTable::new(MAX_TALKERS),
+            retransmits: RetransmitTracker::new(MAX_RETRANSMIT_FLOWS),
+            sequence_bases: SequenceBases::new(MAX_RETRANSMIT_FLOWS),
+            ip_conflicts: ConflictTracker::default(),
+            relative_seq: true,
+            time_format: TimeFormat::default(),
+            time_delta: false,
+            echoes: EchoTracker::new(MAX_PENDING_ECHOES),
+            groups: GroupTable::default(),
+            clock: CaptureClock::default(),
+            snapshot: None,
+            replay: None,
+            byte_rate: ByteRate::default(),
+            kernel_stats: None,
+            anomalies: PendingAnomalies::default(),
+            anomalies_reported: Instant::now(),
+            complete: None,
+            stream_json: false,
+            capture_unavailable: None,
 
             arp_packets: MaxSizeVec::new(MAX_PACKET_HISTORY),
             udp_packets: MaxSizeVec::new(MAX_PACKET_HISTORY),
             tcp_packets: MaxSizeVec::new(MAX_PACKET_HISTORY),
             icmp_packets: MaxSizeVec::new(MAX_PACKET_HISTORY),
             icmp6_packets: MaxSizeVec::new(MAX_PACKET_HISTORY),
+            igmp_packets: MaxSizeVec::new(MAX_PACKET_HISTORY),
+            other_packets: MaxSizeVec::new(MAX_PACKET_HISTORY),
             all_packets: MaxSizeVec::new(MAX_PACKET_HISTORY),
         }
     }
 
+    /// Start with this packet filter, as if typed into the filter input.
+    pub fn with_filter(mut self, filter: Option<String>) -> Self {
+        if let Some(filter) = filter {
+            self.input = Input::new(filter.clone());
+            self.set_filter_str(filter);
+        }
+        self
+    }
+
+    pub fn with_stream_json(mut self, stream_json: bool) -> Self {
+        self.stream_json = stream_json;
+        self
+    }
+
     fn handle_udp_packet(
         interface_name: &str,
         source: IpAddr,
         destination: IpAddr,
         packet: &[u8],
+        frame: &CapturedFrame,
         action_tx: Sender<Action>,
     ) {
         let udp = UdpPacket::new(packet);
         if let Some(udp) = udp {
-            let raw_str = format!(
-                "[{}]: UDP Packet: {}:{} > {}:{}; length: {}",
+            let capture = frame.capture;
+            let is_dns = |port: u16| {
+                DNS_PORTS.contains(&port)
+                    || capture.port_decoders.get(&port) == Some(&PortProtocol::Dns)
+            };
+            let ports = [udp.get_source(), udp.get_destination()];
+            let is_mdns = ports.contains(&mdns::MDNS_PORT);
+            let mdns_info = if is_mdns { mdns::parse(udp.payload()).map(Box::new) } else { None };
+            let dns_info = if !is_mdns && ports.into_iter().any(is_dns) {
+                dns::parse(udp.payload())
+            } else {
+                None
+            };
+            let is_dhcp = |port: u16| dhcp::DHCP_PORTS.contains(&port);
+            let dhcp_info = if is_dhcp(udp.get_source()) && is_dhcp(udp.get_destination()) {
+                dhcp::parse(udp.payload()).map(Box::new)
+            } else {
+                None
+            };
+            let ssdp_info = if ports.contains(&ssdp::SSDP_PORT) {
+                ssdp::parse(udp.payload()).map(Box::new)
+            } else {
+                None
+            };
+
+            let mut raw_str = format!(
+                "[{}]: UDP Packet: {}:{} > {}:{}; length: {}; {}",
                 interface_name,
                 source,
                 udp.get_source(),
                 destination,
                 udp.get_destination(),
-                udp.get_length()
+                udp.get_length(),
+                frame.ip.get()
             );
+            if let Some(dns) = &dns_info {
+                raw_str.push_str(&format!("; {}", dns::summary(dns)));
+            }
+            if let Some(dhcp) = &dhcp_info {
+                raw_str.push_str(&format!("; {}", dhcp::summary(dhcp)));
+            }
+            if let Some(mdns) = &mdns_info {
+                raw_str.push_str(&format!("; {}", mdns::summary(mdns)));
+                // -- Discovery fills in missing hostnames, dropped when the app is busy
+                for (ip, name) in mdns.hosts.iter().filter(|_| mdns.response) {
+                    let _ = action_tx
+                        .try_send(Action::HostnameAnnounced(ip.to_string(), name.clone()));
+                }
+            }
+            if let Some(ssdp) = &ssdp_info {
+                raw_str.push_str(&format!("; {}", ssdp::summary(ssdp)));
+            }
 
-            let _ = action_tx.try_send(Action::PacketDump(
-                Local::now(),
+            frame.send(&action_tx, Action::PacketDump(
+                frame.time,
                 PacketsInfoTypesEnum::Udp(UDPPacketInfo {
                     interface_name: interface_name.to_string(),
                     source,
@@ -154,7 +863,16 @@ impl PacketDump {
                     destination,
                     destination_port: udp.get_destination(),
                     length: udp.get_length() as usize,
+                    dns_info,
+                    dhcp_info,
+                    mdns_info,
+                    ssdp_info,
+                    payload: frame.snippet(udp.payload()),
+                    ip: frame.ip.get(),
+                    vlan: None,
                     raw_str,
+                    raw_bytes: frame.bytes.clone(),
+                    orig_len: frame.orig_len,
                 }),
                 PacketTypeEnum::Udp,
             ));
@@ -166,6 +884,7 @@ impl PacketDump {
         source: IpAddr,
         destination: IpAddr,
         packet: &[u8],
+        frame: &CapturedFrame,
         action_tx: Sender<Action>,
     ) {
         let icmp_packet = IcmpPacket::new(packet);
@@ -178,16 +897,17 @@ impl PacketDump {
                     };
 
                     let raw_str = format!(
-                        "[{}]: ICMP echo reply {} -> {} (seq={:?}, id={:?})",
+                        "[{}]: ICMP echo reply {} -> {} (seq={:?}, id={:?}); {}",
                         interface_name,
                         source,
                         destination,
                         echo_reply_packet.get_sequence_number(),
-                        echo_reply_packet.get_identifier()
+                        echo_reply_packet.get_identifier(),
+                        frame.ip.get()
                     );
 
-                    let _ = action_tx.try_send(Action::PacketDump(
-                        Local::now(),
+                    frame.send(&action_tx, Action::PacketDump(
+                        frame.time,
                         PacketsInfoTypesEnum::Icmp(ICMPPacketInfo {
                             interface_name: interface_name.to_string(),
                             source,
@@ -195,7 +915,16 @@ impl PacketDump {
                             seq: echo_reply_packet.get_sequence_number(),
                             id: echo_reply_packet.get_identifier(),
                             icmp_type: IcmpTypes::EchoReply,
+                            rtt_ms: None,
+                            duplicate: false,
+                            code: icmp_packet.get_icmp_code().0,
+                            quote: None,
+                            gateway: None,
+                            ip: frame.ip.get(),
+                            vlan: None,
                             raw_str,
+                            raw_bytes: frame.bytes.clone(),
+                            orig_len: frame.orig_len,
                         }),
                         PacketTypeEnum::Icmp,
                     ));
@@ -207,16 +936,17 @@ impl PacketDump {
                     };
 
                     let raw_str = format!(
-                        "[{}]: ICMP echo request {} -> {} (seq={:?}, id={:?})",
+                        "[{}]: ICMP echo request {} -> {} (seq={:?}, id={:?}); {}",
                         interface_name,
                         source,
                         destination,
                         echo_request_packet.get_sequence_number(),
-                        echo_request_packet.get_identifier()
+                        echo_request_packet.get_identifier(),
+                        frame.ip.get()
                     );
 
-                    let _ = action_tx.try_send(Action::PacketDump(
-                        Local::now(),
+                    frame.send(&action_tx, Action::PacketDump(
+                        frame.time,
                         PacketsInfoTypesEnum::Icmp(ICMPPacketInfo {
                             interface_name: interface_name.to_string(),
                             source,
@@ -224,12 +954,67 @@ impl PacketDump {
                             seq: echo_request_packet.get_sequence_number(),
                             id: echo_request_packet.get_identifier(),
                             icmp_type: IcmpTypes::EchoRequest,
+                            rtt_ms: None,
+                            duplicate: false,
+                            code: icmp_packet.get_icmp_code().0,
+                            quote: None,
+                            gateway: None,
+                            ip: frame.ip.get(),
+                            vlan: None,
                             raw_str,
+                            raw_bytes: frame.bytes.clone(),
+                            orig_len: frame.orig_len,
+                        }),
+                        PacketTypeEnum::Icmp,
+                    ));
+                }
+                icmp_type => {
+                    let code = icmp_packet.get_icmp_code().0;
+                    let quote = if icmp::is_error(icmp_type) { icmp::quote(packet) } else { None };
+                    let gateway = if icmp_type == IcmpTypes::RedirectMessage {
+                        icmp::redirect_gateway(packet)
+                    } else {
+                        None
+                    };
+
+                    let mut raw_str = format!(
+                        "[{}]: ICMP {} {} -> {}",
+                        interface_name,
+                        icmp::type_str(icmp_type, code),
+                        source,
+                        destination
+                    );
+                    if let Some(quote) = &quote {
+                        raw_str.push_str(&format!(" for {}", quote));
+                    }
+                    if let Some(gateway) = gateway {
+                        raw_str.push_str(&format!(" via {}", gateway));
+                    }
+                    raw_str.push_str(&format!("; {}", frame.ip.get()));
+
+                    frame.send(&action_tx, Action::PacketDump(
+                        frame.time,
+                        PacketsInfoTypesEnum::Icmp(ICMPPacketInfo {
+                            interface_name: interface_name.to_string(),
+                            source,
+                            destination,
+                            seq: 0,
+                            id: 0,
+                            icmp_type,
+                            rtt_ms: None,
+                            duplicate: false,
+                            code,
+                            quote,
+                            gateway,
+                            ip: frame.ip.get(),
+                            vlan: None,
+                            raw_str,
+                            raw_bytes: frame.bytes.clone(),
+                            orig_len: frame.orig_len,
                         }),
                         PacketTypeEnum::Icmp,
                     ));
                 }
-                _ => {}
             }
         }
     }
@@ -239,53 +1024,141 @@ impl PacketDump {
         source: IpAddr,
         destination: IpAddr,
         packet: &[u8],
+        frame: &CapturedFrame,
         action_tx: Sender<Action>,
     ) {
         let icmpv6_packet = Icmpv6Packet::new(packet);
         if let Some(icmpv6_packet) = icmpv6_packet {
-            let raw_str = format!(
-                "[{}]: ICMPv6 packet {} -> {} (type={:?})",
+            let ndp = ndp::parse(source, &icmpv6_packet);
+            if let Some((ip, mac)) = ndp.neighbor {
+                frame.send(&action_tx, Action::Ipv6NeighborDiscovered(Ipv6NeighborData { ip, mac }));
+            }
+
+            let mut raw_str = format!(
+                "[{}]: ICMPv6 packet {} -> {} (type={:?}); {}",
                 interface_name,
                 source,
                 destination,
-                icmpv6_packet.get_icmpv6_type()
+                icmpv6_packet.get_icmpv6_type(),
+                frame.ip.get()
             );
+            if let Some(target) = ndp.target {
+                raw_str.push_str(&format!("; target: {}", target));
+            }
+            if let Some(mac) = ndp.link_addr {
+                raw_str.push_str(&format!("; lladdr: {}", mac));
+            }
+            for (prefix, len) in &ndp.prefixes {
+                raw_str.push_str(&format!("; prefix: {}/{}", prefix, len));
+            }
+            if let Some(lifetime) = ndp.router_lifetime {
+                raw_str.push_str(&format!("; router lifetime: {}s", lifetime));
+            }
 
-            let _ = action_tx.try_send(Action::PacketDump(
-                Local::now(),
+            frame.send(&action_tx, Action::PacketDump(
+                frame.time,
                 PacketsInfoTypesEnum::Icmp6(ICMP6PacketInfo {
                     interface_name: interface_name.to_string(),
                     source,
                     destination,
                     icmp_type: icmpv6_packet.get_icmpv6_type(),
+                    target: ndp.target,
+                    link_addr: ndp.link_addr,
+                    prefixes: ndp.prefixes,
+                    router_lifetime: ndp.router_lifetime,
+                    ip: frame.ip.get(),
+                    vlan: None,
                     raw_str,
+                    raw_bytes: frame.bytes.clone(),
+                    orig_len: frame.orig_len,
                 }),
                 PacketTypeEnum::Icmp6,
             ));
         }
     }
 
+    fn handle_igmp_packet(
+        interface_name: &str,
+        source: IpAddr,
+        destination: IpAddr,
+        packet: &[u8],
+        frame: &CapturedFrame,
+        action_tx: Sender<Action>,
+    ) {
+        let Some(message) = igmp::parse(packet) else {
+            return;
+        };
+        let raw_str = format!(
+            "[{}]: IGMP {} -> {} {}; {}",
+            interface_name,
+            source,
+            destination,
+            igmp::summary(&message),
+            frame.ip.get()
+        );
+
+        frame.send(&action_tx, Action::PacketDump(
+            frame.time,
+            PacketsInfoTypesEnum::Igmp(IGMPPacketInfo {
+                interface_name: interface_name.to_string(),
+                source,
+                destination,
+                igmp_type: message.igmp_type,
+                group: message.group,
+                records: message.records,
+                ip: frame.ip.get(),
+                vlan: None,
+                raw_str,
+                raw_bytes: frame.bytes.clone(),
+                orig_len: frame.orig_len,
+            }),
+            PacketTypeEnum::Igmp,
+        ));
+    }
+
     fn handle_tcp_packet(
         interface_name: &str,
         source: IpAddr,
         destination: IpAddr,
         packet: &[u8],
+        frame: &CapturedFrame,
         action_tx: Sender<Action>,
     ) {
         let tcp = TcpPacket::new(packet);
         if let Some(tcp) = tcp {
-            let raw_str = format!(
-                "[{}]: TCP Packet: {}:{} > {}:{}; length: {}",
+            let capture = frame.capture;
+            let is_http = |port: u16| {
+                capture.http_ports.contains(&port)
+                    || capture.port_decoders.get(&port) == Some(&PortProtocol::Http)
+            };
+            let http_info = if is_http(tcp.get_source()) || is_http(tcp.get_destination()) {
+                http::parse(tcp.payload())
+            } else {
+                None
+            };
+
+            let mut raw_str = format!(
+                "[{}]: TCP Packet: {}:{} > {}:{} {} seq={} ack={}; length: {}; {}",
                 interface_name,
                 source,
                 tcp.get_source(),
                 destination,
                 tcp.get_destination(),
-                packet.len()
+                tcp_flags::tag(tcp.get_flags()),
+                tcp.get_sequence(),
+                tcp.get_acknowledgement(),
+                packet.len(),
+                frame.ip.get()
             );
+            if let Some(http) = &http_info {
+                raw_str.push_str(&format!("; {}", http.start_line));
+                if let Some(host) = &http.host {
+                    raw_str.push_str(&format!(" Host: {}", host));
+                }
+            }
 
-            let _ = action_tx.try_send(Action::PacketDump(
-                Local::now(),
+            frame.send(&action_tx, Action::PacketDump(
+                frame.time,
                 PacketsInfoTypesEnum::Tcp(TCPPacketInfo {
                     interface_name: interface_name.to_string(),
                     source,
@@ -293,7 +1166,20 @@ impl PacketDump {
                     destination,
                     destination_port: tcp.get_destination(),
                     length: packet.len(),
+                    flags: tcp.get_flags(),
+                    sequence: tcp.get_sequence(),
+                    acknowledgement: tcp.get_acknowledgement(),
+                    relative_sequence: None,
+                    relative_acknowledgement: None,
+                    payload_len: tcp.payload().len(),
+                    retransmission: false,
+                    http_info,
+                    payload: frame.snippet(tcp.payload()),
+                    ip: frame.ip.get(),
+                    vlan: None,
                     raw_str,
+                    raw_bytes: frame.bytes.clone(),
+                    orig_len: frame.orig_len,
                 }),
                 PacketTypeEnum::Tcp,
             ));
@@ -306,78 +1192,228 @@ impl PacketDump {
         destination: IpAddr,
         protocol: IpNextHeaderProtocol,
         packet: &[u8],
+        frame: &CapturedFrame,
         action_tx: Sender<Action>,
     ) {
         match protocol {
-            IpNextHeaderProtocols::Udp => {
-                Self::handle_udp_packet(interface_name, source, destination, packet, action_tx)
+            IpNextHeaderProtocols::Udp if frame.wants(PacketTypeEnum::Udp) => {
+                Self::handle_udp_packet(
+                    interface_name,
+                    source,
+                    destination,
+                    packet,
+                    frame,
+                    action_tx,
+                )
+            }
+            IpNextHeaderProtocols::Tcp if frame.wants(PacketTypeEnum::Tcp) => {
+                Self::handle_tcp_packet(
+                    interface_name,
+                    source,
+                    destination,
+                    packet,
+                    frame,
+                    action_tx,
+                )
             }
-            IpNextHeaderProtocols::Tcp => {
-                Self::handle_tcp_packet(interface_name, source, destination, packet, action_tx)
+            IpNextHeaderProtocols::Icmp if frame.wants(PacketTypeEnum::Icmp) => {
+                Self::handle_icmp_packet(
+                    interface_name,
+                    source,
+                    destination,
+                    packet,
+                    frame,
+                    action_tx,
+                )
             }
-            IpNextHeaderProtocols::Icmp => {
-                Self::handle_icmp_packet(interface_name, source, destination, packet, action_tx)
+            IpNextHeaderProtocols::Igmp if frame.wants(PacketTypeEnum::Igmp) => {
+                Self::handle_igmp_packet(
+                    interface_name,
+                    source,
+                    destination,
+                    packet,
+                    frame,
+                    action_tx,
+                )
             }
-            IpNextHeaderProtocols::Icmpv6 => {
-                Self::handle_icmpv6_packet(interface_name, source, destination, packet, action_tx)
+            IpNextHeaderProtocols::Icmpv6 if frame.wants(PacketTypeEnum::Icmp6) => {
+                Self::handle_icmpv6_packet(
+                    interface_name,
+                    source,
+                    destination,
+                    packet,
+                    frame,
+                    action_tx,
+                )
             }
             _ => {}
         }
     }
 
+    /// Whether a TTL is suspiciously low. Traffic that never leaves the link is
+    /// exempt: loopback, link-local, multicast and broadcast senders pick small
+    /// TTLs on purpose (IGMP and SSDP use 1 and 2).
+    fn is_low_ttl(ttl: u8, source: IpAddr, destination: IpAddr) -> bool {
+        let on_link = |ip: IpAddr| match ip {
+            IpAddr::V4(ip) => {
+                ip.is_loopback() || ip.is_link_local() || ip.is_multicast() || ip.is_broadcast()
+            }
+            IpAddr::V6(ip) => {
+                ip.is_loopback() || ip.is_multicast() || (ip.segments()[0] & 0xffc0) == 0xfe80
+            }
+        };
+        ttl <= LOW_TTL && !on_link(source) && !on_link(destination)
+    }
+
     fn handle_ipv4_packet(
         interface_name: &str,
         ethernet: &EthernetPacket,
+        frame: &CapturedFrame,
         action_tx: Sender<Action>,
     ) {
         let header = Ipv4Packet::new(ethernet.payload());
         if let Some(header) = header {
+            let (source, destination) =
+                (IpAddr::V4(header.get_source()), IpAddr::V4(header.get_destination()));
+            frame.ip.set(IpMeta {
+                ttl: header.get_ttl(),
+                dscp: header.get_dscp(),
+                low_ttl: Self::is_low_ttl(header.get_ttl(), source, destination),
+            });
             Self::handle_transport_protocol(
                 interface_name,
-                IpAddr::V4(header.get_source()),
-                IpAddr::V4(header.get_destination()),
+                source,
+                destination,
                 header.get_next_level_protocol(),
                 header.payload(),
+                frame,
                 action_tx,
             );
+        } else {
+            log::debug!("[{}]: Malformed IPv4 Packet", interface_name);
+            frame.malformed();
         }
     }
 
     fn handle_ipv6_packet(
         interface_name: &str,
         ethernet: &EthernetPacket,
+        frame: &CapturedFrame,
         action_tx: Sender<Action>,
     ) {
         let header = Ipv6Packet::new(ethernet.payload());
         if let Some(header) = header {
-            Self::handle_transport_protocol(
-                interface_name,
-                IpAddr::V6(header.get_source()),
-                IpAddr::V6(header.get_destination()),
-                header.get_next_header(),
-                header.payload(),
-                action_tx,
-            );
+            // -- hop limit is the IPv6 TTL, DSCP is the upper six bits of the traffic class
+            let (source, destination) =
+                (IpAddr::V6(header.get_source()), IpAddr::V6(header.get_destination()));
+            frame.ip.set(IpMeta {
+                ttl: header.get_hop_limit(),
+                dscp: header.get_traffic_class() >> 2,
+                low_ttl: Self::is_low_ttl(header.get_hop_limit(), source, destination),
+            });
+            match ipv6_ext::walk(header.get_next_header(), header.payload()) {
+                Some(ipv6_ext::Upper::Protocol(protocol, payload)) => {
+                    Self::handle_transport_protocol(
+                        interface_name,
+                        source,
+                        destination,
+                        protocol,
+                        payload,
+                        frame,
+                        action_tx,
+                    )
+                }
+                Some(ipv6_ext::Upper::Fragment(fragment)) if frame.wants(PacketTypeEnum::Other) => {
+                    Self::handle_ipv6_fragment(
+                        interface_name,
+                        ethernet,
+                        (source, destination),
+                        fragment,
+                        frame,
+                        action_tx,
+                    )
+                }
+                Some(ipv6_ext::Upper::Fragment(_)) => {}
+                None => {
+                    log::debug!("[{}]: Malformed IPv6 extension headers", interface_name);
+                    frame.malformed();
+                }
+            }
         } else {
-            println!("[{}]: Malformed IPv6 Packet", interface_name);
+            log::debug!("[{}]: Malformed IPv6 Packet", interface_name);
+            frame.malformed();
         }
     }
 
+    /// Fragments after the first have no upper-layer header to decode, they go
+    /// to the other frames with the fragment header described.
+    fn handle_ipv6_fragment(
+        interface_name: &str,
+        ethernet: &EthernetPacket,
+        (source, destination): (IpAddr, IpAddr),
+        fragment: ipv6_ext::Fragment,
+        frame: &CapturedFrame,
+        action_tx: Sender<Action>,
+    ) {
+        let note = format!(
+            "fragment {} > {} id 0x{:08x} offset {}{}, next header {}",
+            source,
+            destination,
+            fragment.id,
+            fragment.offset,
+            if fragment.more { " more" } else { "" },
+            fragment.next
+        );
+        let length = ethernet.payload().len();
+        let raw_str = format!(
+            "[{}]: {} frame: {} > {}; length: {}; {}",
+            interface_name,
+            ethertypes::format(EtherTypes::Ipv6.0),
+            ethernet.get_source(),
+            ethernet.get_destination(),
+            length,
+            note
+        );
+        frame.send(&action_tx, Action::PacketDump(
+            frame.time,
+            PacketsInfoTypesEnum::Other(OtherPacketInfo {
+                interface_name: interface_name.to_string(),
+                source_mac: ethernet.get_source(),
+                destination_mac: ethernet.get_destination(),
+                ethertype: EtherTypes::Ipv6.0,
+                length,
+                note: Some(note),
+                vlan: None,
+                raw_str,
+                raw_bytes: frame.bytes.clone(),
+                orig_len: frame.orig_len,
+            }),
+            PacketTypeEnum::Other,
+        ));
+    }
+
     fn handle_arp_packet(
         interface_name: &str,
         ethernet: &EthernetPacket,
+        frame: &CapturedFrame,
         action_tx: Sender<Action>,
     ) {
         let header = ArpPacket::new(ethernet.payload());
         if let Some(header) = header {
-            let _ = action_tx.try_send(Action::ArpRecieve(ArpPacketData {
+            frame.send(&action_tx, Action::ArpRecieve(ArpPacketData {
                 sender_mac: header.get_sender_hw_addr(),
                 sender_ip: header.get_sender_proto_addr(),
                 target_mac: header.get_target_hw_addr(),
                 target_ip: header.get_target_proto_addr(),
             }));
+            // -- discovery still learns from disabled ARP
+            if frame.disabled.contains(PacketTypeEnum::Arp) {
+                return;
+            }
 
-            let raw_str = format!(
+            let source_vendor =
+                vendors::vendor(&ethernet.get_source().to_string()).map(str::to_string);
+            let mut raw_str = format!(
                 "[{}]: ARP packet: {}({}) > {}({}); operation: {:?}",
                 interface_name,
                 ethernet.get_source(),
@@ -386,9 +1422,13 @@ impl PacketDump {
                 header.get_target_proto_addr(),
                 header.get_operation()
             );
+            // -- so the filter finds the rows of a vendor
+            if let Some(vendor) = &source_vendor {
+                raw_str.push_str(&format!("; vendor: {}", vendor));
+            }
 
-            let _ = action_tx.try_send(Action::PacketDump(
-                Local::now(),
+            frame.send(&action_tx, Action::PacketDump(
+                frame.time,
                 PacketsInfoTypesEnum::Arp(ARPPacketInfo {
                     interface_name: interface_name.to_string(),
                     source_mac: ethernet.get_source(),
@@ -396,49 +1436,183 @@ impl PacketDump {
                     destination_mac: ethernet.get_destination(),
                     destination_ip: header.get_target_proto_addr(),
                     operation: header.get_operation(),
+                    source_vendor,
+                    vlan: None,
                     raw_str,
+                    raw_bytes: frame.bytes.clone(),
+                    orig_len: frame.orig_len,
                 }),
                 PacketTypeEnum::Arp,
             ));
         }
     }
 
-    fn handle_ethernet_frame(
-        interface: &NetworkInterface,
+    fn handle_other_frame(
+        interface_name: &str,
+        ethernet: &EthernetPacket,
+        frame: &CapturedFrame,
+        action_tx: Sender<Action>,
+    ) {
+        let ethertype = ethernet.get_ethertype().0;
+        let mut length = ethernet.payload().len();
+        if ethertype <= ethertypes::MAX_8023_LENGTH {
+            // -- 802.3 frames carry their payload length, anything shorter is truncated
+            if usize::from(ethertype) > length {
+                return;
+            }
+            length = usize::from(ethertype);
+        }
+        if length == 0 {
+            return;
+        }
+
+        let raw_str = format!(
+            "[{}]: {} frame: {} > {}; length: {}",
+            interface_name,
+            ethertypes::format(ethertype),
+            ethernet.get_source(),
+            ethernet.get_destination(),
+            length
+        );
+
+        frame.send(&action_tx, Action::PacketDump(
+            frame.time,
+            PacketsInfoTypesEnum::Other(OtherPacketInfo {
+                interface_name: interface_name.to_string(),
+                source_mac: ethernet.get_source(),
+                destination_mac: ethernet.get_destination(),
+                ethertype,
+                length,
+                note: None,
+                vlan: None,
+                raw_str,
+                raw_bytes: frame.bytes.clone(),
+                orig_len: frame.orig_len,
+            }),
+            PacketTypeEnum::Other,
+        ));
+    }
+
+    fn handle_ethernet_frame(
+        interface: &NetworkInterface,
         ethernet: &EthernetPacket,
+        frame: &CapturedFrame,
         action_tx: Sender<Action>,
     ) {
         let interface_name = &interface.name[..];
+        // -- reject early what the selected or disabled packet types would drop
+        // anyway; ARP is always decoded as discovery learns MAC addresses from it
+        let wants_ipv4 = [
+            PacketTypeEnum::Tcp,
+            PacketTypeEnum::Udp,
+            PacketTypeEnum::Icmp,
+            PacketTypeEnum::Igmp,
+        ]
+        .into_iter()
+        .any(|t| frame.wants(t));
+        // -- IPv6 fragments without an upper-layer header are other frames
+        let wants_ipv6 = [
+            PacketTypeEnum::Tcp,
+            PacketTypeEnum::Udp,
+            PacketTypeEnum::Icmp6,
+            PacketTypeEnum::Other,
+        ]
+        .into_iter()
+        .any(|t| frame.wants(t));
         match ethernet.get_ethertype() {
-            EtherTypes::Ipv4 => Self::handle_ipv4_packet(interface_name, ethernet, action_tx),
-            EtherTypes::Ipv6 => Self::handle_ipv6_packet(interface_name, ethernet, action_tx),
-            EtherTypes::Arp => Self::handle_arp_packet(interface_name, ethernet, action_tx),
+            EtherTypes::Ipv4 if wants_ipv4 => {
+                Self::handle_ipv4_packet(interface_name, ethernet, frame, action_tx)
+            }
+            EtherTypes::Ipv6 if wants_ipv6 => {
+                Self::handle_ipv6_packet(interface_name, ethernet, frame, action_tx)
+            }
+            EtherTypes::Arp => Self::handle_arp_packet(interface_name, ethernet, frame, action_tx),
+            EtherTypes::Vlan | EtherTypes::PBridge | EtherTypes::QinQ => {
+                Self::handle_vlan_frame(interface, ethernet, frame, action_tx)
+            }
+            EtherTypes::Ipv4 | EtherTypes::Ipv6 => {}
+            _ if frame.wants(PacketTypeEnum::Other) => {
+                Self::handle_other_frame(interface_name, ethernet, frame, action_tx)
+            }
             _ => {}
         }
     }
 
-    fn t_logic(action_tx: Sender<Action>, interface: NetworkInterface, stop: Arc<AtomicBool>) {
+    /// Strips an 802.1Q tag (or the outer tag of a QinQ pair) and decodes the
+    /// inner frame as if it was untagged. Tags cut short and a third level of
+    /// tagging are shown as they are.
+    fn handle_vlan_frame(
+        interface: &NetworkInterface,
+        ethernet: &EthernetPacket,
+        frame: &CapturedFrame,
+        action_tx: Sender<Action>,
+    ) {
+        let tag = ethernet.payload();
+        let outer = frame.vlan.get();
+        if tag.len() < 4 || outer.is_some_and(|vlan| vlan.outer.is_some()) {
+            if frame.wants(PacketTypeEnum::Other) {
+                Self::handle_other_frame(&interface.name, ethernet, frame, action_tx);
+            }
+            return;
+        }
+        let id = u16::from_be_bytes([tag[0], tag[1]]) & 0x0fff;
+        frame.vlan.set(Some(Vlan {
+            id,
+            outer: outer.map(|vlan| vlan.id),
+        }));
+
+        // -- addresses followed by the inner EtherType and payload
+        let mut inner = Vec::with_capacity(ethernet.packet().len() - 4);
+        inner.extend_from_slice(&ethernet.packet()[..12]);
+        inner.extend_from_slice(&tag[2..]);
+        if let Some(inner) = EthernetPacket::new(&inner) {
+            Self::handle_ethernet_frame(interface, &inner, frame, action_tx);
+        }
+    }
+
+    fn t_logic(
+        action_tx: Sender<Action>,
+        interface: NetworkInterface,
+        stop: Arc<AtomicBool>,
+        paused: Arc<AtomicBool>,
+        counters: Arc<CaptureCounters>,
+        decode: Arc<DecodeFilter>,
+        capture: CaptureConfig,
+    ) {
+        // pnet has no BPF API, so on Linux the filter is attached to a socket we
+        // create and hand over; pnet binds it to the interface and owns it from there.
+        // Elsewhere, or if attaching fails, packets are only filtered in userspace.
+        #[cfg(target_os = "linux")]
+        let socket_fd = decode.kernel_program().and_then(|program| {
+            match bpf::filtered_socket(&program) {
+                Ok(fd) => Some(fd),
+                Err(e) => {
+                    log::warn!("Could not attach BPF filter, filtering in userspace: {}", e);
+                    None
+                }
+            }
+        });
+        #[cfg(not(target_os = "linux"))]
+        let socket_fd = None;
+
         // Configure optimized packet capture settings
-        // Note: pnet does not support BPF filtering at the API level - all filtering
-        // must be done in userspace after packets are captured. This is a known limitation
-        // of the pnet library. For kernel-level filtering, consider using the pcap crate instead.
         let config = pnet::datalink::Config {
             // Increased buffer sizes for better performance with high packet rates
             // Larger buffers reduce syscall overhead and can handle burst traffic better
             write_buffer_size: 65536, // 64KB - sufficient for batch writes
             read_buffer_size: 65536,  // 64KB - can hold ~40-70 standard packets (MTU 1500)
 
-            // Reduced read timeout for more responsive packet capture and faster shutdown
-            // 100ms provides a good balance between CPU usage and responsiveness
-            // This also ensures the stop signal is checked every 100ms maximum
-            read_timeout: Some(Duration::from_millis(100)),
+            // The read timeout bounds how long the thread blocks on an idle link, so it is
+            // also the maximum delay before the stop signal is noticed. Defaults to 100ms;
+            // longer values (capture.read_timeout_ms) wake the CPU less often.
+            read_timeout: Some(Duration::from_millis(capture.read_timeout_ms)),
 
             write_timeout: None, // No write timeout needed for packet capture
             channel_type: ChannelType::Layer2, // Capture at Layer 2 (Ethernet)
             bpf_fd_attempts: 1000, // macOS/BSD: Try up to 1000 /dev/bpf* descriptors
             linux_fanout: None,    // Linux fanout not used for single-threaded capture
             promiscuous: true,     // Capture all packets on the interface, not just those addressed to this host
-            socket_fd: None,       // Let pnet create its own socket
+            socket_fd,             // Filtered socket, or None to let pnet create its own
         };
 
         let (_, mut receiver) = match pnet::datalink::channel(&interface, config) {
@@ -464,6 +1638,18 @@ impl PacketDump {
             }
         };
 
+        // -- frames read so far, for 1-in-N sampling
+        let mut seen: u64 = 0;
+        // -- capture time checked against capture.max_*, paused time doesn't count
+        let mut active = Duration::ZERO;
+        if capture.has_limits() {
+            counters.packets.store(0, Ordering::Relaxed);
+            counters.bytes.store(0, Ordering::Relaxed);
+        }
+        let mut last_check = Instant::now();
+        // -- kernel counters are cumulative since boot, report them from capture start
+        let kernel_baseline = KernelStats::read(&interface.name);
+        let mut kernel_reported = Instant::now();
         loop {
             // Use SeqCst ordering to ensure we see the stop signal
             if stop.load(Ordering::SeqCst) {
@@ -471,6 +1657,48 @@ impl PacketDump {
                 break;
             }
 
+            if let Some(baseline) = kernel_baseline {
+                if kernel_reported.elapsed() >= KERNEL_STATS_INTERVAL {
+                    kernel_reported = Instant::now();
+                    if let Some(stats) = KernelStats::read(&interface.name) {
+                        let stats = stats.since(baseline);
+                        let _ = action_tx.try_send(Action::CaptureStats {
+                            received: stats.received,
+                            dropped: stats.dropped,
+                            iface: interface.name.clone(),
+                        });
+                    }
+                }
+            }
+
+            let is_paused = paused.load(Ordering::Relaxed);
+            if capture.has_limits() {
+                let now = Instant::now();
+                if !is_paused {
+                    active += now - last_check;
+                }
+                last_check = now;
+                let packets = counters.packets.load(Ordering::Relaxed);
+                let bytes = counters.bytes.load(Ordering::Relaxed);
+                if capture.limit_reached(packets, bytes, active.as_secs()) {
+                    log::info!("Capture limit reached on interface {}", interface.name);
+                    let _ = action_tx.blocking_send(Action::CaptureComplete(CaptureSummary {
+                        packets,
+                        bytes,
+                        duration: active,
+                    }));
+                    stop.store(true, Ordering::SeqCst);
+                    break;
+                }
+            }
+
+            // -- paused: keep the channel open but stop reading, the kernel buffers
+            // what arrives meanwhile and reading picks up from there on resume
+            if is_paused {
+                thread::sleep(Duration::from_millis(capture.read_timeout_ms));
+                continue;
+            }
+
             let mut buf: [u8; MAX_PACKET_BUFFER_SIZE] = [0u8; MAX_PACKET_BUFFER_SIZE];
             // Create mutable ethernet frame for handling special cases
             let Some(mut fake_ethernet_frame) = MutableEthernetPacket::new(&mut buf[..]) else {
@@ -480,15 +1708,33 @@ impl PacketDump {
 
             match receiver.next() {
                 Ok(packet) => {
-                    // Log warning if packet exceeds buffer size (indicates potential data loss)
+                    let sampled = seen.is_multiple_of(capture.sample_rate as u64);
+                    seen += 1;
+                    counters.packets.fetch_add(1, Ordering::Relaxed);
+                    counters.bytes.fetch_add(packet.len() as u64, Ordering::Relaxed);
+                    if !sampled {
+                        continue;
+                    }
+                    let wanted = if capture.selected_type_only {
+                        let selected = decode.selected.load(Ordering::Relaxed) as usize;
+                        PacketTypeEnum::from_repr(selected).unwrap_or_default()
+                    } else {
+                        PacketTypeEnum::All
+                    };
+                    let mut frame =
+                        CapturedFrame::new(packet, &capture, &counters, wanted, decode.disabled());
+
+                    // Log if packet exceeds buffer size (indicates potential data loss)
                     if packet.len() > MAX_PACKET_BUFFER_SIZE {
-                        log::warn!(
+                        log::debug!(
                             "Packet size ({} bytes) exceeds buffer capacity ({} bytes) on interface {}. \
                             Packet may be truncated.",
                             packet.len(),
                             MAX_PACKET_BUFFER_SIZE,
                             interface.name
                         );
+                        // -- GRO/TSO can make every frame oversized, the UI reports them in bulk
+                        counters.oversized.fetch_add(1, Ordering::Relaxed);
                     }
 
                     let payload_offset;
@@ -526,9 +1772,12 @@ impl PacketDump {
                                 fake_ethernet_frame.set_source(MacAddr(0, 0, 0, 0, 0, 0));
                                 fake_ethernet_frame.set_ethertype(EtherTypes::Ipv4);
                                 fake_ethernet_frame.set_payload(&packet[payload_offset..]);
+                                let frame_len = (14 + payload_size).min(MAX_PACKET_BUFFER_SIZE);
+                                frame.rewrap(&fake_ethernet_frame.packet()[..frame_len]);
                                 Self::handle_ethernet_frame(
                                     &interface,
                                     &fake_ethernet_frame.to_immutable(),
+                                    &frame,
                                     action_tx.clone(),
                                 );
                                 continue;
@@ -537,78 +1786,616 @@ impl PacketDump {
                                 fake_ethernet_frame.set_source(MacAddr(0, 0, 0, 0, 0, 0));
                                 fake_ethernet_frame.set_ethertype(EtherTypes::Ipv6);
                                 fake_ethernet_frame.set_payload(&packet[payload_offset..]);
+                                let frame_len = (14 + payload_size).min(MAX_PACKET_BUFFER_SIZE);
+                                frame.rewrap(&fake_ethernet_frame.packet()[..frame_len]);
                                 Self::handle_ethernet_frame(
                                     &interface,
                                     &fake_ethernet_frame.to_immutable(),
+                                    &frame,
                                     action_tx.clone(),
                                 );
                                 continue;
                             }
                         }
                     }
+                    // Linux loopback: frames may come cooked or as bare IP, so put the
+                    // zeroed Ethernet header back where it is missing
+                    if cfg!(target_os = "linux") && interface.is_loopback() {
+                        let Some((ethertype, payload_offset)) = loopback::framing(packet) else {
+                            continue;
+                        };
+                        if payload_offset != 14 {
+                            let payload = &packet[payload_offset..];
+                            let payload_size = payload.len().min(MAX_PACKET_BUFFER_SIZE - 14);
+                            fake_ethernet_frame.set_destination(MacAddr(0, 0, 0, 0, 0, 0));
+                            fake_ethernet_frame.set_source(MacAddr(0, 0, 0, 0, 0, 0));
+                            fake_ethernet_frame.set_ethertype(ethertype);
+                            fake_ethernet_frame.set_payload(&payload[..payload_size]);
+                            let frame_len = 14 + payload_size;
+                            frame.rewrap(&fake_ethernet_frame.packet()[..frame_len]);
+                            Self::handle_ethernet_frame(
+                                &interface,
+                                &fake_ethernet_frame.to_immutable(),
+                                &frame,
+                                action_tx.clone(),
+                            );
+                            continue;
+                        }
+                    }
                     // Parse ethernet packet - skip if invalid
                     if let Some(ethernet_packet) = EthernetPacket::new(packet) {
                         Self::handle_ethernet_frame(
                             &interface,
                             &ethernet_packet,
+                            &frame,
                             action_tx.clone(),
                         );
                     }
                 }
-                // Err(e) => println!("packetdump: unable to receive packet: {}", e),
-                Err(_e) => {}
+                // -- the read timeout ends up here too, nothing to report
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => log::debug!("Unable to receive packet on {}: {}", interface.name, e),
             }
         }
     }
 
-    fn start_loop(&mut self) {
-        if self.loop_thread.is_none() {
-            // Require both action_tx and active_interface to start loop
-            let Some(tx) = self.action_tx.clone() else {
+    /// Feed the frames of a pcap file through the same decoders as live capture,
+    /// stamped with their time from the file. Ends with `CaptureComplete`.
+    fn replay_logic(
+        action_tx: Sender<Action>,
+        path: PathBuf,
+        stop: Arc<AtomicBool>,
+        paused: Arc<AtomicBool>,
+        counters: Arc<CaptureCounters>,
+        decode: Arc<DecodeFilter>,
+        capture: CaptureConfig,
+    ) {
+        let mut reader = match PcapReader::open(&path) {
+            Ok(reader) => reader,
+            Err(e) => {
+                let _ = action_tx.blocking_send(Action::Error(format!(
+                    "Failed to read pcap file {}: {}",
+                    path.display(),
+                    e
+                )));
                 return;
-            };
-            let Some(interface) = self.active_interface.clone() else {
+            }
+        };
+        // -- the decoders only need a name for the interface column
+        let interface = NetworkInterface {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| "pcap".to_string()),
+            description: String::new(),
+            index: 0,
+            mac: None,
+            ips: vec![],
+            flags: 0,
+        };
+
+        let mut packets: u64 = 0;
+        let mut bytes: u64 = 0;
+        let mut first: Option<DateTime<Local>> = None;
+        let mut last: Option<DateTime<Local>> = None;
+        loop {
+            if stop.load(Ordering::SeqCst) {
+                log::debug!("Pcap replay thread received stop signal");
                 return;
+            }
+            if paused.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(capture.read_timeout_ms));
+                continue;
+            }
+
+            let packet = match reader.next_frame() {
+                Ok(Some(packet)) => packet,
+                Ok(None) => break,
+                Err(e) => {
+                    let _ = action_tx.blocking_send(Action::Error(format!(
+                        "Failed to read pcap file {}: {}",
+                        path.display(),
+                        e
+                    )));
+                    break;
+                }
             };
+            packets += 1;
+            bytes += packet.orig_len as u64;
+            first.get_or_insert(packet.time);
+            last = Some(packet.time);
 
-            log::debug!("Starting packet capture thread for interface: {}", interface.name);
-            let dump_stop = self.dump_stop.clone();
-            let t_handle = thread::spawn(move || {
-                Self::t_logic(tx, interface, dump_stop);
-            });
-            self.loop_thread = Some(t_handle);
+            let wanted = if capture.selected_type_only {
+                let selected = decode.selected.load(Ordering::Relaxed) as usize;
+                PacketTypeEnum::from_repr(selected).unwrap_or_default()
+            } else {
+                PacketTypeEnum::All
+            };
+            let frame =
+                CapturedFrame::new(&packet.bytes, &capture, &counters, wanted, decode.disabled())
+                    .replayed(packet.time, packet.orig_len);
+            if let Some(ethernet_packet) = EthernetPacket::new(&packet.bytes) {
+                Self::handle_ethernet_frame(&interface, &ethernet_packet, &frame, action_tx.clone());
+            }
         }
+
+        log::info!("Finished replaying {}", path.display());
+        // -- the span covered by the file, not how long reading it took
+        let duration = match (first, last) {
+            (Some(first), Some(last)) => (last - first).to_std().unwrap_or_default(),
+            _ => Duration::ZERO,
+        };
+        let _ = action_tx.blocking_send(Action::CaptureComplete(CaptureSummary {
+            packets,
+            bytes,
+            duration,
+        }));
     }
 
-    fn restart_loop(&mut self) {
-        log::debug!("Requesting packet capture thread to stop");
+    fn start_loop(&mut self) {
+        // -- one thread at a time: pausing keeps it running, and restart_loop
+        // -- collects a stopped one before the next can start
+        if let Some(handle) = &self.loop_thread {
+            log::debug!("Capture thread already running, finished: {}", handle.is_finished());
+            return;
+        }
+        // -- replaying a file needs no capture privileges
+        if let Some(path) = self.replay.clone() {
+            self.start_replay(path);
+            return;
+        }
+        if self.capture_unavailable.is_some() {
+            return;
+        }
+        // Require both action_tx and active_interface to start loop
+        let Some(tx) = self.action_tx.clone() else {
+            return;
+        };
+        let Some(interface) = self.active_interface.clone() else {
+            return;
+        };
+
+        log::debug!("Starting packet capture thread for interface: {}", interface.name);
+        let (t_handle, done_rx) = self.spawn_capture(tx, interface, self.dump_stop.clone(), true);
+        self.loop_thread = Some(t_handle);
+        self.loop_done = Some(done_rx);
+        if !self.dump_paused.load(Ordering::Relaxed) {
+            self.clock.resume();
+        }
+    }
+
+    // Capture thread on `interface` sharing the pause flag, counters and decode
+    // settings, with its own stop flag. Only the active capture stops at the
+    // capture.max_* limits, the extra ones count towards them.
+    fn spawn_capture(
+        &self,
+        tx: Sender<Action>,
+        interface: NetworkInterface,
+        stop: Arc<AtomicBool>,
+        limited: bool,
+    ) -> (JoinHandle<()>, Receiver<()>) {
+        let dump_paused = self.dump_paused.clone();
+        let counters = self.counters.clone();
+        let decode = self.decode.clone();
+        let capture = if limited {
+            self.config.capture.clone()
+        } else {
+            self.config.capture.without_limits()
+        };
+        let (done_tx, done_rx) = mpsc::channel();
+        let t_handle = thread::spawn(move || {
+            Self::t_logic(tx, interface, stop, dump_paused, counters, decode, capture);
+            let _ = done_tx.send(());
+        });
+        (t_handle, done_rx)
+    }
+
+    /// Capture on `interface` next to the active one, into the same log.
+    fn start_extra(&mut self, interface: NetworkInterface) {
+        let is_active = self.active_interface.as_ref().is_some_and(|i| i.name == interface.name);
+        if is_active
+            || self.replay.is_some()
+            || self.capture_unavailable.is_some()
+            || self.extra_captures.contains_key(&interface.name)
+        {
+            return;
+        }
+        let Some(tx) = self.action_tx.clone() else {
+            return;
+        };
+        log::debug!("Starting extra packet capture thread for interface: {}", interface.name);
+        let name = interface.name.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (handle, done) = self.spawn_capture(tx, interface.clone(), stop.clone(), false);
+        self.extra_captures.insert(
+            name,
+            ExtraCapture {
+                interface,
+                handle,
+                stop,
+                done,
+            },
+        );
+        self.send_extra_interfaces();
+    }
+
+    // -- the Interfaces tab marks only the captures that are running
+    fn send_extra_interfaces(&self) {
+        if let Some(tx) = &self.action_tx {
+            let names = self.extra_captures.keys().cloned().collect();
+            let _ = tx.try_send(Action::ExtraInterfaces(names));
+        }
+    }
+
+    fn stop_extra(&mut self, name: &str) {
+        let Some(extra) = self.extra_captures.remove(name) else {
+            return;
+        };
+        self.send_extra_interfaces();
+        extra.stop.store(true, Ordering::SeqCst);
+        match extra.done.recv_timeout(self.stop_timeout()) {
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => {
+                if extra.handle.join().is_err() {
+                    log::warn!("Packet capture thread on {} panicked", name);
+                }
+            }
+            // -- it has been told to stop and exits after its current read
+            Err(RecvTimeoutError::Timeout) => {
+                log::warn!("Packet capture thread on {} did not stop within timeout", name);
+            }
+        }
+    }
+
+    fn stop_extras(&mut self) {
+        // -- signal all of them first so their timeouts overlap
+        for extra in self.extra_captures.values() {
+            extra.stop.store(true, Ordering::SeqCst);
+        }
+        let names: Vec<String> = self.extra_captures.keys().cloned().collect();
+        for name in names {
+            self.stop_extra(&name);
+        }
+    }
+
+    // A capture that failed to open its channel has exited on its own
+    fn reap_extras(&mut self) {
+        let finished: Vec<String> = self
+            .extra_captures
+            .iter()
+            .filter(|(_, extra)| extra.handle.is_finished())
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in finished {
+            self.stop_extra(&name);
+        }
+    }
+
+    // The kernel filter is attached when a thread opens its channel, so every
+    // capture has to start over to pick up a new one
+    fn restart_extras(&mut self) {
+        let interfaces: Vec<NetworkInterface> =
+            self.extra_captures.values().map(|extra| extra.interface.clone()).collect();
+        self.stop_extras();
+        for interface in interfaces {
+            self.start_extra(interface);
+        }
+    }
+
+    // Every replay starts over from the beginning of the file
+    fn start_replay(&mut self, path: PathBuf) {
+        let Some(tx) = self.action_tx.clone() else {
+            return;
+        };
+        log::debug!("Starting pcap replay thread for {}", path.display());
+        self.clear_packets();
+        let dump_stop = self.dump_stop.clone();
+        let dump_paused = self.dump_paused.clone();
+        let counters = self.counters.clone();
+        let decode = self.decode.clone();
+        let capture = self.config.capture.clone();
+        let (done_tx, done_rx) = mpsc::channel();
+        let t_handle = thread::spawn(move || {
+            Self::replay_logic(tx, path, dump_stop, dump_paused, counters, decode, capture);
+            let _ = done_tx.send(());
+        });
+        self.loop_thread = Some(t_handle);
+        self.loop_done = Some(done_rx);
+        if !self.dump_paused.load(Ordering::Relaxed) {
+            self.clock.resume();
+        }
+    }
+
+    // The capture thread only checks the stop flag between reads, so give it at
+    // least a couple of read timeouts to exit
+    fn stop_timeout(&self) -> Duration {
+        let capture = &self.config.capture;
+        Duration::from_millis(capture.join_timeout_ms.max(capture.read_timeout_ms.saturating_mul(2)))
+    }
+
+    /// Signal the capture thread to stop and wait for its done signal. Returns
+    /// `Err` with the thread handle if it did not exit within the join timeout.
+    fn stop_loop(&mut self) -> Result<(), JoinHandle<()>> {
         // Use SeqCst ordering for consistent memory visibility across threads
         self.dump_stop.store(true, Ordering::SeqCst);
 
-        // Wait for thread to finish with timeout
-        if let Some(handle) = self.loop_thread.take() {
-            // Try to join the thread with a timeout
-            // We use a simple timeout mechanism by checking if thread is finished
-            let start = std::time::Instant::now();
-            let timeout = Duration::from_secs(1);
+        let Some(handle) = self.loop_thread.take() else {
+            return Ok(());
+        };
+        let stopped = match self.loop_done.take() {
+            // -- a panicking thread drops the sender, which also ends the wait
+            Some(done) => match done.recv_timeout(self.stop_timeout()) {
+                Ok(()) | Err(RecvTimeoutError::Disconnected) => true,
+                Err(RecvTimeoutError::Timeout) => {
+                    self.loop_done = Some(done);
+                    false
+                }
+            },
+            None => handle.is_finished(),
+        };
+        if !stopped {
+            return Err(handle);
+        }
+
+        match handle.join() {
+            Ok(_) => log::debug!("Packet capture thread stopped successfully"),
+            Err(_) => log::warn!("Packet capture thread panicked during shutdown"),
+        }
+        Ok(())
+    }
+
+    fn restart_loop(&mut self) {
+        log::debug!("Requesting packet capture thread to stop");
+        if let Err(handle) = self.stop_loop() {
+            // Thread didn't finish in time, but we've signaled it to stop
+            // Store the handle back so Drop can handle it
+            log::warn!("Packet capture thread did not stop within timeout, will be cleaned up on drop");
+            self.loop_thread = Some(handle);
+        }
+    }
+
+    fn set_packet_type(&mut self, packet_type: PacketTypeEnum) {
+        // -- disabled types have no data, show everything instead
+        let packet_type = if self.decode.disabled().contains(packet_type) {
+            PacketTypeEnum::All
+        } else {
+            packet_type
+        };
+        self.packet_type = packet_type;
+        self.decode.selected.store(packet_type as u8, Ordering::Relaxed);
+    }
 
-            while !handle.is_finished() && start.elapsed() < timeout {
-                thread::sleep(Duration::from_millis(50));
+    // Next packet type in the cycle, skipping disabled ones
+    fn step_packet_type(&self, forward: bool) -> PacketTypeEnum {
+        let disabled = self.decode.disabled();
+        let mut packet_type = self.packet_type;
+        loop {
+            let next = if forward { packet_type.next() } else { packet_type.previous() };
+            if next == packet_type {
+                return self.packet_type;
             }
+            if !disabled.contains(next) {
+                return next;
+            }
+            packet_type = next;
+        }
+    }
 
-            if handle.is_finished() {
-                // Thread finished gracefully, join it to clean up
-                match handle.join() {
-                    Ok(_) => log::debug!("Packet capture thread stopped successfully"),
-                    Err(_) => log::warn!("Packet capture thread panicked during shutdown"),
-                }
-            } else {
-                // Thread didn't finish in time, but we've signaled it to stop
-                // Store the handle back so Drop can handle it
-                log::warn!("Packet capture thread did not stop within timeout, will be cleaned up on drop");
-                self.loop_thread = Some(handle);
+    fn set_disabled_types(&mut self, disabled: DisabledTypes) {
+        self.decode.disabled.store(disabled.0, Ordering::Relaxed);
+        // -- free what was buffered of the disabled types
+        let history_size = self.all_packets.max_len();
+        let mut buffers = [
+            (PacketTypeEnum::Arp, &mut self.arp_packets),
+            (PacketTypeEnum::Tcp, &mut self.tcp_packets),
+            (PacketTypeEnum::Udp, &mut self.udp_packets),
+            (PacketTypeEnum::Icmp, &mut self.icmp_packets),
+            (PacketTypeEnum::Icmp6, &mut self.icmp6_packets),
+            (PacketTypeEnum::Igmp, &mut self.igmp_packets),
+            (PacketTypeEnum::Other, &mut self.other_packets),
+        ];
+        for (packet_type, buffer) in buffers.iter_mut() {
+            if disabled.contains(*packet_type) {
+                **buffer = MaxSizeVec::new(history_size);
             }
         }
+        self.all_packets.retain(|(_, p)| !disabled.contains(p.packet_type()));
+        self.set_packet_type(self.packet_type);
+        self.table_state.select(Some(0));
+        self.set_scrollbar_height();
+    }
+
+    fn store_packet(
+        &mut self,
+        time: DateTime<Local>,
+        packet: PacketsInfoTypesEnum,
+        packet_type: PacketTypeEnum,
+    ) {
+        match packet_type {
+            PacketTypeEnum::Tcp => self.tcp_packets.push((time, packet.clone())),
+            PacketTypeEnum::Arp => self.arp_packets.push((time, packet.clone())),
+            PacketTypeEnum::Udp => self.udp_packets.push((time, packet.clone())),
+            PacketTypeEnum::Icmp => self.icmp_packets.push((time, packet.clone())),
+            PacketTypeEnum::Icmp6 => self.icmp6_packets.push((time, packet.clone())),
+            PacketTypeEnum::Igmp => self.igmp_packets.push((time, packet.clone())),
+            PacketTypeEnum::Other => self.other_packets.push((time, packet.clone())),
+            _ => {}
+        }
+        self.flows.update(time, &packet);
+        self.talkers.update(time, &packet);
+        if let PacketsInfoTypesEnum::Igmp(ref igmp) = packet {
+            self.groups.update(time, igmp);
+        }
+        self.all_packets.push((time, packet));
+    }
+
+    fn flow_idle_timeout(&self) -> Option<chrono::Duration> {
+        match self.config.capture.flow_idle_secs {
+            0 => None,
+            secs => chrono::Duration::try_seconds(secs as i64),
+        }
+    }
+
+    fn ip_conflict_window(&self) -> Option<chrono::Duration> {
+        match self.config.discovery.ip_conflict_secs {
+            0 => None,
+            secs => chrono::Duration::try_seconds(secs as i64),
+        }
+    }
+
+    /// Red line under the packet log while addresses are in conflict
+    fn make_conflict_line(conflicts: &[&Conflict]) -> Option<Paragraph<'static>> {
+        let latest = conflicts.first()?;
+        let mut spans = vec![
+            Span::styled(
+                " IP conflict: ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "{} claimed by {} and {}, last at {}",
+                    latest.ip,
+                    latest.macs.0,
+                    latest.macs.1,
+                    latest.last_seen.format("%H:%M:%S")
+                ),
+                Style::default().fg(Color::Red),
+            ),
+        ];
+        if conflicts.len() > 1 {
+            spans.push(Span::styled(
+                format!(" (+{} more on the Anomalies tab)", conflicts.len() - 1),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        Some(Paragraph::new(Line::from(spans)))
+    }
+
+    fn send_anomaly(&self, category: AnomalyCategory, severity: Severity, detail: String) {
+        if let Some(tx) = &self.action_tx {
+            let _ = tx.try_send(Action::Anomaly(Anomaly::new(category, severity, detail)));
+        }
+    }
+
+    // -- one anomaly per kind for everything detected since the last report
+    fn report_anomalies(&mut self) {
+        let pending = std::mem::take(&mut self.anomalies);
+        if pending.retransmissions > 0 {
+            self.send_anomaly(
+                AnomalyCategory::Retransmission,
+                Severity::Info,
+                format!("{} likely TCP retransmissions", pending.retransmissions),
+            );
+        }
+        if pending.low_ttl > 0 {
+            self.send_anomaly(
+                AnomalyCategory::LowTtl,
+                Severity::Warning,
+                format!("{} routed packets with a TTL of {} or less", pending.low_ttl, LOW_TTL),
+            );
+        }
+        let dropped = self.counters.dropped.load(Ordering::Relaxed);
+        if dropped > pending.dropped {
+            self.send_anomaly(
+                AnomalyCategory::Drops,
+                Severity::Warning,
+                format!("{} packets dropped, the UI could not keep up", dropped - pending.dropped),
+            );
+        }
+        let oversized = self.counters.oversized.load(Ordering::Relaxed);
+        if oversized > pending.oversized {
+            self.send_anomaly(
+                AnomalyCategory::OversizedFrame,
+                Severity::Warning,
+                format!(
+                    "{} frames over the {} byte buffer, possibly truncated",
+                    oversized - pending.oversized,
+                    MAX_PACKET_BUFFER_SIZE
+                ),
+            );
+        }
+        self.anomalies.dropped = dropped;
+        self.anomalies.kernel_dropped = pending.kernel_dropped;
+        self.anomalies.oversized = oversized;
+    }
+
+    fn clear_packets(&mut self) {
+        let history_size = self.all_packets.max_len();
+        self.arp_packets = MaxSizeVec::new(history_size);
+        self.udp_packets = MaxSizeVec::new(history_size);
+        self.tcp_packets = MaxSizeVec::new(history_size);
+        self.icmp_packets = MaxSizeVec::new(history_size);
+        self.icmp6_packets = MaxSizeVec::new(history_size);
+        self.igmp_packets = MaxSizeVec::new(history_size);
+        self.other_packets = MaxSizeVec::new(history_size);
+        self.all_packets = MaxSizeVec::new(history_size);
+        self.flows = FlowTable::new(MAX_FLOWS);
+        self.talkers = TalkerTable::new(MAX_TALKERS);
+        self.retransmits = RetransmitTracker::new(MAX_RETRANSMIT_FLOWS);
+        self.sequence_bases = SequenceBases::new(MAX_RETRANSMIT_FLOWS);
+        self.ip_conflicts = ConflictTracker::default();
+        self.echoes = EchoTracker::new(MAX_PENDING_ECHOES);
+        self.groups = GroupTable::default();
+        self.byte_rate = ByteRate::default();
+        self.table_state.select(Some(0));
+        self.scrollbar_state = ScrollbarState::new(0);
+    }
+
+    // Packets kept per type, the oldest are dropped when it shrinks
+    fn resize_history(&mut self, size: usize) {
+        for buffer in [
+            &mut self.arp_packets,
+            &mut self.udp_packets,
+            &mut self.tcp_packets,
+            &mut self.icmp_packets,
+            &mut self.icmp6_packets,
+            &mut self.igmp_packets,
+            &mut self.other_packets,
+            &mut self.all_packets,
+        ] {
+            buffer.resize(size);
+        }
+        self.set_scrollbar_height();
+    }
+
+    fn dump_source(&self) -> DumpSource {
+        if let Some(saved_at) = self.snapshot {
+            return DumpSource::Session(saved_at);
+        }
+        match &self.replay {
+            Some(path) => DumpSource::File(
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            ),
+            None => DumpSource::Live,
+        }
+    }
+
+    // Replace the captured packets with the ones from a session file and keep
+    // capture paused, so the snapshot is not mixed with live traffic
+    fn load_session(&mut self, session: &Session) {
+        self.dump_paused.store(true, Ordering::Relaxed);
+        self.clock.pause();
+        self.clear_packets();
+        for (time, packet) in &session.packets {
+            self.store_packet(*time, packet.clone(), packet.packet_type());
+        }
+        self.snapshot = Some(session.saved_at);
+        self.set_scrollbar_height();
+    }
+
+    /// Packets of every protocol, oldest first, for saving a session.
+    pub fn session_packets(&self) -> Vec<(DateTime<Local>, PacketsInfoTypesEnum)> {
+        let mut packets: Vec<(DateTime<Local>, PacketsInfoTypesEnum)> = PacketTypeEnum::iter()
+            .filter(|t| *t != PacketTypeEnum::All)
+            .flat_map(|t| self.get_array_by_packet_type(t).iter().cloned())
+            .collect();
+        packets.sort_by_key(|(time, _)| *time);
+        packets
+    }
+
+    pub fn get_active_interface(&self) -> Option<&NetworkInterface> {
+        self.active_interface.as_ref()
     }
 
     pub fn get_array_by_packet_type(
@@ -621,10 +2408,29 @@ impl PacketDump {
             PacketTypeEnum::Udp => self.udp_packets.get_deque(),
             PacketTypeEnum::Icmp => self.icmp_packets.get_deque(),
             PacketTypeEnum::Icmp6 => self.icmp6_packets.get_deque(),
+            PacketTypeEnum::Igmp => self.igmp_packets.get_deque(),
+            PacketTypeEnum::Other => self.other_packets.get_deque(),
             PacketTypeEnum::All => self.all_packets.get_deque(),
         }
     }
 
+    /// Packets dropped before the UI and by the kernel, `None` when the
+    /// capture backend has no kernel counters.
+    pub fn get_capture_drops(&self) -> (u64, Option<u64>) {
+        let kernel = self.kernel_stats.map(|stats| stats.dropped);
+        (self.counters.dropped.load(Ordering::Relaxed), kernel)
+    }
+
+    /// How the packet log shows times, export writes them the same way.
+    pub fn get_time_format(&self) -> TimeFormat {
+        self.time_format
+    }
+
+    /// Flows of the flows view, in its current order.
+    pub fn get_flows(&self) -> Vec<FlowStats> {
+        self.flows.sorted(self.flow_order).into_iter().cloned().collect()
+    }
+
     pub fn get_arp_packages(&self) -> Vec<(DateTime<Local>, PacketsInfoTypesEnum)> {
         self.arp_packets.get_vec()
     }
@@ -639,25 +2445,51 @@ impl PacketDump {
             PacketTypeEnum::Udp => self.udp_packets.get_vec(),
             PacketTypeEnum::Icmp => self.icmp_packets.get_vec(),
             PacketTypeEnum::Icmp6 => self.icmp6_packets.get_vec(),
+            PacketTypeEnum::Igmp => self.igmp_packets.get_vec(),
+            PacketTypeEnum::Other => self.other_packets.get_vec(),
             PacketTypeEnum::All => self.all_packets.get_vec(),
         }
     }
 
-    fn set_scrollbar_height(&mut self) {
-        let logs_len = self.get_array_by_packet_type(self.packet_type).len();
-        if logs_len > 0 {
-            self.scrollbar_state = self.scrollbar_state.content_length(logs_len - 1);
+    // Number of rows in the current view, after filtering
+    fn view_len(&self) -> usize {
+        if self.show_talkers {
+            self.talkers.len()
+        } else if self.show_flows && self.packet_type == PacketTypeEnum::Igmp {
+            self.groups.len()
+        } else if self.show_flows {
+            self.flows.len()
+        } else {
+            let now = Local::now();
+            self.get_array_by_packet_type(self.packet_type)
+                .iter()
+                .filter(|(d, p)| self.is_visible(d, p, &now))
+                .count()
         }
     }
 
+    fn set_scrollbar_height(&mut self) {
+        self.sync_selection(self.view_len());
+    }
+
+    // Keep the selection within `rows` and the scrollbar on it, e.g. after the
+    // filter shrank the view underneath the selection
+    fn sync_selection(&mut self, rows: usize) {
+        let selected = self.table_state.selected().map(|i| i.min(rows.saturating_sub(1)));
+        self.table_state.select(selected);
+        self.scrollbar_state = self
+            .scrollbar_state
+            .content_length(rows)
+            .position(selected.unwrap_or(0));
+    }
+
     fn previous_in_table(&mut self) {
         let index = match self.table_state.selected() {
             Some(index) => {
-                let logs = self.get_array_by_packet_type(self.packet_type);
-                let logs_len = logs.len();
+                let logs_len = self.view_len();
                 if index == 0 {
                     if logs_len > 0 {
-                        logs.len() - 1
+                        logs_len - 1
                     } else {
                         0
                     }
@@ -671,11 +2503,63 @@ impl PacketDump {
         self.scrollbar_state = self.scrollbar_state.position(index);
     }
 
+    /// The packet log is shown rather than the flows or top talkers view.
+    fn in_packet_log(&self) -> bool {
+        !self.show_flows && !self.show_talkers
+    }
+
+    // A packet was added on top of the packet log, move the selection with
+    // the rows unless following the newest one
+    fn row_added(&mut self) {
+        let index = match self.table_state.selected() {
+            Some(index) if !self.follow => index + 1,
+            _ => 0,
+        };
+        self.table_state.select(Some(index));
+        self.scrollbar_state = self.scrollbar_state.position(index);
+    }
+
+    fn select_in_table(&mut self, index: usize) {
+        self.table_state.select(Some(index));
+        self.scrollbar_state = self.scrollbar_state.position(index);
+    }
+
+    fn page_in_table(&mut self, down: bool) {
+        let selected = self.table_state.selected().unwrap_or(0);
+        let rows = self.page_len(selected, down);
+        let index = if down {
+            (selected + rows).min(self.view_len().saturating_sub(1))
+        } else {
+            selected.saturating_sub(rows)
+        };
+        self.select_in_table(index);
+    }
+
+    // Rows a page moves the selection by, fewer than `page_rows` when wrapped
+    // rows take several lines
+    fn page_len(&self, selected: usize, down: bool) -> usize {
+        if !self.wrap_rows || !self.in_packet_log() {
+            return self.page_rows.max(1);
+        }
+        let (before, after) = self.row_heights.split_at(selected.min(self.row_heights.len()));
+        let mut lines = 0;
+        let mut fits = |height: &&usize| {
+            lines += **height;
+            lines <= self.page_rows
+        };
+        let rows = if down {
+            after.iter().skip(1).take_while(&mut fits).count()
+        } else {
+            before.iter().rev().take_while(&mut fits).count()
+        };
+        rows.max(1)
+    }
+
     fn next_in_table(&mut self) {
         let index = match self.table_state.selected() {
             Some(index) => {
-                let logs = self.get_array_by_packet_type(self.packet_type);
-                if logs.is_empty() || index >= logs.len() - 1 {
+                let logs_len = self.view_len();
+                if logs_len == 0 || index >= logs_len - 1 {
                     0
                 } else {
                     index + 1
@@ -687,34 +2571,105 @@ impl PacketDump {
         self.scrollbar_state = self.scrollbar_state.position(index);
     }
 
-    /// Formats an ICMP packet into styled spans for table display
-    fn format_icmp_packet_row(icmp: &ICMPPacketInfo) -> Vec<Span<'static>> {
-        let mut spans = vec![];
-
-        spans.push(Span::styled(
-            format!("[{}] ", icmp.interface_name.clone()),
-            Style::default().fg(Color::Green),
-        ));
-        spans.push(Span::styled(
-            "ICMP",
-            Style::default().fg(Color::Black).bg(Color::White),
-        ));
+    /// Marks a row as outbound (source is local), inbound (destination is local)
+    /// or neither, e.g. forwarded or mirrored traffic
+    fn direction_span(source: IpAddr, destination: IpAddr, local_ips: &[IpAddr]) -> Span<'static> {
+        if local_ips.contains(&source) {
+            Span::styled("\u{2192} ", Style::default().fg(Color::LightGreen))
+        } else if local_ips.contains(&destination) {
+            Span::styled("\u{2190} ", Style::default().fg(Color::LightCyan))
+        } else {
+            Span::styled("\u{00b7} ", Style::default().fg(Color::DarkGray))
+        }
+    }
 
-        match icmp.icmp_type {
-            IcmpTypes::EchoRequest => {
-                spans.push(Span::styled(
-                    " echo request ",
-                    Style::default().fg(Color::Yellow),
-                ));
+    /// Compact `ttl=64 dscp=46` suffix, DSCP only when the packet is marked.
+    /// A low TTL is highlighted.
+    fn ip_meta_spans(ip: IpMeta) -> Vec<Span<'static>> {
+        let mut spans = vec![if ip.low_ttl {
+            Span::styled(
+                format!(" ttl={} [low ttl]", ip.ttl),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::styled(format!(" ttl={}", ip.ttl), Style::default().fg(Color::DarkGray))
+        }];
+        if ip.dscp != 0 {
+            spans.push(Span::styled(
+                format!(" dscp={}", ip.dscp),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        spans
+    }
+
+    /// Protocol badge of a row, recolored with the conversation's color when
+    /// flow coloring is on
+    fn protocol_badge(label: &'static str, style: Style, flow: Option<Color>) -> Span<'static> {
+        match flow {
+            Some(color) => Span::styled(label, Style::default().fg(Color::Black).bg(color)),
+            None => Span::styled(label, style),
+        }
+    }
+
+    /// Color of an interface name while capturing on several interfaces.
+    fn interface_color(name: &str) -> Color {
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        FLOW_PALETTE[(hasher.finish() % FLOW_PALETTE.len() as u64) as usize]
+    }
+
+    /// Color of the conversation a packet belongs to, hashed from its endpoints
+    /// in a fixed order so both directions get the same color. `None` for
+    /// frames without IP addresses.
+    fn flow_color(packet: &PacketsInfoTypesEnum) -> Option<Color> {
+        let (source, destination) = match packet {
+            PacketsInfoTypesEnum::Tcp(p) => {
+                ((p.source, p.source_port), (p.destination, p.destination_port))
             }
-            IcmpTypes::EchoReply => {
-                spans.push(Span::styled(
-                    " echo reply ",
-                    Style::default().fg(Color::Yellow),
-                ));
+            PacketsInfoTypesEnum::Udp(p) => {
+                ((p.source, p.source_port), (p.destination, p.destination_port))
             }
-            _ => {}
-        }
+            _ => {
+                let (source, destination) = packet.addresses()?;
+                ((source, 0), (destination, 0))
+            }
+        };
+        let endpoints = if source <= destination {
+            (source, destination)
+        } else {
+            (destination, source)
+        };
+        let mut hasher = DefaultHasher::new();
+        endpoints.hash(&mut hasher);
+        Some(FLOW_PALETTE[(hasher.finish() % FLOW_PALETTE.len() as u64) as usize])
+    }
+
+    /// Formats an ICMP packet into styled spans for table display
+    fn format_icmp_packet_row(
+        icmp: &ICMPPacketInfo,
+        local_ips: &[IpAddr],
+        flow: Option<Color>,
+    ) -> Vec<Span<'static>> {
+        let mut spans = vec![Self::direction_span(icmp.source, icmp.destination, local_ips)];
+
+        spans.push(Span::styled(
+            format!("[{}] ", icmp.interface_name.clone()),
+            Style::default().fg(Color::Green),
+        ));
+        spans.push(Self::protocol_badge(
+            "ICMP",
+            Style::default().fg(Color::Black).bg(Color::White),
+            flow,
+        ));
+
+        let is_echo = matches!(icmp.icmp_type, IcmpTypes::EchoRequest | IcmpTypes::EchoReply);
+        // -- errors stand out from the echo traffic around them
+        let type_color = if icmp::is_error(icmp.icmp_type) { Color::Red } else { Color::Yellow };
+        spans.push(Span::styled(
+            format!(" {} ", icmp::type_str(icmp.icmp_type, icmp.code)),
+            Style::default().fg(type_color),
+        ));
 
         spans.push(Span::styled(
             icmp.source.to_string(),
@@ -725,6 +2680,17 @@ impl PacketDump {
             icmp.destination.to_string(),
             Style::default().fg(Color::Blue),
         ));
+        if !is_echo {
+            if let Some(quote) = &icmp.quote {
+                spans.push(Span::styled(" for ", Style::default().fg(Color::Yellow)));
+                spans.push(Span::styled(quote.to_string(), Style::default().fg(Color::Magenta)));
+            }
+            if let Some(gateway) = icmp.gateway {
+                spans.push(Span::styled(" via ", Style::default().fg(Color::Yellow)));
+                spans.push(Span::styled(gateway.to_string(), Style::default().fg(Color::Blue)));
+            }
+            return spans;
+        }
         spans.push(Span::styled("(seq=", Style::default().fg(Color::Yellow)));
         spans.push(Span::styled(
             format!("{:?}", icmp.seq.to_string()),
@@ -737,21 +2703,35 @@ impl PacketDump {
             Style::default().fg(Color::Green),
         ));
         spans.push(Span::styled(")", Style::default().fg(Color::Yellow)));
+        if let Some(rtt) = icmp.rtt_ms {
+            spans.push(Span::styled(format!(" rtt={:.1}ms", rtt), Style::default().fg(Color::Cyan)));
+        }
+        if icmp.duplicate {
+            spans.push(Span::styled(
+                " [dup]",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
 
         spans
     }
 
     /// Formats an ICMPv6 packet into styled spans for table display
-    fn format_icmp6_packet_row(icmp: &ICMP6PacketInfo) -> Vec<Span<'static>> {
-        let mut spans = vec![];
+    fn format_icmp6_packet_row(
+        icmp: &ICMP6PacketInfo,
+        local_ips: &[IpAddr],
+        flow: Option<Color>,
+    ) -> Vec<Span<'static>> {
+        let mut spans = vec![Self::direction_span(icmp.source, icmp.destination, local_ips)];
 
         spans.push(Span::styled(
             format!("[{}] ", icmp.interface_name.clone()),
             Style::default().fg(Color::Green),
         ));
-        spans.push(Span::styled(
+        spans.push(Self::protocol_badge(
             "ICMP6",
             Style::default().fg(Color::Red).bg(Color::Black),
+            flow,
         ));
 
         let icmp_type_str = match icmp.icmp_type {
@@ -759,6 +2739,8 @@ impl PacketDump {
             Icmpv6Types::EchoReply => " echo reply ",
             Icmpv6Types::NeighborAdvert => " neighbor advert ",
             Icmpv6Types::NeighborSolicit => " neighbor solicit ",
+            Icmpv6Types::RouterAdvert => " router advert ",
+            Icmpv6Types::RouterSolicit => " router solicit ",
             Icmpv6Types::Redirect => " redirect ",
             _ => " unknown ",
         };
@@ -776,23 +2758,107 @@ impl PacketDump {
             icmp.destination.to_string(),
             Style::default().fg(Color::Blue),
         ));
+        if let Some(target) = icmp.target {
+            spans.push(Span::styled(" target: ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(target.to_string(), Style::default().fg(Color::Blue)));
+        }
+        if let Some(mac) = icmp.link_addr {
+            spans.push(Span::styled(" lladdr: ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(mac.to_string(), Style::default().fg(Color::Green)));
+        }
+        for (prefix, len) in &icmp.prefixes {
+            spans.push(Span::styled(" prefix: ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(
+                format!("{}/{}", prefix, len),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        if let Some(lifetime) = icmp.router_lifetime {
+            spans.push(Span::styled(" lifetime: ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(format!("{}s", lifetime), Style::default().fg(Color::Red)));
+        }
         spans.push(Span::styled(", ", Style::default().fg(Color::Yellow)));
         spans.push(Span::styled(")", Style::default().fg(Color::Yellow)));
 
         spans
     }
 
+    /// Formats an IGMP packet into styled spans for table display
+    fn format_igmp_packet_row(
+        igmp: &IGMPPacketInfo,
+        local_ips: &[IpAddr],
+        flow: Option<Color>,
+    ) -> Vec<Span<'static>> {
+        let mut spans = vec![Self::direction_span(igmp.source, igmp.destination, local_ips)];
+
+        spans.push(Span::styled(
+            format!("[{}] ", igmp.interface_name.clone()),
+            Style::default().fg(Color::Green),
+        ));
+        spans.push(Self::protocol_badge(
+            "IGMP",
+            Style::default().fg(Color::Black).bg(Color::LightMagenta),
+            flow,
+        ));
+        spans.push(Span::styled(
+            format!(" {} ", igmp::type_str(igmp.igmp_type)),
+            Style::default().fg(Color::Yellow),
+        ));
+
+        spans.push(Span::styled(
+            igmp.source.to_string(),
+            Style::default().fg(Color::Blue),
+        ));
+        spans.push(Span::styled(" -> ", Style::default().fg(Color::Yellow)));
+        spans.push(Span::styled(
+            igmp.destination.to_string(),
+            Style::default().fg(Color::Blue),
+        ));
+
+        if let Some(group) = igmp.group {
+            spans.push(Span::styled(" group=", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(
+                group.to_string(),
+                Style::default().fg(Color::Green),
+            ));
+        }
+        if !igmp.records.is_empty() {
+            spans.push(Span::styled(" records=", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(
+                igmp.records
+                    .iter()
+                    .map(igmp::record_str)
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                Style::default().fg(Color::Green),
+            ));
+        }
+
+        spans
+    }
+
+    /// A port with its service name when `names` is on and it has one, `443(https)`.
+    fn port_str(port: u16, service: fn(u16) -> Option<&'static str>, names: bool) -> String {
+        services::port_label(port, service(port).filter(|_| names))
+    }
+
     /// Formats a UDP packet into styled spans for table display
-    fn format_udp_packet_row(udp: &UDPPacketInfo) -> Vec<Span<'static>> {
-        let mut spans = vec![];
+    fn format_udp_packet_row(
+        udp: &UDPPacketInfo,
+        local_ips: &[IpAddr],
+        flow: Option<Color>,
+        port_names: bool,
+    ) -> Vec<Span<'static>> {
+        let mut spans = vec![Self::direction_span(udp.source, udp.destination, local_ips)];
 
         spans.push(Span::styled(
             format!("[{}] ", udp.interface_name.clone()),
             Style::default().fg(Color::Green),
         ));
-        spans.push(Span::styled(
+        spans.push(Self::protocol_badge(
             "UDP",
             Style::default().fg(Color::Yellow).bg(Color::Blue),
+            flow,
         ));
         spans.push(Span::styled(
             " Packet: ",
@@ -804,7 +2870,7 @@ impl PacketDump {
         ));
         spans.push(Span::styled(":", Style::default().fg(Color::Yellow)));
         spans.push(Span::styled(
-            udp.source_port.to_string(),
+            Self::port_str(udp.source_port, services::udp_service, port_names),
             Style::default().fg(Color::Green),
         ));
         spans.push(Span::styled(" > ", Style::default().fg(Color::Yellow)));
@@ -814,7 +2880,7 @@ impl PacketDump {
         ));
         spans.push(Span::styled(":", Style::default().fg(Color::Yellow)));
         spans.push(Span::styled(
-            udp.destination_port.to_string(),
+            Self::port_str(udp.destination_port, services::udp_service, port_names),
             Style::default().fg(Color::Green),
         ));
         spans.push(Span::styled(";", Style::default().fg(Color::Yellow)));
@@ -826,22 +2892,51 @@ impl PacketDump {
             format!("{}", udp.length),
             Style::default().fg(Color::Red),
         ));
+        if let Some(dns) = &udp.dns_info {
+            spans.push(Span::styled("; ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(dns::summary(dns), Style::default().fg(Color::Magenta)));
+        }
+        if let Some(dhcp) = &udp.dhcp_info {
+            spans.push(Span::styled("; ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(dhcp::summary(dhcp), Style::default().fg(Color::Magenta)));
+        }
+        if let Some(mdns) = &udp.mdns_info {
+            spans.push(Span::styled("; ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(mdns::summary(mdns), Style::default().fg(Color::Magenta)));
+        }
+        if let Some(ssdp) = &udp.ssdp_info {
+            spans.push(Span::styled("; ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(ssdp::summary(ssdp), Style::default().fg(Color::Magenta)));
+        }
 
         spans
     }
 
     /// Formats a TCP packet into styled spans for table display
-    fn format_tcp_packet_row(tcp: &TCPPacketInfo) -> Vec<Span<'static>> {
-        let mut spans = vec![];
+    fn format_tcp_packet_row(
+        tcp: &TCPPacketInfo,
+        local_ips: &[IpAddr],
+        flow: Option<Color>,
+        relative_seq: bool,
+        port_names: bool,
+    ) -> Vec<Span<'static>> {
+        let mut spans = vec![Self::direction_span(tcp.source, tcp.destination, local_ips)];
 
         spans.push(Span::styled(
             format!("[{}] ", tcp.interface_name.clone()),
             Style::default().fg(Color::Green),
         ));
-        spans.push(Span::styled(
+        spans.push(Self::protocol_badge(
             "TCP",
             Style::default().fg(Color::Black).bg(Color::Green),
+            flow,
         ));
+        if tcp.retransmission {
+            spans.push(Span::styled(
+                " [retx]",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
         spans.push(Span::styled(
             " Packet: ",
             Style::default().fg(Color::Yellow),
@@ -852,7 +2947,7 @@ impl PacketDump {
         ));
         spans.push(Span::styled(":", Style::default().fg(Color::Yellow)));
         spans.push(Span::styled(
-            tcp.source_port.to_string(),
+            Self::port_str(tcp.source_port, services::tcp_service, port_names),
             Style::default().fg(Color::Green),
         ));
         spans.push(Span::styled(" > ", Style::default().fg(Color::Yellow)));
@@ -862,9 +2957,28 @@ impl PacketDump {
         ));
         spans.push(Span::styled(":", Style::default().fg(Color::Yellow)));
         spans.push(Span::styled(
-            tcp.destination_port.to_string(),
+            Self::port_str(tcp.destination_port, services::tcp_service, port_names),
             Style::default().fg(Color::Green),
         ));
+        spans.push(Span::styled(
+            format!(" {}", tcp_flags::tag(tcp.flags)),
+            Style::default().fg(tcp_flags::color(tcp.flags)),
+        ));
+        // -- relative numbers are missing for packets from older sessions
+        let (sequence, acknowledgement) = match tcp.relative_sequence {
+            Some(sequence) if relative_seq => (sequence, tcp.relative_acknowledgement),
+            _ => (tcp.sequence, Some(tcp.acknowledgement)),
+        };
+        spans.push(Span::styled(" seq=", Style::default().fg(Color::Yellow)));
+        spans.push(Span::styled(sequence.to_string(), Style::default().fg(Color::Cyan)));
+        let acknowledgement = acknowledgement.filter(|_| tcp.flags & TcpFlags::ACK != 0);
+        if let Some(acknowledgement) = acknowledgement {
+            spans.push(Span::styled(" ack=", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(
+                acknowledgement.to_string(),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
         spans.push(Span::styled(";", Style::default().fg(Color::Yellow)));
         spans.push(Span::styled(
             " length: ",
@@ -874,21 +2988,33 @@ impl PacketDump {
             format!("{}", tcp.length),
             Style::default().fg(Color::Red),
         ));
+        if let Some(http) = &tcp.http_info {
+            spans.push(Span::styled("; ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(
+                http.start_line.clone(),
+                Style::default().fg(Color::Magenta),
+            ));
+            if let Some(host) = &http.host {
+                spans.push(Span::styled(" Host: ", Style::default().fg(Color::Yellow)));
+                spans.push(Span::styled(host.clone(), Style::default().fg(Color::Magenta)));
+            }
+        }
 
         spans
     }
 
     /// Formats an ARP packet into styled spans for table display
-    fn format_arp_packet_row(arp: &ARPPacketInfo) -> Vec<Span<'static>> {
+    fn format_arp_packet_row(arp: &ARPPacketInfo, flow: Option<Color>) -> Vec<Span<'static>> {
         let mut spans = vec![];
 
         spans.push(Span::styled(
             format!("[{}] ", arp.interface_name.clone()),
             Style::default().fg(Color::Green),
         ));
-        spans.push(Span::styled(
+        spans.push(Self::protocol_badge(
             "ARP",
             Style::default().fg(Color::Yellow).bg(Color::Red),
+            flow,
         ));
         spans.push(Span::styled(
             " Packet: ",
@@ -898,6 +3024,12 @@ impl PacketDump {
             arp.source_mac.to_string(),
             Style::default().fg(Color::Green),
         ));
+        if let Some(vendor) = &arp.source_vendor {
+            spans.push(Span::styled(
+                format!(" ({}) ", vendor),
+                Style::default().fg(Color::LightMagenta),
+            ));
+        }
         spans.push(Span::styled(
             arp.source_ip.to_string(),
             Style::default().fg(Color::Blue),
@@ -920,58 +3052,289 @@ impl PacketDump {
         spans
     }
 
-    /// Retrieves and filters packet data based on packet type and filter string,
-    /// then formats each packet into a table row with styled spans
-    fn get_table_rows_by_packet_type<'a>(&mut self, packet_type: PacketTypeEnum) -> Vec<Row<'a>> {
-        let f_str = self.filter_str.clone();
-        let logs_data = self.get_array_by_packet_type(packet_type);
-
-        // Filter packets based on filter string
-        let mut logs: Vec<(DateTime<Local>, PacketsInfoTypesEnum)> = vec![];
-        for (d, p) in logs_data {
-            let matches_filter = match p {
-                PacketsInfoTypesEnum::Icmp(log) => log.raw_str.contains(f_str.as_str()),
-                PacketsInfoTypesEnum::Arp(log) => log.raw_str.contains(f_str.as_str()),
-                PacketsInfoTypesEnum::Icmp6(log) => log.raw_str.contains(f_str.as_str()),
-                PacketsInfoTypesEnum::Udp(log) => log.raw_str.contains(f_str.as_str()),
-                PacketsInfoTypesEnum::Tcp(log) => log.raw_str.contains(f_str.as_str()),
-            };
+    fn format_other_packet_row(other: &OtherPacketInfo) -> Vec<Span<'static>> {
+        let mut spans = vec![];
+
+        spans.push(Span::styled(
+            format!("[{}] ", other.interface_name.clone()),
+            Style::default().fg(Color::Green),
+        ));
+        spans.push(Span::styled(
+            ethertypes::format(other.ethertype),
+            Style::default().fg(Color::Black).bg(Color::Gray),
+        ));
+        spans.push(Span::styled(
+            " Frame: ",
+            Style::default().fg(Color::Yellow),
+        ));
+        spans.push(Span::styled(
+            other.source_mac.to_string(),
+            Style::default().fg(Color::Green),
+        ));
+        spans.push(Span::styled(" > ", Style::default().fg(Color::Yellow)));
+        spans.push(Span::styled(
+            other.destination_mac.to_string(),
+            Style::default().fg(Color::Green),
+        ));
+        spans.push(Span::styled(";", Style::default().fg(Color::Yellow)));
+        spans.push(Span::styled(
+            format!(" length: {}", other.length),
+            Style::default().fg(Color::Red),
+        ));
+        if let Some(note) = &other.note {
+            spans.push(Span::styled("; ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(note.clone(), Style::default().fg(Color::Magenta)));
+        }
+
+        spans
+    }
 
-            if matches_filter {
-                logs.push((d.to_owned(), p.to_owned()));
+    /// Splits styled spans into lines no wider than `width` characters,
+    /// keeping the style of every character intact
+    /// Mark the byte `ranges` of the text the spans make up, splitting spans
+    /// where a range starts or ends inside one.
+    fn highlight_spans(spans: Vec<Span<'static>>, ranges: &[(usize, usize)]) -> Vec<Span<'static>> {
+        if ranges.is_empty() {
+            return spans;
+        }
+        let mut highlighted = vec![];
+        let mut offset = 0;
+        for span in spans {
+            let content = span.content.as_ref();
+            let end = offset + content.len();
+            let mut pos = 0;
+            for &(start, stop) in ranges.iter().filter(|(start, stop)| *stop > offset && *start < end) {
+                let from = start.saturating_sub(offset).max(pos);
+                let to = (stop - offset).min(content.len());
+                if from > pos {
+                    highlighted.push(Span::styled(content[pos..from].to_string(), span.style));
+                }
+                highlighted.push(Span::styled(
+                    content[from..to].to_string(),
+                    span.style.add_modifier(Modifier::REVERSED | Modifier::UNDERLINED),
+                ));
+                pos = to;
             }
+            if pos < content.len() {
+                highlighted.push(Span::styled(content[pos..].to_string(), span.style));
+            }
+            offset = end;
         }
+        highlighted
+    }
+
+    fn wrap_spans(spans: Vec<Span<'static>>, width: usize) -> Vec<Line<'static>> {
+        let width = width.max(1);
+        let mut lines: Vec<Line<'static>> = vec![];
+        let mut current: Vec<Span<'static>> = vec![];
+        let mut current_len = 0;
+
+        for span in spans {
+            let mut chunk = String::new();
+            for c in span.content.chars() {
+                if current_len == width {
+                    if !chunk.is_empty() {
+                        current.push(Span::styled(std::mem::take(&mut chunk), span.style));
+                    }
+                    lines.push(Line::from(std::mem::take(&mut current)));
+                    current_len = 0;
+                }
+                chunk.push(c);
+                current_len += 1;
+            }
+            if !chunk.is_empty() {
+                current.push(Span::styled(chunk, span.style));
+            }
+        }
+        if !current.is_empty() || lines.is_empty() {
+            lines.push(Line::from(current));
+        }
+        lines
+    }
+
+    /// Whether a packet passes the time range, filter string or expression, field tokens,
+    /// preset and subnets
+    fn is_visible(
+        &self,
+        time: &DateTime<Local>,
+        packet: &PacketsInfoTypesEnum,
+        now: &DateTime<Local>,
+    ) -> bool {
+        if !self.time_range.contains(time, now) {
+            return false;
+        }
+        let text = &self.text_filter;
+        let matches_filter = match &self.bpf_filter {
+            Some(expr) => expr.matches(packet),
+            None => match packet {
+                PacketsInfoTypesEnum::Icmp(log) => text.matches(&log.raw_str),
+                PacketsInfoTypesEnum::Arp(log) => text.matches(&log.raw_str),
+                PacketsInfoTypesEnum::Icmp6(log) => text.matches(&log.raw_str),
+                PacketsInfoTypesEnum::Udp(log) => text.matches(&log.raw_str),
+                PacketsInfoTypesEnum::Tcp(log) => text.matches(&log.raw_str),
+                PacketsInfoTypesEnum::Igmp(log) => text.matches(&log.raw_str),
+                PacketsInfoTypesEnum::Other(log) => text.matches(&log.raw_str),
+            },
+        };
+        matches_filter
+            && self.field_filter.matches(packet)
+            && self.preset.matches(packet, &self.dns_ports)
+            && self.net_filter.matches(packet)
+            && self.iface_filter.matches(packet)
+    }
+
+    /// Packets of a type that pass the filters, in table order
+    fn visible_packets(
+        &self,
+        packet_type: PacketTypeEnum,
+    ) -> Vec<(DateTime<Local>, PacketsInfoTypesEnum)> {
+        let now = Local::now();
+        self.get_array_by_packet_type(packet_type)
+            .iter()
+            .filter(|(d, p)| self.is_visible(d, p, &now))
+            .cloned()
+            .collect()
+    }
+
+    /// Retrieves and filters packet data based on packet type and filter string,
+    /// then formats each packet into a table row with styled spans
+    fn get_table_rows_by_packet_type<'a>(&mut self, packet_type: PacketTypeEnum) -> Vec<Row<'a>> {
+        let logs = self.visible_packets(packet_type);
 
         // Format each packet into a table row
+        let local_ips: Vec<IpAddr> = match &self.active_interface {
+            Some(interface) => interface.ips.iter().map(|ip| ip.ip()).collect(),
+            None => vec![],
+        };
+        let wrap_width = if self.wrap_rows { Some(self.log_width) } else { None };
+        let zebra = self.config.ui.zebra_rows;
+        let flow_colors = self.flow_colors;
+        let relative_seq = self.relative_seq;
+        let time_format = self.time_format;
+        let time_delta = self.time_delta;
+        let color_rules = &self.color_rules;
+        let port_names = self.config.ui.port_names;
+        // -- the interface every row starts with is colored per interface once
+        // there is more than one
+        let multi_interface = !self.extra_captures.is_empty();
+        // -- an expression filter matches fields, there is no text to mark
+        let text_filter = if self.bpf_filter.is_none() { Some(&self.text_filter) } else { None };
+        self.row_heights.clear();
+        let row_heights = &mut self.row_heights;
         let rows: Vec<Row> = logs
             .iter()
-            .map(|(time, log)| {
-                let t = time.format("%H:%M:%S").to_string();
-
-                let spans = match log {
-                    PacketsInfoTypesEnum::Icmp(icmp) => Self::format_icmp_packet_row(icmp),
-                    PacketsInfoTypesEnum::Icmp6(icmp6) => Self::format_icmp6_packet_row(icmp6),
-                    PacketsInfoTypesEnum::Udp(udp) => Self::format_udp_packet_row(udp),
-                    PacketsInfoTypesEnum::Tcp(tcp) => Self::format_tcp_packet_row(tcp),
-                    PacketsInfoTypesEnum::Arp(arp) => Self::format_arp_packet_row(arp),
+            .enumerate()
+            .map(|(idx, (time, log))| {
+                // -- newest first, the previous packet is the next row
+                let t = if time_delta {
+                    format_delta(logs.get(idx + 1).map(|(previous, _)| *time - *previous))
+                } else {
+                    time_format.format(time)
                 };
 
-                let line = Line::from(spans);
-                Row::new(vec![
-                    Cell::from(Span::styled(t, Style::default().fg(Color::Cyan))),
-                    Cell::from(line),
-                ])
+                let flow = if flow_colors { Self::flow_color(log) } else { None };
+                let mut spans = match log {
+                    PacketsInfoTypesEnum::Icmp(icmp) => {
+                        Self::format_icmp_packet_row(icmp, &local_ips, flow)
+                    }
+                    PacketsInfoTypesEnum::Icmp6(icmp6) => {
+                        Self::format_icmp6_packet_row(icmp6, &local_ips, flow)
+                    }
+                    PacketsInfoTypesEnum::Udp(udp) => {
+                        Self::format_udp_packet_row(udp, &local_ips, flow, port_names)
+                    }
+                    PacketsInfoTypesEnum::Tcp(tcp) => {
+                        Self::format_tcp_packet_row(
+                            tcp,
+                            &local_ips,
+                            flow,
+                            relative_seq,
+                            port_names,
+                        )
+                    }
+                    PacketsInfoTypesEnum::Arp(arp) => Self::format_arp_packet_row(arp, flow),
+                    PacketsInfoTypesEnum::Igmp(igmp) => {
+                        Self::format_igmp_packet_row(igmp, &local_ips, flow)
+                    }
+                    PacketsInfoTypesEnum::Other(other) => Self::format_other_packet_row(other),
+                };
+                if let Some(ip) = log.ip_meta() {
+                    spans.extend(Self::ip_meta_spans(ip));
+                }
+                if let Some(vlan) = log.vlan() {
+                    spans.push(Span::styled(
+                        format!(" [{}]", vlan),
+                        Style::default().fg(Color::LightBlue),
+                    ));
+                }
+                if multi_interface {
+                    let name = log.interface_name();
+                    let tag = format!("[{}] ", name);
+                    if let Some(span) = spans.iter_mut().find(|span| span.content == tag) {
+                        span.style = span.style.fg(Self::interface_color(name));
+                    }
+                }
+                // -- a configured style covers the protocol and flow colors
+                if let Some(style) = color_rules.style(log) {
+                    for span in spans.iter_mut() {
+                        span.style = span.style.patch(style);
+                    }
+                }
+                if let Some(text_filter) = text_filter {
+                    let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+                    spans = Self::highlight_spans(spans, &text_filter.match_ranges(&text));
+                }
+
+                let time_cell = Cell::from(Span::styled(t, Style::default().fg(Color::Cyan)));
+                let row = match wrap_width {
+                    Some(width) => {
+                        let lines = Self::wrap_spans(spans, width);
+                        row_heights.push(lines.len());
+                        let height = lines.len() as u16;
+                        Row::new(vec![time_cell, Cell::from(Text::from(lines))]).height(height)
+                    }
+                    None => Row::new(vec![time_cell, Cell::from(Line::from(spans))]),
+                };
+                // -- the reversed highlight of the selected row still wins over the shade
+                if zebra && idx % 2 == 1 {
+                    row.style(Style::default().bg(ZEBRA_ROW_BG))
+                } else {
+                    row
+                }
             })
             .collect();
         rows
     }
 
-    fn make_table(rows: Vec<Row>, packet_type: PacketTypeEnum, dump_paused: bool) -> Table {
+    /// `types` are the enabled packet types with the number of packets stored.
+    fn make_table<'a>(
+        rows: Vec<Row<'a>>,
+        packet_type: PacketTypeEnum,
+        types: Vec<(PacketTypeEnum, usize)>,
+        dump_title: Line<'static>,
+        wrap_rows: bool,
+        preset: FilterPreset,
+        interface_title: Line<'static>,
+    ) -> Table<'a> {
         let header = Row::new(vec!["time", "packet log"])
             .style(Style::default().fg(Color::Yellow))
             .top_margin(1)
             .bottom_margin(1);
 
+        // -- rows left by the filters out of the stored packets of the type, dim
+        // while nothing is filtered out
+        let total = types.iter().find(|(p, _)| *p == packet_type).map_or(0, |(_, c)| *c);
+        let shown_style = if rows.len() < total {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let shown_spans = vec![
+            Span::raw("|"),
+            Span::styled(format!("{}/{} shown", rows.len(), total), shown_style),
+            Span::raw("|"),
+        ];
+
         let mut type_titles = vec![
             Span::styled("|", Style::default().fg(Color::Yellow)),
             Span::styled(
@@ -979,15 +3342,18 @@ impl PacketDump {
                 Style::default().fg(Color::Red),
             ),
         ];
-        let mut enum_titles = PacketTypeEnum::iter()
+        let mut enum_titles = types
+            .iter()
             .enumerate()
-            .map(|(idx, p)| {
-                let mut span_str = format!("{} ", p);
-                if idx == PacketTypeEnum::COUNT - 1 {
-                    span_str = format!("{}", p);
+            .map(|(idx, &(p, count))| {
+                let mut span_str = format!("{}({}) ", p, count);
+                if idx == types.len() - 1 {
+                    span_str = format!("{}({})", p, count);
                 }
                 if p == packet_type {
                     Span::styled(span_str, Style::new().green().bold())
+                } else if count > 0 {
+                    Span::styled(span_str, Style::new().gray())
                 } else {
                     Span::styled(span_str, Style::new().dark_gray())
                 }
@@ -1000,28 +3366,45 @@ impl PacketDump {
         ));
         type_titles.push(Span::styled("|", Style::default().fg(Color::Yellow)));
 
-        // -- dump title
-        let mut dump_spans = vec![
-            Span::styled("|", Style::default().fg(Color::Yellow)),
+        // -- wrap title
+        let mut wrap_spans = vec![
+            Span::raw("|"),
             Span::styled(
-                "d",
+                "w",
                 Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
             ),
-            Span::styled("ump:", Style::default().fg(Color::Yellow)),
+            Span::styled("rap:", Style::default().fg(Color::Yellow)),
         ];
-        if dump_paused {
-            dump_spans.push(Span::styled("paused", Style::default().fg(Color::DarkGray)))
+        if wrap_rows {
+            wrap_spans.push(Span::styled("on", Style::default().fg(Color::Green)));
         } else {
-            dump_spans.push(Span::styled("running", Style::default().fg(Color::Green)))
+            wrap_spans.push(Span::styled("off", Style::default().fg(Color::DarkGray)));
         }
-        dump_spans.push(Span::styled("|", Style::default().fg(Color::Yellow)));
+        wrap_spans.push(Span::raw("|"));
+
+        // -- preset title
+        let preset_style = if preset == FilterPreset::None {
+            Style::default().fg(Color::DarkGray)
+        } else {
+            Style::default().fg(Color::Green)
+        };
+        let preset_spans = vec![
+            Span::raw("|"),
+            Span::styled(
+                "p",
+                Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
+            ),
+            Span::styled("reset:", Style::default().fg(Color::Yellow)),
+            Span::styled(preset.to_string(), preset_style),
+            Span::raw("|"),
+        ];
 
         let table = Table::new(rows, [Constraint::Min(10), Constraint::Percentage(100)])
             .header(header)
             .block(
                 Block::new()
                     .title(
-                        ratatui::widgets::block::Title::from(Line::from(dump_spans))
+                        ratatui::widgets::block::Title::from(dump_title)
                             .position(ratatui::widgets::block::Position::Top)
                             .alignment(Alignment::Right),
                     )
@@ -1038,44 +3421,734 @@ impl PacketDump {
                         .alignment(Alignment::Left)
                         .position(ratatui::widgets::block::Position::Bottom),
                     )
+                    .title(
+                        ratatui::widgets::block::Title::from(Line::from(wrap_spans))
+                            .alignment(Alignment::Left)
+                            .position(ratatui::widgets::block::Position::Bottom),
+                    )
+                    .title(
+                        ratatui::widgets::block::Title::from(Line::from(preset_spans))
+                            .alignment(Alignment::Left)
+                            .position(ratatui::widgets::block::Position::Bottom),
+                    )
+                    .title(
+                        ratatui::widgets::block::Title::from(Self::make_view_title(
+                            false,
+                            if packet_type == PacketTypeEnum::Igmp { "groups" } else { "flows" },
+                        ))
+                            .alignment(Alignment::Left)
+                            .position(ratatui::widgets::block::Position::Bottom),
+                    )
+                    .title(
+                        ratatui::widgets::block::Title::from(interface_title)
+                        .position(ratatui::widgets::block::Position::Top)
+                        .alignment(Alignment::Right),
+                    )
+                    .title(
+                        ratatui::widgets::block::Title::from(Line::from(type_titles))
+                            .position(ratatui::widgets::block::Position::Top)
+                            .alignment(Alignment::Left),
+                    )
+                    .title(
+                        ratatui::widgets::block::Title::from(Line::from(shown_spans))
+                            .position(ratatui::widgets::block::Position::Bottom)
+                            .alignment(Alignment::Right),
+                    )
+                    .title(
+                        ratatui::widgets::block::Title::from(Line::from(vec![
+                            Span::styled("|", Style::default().fg(Color::Yellow)),
+                            Span::styled(
+                                String::from(char::from_u32(0x25b2).unwrap_or('>')),
+                                Style::default().fg(Color::Red),
+                            ),
+                            Span::styled(
+                                String::from(char::from_u32(0x25bc).unwrap_or('>')),
+                                Style::default().fg(Color::Red),
+                            ),
+                            Span::styled("select|", Style::default().fg(Color::Yellow)),
+                        ]))
+                        .position(ratatui::widgets::block::Position::Bottom)
+                        .alignment(Alignment::Right),
+                    )
+                    .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+                    .borders(Borders::ALL) // .padding(Padding::new(1, 0, 2, 0)),
+                    .border_type(DEFAULT_BORDER_STYLE),
+            )
+            .highlight_symbol(Span::styled(
+                String::from(char::from_u32(0x25b6).unwrap_or('>')),
+                Style::default().fg(Color::Red),
+            ))
+            .column_spacing(1);
+        table
+    }
+
+    fn make_name_prompt(input: &Input, overwrite: bool) -> Paragraph<'_> {
+        let title = if overwrite {
+            Span::styled(
+                "|name exists, Enter to overwrite|",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::styled("|save filter as|", Style::default().fg(Color::Yellow))
+        };
+        Paragraph::new(input.value())
+            .style(Style::default().fg(Color::Green))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Green))
+                    .border_type(DEFAULT_BORDER_STYLE)
+                    .title(ratatui::widgets::block::Title::from(title)),
+            )
+    }
+
+    fn make_tcpdump_popup(command: &TcpdumpCommand) -> Paragraph<'static> {
+        let mut lines = vec![Line::from(Span::styled(
+            command.command.clone(),
+            Style::default().fg(Color::Green),
+        ))];
+        for note in &command.notes {
+            lines.push(Line::from(Span::styled(
+                format!("* {}", note),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green))
+                .border_type(DEFAULT_BORDER_STYLE)
+                .title(ratatui::widgets::block::Title::from(Span::styled(
+                    "|tcpdump command|",
+                    Style::default().fg(Color::Yellow),
+                ))),
+        )
+    }
+
+    fn make_detail_lines(detail: &PacketDetail) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line> = detail
+            .fields()
+            .into_iter()
+            .map(|(name, value)| {
+                Line::from(vec![
+                    Span::styled(format!("{:>16} ", name), Style::default().fg(Color::Yellow)),
+                    Span::styled(value, Style::default().fg(Color::Green)),
+                ])
+            })
+            .collect();
+        lines.push(Line::from(""));
+        for line in hex_dump(detail.packet.raw_bytes()) {
+            lines.push(Line::from(Span::styled(line, Style::default().fg(Color::Gray))));
+        }
+        lines
+    }
+
+    fn make_detail_popup(detail: &PacketDetail) -> Paragraph<'static> {
+        Paragraph::new(Self::make_detail_lines(detail))
+            .scroll((detail.scroll, 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Green))
+                    .border_type(DEFAULT_BORDER_STYLE)
+                    .title(ratatui::widgets::block::Title::from(Span::styled(
+                        "|packet detail|",
+                        Style::default().fg(Color::Yellow),
+                    ))),
+            )
+    }
+
+    fn make_recall_list(filters: &SavedFilters) -> List<'static> {
+        let items: Vec<ListItem> = if filters.is_empty() {
+            vec![ListItem::new(Span::styled(
+                "no saved filters",
+                Style::default().fg(Color::DarkGray),
+            ))]
+        } else {
+            filters
+                .iter()
+                .map(|(name, filter)| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{} ", name), Style::default().fg(Color::Yellow)),
+                        Span::styled(filter.clone(), Style::default().fg(Color::Green)),
+                    ]))
+                })
+                .collect()
+        };
+        List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Green))
+                    .border_type(DEFAULT_BORDER_STYLE)
+                    .title(ratatui::widgets::block::Title::from(Span::styled(
+                        "|saved filters|",
+                        Style::default().fg(Color::Yellow),
+                    ))),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    }
+
+    /// Dimmed while capture is paused, the rate then only shows the past.
+    fn make_sparkline(history: &[u64], paused: bool) -> Sparkline<'_> {
+        let peak = history.iter().copied().max().unwrap_or(0);
+        let color = if paused { Color::DarkGray } else { Color::Green };
+        Sparkline::default()
+            .data(history)
+            .style(Style::default().fg(color))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(DEFAULT_BORDER_STYLE)
+                    .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+                    .title(Line::from(vec![
+                        Span::raw("|"),
+                        Span::styled("packets/s", Style::default().fg(Color::Yellow)),
+                        Span::raw(" "),
+                        Span::styled(
+                            format!("peak {}", count_str(peak)),
+                            Style::default().fg(color),
+                        ),
+                        Span::raw("|"),
+                    ]))
+                    .title_alignment(Alignment::Right),
+            )
+    }
+
+    fn make_net_error() -> Paragraph<'static> {
+        Paragraph::new("CIDR parse error")
+            .style(Style::default().fg(Color::Red))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Double)
+                    .border_style(Style::default().fg(Color::Red)),
+            )
+    }
+
+    fn make_capture_banner(msg: &str) -> Paragraph<'_> {
+        Paragraph::new(msg)
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Red))
+                    .border_type(DEFAULT_BORDER_STYLE)
                     .title(
                         ratatui::widgets::block::Title::from(Span::styled(
-                            "|Packets|",
-                            Style::default().fg(Color::Yellow),
+                            "|packet capture unavailable|",
+                            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                         ))
+                        .alignment(Alignment::Center),
+                    ),
+            )
+    }
+
+    fn make_dump_title(
+        dump_paused: bool,
+        clock: &CaptureClock,
+        counters: &CaptureCounters,
+        kernel: Option<KernelStats>,
+        sample_rate: u32,
+        source: DumpSource,
+        complete: Option<&CaptureSummary>,
+    ) -> Line<'static> {
+        let mut dump_spans = vec![
+            Span::styled("|", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "d",
+                Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
+            ),
+            Span::styled("ump:", Style::default().fg(Color::Yellow)),
+        ];
+        if let DumpSource::Session(saved_at) = source {
+            dump_spans.push(Span::styled(
+                format!("session from {}", saved_at.format("%Y-%m-%d %H:%M:%S")),
+                Style::default().fg(Color::Magenta),
+            ));
+            dump_spans.push(Span::styled("|", Style::default().fg(Color::Yellow)));
+            return Line::from(dump_spans);
+        }
+        if let DumpSource::File(ref name) = source {
+            dump_spans.push(Span::styled(format!("{} ", name), Style::default().fg(Color::Magenta)));
+        }
+        if let Some(summary) = complete {
+            let done = match source {
+                DumpSource::File(_) => "finished",
+                _ => "capture complete",
+            };
+            dump_spans.push(Span::styled(
+                format!("{}: {}", done, summary),
+                Style::default().fg(Color::Magenta),
+            ))
+        } else if dump_paused {
+            dump_spans.push(Span::styled("paused", Style::default().fg(Color::DarkGray)))
+        } else {
+            dump_spans.push(Span::styled("running", Style::default().fg(Color::Green)))
+        }
+        // -- capture window: start time and running duration
+        if let Some(started) = clock.started {
+            dump_spans.push(Span::styled(
+                format!(" {}", clock.duration_str()),
+                Style::default().fg(Color::Cyan),
+            ));
+            dump_spans.push(Span::styled(" since ", Style::default().fg(Color::Yellow)));
+            dump_spans.push(Span::styled(
+                started.format("%H:%M:%S").to_string(),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        // -- counts only cover the sampled frames
+        if sample_rate > 1 {
+            dump_spans.push(Span::styled(" sampled: ", Style::default().fg(Color::Yellow)));
+            dump_spans.push(Span::styled(
+                format!("1/{}", sample_rate),
+                Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+            ));
+        }
+        // -- packets lost between the capture thread and the UI
+        let dropped = counters.dropped.load(Ordering::Relaxed);
+        if dropped > 0 {
+            dump_spans.push(Span::styled(" dropped: ", Style::default().fg(Color::Yellow)));
+            dump_spans.push(Span::styled(
+                dropped.to_string(),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        // -- frames the NIC or kernel discarded before they reached the capture
+        if let Some(kernel) = kernel {
+            let style = if kernel.dropped > 0 {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Green)
+            };
+            dump_spans.push(Span::styled(" kernel drops: ", Style::default().fg(Color::Yellow)));
+            dump_spans.push(Span::styled(kernel.dropped.to_string(), style));
+        }
+        // -- frames skipped because their IP header didn't parse
+        let malformed = counters.malformed.load(Ordering::Relaxed);
+        if malformed > 0 {
+            dump_spans.push(Span::styled(" malformed: ", Style::default().fg(Color::Yellow)));
+            dump_spans.push(Span::styled(malformed.to_string(), Style::default().fg(Color::Red)));
+        }
+        dump_spans.push(Span::styled("|", Style::default().fg(Color::Yellow)));
+        Line::from(dump_spans)
+    }
+
+    /// Rate block shown after the dump title, e.g. `|1.2k pps / 8.4 Mbps|`.
+    fn make_rate_spans(rate: Rate) -> Vec<Span<'static>> {
+        vec![
+            Span::styled("|", Style::default().fg(Color::Yellow)),
+            Span::styled(rate.to_string(), Style::default().fg(Color::Cyan)),
+            Span::styled("|", Style::default().fg(Color::Yellow)),
+        ]
+    }
+
+    /// `|follow|` while the selection stays on the newest packet.
+    fn make_follow_spans(follow: bool) -> Vec<Span<'static>> {
+        let (text, color) = match follow {
+            true => ("follow", Color::Green),
+            false => ("follow off", Color::DarkGray),
+        };
+        vec![
+            Span::styled("|", Style::default().fg(Color::Yellow)),
+            Span::styled(text, Style::default().fg(color)),
+            Span::styled("|", Style::default().fg(Color::Yellow)),
+        ]
+    }
+
+    /// `shown` lists the interfaces an `iface:` filter narrows the rows to.
+    /// `extra` are the interfaces captured on next to the active one.
+    fn make_interface_title(
+        label: &str,
+        interface: Option<&NetworkInterface>,
+        extra: &[&str],
+        shown: &[String],
+    ) -> Line<'static> {
+        let mut spans = vec![Span::styled(
+            format!("|{}", label),
+            Style::default().fg(Color::Yellow),
+        )];
+        if let Some(interface) = interface {
+            spans.push(Span::styled(" on ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(interface.name.clone(), Style::default().fg(Color::Green)));
+        }
+        for name in extra {
+            spans.push(Span::styled("+", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(
+                name.to_string(),
+                Style::default().fg(Self::interface_color(name)),
+            ));
+        }
+        if !shown.is_empty() {
+            spans.push(Span::styled(" iface:", Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(shown.join(","), Style::default().fg(Color::Magenta)));
+        }
+        spans.push(Span::styled("|", Style::default().fg(Color::Yellow)));
+        Line::from(spans)
+    }
+
+    /// `alt_view` names the non-packet view: flows, or groups for IGMP.
+    fn make_view_title(show_flows: bool, alt_view: &'static str) -> Line<'static> {
+        let (active, inactive) = if show_flows {
+            (alt_view, "packets")
+        } else {
+            ("packets", alt_view)
+        };
+        Line::from(vec![
+            Span::raw("|"),
+            Span::styled(
+                "v",
+                Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
+            ),
+            Span::styled("iew:", Style::default().fg(Color::Yellow)),
+            Span::styled(active, Style::default().fg(Color::Green)),
+            Span::styled("/", Style::default().fg(Color::Yellow)),
+            Span::styled(inactive, Style::default().fg(Color::DarkGray)),
+            Span::raw("|"),
+        ])
+    }
+
+    fn get_flow_rows<'a>(&self) -> Vec<Row<'a>> {
+        self.flows
+            .sorted(self.flow_order)
+            .into_iter()
+            .map(|flow| {
+                let state = match flow.tcp_state {
+                    Some(state) => state.to_string(),
+                    None => String::from("-"),
+                };
+                Row::new(vec![
+                    Cell::from(Span::styled(
+                        flow.protocol.to_string(),
+                        Style::default().fg(Color::Yellow),
+                    )),
+                    Cell::from(Span::styled(
+                        format!("{}:{}", flow.source.0, flow.source.1),
+                        Style::default().fg(Color::Blue),
+                    )),
+                    Cell::from(Span::styled(
+                        format!("{}:{}", flow.destination.0, flow.destination.1),
+                        Style::default().fg(Color::Blue),
+                    )),
+                    Cell::from(Span::styled(
+                        format!("{}/{}", flow.packets_out, flow.packets_in),
+                        Style::default().fg(Color::Green),
+                    )),
+                    Cell::from(Span::styled(
+                        format!("{}/{}", flow.bytes_out, flow.bytes_in),
+                        Style::default().fg(Color::Green),
+                    )),
+                    Cell::from(Span::styled(
+                        flow.first_seen.format("%H:%M:%S").to_string(),
+                        Style::default().fg(Color::Cyan),
+                    )),
+                    Cell::from(Span::styled(
+                        flow.last_seen.format("%H:%M:%S").to_string(),
+                        Style::default().fg(Color::Cyan),
+                    )),
+                    Cell::from(Span::styled(state, Style::default().fg(Color::Magenta))),
+                ])
+            })
+            .collect()
+    }
+
+    fn make_flows_table<'a>(
+        rows: Vec<Row<'a>>,
+        dump_title: Line<'static>,
+        interface: Option<&NetworkInterface>,
+        order: FlowOrder,
+    ) -> Table<'a> {
+        let header = Row::new(vec![
+            "proto",
+            "source",
+            "destination",
+            "packets out/in",
+            "bytes out/in",
+            "first",
+            "last",
+            "state",
+        ])
+        .style(Style::default().fg(Color::Yellow))
+        .top_margin(1)
+        .bottom_margin(1);
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(5),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+                Constraint::Length(14),
+                Constraint::Length(17),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(11),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::new()
+                .title(
+                    ratatui::widgets::block::Title::from(dump_title)
+                        .position(ratatui::widgets::block::Position::Top)
+                        .alignment(Alignment::Right),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Self::make_interface_title(
+                        "Flows", interface, &[], &[],
+                    ))
+                    .position(ratatui::widgets::block::Position::Top)
+                    .alignment(Alignment::Right),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Self::make_view_title(true, "flows"))
+                        .alignment(Alignment::Left)
+                        .position(ratatui::widgets::block::Position::Bottom),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Line::from(vec![
+                        Span::raw("|"),
+                        Span::styled(
+                            "o",
+                            Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
+                        ),
+                        Span::styled("rder:", Style::default().fg(Color::Yellow)),
+                        Span::styled(order.to_string(), Style::default().fg(Color::Green)),
+                        Span::raw("|"),
+                    ]))
+                    .alignment(Alignment::Left)
+                    .position(ratatui::widgets::block::Position::Bottom),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Line::from(vec![
+                        Span::styled("|", Style::default().fg(Color::Yellow)),
+                        Span::styled(
+                            String::from(char::from_u32(0x25b2).unwrap_or('>')),
+                            Style::default().fg(Color::Red),
+                        ),
+                        Span::styled(
+                            String::from(char::from_u32(0x25bc).unwrap_or('>')),
+                            Style::default().fg(Color::Red),
+                        ),
+                        Span::styled("select|", Style::default().fg(Color::Yellow)),
+                    ]))
+                    .position(ratatui::widgets::block::Position::Bottom)
+                    .alignment(Alignment::Right),
+                )
+                .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+                .borders(Borders::ALL)
+                .border_type(DEFAULT_BORDER_STYLE),
+        )
+        .highlight_symbol(Span::styled(
+            String::from(char::from_u32(0x25b6).unwrap_or('>')),
+            Style::default().fg(Color::Red),
+        ))
+        .column_spacing(1);
+        table
+    }
+
+    fn get_talker_rows<'a>(&self) -> Vec<Row<'a>> {
+        self.talkers
+            .sorted()
+            .into_iter()
+            .map(|talker| {
+                Row::new(vec![
+                    Cell::from(Span::styled(
+                        talker.ip.to_string(),
+                        Style::default().fg(Color::Blue),
+                    )),
+                    Cell::from(Span::styled(
+                        talker.packets.to_string(),
+                        Style::default().fg(Color::Green),
+                    )),
+                    Cell::from(Span::styled(
+                        bytes_convert(talker.bytes as f64),
+                        Style::default().fg(Color::Green),
+                    )),
+                    Cell::from(Span::styled(
+                        talker.protocols_str(),
+                        Style::default().fg(Color::Yellow),
+                    )),
+                    Cell::from(Span::styled(
+                        talker.last_seen.format("%H:%M:%S").to_string(),
+                        Style::default().fg(Color::Cyan),
+                    )),
+                ])
+            })
+            .collect()
+    }
+
+    fn make_talkers_table<'a>(
+        rows: Vec<Row<'a>>,
+        dump_title: Line<'static>,
+        interface: Option<&NetworkInterface>,
+    ) -> Table<'a> {
+        let header = Row::new(vec!["source", "packets", "bytes", "protocols", "last"])
+            .style(Style::default().fg(Color::Yellow))
+            .top_margin(1)
+            .bottom_margin(1);
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(30),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Min(20),
+                Constraint::Length(8),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::new()
+                .title(
+                    ratatui::widgets::block::Title::from(dump_title)
+                        .position(ratatui::widgets::block::Position::Top)
+                        .alignment(Alignment::Right),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Self::make_interface_title(
+                        "Top talkers", interface, &[], &[],
+                    ))
+                    .position(ratatui::widgets::block::Position::Top)
+                    .alignment(Alignment::Right),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Self::make_view_title(true, "talkers"))
+                        .alignment(Alignment::Left)
+                        .position(ratatui::widgets::block::Position::Bottom),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Line::from(vec![
+                        Span::styled("|", Style::default().fg(Color::Yellow)),
+                        Span::styled(
+                            String::from(char::from_u32(0x25b2).unwrap_or('>')),
+                            Style::default().fg(Color::Red),
+                        ),
+                        Span::styled(
+                            String::from(char::from_u32(0x25bc).unwrap_or('>')),
+                            Style::default().fg(Color::Red),
+                        ),
+                        Span::styled("select ", Style::default().fg(Color::Yellow)),
+                        Span::styled("Enter", Style::default().fg(Color::Red)),
+                        Span::styled(" filter host|", Style::default().fg(Color::Yellow)),
+                    ]))
+                    .position(ratatui::widgets::block::Position::Bottom)
+                    .alignment(Alignment::Right),
+                )
+                .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+                .borders(Borders::ALL)
+                .border_type(DEFAULT_BORDER_STYLE),
+        )
+        .highlight_symbol(Span::styled(
+            String::from(char::from_u32(0x25b6).unwrap_or('>')),
+            Style::default().fg(Color::Red),
+        ))
+        .column_spacing(1);
+        table
+    }
+
+    fn get_group_rows<'a>(&self) -> Vec<Row<'a>> {
+        self.groups
+            .sorted()
+            .into_iter()
+            .map(|membership| {
+                let members = membership
+                    .members
+                    .iter()
+                    .map(|m| m.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                Row::new(vec![
+                    Cell::from(Span::styled(
+                        membership.group.to_string(),
+                        Style::default().fg(Color::Blue),
+                    )),
+                    Cell::from(Span::styled(
+                        membership.members.len().to_string(),
+                        Style::default().fg(Color::Green),
+                    )),
+                    Cell::from(Span::styled(
+                        membership.first_seen.format("%H:%M:%S").to_string(),
+                        Style::default().fg(Color::Cyan),
+                    )),
+                    Cell::from(Span::styled(
+                        membership.last_seen.format("%H:%M:%S").to_string(),
+                        Style::default().fg(Color::Cyan),
+                    )),
+                    Cell::from(Span::styled(members, Style::default().fg(Color::Yellow))),
+                ])
+            })
+            .collect()
+    }
+
+    fn make_groups_table<'a>(
+        rows: Vec<Row<'a>>,
+        dump_title: Line<'static>,
+        interface: Option<&NetworkInterface>,
+    ) -> Table<'a> {
+        let header = Row::new(vec!["group", "count", "first", "last", "members"])
+            .style(Style::default().fg(Color::Yellow))
+            .top_margin(1)
+            .bottom_margin(1);
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Percentage(100),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::new()
+                .title(
+                    ratatui::widgets::block::Title::from(dump_title)
                         .position(ratatui::widgets::block::Position::Top)
                         .alignment(Alignment::Right),
-                    )
-                    .title(
-                        ratatui::widgets::block::Title::from(Line::from(type_titles))
-                            .position(ratatui::widgets::block::Position::Top)
-                            .alignment(Alignment::Left),
-                    )
-                    .title(
-                        ratatui::widgets::block::Title::from(Line::from(vec![
-                            Span::styled("|", Style::default().fg(Color::Yellow)),
-                            Span::styled(
-                                String::from(char::from_u32(0x25b2).unwrap_or('>')),
-                                Style::default().fg(Color::Red),
-                            ),
-                            Span::styled(
-                                String::from(char::from_u32(0x25bc).unwrap_or('>')),
-                                Style::default().fg(Color::Red),
-                            ),
-                            Span::styled("select|", Style::default().fg(Color::Yellow)),
-                        ]))
-                        .position(ratatui::widgets::block::Position::Bottom)
-                        .alignment(Alignment::Right),
-                    )
-                    .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
-                    .borders(Borders::ALL) // .padding(Padding::new(1, 0, 2, 0)),
-                    .border_type(DEFAULT_BORDER_STYLE),
-            )
-            .highlight_symbol(Span::styled(
-                String::from(char::from_u32(0x25b6).unwrap_or('>')),
-                Style::default().fg(Color::Red),
-            ))
-            .column_spacing(1);
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Self::make_interface_title(
+                        "Multicast groups", interface, &[], &[],
+                    ))
+                    .position(ratatui::widgets::block::Position::Top)
+                    .alignment(Alignment::Right),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Self::make_view_title(true, "groups"))
+                        .alignment(Alignment::Left)
+                        .position(ratatui::widgets::block::Position::Bottom),
+                )
+                .title(
+                    ratatui::widgets::block::Title::from(Line::from(vec![
+                        Span::styled("|", Style::default().fg(Color::Yellow)),
+                        Span::styled(
+                            String::from(char::from_u32(0x25b2).unwrap_or('>')),
+                            Style::default().fg(Color::Red),
+                        ),
+                        Span::styled(
+                            String::from(char::from_u32(0x25bc).unwrap_or('>')),
+                            Style::default().fg(Color::Red),
+                        ),
+                        Span::styled("select|", Style::default().fg(Color::Yellow)),
+                    ]))
+                    .position(ratatui::widgets::block::Position::Bottom)
+                    .alignment(Alignment::Right),
+                )
+                .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+                .borders(Borders::ALL)
+                .border_type(DEFAULT_BORDER_STYLE),
+        )
+        .highlight_symbol(Span::styled(
+            String::from(char::from_u32(0x25b6).unwrap_or('>')),
+            Style::default().fg(Color::Red),
+        ))
+        .column_spacing(1);
         table
     }
 
@@ -1089,6 +4162,17 @@ impl PacketDump {
     }
 
     fn make_input(&self, scroll: usize) -> Paragraph<'_> {
+        // -- a `=` filter matches with exact case
+        let case_title = if self.text_filter.is_case_sensitive() {
+            Line::from(vec![
+                Span::raw("|"),
+                Span::styled("=", Style::default().add_modifier(Modifier::BOLD).fg(Color::Red)),
+                Span::styled("Aa", Style::default().fg(Color::Yellow)),
+                Span::raw("|"),
+            ])
+        } else {
+            Line::default()
+        };
         let input = Paragraph::new(self.input.value())
             .style(Style::default().fg(Color::Green))
             .scroll((0, scroll as u16))
@@ -1096,6 +4180,13 @@ impl PacketDump {
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(match self.mode {
+                        // -- the applied filter doesn't compile
+                        _ if self.text_filter.is_invalid()
+                            || self.field_filter.is_invalid()
+                            || self.bpf_invalid =>
+                        {
+                            Style::default().fg(Color::Red)
+                        }
                         Mode::Input => Style::default().fg(Color::Green),
                         Mode::Normal => Style::default().fg(Color::Rgb(100, 100, 100)),
                     })
@@ -1130,39 +4221,115 @@ impl PacketDump {
                         ]))
                         .alignment(Alignment::Left)
                         .position(ratatui::widgets::block::Position::Bottom),
+                    )
+                    .title(
+                        ratatui::widgets::block::Title::from(case_title)
+                            .alignment(Alignment::Right)
+                            .position(ratatui::widgets::block::Position::Top),
                     ),
             );
         input
     }
 
+    /// Enter in input mode: apply the typed filter, or finish the saved filter prompt.
+    fn submit(&mut self) -> Option<Action> {
+        match &mut self.filter_prompt {
+            FilterPrompt::None => {
+                let filter = self.input.value().to_string();
+                self.remember_filter(&filter);
+                self.set_filter_str(filter);
+            }
+            FilterPrompt::Name { input, overwrite } => {
+                let name = input.value().trim().to_string();
+                if name.is_empty() {
+                    return None;
+                }
+                let mut filters = SavedFilters::load();
+                // -- a duplicate name needs a second Enter to overwrite
+                if filters.contains(&name) && !*overwrite {
+                    *overwrite = true;
+                    return None;
+                }
+                if let Err(e) = filters.save(&name, self.input.value()) {
+                    log::error!("Failed to save filter {}: {}", name, e);
+                }
+            }
+            FilterPrompt::Recall { filters, selected } => {
+                if let Some(filter) = filters.get(*selected).cloned() {
+                    self.input = Input::new(filter.clone());
+                    self.remember_filter(&filter);
+                    self.set_filter_str(filter);
+                }
+            }
+            FilterPrompt::Tcpdump(_) | FilterPrompt::Detail(_) => {}
+        }
+        Some(Action::ModeChange(Mode::Normal))
+    }
+
+    fn remember_filter(&mut self, filter: &str) {
+        if self.filter_history.push(filter) && self.config.ui.save_filter_history {
+            if let Err(e) = self.filter_history.save() {
+                log::error!("Failed to save filter history: {}", e);
+            }
+        }
+    }
+
     fn set_filter_str(&mut self, value: String) {
-        self.filter_str = value;
+        let (net_filter, value) = NetFilter::parse(&value);
+        self.net_filter = net_filter;
+        let (iface_filter, value) = IfaceFilter::parse(&value);
+        self.iface_filter = iface_filter;
+        let (field_filter, value) = FieldFilter::parse(&value);
+        self.field_filter = field_filter;
+        let (time_range, filter_str) = TimeRange::parse(&value);
+        self.time_range = time_range;
+        // -- only `bpf:` text goes to the kernel, plain words like `tcp` stay text
+        match bpf::strip_prefix(&filter_str) {
+            Some(expr) => {
+                let filter = bpf::parse(expr);
+                self.bpf_invalid = filter.is_none();
+                self.set_bpf_filter(filter);
+                self.text_filter = TextFilter::default();
+            }
+            None => {
+                self.bpf_invalid = false;
+                self.set_bpf_filter(None);
+                self.text_filter = TextFilter::parse(&filter_str);
+            }
+        }
+        self.filter_str = filter_str;
+        self.set_scrollbar_height();
+    }
+
+    /// Filter text parsed as a BPF expression, compiled and attached to the
+    /// capture sockets as well. The captures restart when the program changes.
+    fn set_bpf_filter(&mut self, filter: Option<BpfExpr>) {
+        let program = filter.as_ref().and_then(BpfExpr::compile);
+        self.bpf_filter = filter;
+        if !self.decode.set_kernel_program(program) || self.replay.is_some() {
+            return;
+        }
+        if self.loop_thread.is_some() && self.complete.is_none() {
+            log::debug!("Kernel packet filter changed, restarting capture");
+            self.changed_interface = true;
+            self.restart_loop();
+        }
+        if !self.extra_captures.is_empty() {
+            log::debug!("Kernel packet filter changed, restarting extra captures");
+            self.restart_extras();
+        }
     }
 }
 
 impl Drop for PacketDump {
     fn drop(&mut self) {
-        // Signal thread to stop
-        self.dump_stop.store(true, Ordering::SeqCst);
-
-        // Wait for thread to finish with timeout
-        if let Some(handle) = self.loop_thread.take() {
+        self.stop_extras();
+        if self.loop_thread.is_some() {
             log::debug!("PacketDump dropping, waiting for thread to finish");
-            let start = std::time::Instant::now();
-            let timeout = Duration::from_secs(2);
-
-            while !handle.is_finished() && start.elapsed() < timeout {
-                thread::sleep(Duration::from_millis(50));
-            }
-
-            if handle.is_finished() {
-                // Thread finished gracefully
-                let _ = handle.join();
-                log::debug!("PacketDump thread cleaned up successfully");
-            } else {
-                log::warn!("PacketDump thread did not finish within timeout during drop");
-                // Thread handle will be dropped, potentially causing thread termination
-            }
+        }
+        if self.stop_loop().is_err() {
+            log::warn!("PacketDump thread did not finish within timeout during drop");
+            // Thread handle will be dropped, potentially causing thread termination
         }
     }
 }
@@ -1173,35 +4340,102 @@ impl Component for PacketDump {
         Ok(())
     }
 
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        let disabled = DisabledTypes::from_types(&config.capture.disabled_protocols);
+        self.dns_ports = DNS_PORTS.to_vec();
+        self.dns_ports.extend(config.capture.ports_for(PortProtocol::Dns));
+        self.dns_ports.sort_unstable();
+        self.dns_ports.dedup();
+        let history_size = config.capture.packet_history_size;
+        if config.ui.save_filter_history {
+            self.filter_history = FilterHistory::load();
+        }
+        self.color_rules = ColorRules::new(&config.packet_colors);
+        let skipped = self.color_rules.skipped();
+        if let Some(tx) = self.action_tx.as_ref().filter(|_| skipped > 0) {
+            let _ = tx.try_send(Action::Error(format!(
+                "packet_colors: {} invalid rule(s) skipped, see the log",
+                skipped
+            )));
+        }
+        self.config = config;
+        self.resize_history(history_size);
+        self.set_disabled_types(disabled);
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
-        if self.active_tab == TabsEnum::Packets {
-            let action = match self.mode {
-                Mode::Normal => return Ok(None),
-                Mode::Input => match key.code {
-                    KeyCode::Enter => {
-                        if let Some(_sender) = &self.action_tx {
-                            self.set_filter_str(self.input.value().to_string());
-                            // self.set_cidr(self.input.value().to_string(), true);
+        // -- keys bound in input mode (submit, cancel) are dispatched by the app
+        if self.active_tab == TabsEnum::Packets
+            && self.mode == Mode::Input
+            && !self.config.keybindings.is_bound(Mode::Input, &key)
+        {
+            let copy = self
+                .config
+                .keybindings
+                .get(&Mode::Normal)
+                .and_then(|keymap| keymap.get(&vec![key]))
+                == Some(&Action::CopySelection);
+            match &mut self.filter_prompt {
+                FilterPrompt::None => {
+                    let recalled = match key.code {
+                        KeyCode::Up => self.filter_history.previous(self.input.value()),
+                        KeyCode::Down => self.filter_history.next(),
+                        _ => {
+                            self.input.handle_event(&crossterm::event::Event::Key(key));
+                            None
                         }
-                        Action::ModeChange(Mode::Normal)
+                    };
+                    if let Some(filter) = recalled {
+                        self.input = Input::new(filter.to_string());
+                    }
+                }
+                FilterPrompt::Name { input, overwrite } => {
+                    // -- editing the name withdraws the overwrite confirmation
+                    if input.handle_event(&crossterm::event::Event::Key(key)).is_some() {
+                        *overwrite = false;
                     }
-                    _ => {
-                        self.input.handle_event(&crossterm::event::Event::Key(key));
-                        return Ok(None);
+                }
+                FilterPrompt::Recall { filters, selected } => match key.code {
+                    KeyCode::Down => *selected = (*selected + 1).min(filters.len().saturating_sub(1)),
+                    KeyCode::Up => *selected = selected.saturating_sub(1),
+                    _ => {}
+                },
+                // -- scrolls the popup, the table selection underneath stays
+                FilterPrompt::Detail(detail) => match key.code {
+                    KeyCode::Down => {
+                        let last = Self::make_detail_lines(detail).len().saturating_sub(1);
+                        detail.scroll = (detail.scroll + 1).min(last as u16);
                     }
+                    KeyCode::Up => detail.scroll = detail.scroll.saturating_sub(1),
+                    _ if copy => return Ok(Some(Action::Copy(detail.text()))),
+                    _ => {}
                 },
-            };
-            Ok(Some(action))
-        } else {
-            Ok(None)
+                FilterPrompt::Tcpdump(_) => {}
+            }
         }
+        Ok(None)
     }
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::CaptureUnavailable(ref msg) = action {
+            self.capture_unavailable = Some(msg.clone());
+            self.stop_extras();
+            self.restart_loop();
+        }
+
+        // -- capture on more interfaces at once
+        if let Action::AddInterface(ref interface) = action {
+            self.start_extra(interface.clone());
+        }
+        if let Action::RemoveInterface(ref name) = action {
+            self.stop_extra(name);
+        }
+
         // -- change thread loop if interface is changed
         if self.changed_interface {
             if let Some(ref lt) = self.loop_thread {
@@ -1221,10 +4455,59 @@ impl Component for PacketDump {
             }
         }
 
+        if let Action::Tick = action {
+            self.reap_extras();
+            let now = Local::now();
+            self.byte_rate.rotate(now);
+            if let Some(max_idle) = chrono::Duration::try_seconds(RETRANSMIT_IDLE_SECS) {
+                self.retransmits.prune(now, max_idle);
+                self.sequence_bases.prune(now, max_idle);
+            }
+            if let Some(window) = self.ip_conflict_window() {
+                let sticky = self.config.discovery.sticky_ip_conflicts;
+                self.ip_conflicts.prune(now, window, sticky);
+            }
+            if let Some(max_idle) = self.flow_idle_timeout() {
+                self.flows.prune(max_idle);
+            }
+            if let Some(timeout) = chrono::Duration::try_seconds(ECHO_TIMEOUT_SECS) {
+                self.echoes.prune(now, timeout);
+            }
+            if self.anomalies_reported.elapsed() >= ANOMALY_INTERVAL {
+                self.anomalies_reported = Instant::now();
+                self.report_anomalies();
+            }
+        }
+
         // -- tab change
         if let Action::TabChange(tab) = action {
             let _ = self.tab_changed(tab);
         }
+        if let Action::CaptureComplete(ref summary) = action {
+            self.complete = Some(summary.clone());
+            self.dump_paused.store(true, Ordering::Relaxed);
+            self.clock.pause();
+            // -- the thread has exited, collect it so resuming can start a new one
+            self.restart_loop();
+        }
+        // -- a late report of the previous interface's thread is ignored
+        if let Action::CaptureStats { received, dropped, ref iface } = action {
+            if self.active_interface.as_ref().is_some_and(|i| &i.name == iface) {
+                self.kernel_stats = Some(KernelStats { received, dropped });
+                if dropped > self.anomalies.kernel_dropped {
+                    self.send_anomaly(
+                        AnomalyCategory::Drops,
+                        Severity::Warning,
+                        format!(
+                            "kernel dropped {} packets on {}",
+                            dropped - self.anomalies.kernel_dropped,
+                            iface
+                        ),
+                    );
+                    self.anomalies.kernel_dropped = dropped;
+                }
+            }
+        }
         // -- active interface set
         if let Action::ActiveInterface(ref interface) = action {
             let mut was_none = false;
@@ -1232,6 +4515,22 @@ impl Component for PacketDump {
                 was_none = true;
             }
             self.active_interface = Some(interface.clone());
+            // -- the active capture takes over an interface that was an extra one
+            self.stop_extra(&interface.name);
+            // -- a replayed file keeps its packets, the interface is only remembered
+            if self.replay.is_some() {
+                return Ok(None);
+            }
+            // -- new interface, new capture window
+            self.clock = CaptureClock::default();
+            self.byte_rate = ByteRate::default();
+            self.talkers = TalkerTable::new(MAX_TALKERS);
+            self.kernel_stats = None;
+            self.anomalies.kernel_dropped = 0;
+            self.counters.reset();
+            self.anomalies.dropped = 0;
+            self.anomalies.oversized = 0;
+            self.complete = None;
             if was_none {
                 self.start_loop();
             } else {
@@ -1242,36 +4541,258 @@ impl Component for PacketDump {
         if self.active_tab == TabsEnum::Packets {
             // -- prev & next select item in table
             if let Action::Down = action {
+                // -- moving by hand stops following the newest packet
+                if self.in_packet_log() {
+                    self.follow = false;
+                }
                 self.next_in_table();
             }
             if let Action::Up = action {
+                // -- moving by hand stops following the newest packet
+                if self.in_packet_log() {
+                    self.follow = false;
+                }
                 self.previous_in_table();
             }
+            // -- first and last row of the filtered view
+            if let Action::Home = action {
+                if self.in_packet_log() {
+                    self.follow = true;
+                }
+                self.select_in_table(0);
+            }
+            if let Action::End = action {
+                if self.in_packet_log() {
+                    self.follow = false;
+                }
+                self.select_in_table(self.view_len().saturating_sub(1));
+            }
+            if let Action::PageUp = action {
+                if self.in_packet_log() {
+                    self.follow = false;
+                }
+                self.page_in_table(false);
+            }
+            if let Action::PageDown = action {
+                if self.in_packet_log() {
+                    self.follow = false;
+                }
+                self.page_in_table(true);
+            }
             if let Action::Left = action {
-                self.packet_type = self.packet_type.previous();
+                self.set_packet_type(self.step_packet_type(false));
                 self.set_scrollbar_height();
                 self.table_state.select(Some(0));
                 self.set_scrollbar_height();
             }
             if let Action::Right = action {
-                self.packet_type = self.packet_type.next();
+                self.set_packet_type(self.step_packet_type(true));
                 self.set_scrollbar_height();
                 self.table_state.select(Some(0));
                 self.set_scrollbar_height();
             }
             // -- dumping toggle
             if let Action::DumpToggle = action {
+                // -- resuming live capture drops a loaded session snapshot
+                if self.snapshot.take().is_some() {
+                    self.clear_packets();
+                }
+                // -- the capture thread keeps its channel across pause and resume
                 if self.dump_paused.load(Ordering::Relaxed) {
+                    // -- after a limit was hit resuming starts a new bounded capture
+                    if self.complete.take().is_some() {
+                        self.dump_stop.store(false, Ordering::SeqCst);
+                    }
                     self.dump_paused.store(false, Ordering::Relaxed);
+                    self.clock.resume();
                     self.start_loop();
                 } else {
                     self.dump_paused.store(true, Ordering::Relaxed);
-                    self.loop_thread = None;
+                    self.clock.pause();
+                }
+            }
+
+            // -- wrapping toggle
+            if let Action::WrapToggle = action {
+                self.wrap_rows = !self.wrap_rows;
+                if !self.wrap_rows {
+                    self.set_scrollbar_height();
+                    let selected = self.table_state.selected().unwrap_or(0);
+                    self.scrollbar_state = self.scrollbar_state.position(selected);
+                }
+            }
+
+            // -- follow mode, turning it on jumps to the newest packet
+            if let Action::FollowToggle = action {
+                self.follow = !self.follow;
+                if self.follow {
+                    self.select_in_table(0);
+                }
+            }
+
+            if let Action::FlowColorsToggle = action {
+                self.flow_colors = !self.flow_colors;
+            }
+
+            if let Action::RelativeSeqToggle = action {
+                self.relative_seq = !self.relative_seq;
+            }
+
+            if let Action::TimeFormatCycle = action {
+                self.time_format = self.time_format.next();
+            }
+
+            if let Action::TimeDeltaToggle = action {
+                self.time_delta = !self.time_delta;
+            }
+
+            // -- flows view toggle
+            if let Action::FlowsToggle = action {
+                self.show_flows = !self.show_flows;
+                self.show_talkers = false;
+                self.table_state.select(Some(0));
+                self.scrollbar_state = self.scrollbar_state.position(0);
+                self.set_scrollbar_height();
+            }
+
+            // -- packet history size
+            if let Action::HistoryGrow = action {
+                let size = self.all_packets.max_len().saturating_mul(2);
+                self.resize_history(size.min(MAX_PACKET_HISTORY_SIZE));
+            }
+            if let Action::HistoryShrink = action {
+                // -- a configured size below the minimum is kept
+                let current = self.all_packets.max_len();
+                self.resize_history((current / 2).max(MIN_PACKET_HISTORY.min(current)));
+            }
+
+            // -- flows sorted by recency or bytes
+            if let Action::FlowOrderCycle = action {
+                self.flow_order = self.flow_order.next();
+            }
+
+            // -- top talkers view toggle
+            if let Action::TalkersToggle = action {
+                self.show_talkers = !self.show_talkers;
+                self.show_flows = false;
+                self.table_state.select(Some(0));
+                self.scrollbar_state = self.scrollbar_state.position(0);
+                self.set_scrollbar_height();
+            }
+
+            // -- filter preset cycle
+            if let Action::PresetCycle = action {
+                self.preset = self.preset.next();
+                self.set_packet_type(self.preset.packet_type());
+                self.table_state.select(Some(0));
+                self.set_scrollbar_height();
+            }
+
+            // -- saved filters
+            if let Action::SaveFilter = action {
+                if self.mode == Mode::Normal && !self.input.value().is_empty() {
+                    self.filter_prompt = FilterPrompt::Name {
+                        input: Input::default(),
+                        overwrite: false,
+                    };
+                    return Ok(Some(Action::ModeChange(Mode::Input)));
+                }
+            }
+            if let Action::RecallFilter = action {
+                if self.mode == Mode::Normal {
+                    self.filter_prompt = FilterPrompt::Recall {
+                        filters: SavedFilters::load(),
+                        selected: 0,
+                    };
+                    return Ok(Some(Action::ModeChange(Mode::Input)));
+                }
+            }
+
+            // -- per protocol capture
+            if let Action::DisableProtocol = action {
+                if self.packet_type != PacketTypeEnum::All {
+                    self.set_disabled_types(self.decode.disabled().with(self.packet_type));
+                }
+            }
+            if let Action::EnableProtocols = action {
+                self.set_disabled_types(DisabledTypes::default());
+            }
+
+            // -- selected packet in detail
+            if let Action::ShowDetail = action {
+                // -- a top talker narrows the packet log down to its address
+                if self.mode == Mode::Normal && self.show_talkers {
+                    let selected = self.table_state.selected().unwrap_or(0);
+                    if let Some(ip) = self.talkers.sorted().get(selected).map(|t| t.ip) {
+                        let filter = format!("host {}", ip);
+                        self.input = Input::new(filter.clone());
+                        self.set_filter_str(filter);
+                        self.show_talkers = false;
+                        self.table_state.select(Some(0));
+                        self.set_scrollbar_height();
+                    }
+                } else if self.mode == Mode::Normal && !self.show_flows {
+                    let selected = self.table_state.selected().unwrap_or(0);
+                    if let Some((time, packet)) =
+                        self.visible_packets(self.packet_type).into_iter().nth(selected)
+                    {
+                        let detail = PacketDetail::new(time, packet);
+                        self.filter_prompt = FilterPrompt::Detail(Box::new(detail));
+                        return Ok(Some(Action::ModeChange(Mode::Input)));
+                    }
+                }
+            }
+
+            // -- selected packet line to the clipboard
+            if let Action::CopySelection = action {
+                if self.mode == Mode::Normal && self.in_packet_log() {
+                    let selected = self.table_state.selected().unwrap_or(0);
+                    if let Some((_, packet)) =
+                        self.visible_packets(self.packet_type).into_iter().nth(selected)
+                    {
+                        return Ok(Some(Action::Copy(packet.raw_str().to_string())));
+                    }
+                }
+            }
+
+            // -- filter as a tcpdump command
+            if let Action::TcpdumpCommand = action {
+                if self.mode == Mode::Normal {
+                    // -- tcpdump listens on one interface, a single iface: token picks it
+                    let interface = match self.iface_filter.names.as_slice() {
+                        [name] => Some(name.as_str()),
+                        _ => self.active_interface.as_ref().map(|i| i.name.as_str()),
+                    };
+                    let command = TcpdumpCommand::build(
+                        interface,
+                        self.packet_type,
+                        self.preset,
+                        &self.dns_ports,
+                        &self.net_filter.nets,
+                        &self.filter_str,
+                        !self.time_range.is_empty(),
+                    );
+                    self.filter_prompt = FilterPrompt::Tcpdump(command);
+                    return Ok(Some(Action::ModeChange(Mode::Input)));
+                }
+            }
+
+            // -- submit filter
+            if let Action::Submit = action {
+                if self.mode == Mode::Input {
+                    return Ok(self.submit());
                 }
             }
 
             // -- MODE CHANGE
             if let Action::ModeChange(mode) = action {
+                if mode == Mode::Normal {
+                    self.filter_prompt = FilterPrompt::None;
+                    // -- cancelled while browsing the history: back to what was typed
+                    if let Some(typed) = self.filter_history.cancel() {
+                        self.input = Input::new(typed);
+                    }
+                }
                 if let Some(tx) = &self.action_tx {
                     let _ = tx.clone().try_send(Action::AppModeChange(mode));
                 }
@@ -1282,21 +4803,111 @@ impl Component for PacketDump {
             if let Action::Clear = action {
                 self.input.reset();
                 self.filter_str = String::from("");
+                self.text_filter = TextFilter::default();
+                self.field_filter = FieldFilter::default();
+                self.set_bpf_filter(None);
+                self.bpf_invalid = false;
+                self.time_range = TimeRange::default();
+                self.net_filter = NetFilter::default();
+                self.iface_filter = IfaceFilter::default();
+                self.preset = FilterPreset::None;
             }
         }
 
-        // -- packet recieved
-        if !self.dump_paused.load(Ordering::Relaxed) {
-            if let Action::PacketDump(time, packet, packet_type) = action {
-                match packet_type {
-                    PacketTypeEnum::Tcp => self.tcp_packets.push((time, packet.clone())),
-                    PacketTypeEnum::Arp => self.arp_packets.push((time, packet.clone())),
-                    PacketTypeEnum::Udp => self.udp_packets.push((time, packet.clone())),
-                    PacketTypeEnum::Icmp => self.icmp_packets.push((time, packet.clone())),
-                    PacketTypeEnum::Icmp6 => self.icmp6_packets.push((time, packet.clone())),
-                    _ => {}
+        // -- replay a pcap file in place of live capture
+        if let Action::ReadPcap(ref path) = action {
+            self.stop_extras();
+            self.replay = Some(path.clone());
+            self.snapshot = None;
+            self.complete = None;
+            self.clock = CaptureClock::default();
+            self.dump_paused.store(false, Ordering::Relaxed);
+            self.changed_interface = true;
+            self.restart_loop();
+        }
+
+        if let Action::ArpRecieve(ref arp) = action {
+            if let Some(window) = self.ip_conflict_window() {
+                let started =
+                    self.ip_conflicts.claim(Local::now(), arp.sender_ip, arp.sender_mac, window);
+                for conflict in started {
+                    self.send_anomaly(
+                        AnomalyCategory::IpConflict,
+                        Severity::Critical,
+                        format!(
+                            "{} claimed by {} and {}",
+                            conflict.ip, conflict.macs.0, conflict.macs.1
+                        ),
+                    );
+                }
+            }
+        }
+
+        // -- session snapshot, shown paused until live capture is resumed
+        if let Action::SessionLoaded(ref session) = action {
+            self.load_session(session);
+        }
+
+        // -- packet recieved; the capture thread stops reading while paused, so
+        // -- packets arriving now were read before the pause and are kept, unless
+        // -- a session snapshot replaced the live packets
+        if self.snapshot.is_none() {
+            if let Action::PacketDump(time, mut packet, packet_type) = action {
+                // -- decoded before the type was disabled
+                if self.decode.disabled().contains(packet_type) {
+                    return Ok(None);
+                }
+                self.byte_rate.record(time, packet_type, packet.orig_len());
+                if packet.ip_meta().is_some_and(|ip| ip.low_ttl) {
+                    self.anomalies.low_ttl += 1;
+                }
+                if let PacketsInfoTypesEnum::Tcp(ref mut tcp) = packet {
+                    let (sequence, acknowledgement) = self.sequence_bases.relative(time, tcp);
+                    tcp.relative_sequence = Some(sequence);
+                    tcp.relative_acknowledgement = acknowledgement;
+                    if self.retransmits.check(time, tcp) {
+                        self.anomalies.retransmissions += 1;
+                        tcp.retransmission = true;
+                        // -- lets the text filter find them
+                        tcp.raw_str.push_str("; retx");
+                    }
+                }
+                if let PacketsInfoTypesEnum::Icmp(ref mut icmp) = packet {
+                    match icmp.icmp_type {
+                        IcmpTypes::EchoRequest => self.echoes.request(time, icmp),
+                        IcmpTypes::EchoReply => match self.echoes.reply(time, icmp) {
+                            EchoReply::Rtt(rtt) => {
+                                icmp.rtt_ms = Some(rtt);
+                                icmp.raw_str.push_str(&format!("; rtt={:.1}ms", rtt));
+                            }
+                            EchoReply::Duplicate => {
+                                icmp.duplicate = true;
+                                icmp.raw_str.push_str("; dup");
+                            }
+                            EchoReply::Unmatched => {}
+                        },
+                        _ => {}
+                    }
+                }
+                if self.stream_json && self.is_visible(&time, &packet, &time) {
+                    let mut out = std::io::stdout().lock();
+                    if let Err(e) = stream::write_line(&mut out, &time, &packet) {
+                        // -- the reading end went away, nothing left to stream to
+                        if e.kind() == std::io::ErrorKind::BrokenPipe {
+                            log::info!("Packet stream closed by the reader");
+                        } else {
+                            log::error!("Failed to stream packet: {}", e);
+                        }
+                        return Ok(Some(Action::Quit));
+                    }
+                }
+                let in_view = self.in_packet_log()
+                    && (self.packet_type == PacketTypeEnum::All || self.packet_type == packet_type)
+                    && self.is_visible(&time, &packet, &time);
+                self.store_packet(time, packet, packet_type);
+                if in_view {
+                    self.row_added();
                 }
-                self.all_packets.push((time, packet.clone()));
             }
         }
 
@@ -1310,27 +4921,10 @@ impl Component for PacketDump {
 
     fn shutdown(&mut self) -> Result<()> {
         log::info!("Shutting down packet capture component");
+        self.stop_extras();
 
-        // Signal thread to stop
-        self.dump_stop.store(true, Ordering::SeqCst);
-
-        // Wait for thread to finish with timeout
-        if let Some(handle) = self.loop_thread.take() {
-            let start = std::time::Instant::now();
-            let timeout = Duration::from_secs(2);
-
-            while !handle.is_finished() && start.elapsed() < timeout {
-                thread::sleep(Duration::from_millis(50));
-            }
-
-            if handle.is_finished() {
-                match handle.join() {
-                    Ok(_) => log::info!("Packet capture thread stopped successfully during shutdown"),
-                    Err(_) => log::error!("Packet capture thread panicked during shutdown"),
-                }
-            } else {
-                log::warn!("Packet capture thread did not stop within timeout during shutdown");
-            }
+        if self.stop_loop().is_err() {
+            log::warn!("Packet capture thread did not stop within timeout during shutdown");
         }
 
         Ok(())
@@ -1341,47 +4935,214 @@ impl Component for PacketDump {
             let layout = get_vertical_layout(area);
             let mut table_rect = layout.bottom;
             table_rect.y += 1;
-            table_rect.height -= 1;
+            table_rect.height = table_rect.height.saturating_sub(1);
+            // -- conflict warning takes the last line
+            let conflicts = self.ip_conflicts.conflicts();
+            if let Some(line) = Self::make_conflict_line(&conflicts) {
+                if table_rect.height > 4 {
+                    table_rect.height -= 1;
+                    let line_rect = Rect::new(table_rect.x, table_rect.bottom(), table_rect.width, 1);
+                    f.render_widget(line, line_rect);
+                }
+            }
 
             // -- TABLE
+            // -- borders and the header with its margins
+            self.page_rows = table_rect.height.saturating_sub(5) as usize;
             let mut dump_paused = false;
             if self.dump_paused.load(Ordering::Relaxed) {
                 dump_paused = true;
             }
-            let rows = self.get_table_rows_by_packet_type(self.packet_type);
-            let table = Self::make_table(rows, self.packet_type, dump_paused);
-            f.render_stateful_widget(table, table_rect, &mut self.table_state.clone());
+            let dump_source = self.dump_source();
+            // -- rates only mean something for a capture that is running
+            let show_rate =
+                matches!(dump_source, DumpSource::Live) && !dump_paused && self.complete.is_none();
+            let mut dump_title = Self::make_dump_title(
+                dump_paused,
+                &self.clock,
+                &self.counters,
+                self.kernel_stats,
+                self.config.capture.sample_rate,
+                dump_source,
+                self.complete.as_ref(),
+            );
+            if show_rate {
+                let rate = self.byte_rate.current(self.packet_type);
+                dump_title.spans.extend(Self::make_rate_spans(rate));
+            }
+            if self.in_packet_log() {
+                dump_title.spans.extend(Self::make_follow_spans(self.follow));
+            }
+            if self.show_talkers {
+                let rows = self.get_talker_rows();
+                self.sync_selection(rows.len());
+                let table =
+                    Self::make_talkers_table(rows, dump_title, self.active_interface.as_ref());
+                f.render_stateful_widget(table, table_rect, &mut self.table_state.clone());
+            } else if self.show_flows && self.packet_type == PacketTypeEnum::Igmp {
+                let rows = self.get_group_rows();
+                self.sync_selection(rows.len());
+                let table =
+                    Self::make_groups_table(rows, dump_title, self.active_interface.as_ref());
+                f.render_stateful_widget(table, table_rect, &mut self.table_state.clone());
+            } else if self.show_flows {
+                let rows = self.get_flow_rows();
+                self.sync_selection(rows.len());
+                let table = Self::make_flows_table(
+                    rows,
+                    dump_title,
+                    self.active_interface.as_ref(),
+                    self.flow_order,
+                );
+                f.render_stateful_widget(table, table_rect, &mut self.table_state.clone());
+            } else {
+                // -- borders, highlight symbol, time column and column spacing
+                let time_width = match self.time_delta {
+                    true => DELTA_WIDTH,
+                    false => self.time_format.width(),
+                };
+                self.log_width = (table_rect.width as usize).saturating_sub(time_width + 4);
+                let rows = self.get_table_rows_by_packet_type(self.packet_type);
+                // -- new packets or an expired time range change the rows between actions
+                self.sync_selection(rows.len());
+                let disabled = self.decode.disabled();
+                let types = PacketTypeEnum::iter()
+                    .filter(|p| !disabled.contains(*p))
+                    .map(|p| (p, self.get_array_by_packet_type(p).len()))
+                    .collect();
+                let table = Self::make_table(
+                    rows,
+                    self.packet_type,
+                    types,
+                    dump_title,
+                    self.wrap_rows,
+                    self.preset,
+                    Self::make_interface_title(
+                        "Packets",
+                        self.active_interface.as_ref(),
+                        &self.extra_captures.keys().map(String::as_str).collect::<Vec<_>>(),
+                        &self.iface_filter.names,
+                    ),
+                )
+                // -- the time column fits the time format
+                .widths([Constraint::Length(time_width as u16), Constraint::Percentage(100)]);
+                f.render_stateful_widget(table, table_rect, &mut self.table_state.clone());
+            }
 
             // -- INPUT
-            let input_size: u16 = INPUT_SIZE as u16;
-            let input_rect = Rect::new(
-                table_rect.width - (input_size + 1),
-                table_rect.y + 1,
-                input_size,
-                3,
-            );
-            // -- INPUT_SIZE - 3 is offset for border + 1char for cursor
-            let scroll = self.input.visual_scroll(INPUT_SIZE - 3);
+            let input_rect = get_input_rect(table_rect, INPUT_SIZE as u16);
+            // -- width - 3 is offset for border + 1char for cursor
+            let scroll = self.input.visual_scroll((input_rect.width as usize).saturating_sub(3));
             let block = self.make_input(scroll);
             f.render_widget(block, input_rect);
+            // -- invalid net: CIDR next to the input
+            let mut strip_right = input_rect.x;
+            if self.net_filter.invalid {
+                let error_rect = Rect::new(input_rect.x.saturating_sub(19), input_rect.y, 18, 3);
+                f.render_widget(Self::make_net_error(), error_rect);
+                strip_right = error_rect.x;
+            }
+            // -- packets/s sparkline left of the input, shrinks on narrow terminals
+            let spark_width = strip_right
+                .saturating_sub(table_rect.x + SPARKLINE_MIN_LEFT + 1)
+                .min(RATE_WINDOW_SECS as u16 + 1);
+            if spark_width >= SPARKLINE_MIN_WIDTH {
+                let spark_rect = Rect::new(strip_right - spark_width - 1, input_rect.y, spark_width, 3);
+                let history = self.byte_rate.history(self.packet_type);
+                let visible = history.len().min(spark_width as usize - 2);
+                let paused = self.dump_paused.load(Ordering::Relaxed);
+                let sparkline = Self::make_sparkline(&history[history.len() - visible..], paused);
+                f.render_widget(sparkline, spark_rect);
+            }
             // -- cursor
             match self.mode {
-                Mode::Input => {
+                // -- no room for a cursor inside the borders
+                Mode::Input
+                    if matches!(self.filter_prompt, FilterPrompt::None) && input_rect.width >= 3 =>
+                {
+                    let offset = ((self.input.visual_cursor()).max(scroll) - scroll) as u16;
                     f.set_cursor_position(Position {
-                        x: input_rect.x
-                            + ((self.input.visual_cursor()).max(scroll) - scroll) as u16
-                            + 1,
+                        x: input_rect.x + offset.min(input_rect.width - 3) + 1,
                         y: input_rect.y + 1,
                     });
                 }
-                Mode::Normal => {}
+                _ => {}
+            }
+
+            // -- SAVED FILTER PROMPT
+            let popup_width = table_rect.width.saturating_sub(4).min(60);
+            let popup_x = table_rect.x + (table_rect.width.saturating_sub(popup_width)) / 2;
+            match &self.filter_prompt {
+                FilterPrompt::None => {}
+                FilterPrompt::Name { input, overwrite } => {
+                    let height = table_rect.height.saturating_sub(6).min(3);
+                    let rect = Rect::new(popup_x, table_rect.y + 5, popup_width, height);
+                    f.render_widget(Clear, rect);
+                    f.render_widget(Self::make_name_prompt(input, *overwrite), rect);
+                    f.set_cursor_position(Position {
+                        x: rect.x + 1 + (input.visual_cursor() as u16).min(rect.width.saturating_sub(3)),
+                        y: rect.y + 1,
+                    });
+                }
+                FilterPrompt::Recall { filters, selected } => {
+                    let height = (filters.len().max(1) as u16 + 2)
+                        .min(table_rect.height.saturating_sub(6));
+                    let rect = Rect::new(popup_x, table_rect.y + 5, popup_width, height);
+                    let mut state = ListState::default().with_selected(Some(*selected));
+                    f.render_widget(Clear, rect);
+                    f.render_stateful_widget(Self::make_recall_list(filters), rect, &mut state);
+                }
+                FilterPrompt::Detail(detail) => {
+                    let width = table_rect.width.saturating_sub(4).min(DETAIL_WIDTH);
+                    let x = table_rect.x + (table_rect.width.saturating_sub(width)) / 2;
+                    let rect = Rect::new(x, table_rect.y + 3, width, table_rect.height.saturating_sub(4));
+                    f.render_widget(Clear, rect);
+                    f.render_widget(Self::make_detail_popup(detail), rect);
+                }
+                FilterPrompt::Tcpdump(command) => {
+                    let inner_width = popup_width.saturating_sub(2).max(1) as usize;
+                    let lines = command.command.chars().count().div_ceil(inner_width)
+                        + command.notes.len();
+                    let height = (lines as u16 + 2).min(table_rect.height.saturating_sub(6));
+                    let rect = Rect::new(popup_x, table_rect.y + 5, popup_width, height);
+                    f.render_widget(Clear, rect);
+                    f.render_widget(Self::make_tcpdump_popup(command), rect);
+                }
+            }
+
+            // -- CAPTURE UNAVAILABLE BANNER
+            if let Some(msg) = &self.capture_unavailable {
+                let banner = Self::make_capture_banner(msg);
+                let lines = msg.lines().count() as u16 + 2;
+                let banner_rect = Rect::new(
+                    table_rect.x + 2,
+                    table_rect.y + 5,
+                    table_rect.width.saturating_sub(4),
+                    lines.min(table_rect.height.saturating_sub(6)),
+                );
+                f.render_widget(Clear, banner_rect);
+                f.render_widget(banner, banner_rect);
             }
 
             // -- SCROLLBAR
+            if self.wrap_rows && !self.show_flows && !self.show_talkers {
+                // -- rows span multiple lines, so scroll by lines instead of rows
+                let selected = self
+                    .table_state
+                    .selected()
+                    .unwrap_or(0)
+                    .min(self.row_heights.len());
+                let position: usize = self.row_heights[..selected].iter().sum();
+                let content_length: usize = self.row_heights.iter().sum();
+                self.scrollbar_state = self
+                    .scrollbar_state
+                    .content_length(content_length)
+                    .position(position);
+            }
             let scrollbar = Self::make_scrollbar();
             let mut scroll_rect = table_rect;
             scroll_rect.y += 1;
-            scroll_rect.height -= 1;
+            scroll_rect.height = scroll_rect.height.saturating_sub(1);
             f.render_stateful_widget(
                 scrollbar,
                 scroll_rect.inner(Margin {
@@ -1394,3 +5155,57 @@ impl Component for PacketDump {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn low_ttl(ttl: u8, source: &str, destination: &str) -> bool {
+        PacketDump::is_low_ttl(ttl, source.parse().unwrap(), destination.parse().unwrap())
+    }
+
+    #[test]
+    fn disabled_types_from_config() {
+        let disabled = DisabledTypes::from_types(&[PacketTypeEnum::Tcp, PacketTypeEnum::Icmp6]);
+        for packet_type in PacketTypeEnum::iter() {
+            let expected = matches!(packet_type, PacketTypeEnum::Tcp | PacketTypeEnum::Icmp6);
+            assert_eq!(disabled.contains(packet_type), expected, "{}", packet_type);
+        }
+        assert_eq!(DisabledTypes::from_types(&[]), DisabledTypes::default());
+        let twice = DisabledTypes::from_types(&[PacketTypeEnum::Udp, PacketTypeEnum::Udp]);
+        assert_eq!(twice, DisabledTypes::default().with(PacketTypeEnum::Udp));
+    }
+
+    #[test]
+    fn disabled_types_round_trip_through_the_bitmask() {
+        assert!(PacketTypeEnum::iter().count() <= u16::BITS as usize);
+        let decode = DecodeFilter::default();
+        let every: Vec<PacketTypeEnum> =
+            PacketTypeEnum::iter().filter(|t| *t != PacketTypeEnum::All).collect();
+        for types in [vec![], vec![PacketTypeEnum::Other], vec![PacketTypeEnum::Arp], every] {
+            let disabled = DisabledTypes::from_types(&types);
+            decode.disabled.store(disabled.0, Ordering::Relaxed);
+            assert_eq!(decode.disabled(), disabled);
+            let back: Vec<PacketTypeEnum> =
+                PacketTypeEnum::iter().filter(|t| decode.disabled().contains(*t)).collect();
+            assert_eq!(back, types);
+        }
+    }
+
+    #[test]
+    fn low_ttl_only_for_routed_traffic() {
+        assert!(low_ttl(1, "192.168.1.10", "8.8.8.8"));
+        assert!(low_ttl(2, "8.8.8.8", "192.168.1.10"));
+        assert!(!low_ttl(3, "192.168.1.10", "8.8.8.8"));
+        assert!(low_ttl(1, "2001:db8::10", "2606:4700::1111"));
+        // -- link-local, multicast and broadcast are sent with TTL 1 on purpose
+        assert!(!low_ttl(1, "192.168.1.10", "224.0.0.251"));
+        assert!(!low_ttl(1, "192.168.1.10", "239.255.255.250"));
+        assert!(!low_ttl(1, "169.254.10.1", "169.254.10.2"));
+        assert!(!low_ttl(1, "0.0.0.0", "255.255.255.255"));
+        assert!(!low_ttl(1, "127.0.0.1", "127.0.0.1"));
+        assert!(!low_ttl(1, "fe80::1", "2001:db8::1"));
+        assert!(!low_ttl(1, "2001:db8::10", "ff02::fb"));
+        assert!(!low_ttl(255, "fe80::1", "ff02::1"));
+    }
+}
@@ -0,0 +1,118 @@
+//! A map bounded to a capacity, the state kept per flow, direction or address
+//! by the packet dump's trackers.
+//!
+//! Inserting a new key into a full map first evicts the least recently seen
+//! entry, so a flood of new keys costs the oldest entries rather than memory.
+//! Idle entries are left to the owner to prune with `retain`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use chrono::{DateTime, Local};
+
+/// When an entry was last seen, what eviction orders by.
+pub trait LastSeen {
+    fn last_seen(&self) -> DateTime<Local>;
+}
+
+pub struct BoundedMap<K, V> {
+    map: HashMap<K, V>,
+    capacity: usize,
+}
+
+impl<K: Copy + Eq + Hash, V: LastSeen> BoundedMap<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            map: HashMap::new(),
+            capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.map.get_mut(key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.map.values()
+    }
+
+    /// The entry of `key`, inserted with `default` when missing.
+    pub fn get_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> &mut V {
+        self.make_room(&key);
+        self.map.entry(key).or_insert_with(default)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.make_room(&key);
+        self.map.insert(key, value);
+    }
+
+    pub fn retain(&mut self, f: impl FnMut(&K, &mut V) -> bool) {
+        self.map.retain(f);
+    }
+
+    fn make_room(&mut self, key: &K) {
+        if self.map.contains_key(key) || self.map.len() < self.capacity {
+            return;
+        }
+        let oldest = self
+            .map
+            .iter()
+            .min_by_key(|(_, value)| value.last_seen())
+            .map(|(key, _)| *key);
+        if let Some(oldest) = oldest {
+            self.map.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    struct Seen(DateTime<Local>);
+
+    impl LastSeen for Seen {
+        fn last_seen(&self) -> DateTime<Local> {
+            self.0
+        }
+    }
+
+    fn at(secs: i64) -> DateTime<Local> {
+        Local.timestamp_opt(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn the_least_recently_seen_is_evicted() {
+        let mut map = BoundedMap::new(2);
+        map.insert(1, Seen(at(0)));
+        map.insert(2, Seen(at(1)));
+        map.get_mut(&1).unwrap().0 = at(2);
+        map.insert(3, Seen(at(3)));
+        assert_eq!(map.len(), 2);
+        assert!(map.get(&1).is_some());
+        assert!(map.get(&2).is_none());
+        assert!(map.get(&3).is_some());
+    }
+
+    #[test]
+    fn known_keys_evict_nothing() {
+        let mut map = BoundedMap::new(2);
+        map.insert(1, Seen(at(0)));
+        map.insert(2, Seen(at(1)));
+        map.insert(1, Seen(at(2)));
+        map.get_or_insert_with(2, || Seen(at(3)));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&2).unwrap().0, at(1));
+    }
+}
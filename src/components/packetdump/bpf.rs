@@ -0,0 +1,1043 @@
+//! Kernel side packet filtering with classic BPF.
+//!
+//! A packet filter starting with `bpf:` and written like a tcpdump expression
+//! is parsed into a [`BpfExpr`] and compiled to a classic BPF program. On Linux the program is
+//! attached to the capture socket with `SO_ATTACH_FILTER`, so packets it
+//! rejects never leave the kernel. The same expression is matched against the
+//! decoded packets as well, which covers packets stored before the filter was
+//! set and platforms where nothing can be attached. Without the prefix the
+//! same words (`tcp`, `arp`, `! x`) stay substring and field filters, which
+//! drop nothing before Discovery, the talkers and the detectors see it.
+//! Supported:
+//!
+//! - **Protocols**: `tcp`, `udp`, `icmp`, `icmp6`, `igmp`, `arp`, `ip`, `ip6`
+//! - **Ports**: `[tcp|udp] [src|dst] port N`
+//! - **Hosts**: `[src|dst] host ADDR`, IPv4 (including ARP) or IPv6
+//! - **Operators**: `and`/`&&`, `or`/`||`, `not`/`!` and parentheses
+//!
+//! Offsets assume untagged Ethernet frames, the capture runs at layer 2. The
+//! program reads the IPv6 next header and ports right after the fixed header,
+//! so the kernel rejects protocol and port tests on IPv6 packets with
+//! extension headers (fragments, the hop-by-hop header of MLD reports) which
+//! the userspace match, working on decoded packets, would accept.
+
+use std::net::IpAddr;
+
+use crate::enums::PacketsInfoTypesEnum;
+
+// -- classic BPF opcodes, see linux/filter.h
+const BPF_LD: u16 = 0x00;
+const BPF_LDX: u16 = 0x01;
+const BPF_JMP: u16 = 0x05;
+const BPF_RET: u16 = 0x06;
+const BPF_W: u16 = 0x00;
+const BPF_H: u16 = 0x08;
+const BPF_B: u16 = 0x10;
+const BPF_ABS: u16 = 0x20;
+const BPF_IND: u16 = 0x40;
+const BPF_MSH: u16 = 0xa0;
+const BPF_JA: u16 = 0x00;
+const BPF_JEQ: u16 = 0x10;
+const BPF_JSET: u16 = 0x40;
+
+const ETHERTYPE_IPV4: u32 = 0x0800;
+const ETHERTYPE_IPV6: u32 = 0x86dd;
+const ETHERTYPE_ARP: u32 = 0x0806;
+const ETHERNET_HEADER_LEN: u32 = 14;
+
+/// Bytes of an accepted packet handed to userspace, all of it.
+const ACCEPT_LEN: u32 = 0x40000;
+/// The kernel's limit on program length.
+const MAX_INSTRUCTIONS: usize = 4096;
+
+/// One classic BPF instruction, laid out like `struct sock_filter`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BpfInsn {
+    pub code: u16,
+    pub jt: u8,
+    pub jf: u8,
+    pub k: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Proto {
+    Tcp,
+    Udp,
+    Icmp,
+    Icmp6,
+    Igmp,
+    Arp,
+    Ip,
+    Ip6,
+}
+
+impl Proto {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            "tcp" => Some(Proto::Tcp),
+            "udp" => Some(Proto::Udp),
+            "icmp" => Some(Proto::Icmp),
+            "icmp6" => Some(Proto::Icmp6),
+            "igmp" => Some(Proto::Igmp),
+            "arp" => Some(Proto::Arp),
+            "ip" => Some(Proto::Ip),
+            "ip6" => Some(Proto::Ip6),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dir {
+    Any,
+    Src,
+    Dst,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BpfExpr {
+    Proto(Proto),
+    /// `proto` is `Tcp`, `Udp` or `None` for either.
+    Port {
+        proto: Option<Proto>,
+        dir: Dir,
+        port: u16,
+    },
+    Host {
+        dir: Dir,
+        addr: IpAddr,
+    },
+    And(Box<BpfExpr>, Box<BpfExpr>),
+    Or(Box<BpfExpr>, Box<BpfExpr>),
+    Not(Box<BpfExpr>),
+}
+
+struct Parser<'a> {
+    tokens: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.peek();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, words: &[&str]) -> bool {
+        if self.peek().is_some_and(|t| words.contains(&t)) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn or(&mut self) -> Option<BpfExpr> {
+        let mut expr = self.and()?;
+        while self.eat(&["or", "||"]) {
+            expr = BpfExpr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Some(expr)
+    }
+
+    fn and(&mut self) -> Option<BpfExpr> {
+        let mut expr = self.unary()?;
+        while self.eat(&["and", "&&"]) {
+            expr = BpfExpr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Some(expr)
+    }
+
+    fn unary(&mut self) -> Option<BpfExpr> {
+        if self.eat(&["not", "!"]) {
+            return Some(BpfExpr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat(&["("]) {
+            let expr = self.or()?;
+            return self.eat(&[")"]).then_some(expr);
+        }
+        self.primitive()
+    }
+
+    fn primitive(&mut self) -> Option<BpfExpr> {
+        let proto = self.peek().and_then(Proto::parse);
+        if proto.is_some() {
+            self.pos += 1;
+        }
+        let dir = if self.eat(&["src"]) {
+            Dir::Src
+        } else if self.eat(&["dst"]) {
+            Dir::Dst
+        } else {
+            Dir::Any
+        };
+        match self.peek() {
+            Some("port") if matches!(proto, None | Some(Proto::Tcp) | Some(Proto::Udp)) => {
+                self.pos += 1;
+                let port = self.next()?.parse().ok()?;
+                Some(BpfExpr::Port { proto, dir, port })
+            }
+            Some("host") if proto.is_none() => {
+                self.pos += 1;
+                let addr = self.next()?.parse().ok()?;
+                Some(BpfExpr::Host { dir, addr })
+            }
+            _ if dir == Dir::Any => proto.map(BpfExpr::Proto),
+            _ => None,
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    for word in text.split_whitespace() {
+        let mut rest = word;
+        while !rest.is_empty() {
+            let end = rest.find(['(', ')']).unwrap_or(rest.len());
+            if end == 0 {
+                tokens.push(&rest[..1]);
+                rest = &rest[1..];
+            } else {
+                tokens.push(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+    }
+    tokens
+}
+
+/// Filter text marking an expression for the kernel.
+pub const PREFIX: &str = "bpf:";
+
+/// The expression of filter text starting with [`PREFIX`], `None` for text
+/// without it.
+pub fn strip_prefix(text: &str) -> Option<&str> {
+    text.trim_start().strip_prefix(PREFIX)
+}
+
+/// Parse `text` as a filter expression, `None` when it isn't one.
+pub fn parse(text: &str) -> Option<BpfExpr> {
+    let mut parser = Parser {
+        tokens: tokenize(text),
+        pos: 0,
+    };
+    if parser.tokens.is_empty() {
+        return None;
+    }
+    let expr = parser.or()?;
+    (parser.pos == parser.tokens.len()).then_some(expr)
+}
+
+fn dir_matches<T: PartialEq>(dir: Dir, source: T, destination: T, value: T) -> bool {
+    match dir {
+        Dir::Any => source == value || destination == value,
+        Dir::Src => source == value,
+        Dir::Dst => destination == value,
+    }
+}
+
+impl BpfExpr {
+    /// Userspace evaluation, the same as the compiled program would decide.
+    pub fn matches(&self, packet: &PacketsInfoTypesEnum) -> bool {
+        match self {
+            BpfExpr::Proto(proto) => match proto {
+                Proto::Tcp => matches!(packet, PacketsInfoTypesEnum::Tcp(_)),
+                Proto::Udp => matches!(packet, PacketsInfoTypesEnum::Udp(_)),
+                Proto::Icmp => matches!(packet, PacketsInfoTypesEnum::Icmp(_)),
+                Proto::Icmp6 => matches!(packet, PacketsInfoTypesEnum::Icmp6(_)),
+                Proto::Igmp => matches!(packet, PacketsInfoTypesEnum::Igmp(_)),
+                Proto::Arp => matches!(packet, PacketsInfoTypesEnum::Arp(_)),
+                Proto::Ip | Proto::Ip6 => {
+                    let is_arp = matches!(packet, PacketsInfoTypesEnum::Arp(_));
+                    let ipv4 = packet.addresses().map(|(source, _)| source.is_ipv4());
+                    !is_arp && ipv4 == Some(*proto == Proto::Ip)
+                }
+            },
+            BpfExpr::Port { proto, dir, port } => {
+                let (packet_proto, source, destination) = match packet {
+                    PacketsInfoTypesEnum::Tcp(p) => (Proto::Tcp, p.source_port, p.destination_port),
+                    PacketsInfoTypesEnum::Udp(p) => (Proto::Udp, p.source_port, p.destination_port),
+                    _ => return false,
+                };
+                proto.is_none_or(|proto| proto == packet_proto)
+                    && dir_matches(*dir, source, destination, *port)
+            }
+            BpfExpr::Host { dir, addr } => packet
+                .addresses()
+                .is_some_and(|(source, destination)| dir_matches(*dir, source, destination, *addr)),
+            BpfExpr::And(a, b) => a.matches(packet) && b.matches(packet),
+            BpfExpr::Or(a, b) => a.matches(packet) || b.matches(packet),
+            BpfExpr::Not(a) => !a.matches(packet),
+        }
+    }
+
+    /// Classic BPF program accepting the packets the expression matches,
+    /// `None` if it doesn't fit the kernel's limits.
+    pub fn compile(&self) -> Option<Vec<BpfInsn>> {
+        let mut codegen = Codegen::default();
+        let accept = codegen.label();
+        let reject = codegen.label();
+        codegen.cond(&self.lower(), accept, reject);
+        codegen.place(accept);
+        codegen.emit(Op::Ret(ACCEPT_LEN));
+        codegen.place(reject);
+        codegen.emit(Op::Ret(0));
+        codegen.resolve()
+    }
+
+    fn lower(&self) -> Cond {
+        match self {
+            BpfExpr::Proto(proto) => match proto {
+                Proto::Tcp => Cond::Or(vec![ip4_proto(6), ip6_proto(6)]),
+                Proto::Udp => Cond::Or(vec![ip4_proto(17), ip6_proto(17)]),
+                Proto::Icmp => ip4_proto(1),
+                Proto::Icmp6 => ip6_proto(58),
+                Proto::Igmp => ip4_proto(2),
+                Proto::Arp => ethertype(ETHERTYPE_ARP),
+                Proto::Ip => ethertype(ETHERTYPE_IPV4),
+                Proto::Ip6 => ethertype(ETHERTYPE_IPV6),
+            },
+            BpfExpr::Port { proto, dir, port } => {
+                let transport = |offset| match proto {
+                    Some(Proto::Udp) => test(Load::Abs(BPF_B, offset), BPF_JEQ, 17),
+                    Some(_) => test(Load::Abs(BPF_B, offset), BPF_JEQ, 6),
+                    None => Cond::Or(vec![
+                        test(Load::Abs(BPF_B, offset), BPF_JEQ, 6),
+                        test(Load::Abs(BPF_B, offset), BPF_JEQ, 17),
+                    ]),
+                };
+                let port = *port as u32;
+                // -- IPv4 ports follow a variable length header, later fragments have none
+                let ipv4 = Cond::And(vec![
+                    ethertype(ETHERTYPE_IPV4),
+                    transport(23),
+                    Cond::Not(Box::new(test(Load::Abs(BPF_H, 20), BPF_JSET, 0x1fff))),
+                    Cond::SetX(ETHERNET_HEADER_LEN),
+                    by_dir(
+                        *dir,
+                        test(Load::Ind(BPF_H, ETHERNET_HEADER_LEN), BPF_JEQ, port),
+                        test(Load::Ind(BPF_H, ETHERNET_HEADER_LEN + 2), BPF_JEQ, port),
+                    ),
+                ]);
+                let ipv6 = Cond::And(vec![
+                    ethertype(ETHERTYPE_IPV6),
+                    transport(20),
+                    by_dir(
+                        *dir,
+                        test(Load::Abs(BPF_H, 54), BPF_JEQ, port),
+                        test(Load::Abs(BPF_H, 56), BPF_JEQ, port),
+                    ),
+                ]);
+                Cond::Or(vec![ipv4, ipv6])
+            }
+            BpfExpr::Host { dir, addr } => match addr {
+                IpAddr::V4(addr) => {
+                    let addr = u32::from(*addr);
+                    let ip = Cond::And(vec![
+                        ethertype(ETHERTYPE_IPV4),
+                        by_dir(
+                            *dir,
+                            test(Load::Abs(BPF_W, 26), BPF_JEQ, addr),
+                            test(Load::Abs(BPF_W, 30), BPF_JEQ, addr),
+                        ),
+                    ]);
+                    let arp = Cond::And(vec![
+                        ethertype(ETHERTYPE_ARP),
+                        by_dir(
+                            *dir,
+                            test(Load::Abs(BPF_W, 28), BPF_JEQ, addr),
+                            test(Load::Abs(BPF_W, 38), BPF_JEQ, addr),
+                        ),
+                    ]);
+                    Cond::Or(vec![ip, arp])
+                }
+                IpAddr::V6(addr) => {
+                    let octets = addr.octets();
+                    let words: Vec<u32> = octets
+                        .chunks(4)
+                        .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+                        .collect();
+                    let at = |offset: u32| {
+                        Cond::And(
+                            words
+                                .iter()
+                                .enumerate()
+                                .map(|(i, w)| test(Load::Abs(BPF_W, offset + 4 * i as u32), BPF_JEQ, *w))
+                                .collect(),
+                        )
+                    };
+                    Cond::And(vec![ethertype(ETHERTYPE_IPV6), by_dir(*dir, at(22), at(38))])
+                }
+            },
+            BpfExpr::And(a, b) => Cond::And(vec![a.lower(), b.lower()]),
+            BpfExpr::Or(a, b) => Cond::Or(vec![a.lower(), b.lower()]),
+            BpfExpr::Not(a) => Cond::Not(Box::new(a.lower())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Load {
+    /// Size and absolute offset.
+    Abs(u16, u32),
+    /// Size and offset from the index register.
+    Ind(u16, u32),
+}
+
+// -- an expression lowered to loads and comparisons on the raw frame
+#[derive(Debug)]
+enum Cond {
+    Test { load: Load, op: u16, k: u32 },
+    /// Point the index register past the IPv4 header at the offset, always true.
+    SetX(u32),
+    And(Vec<Cond>),
+    Or(Vec<Cond>),
+    Not(Box<Cond>),
+}
+
+fn test(load: Load, op: u16, k: u32) -> Cond {
+    Cond::Test { load, op, k }
+}
+
+fn ethertype(ethertype: u32) -> Cond {
+    test(Load::Abs(BPF_H, 12), BPF_JEQ, ethertype)
+}
+
+fn ip4_proto(proto: u32) -> Cond {
+    Cond::And(vec![ethertype(ETHERTYPE_IPV4), test(Load::Abs(BPF_B, 23), BPF_JEQ, proto)])
+}
+
+fn ip6_proto(proto: u32) -> Cond {
+    Cond::And(vec![ethertype(ETHERTYPE_IPV6), test(Load::Abs(BPF_B, 20), BPF_JEQ, proto)])
+}
+
+fn by_dir(dir: Dir, source: Cond, destination: Cond) -> Cond {
+    match dir {
+        Dir::Any => Cond::Or(vec![source, destination]),
+        Dir::Src => source,
+        Dir::Dst => destination,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Label(usize);
+
+#[derive(Debug)]
+enum Op {
+    Load(Load),
+    SetX(u32),
+    Jump { op: u16, k: u32, jt: Label, jf: Label },
+    Goto(Label),
+    Ret(u32),
+}
+
+// -- jumps go to labels, resolved to offsets once every label is placed
+#[derive(Default)]
+struct Codegen {
+    ops: Vec<Op>,
+    labels: Vec<Option<usize>>,
+}
+
+impl Codegen {
+    fn label(&mut self) -> Label {
+        self.labels.push(None);
+        Label(self.labels.len() - 1)
+    }
+
+    fn place(&mut self, label: Label) {
+        self.labels[label.0] = Some(self.ops.len());
+    }
+
+    fn emit(&mut self, op: Op) {
+        self.ops.push(op);
+    }
+
+    // -- labels are always placed after the code jumping to them, BPF only jumps forward
+    fn cond(&mut self, cond: &Cond, on_true: Label, on_false: Label) {
+        match cond {
+            Cond::Test { load, op, k } => {
+                self.emit(Op::Load(*load));
+                self.emit(Op::Jump {
+                    op: *op,
+                    k: *k,
+                    jt: on_true,
+                    jf: on_false,
+                });
+            }
+            Cond::SetX(offset) => {
+                self.emit(Op::SetX(*offset));
+                self.emit(Op::Goto(on_true));
+            }
+            Cond::And(conds) | Cond::Or(conds) => {
+                let is_and = matches!(cond, Cond::And(_));
+                let Some((last, rest)) = conds.split_last() else {
+                    self.emit(Op::Goto(if is_and { on_true } else { on_false }));
+                    return;
+                };
+                for c in rest {
+                    let next = self.label();
+                    if is_and {
+                        self.cond(c, next, on_false);
+                    } else {
+                        self.cond(c, on_true, next);
+                    }
+                    self.place(next);
+                }
+                self.cond(last, on_true, on_false);
+            }
+            Cond::Not(c) => self.cond(c, on_false, on_true),
+        }
+    }
+
+    fn resolve(self) -> Option<Vec<BpfInsn>> {
+        if self.ops.len() > MAX_INSTRUCTIONS {
+            return None;
+        }
+        let offset = |pos: usize, label: Label| -> Option<usize> {
+            self.labels[label.0]?.checked_sub(pos + 1)
+        };
+        let mut program = Vec::with_capacity(self.ops.len());
+        for (pos, op) in self.ops.iter().enumerate() {
+            let insn = match op {
+                Op::Load(Load::Abs(size, k)) => BpfInsn { code: BPF_LD | size | BPF_ABS, jt: 0, jf: 0, k: *k },
+                Op::Load(Load::Ind(size, k)) => BpfInsn { code: BPF_LD | size | BPF_IND, jt: 0, jf: 0, k: *k },
+                Op::SetX(k) => BpfInsn { code: BPF_LDX | BPF_B | BPF_MSH, jt: 0, jf: 0, k: *k },
+                Op::Jump { op, k, jt, jf } => BpfInsn {
+                    code: BPF_JMP | op,
+                    // -- conditional jumps only reach 255 instructions ahead
+                    jt: u8::try_from(offset(pos, *jt)?).ok()?,
+                    jf: u8::try_from(offset(pos, *jf)?).ok()?,
+                    k: *k,
+                },
+                Op::Goto(label) => BpfInsn {
+                    code: BPF_JMP | BPF_JA,
+                    jt: 0,
+                    jf: 0,
+                    k: offset(pos, *label)? as u32,
+                },
+                Op::Ret(k) => BpfInsn { code: BPF_RET, jt: 0, jf: 0, k: *k },
+            };
+            program.push(insn);
+        }
+        Some(program)
+    }
+}
+
+/// Raw socket for layer 2 capture with `program` attached, handed to pnet as
+/// its `socket_fd`. Linux only, elsewhere the filter is applied in userspace.
+#[cfg(target_os = "linux")]
+pub fn filtered_socket(program: &[BpfInsn]) -> std::io::Result<i32> {
+    let protocol = (libc::ETH_P_ALL as u16).to_be() as i32;
+    let fd = unsafe { libc::socket(libc::AF_PACKET, libc::SOCK_RAW, protocol) };
+    if fd == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let fprog = libc::sock_fprog {
+        len: program.len() as u16,
+        filter: program.as_ptr() as *mut libc::sock_filter,
+    };
+    let attached = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_ATTACH_FILTER,
+            &fprog as *const libc::sock_fprog as *const libc::c_void,
+            std::mem::size_of::<libc::sock_fprog>() as libc::socklen_t,
+        )
+    };
+    if attached == -1 {
+        let err = std::io::Error::last_os_error();
+        unsafe { libc::close(fd) };
+        return Err(err);
+    }
+    Ok(fd)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use pnet::packet::arp::ArpOperations;
+    use pnet::util::MacAddr;
+
+    use super::*;
+    use crate::enums::{ARPPacketInfo, IpMeta, TCPPacketInfo, UDPPacketInfo};
+
+    fn stmt(code: u16, k: u32) -> BpfInsn {
+        BpfInsn {
+            code,
+            jt: 0,
+            jf: 0,
+            k,
+        }
+    }
+
+    fn jump(code: u16, k: u32, jt: u8, jf: u8) -> BpfInsn {
+        BpfInsn { code, jt, jf, k }
+    }
+
+    const LDH: u16 = BPF_LD | BPF_H | BPF_ABS;
+    const LDW: u16 = BPF_LD | BPF_W | BPF_ABS;
+    const JEQ: u16 = BPF_JMP | BPF_JEQ;
+
+    // -- just enough of a classic BPF machine to run what `compile` emits, a
+    // load past the end of the frame rejects it like the kernel does
+    fn run(program: &[BpfInsn], frame: &[u8]) -> u32 {
+        let (mut a, mut x, mut pc) = (0u32, 0u32, 0usize);
+        loop {
+            let insn = program[pc];
+            pc += 1;
+            match insn.code & 0x07 {
+                BPF_LD => {
+                    let at = insn.k as usize
+                        + if insn.code & BPF_IND != 0 {
+                            x as usize
+                        } else {
+                            0
+                        };
+                    let len = match insn.code & 0x18 {
+                        BPF_B => 1,
+                        BPF_H => 2,
+                        _ => 4,
+                    };
+                    let Some(bytes) = frame.get(at..at + len) else {
+                        return 0;
+                    };
+                    a = bytes.iter().fold(0, |a, b| a << 8 | *b as u32);
+                }
+                BPF_LDX => {
+                    let Some(byte) = frame.get(insn.k as usize) else {
+                        return 0;
+                    };
+                    x = 4 * (*byte as u32 & 0x0f);
+                }
+                BPF_JMP => {
+                    let taken = match insn.code & 0xf0 {
+                        BPF_JA => {
+                            pc += insn.k as usize;
+                            continue;
+                        }
+                        BPF_JEQ => a == insn.k,
+                        _ => a & insn.k != 0,
+                    };
+                    pc += if taken { insn.jt } else { insn.jf } as usize;
+                }
+                _ => return insn.k,
+            }
+        }
+    }
+
+    fn compile(text: &str) -> Vec<BpfInsn> {
+        let program = parse(text).and_then(|expr| expr.compile()).expect(text);
+        // -- every jump lands on an instruction of the program
+        for (pc, insn) in program.iter().enumerate() {
+            let targets = match insn.code {
+                code if code == BPF_JMP | BPF_JA => vec![insn.k as usize],
+                code if code & 0x07 == BPF_JMP => vec![insn.jt as usize, insn.jf as usize],
+                _ => vec![],
+            };
+            assert!(
+                targets.iter().all(|t| pc + 1 + t < program.len()),
+                "{} at {}",
+                text,
+                pc
+            );
+        }
+        program
+    }
+
+    fn ethernet(ethertype: u16, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0; 12];
+        frame.extend_from_slice(&ethertype.to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    fn ports(source_port: u16, destination_port: u16) -> Vec<u8> {
+        let mut header = source_port.to_be_bytes().to_vec();
+        header.extend_from_slice(&destination_port.to_be_bytes());
+        header.extend_from_slice(&[0; 16]);
+        header
+    }
+
+    // -- an Ethernet frame carrying `transport` over IPv4 or IPv6
+    fn ip_frame(source: IpAddr, destination: IpAddr, protocol: u8, transport: &[u8]) -> Vec<u8> {
+        let mut packet = match (source, destination) {
+            (IpAddr::V4(source), IpAddr::V4(destination)) => {
+                let mut header = vec![0x45, 0, 0, 0, 0, 0, 0x40, 0, 64, protocol, 0, 0];
+                header.extend_from_slice(&source.octets());
+                header.extend_from_slice(&destination.octets());
+                header
+            }
+            (IpAddr::V6(source), IpAddr::V6(destination)) => {
+                let mut header = vec![0x60, 0, 0, 0, 0, 0, protocol, 64];
+                header.extend_from_slice(&source.octets());
+                header.extend_from_slice(&destination.octets());
+                header
+            }
+            _ => unreachable!(),
+        };
+        packet.extend_from_slice(transport);
+        let ethertype = if source.is_ipv4() { 0x0800 } else { 0x86dd };
+        ethernet(ethertype, &packet)
+    }
+
+    fn tcp(
+        source: &str,
+        source_port: u16,
+        destination: &str,
+        destination_port: u16,
+    ) -> (Vec<u8>, PacketsInfoTypesEnum) {
+        let (source, destination) = (source.parse().unwrap(), destination.parse().unwrap());
+        let frame = ip_frame(
+            source,
+            destination,
+            6,
+            &ports(source_port, destination_port),
+        );
+        let info = PacketsInfoTypesEnum::Tcp(TCPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source,
+            source_port,
+            destination,
+            destination_port,
+            length: 20,
+            flags: 0,
+            sequence: 0,
+            acknowledgement: 0,
+            relative_sequence: None,
+            relative_acknowledgement: None,
+            payload_len: 0,
+            retransmission: false,
+            http_info: None,
+            payload: vec![],
+            ip: IpMeta::default(),
+            vlan: None,
+            raw_str: String::new(),
+            raw_bytes: vec![],
+            orig_len: frame.len(),
+        });
+        (frame, info)
+    }
+
+    fn udp(
+        source: &str,
+        source_port: u16,
+        destination: &str,
+        destination_port: u16,
+    ) -> (Vec<u8>, PacketsInfoTypesEnum) {
+        let (source, destination) = (source.parse().unwrap(), destination.parse().unwrap());
+        let frame = ip_frame(
+            source,
+            destination,
+            17,
+            &ports(source_port, destination_port)[..8],
+        );
+        let info = PacketsInfoTypesEnum::Udp(UDPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source,
+            source_port,
+            destination,
+            destination_port,
+            length: 8,
+            dns_info: None,
+            dhcp_info: None,
+            mdns_info: None,
+            ssdp_info: None,
+            payload: vec![],
+            ip: IpMeta::default(),
+            vlan: None,
+            raw_str: String::new(),
+            raw_bytes: vec![],
+            orig_len: frame.len(),
+        });
+        (frame, info)
+    }
+
+    fn arp(source: &str, destination: &str) -> (Vec<u8>, PacketsInfoTypesEnum) {
+        let (source_ip, destination_ip): (Ipv4Addr, Ipv4Addr) =
+            (source.parse().unwrap(), destination.parse().unwrap());
+        // -- Ethernet/IPv4 request, the sender MAC is left zero
+        let mut packet = vec![0, 1, 0x08, 0, 6, 4, 0, 1, 0, 0, 0, 0, 0, 0];
+        packet.extend_from_slice(&source_ip.octets());
+        packet.extend_from_slice(&[0; 6]);
+        packet.extend_from_slice(&destination_ip.octets());
+        let frame = ethernet(0x0806, &packet);
+        let info = PacketsInfoTypesEnum::Arp(ARPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source_mac: MacAddr::zero(),
+            source_ip,
+            destination_mac: MacAddr::zero(),
+            destination_ip,
+            operation: ArpOperations::Request,
+            source_vendor: None,
+            vlan: None,
+            raw_str: String::new(),
+            raw_bytes: vec![],
+            orig_len: frame.len(),
+        });
+        (frame, info)
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let proto = |p| Box::new(BpfExpr::Proto(p));
+        let port = |port| {
+            Box::new(BpfExpr::Port {
+                proto: None,
+                dir: Dir::Any,
+                port,
+            })
+        };
+        assert_eq!(
+            parse("tcp or udp and port 53"),
+            Some(BpfExpr::Or(
+                proto(Proto::Tcp),
+                Box::new(BpfExpr::And(proto(Proto::Udp), port(53)))
+            ))
+        );
+        assert_eq!(
+            parse("(tcp or udp) and port 53"),
+            Some(BpfExpr::And(
+                Box::new(BpfExpr::Or(proto(Proto::Tcp), proto(Proto::Udp))),
+                port(53)
+            ))
+        );
+        assert_eq!(
+            parse("not tcp and udp"),
+            Some(BpfExpr::And(
+                Box::new(BpfExpr::Not(proto(Proto::Tcp))),
+                proto(Proto::Udp)
+            ))
+        );
+        assert_eq!(
+            parse("!(tcp || udp)"),
+            Some(BpfExpr::Not(Box::new(BpfExpr::Or(
+                proto(Proto::Tcp),
+                proto(Proto::Udp)
+            ))))
+        );
+        assert_eq!(
+            parse("not not arp && ip6"),
+            Some(BpfExpr::And(
+                Box::new(BpfExpr::Not(Box::new(BpfExpr::Not(proto(Proto::Arp))))),
+                proto(Proto::Ip6)
+            ))
+        );
+    }
+
+    #[test]
+    fn only_prefixed_text_is_an_expression() {
+        assert_eq!(strip_prefix("tcp"), None);
+        assert_eq!(strip_prefix("TCP port 443"), None);
+        assert_eq!(strip_prefix(" bpf: tcp").and_then(parse), Some(BpfExpr::Proto(Proto::Tcp)));
+        assert_eq!(strip_prefix("bpf:arp").and_then(parse), Some(BpfExpr::Proto(Proto::Arp)));
+        assert_eq!(strip_prefix("bpf: mynas").and_then(parse), None);
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(
+            parse("udp dst port 53"),
+            Some(BpfExpr::Port {
+                proto: Some(Proto::Udp),
+                dir: Dir::Dst,
+                port: 53,
+            })
+        );
+        assert_eq!(
+            parse("src host fe80::1"),
+            Some(BpfExpr::Host {
+                dir: Dir::Src,
+                addr: IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)),
+            })
+        );
+    }
+
+    #[test]
+    fn trailing_and_incomplete_input_is_text() {
+        let rejected = [
+            "",
+            "mynas",
+            "tcp port 443 foo",
+            "tcp port 443 and",
+            "icmp port 53",
+            "tcp )",
+            "(tcp",
+            "()",
+            "port",
+            "port http",
+            "port 70000",
+            "host nas",
+            "tcp host 10.0.0.1",
+            "src",
+            "dst tcp",
+            "or tcp",
+        ];
+        for text in rejected {
+            assert_eq!(parse(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn tcp_port_program() {
+        let ldb = BPF_LD | BPF_B | BPF_ABS;
+        let ldh_x = BPF_LD | BPF_H | BPF_IND;
+        assert_eq!(
+            compile("tcp port 443"),
+            vec![
+                // -- IPv4, TCP, not a later fragment
+                stmt(LDH, 12),
+                jump(JEQ, 0x0800, 0, 10),
+                stmt(ldb, 23),
+                jump(JEQ, 6, 0, 8),
+                stmt(LDH, 20),
+                jump(BPF_JMP | BPF_JSET, 0x1fff, 6, 0),
+                // -- ports after the IPv4 header
+                stmt(BPF_LDX | BPF_B | BPF_MSH, 14),
+                stmt(BPF_JMP | BPF_JA, 0),
+                stmt(ldh_x, 14),
+                jump(JEQ, 443, 10, 0),
+                stmt(ldh_x, 16),
+                jump(JEQ, 443, 8, 0),
+                // -- IPv6, TCP, ports after the fixed header
+                stmt(LDH, 12),
+                jump(JEQ, 0x86dd, 0, 7),
+                stmt(ldb, 20),
+                jump(JEQ, 6, 0, 5),
+                stmt(LDH, 54),
+                jump(JEQ, 443, 2, 0),
+                stmt(LDH, 56),
+                jump(JEQ, 443, 0, 1),
+                stmt(BPF_RET, ACCEPT_LEN),
+                stmt(BPF_RET, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn ipv4_host_program() {
+        let addr = 0xc0a8010a;
+        assert_eq!(
+            compile("host 192.168.1.10"),
+            vec![
+                // -- IPv4 source or destination
+                stmt(LDH, 12),
+                jump(JEQ, 0x0800, 0, 4),
+                stmt(LDW, 26),
+                jump(JEQ, addr, 8, 0),
+                stmt(LDW, 30),
+                jump(JEQ, addr, 6, 0),
+                // -- ARP sender or target
+                stmt(LDH, 12),
+                jump(JEQ, 0x0806, 0, 5),
+                stmt(LDW, 28),
+                jump(JEQ, addr, 2, 0),
+                stmt(LDW, 38),
+                jump(JEQ, addr, 0, 1),
+                stmt(BPF_RET, ACCEPT_LEN),
+                stmt(BPF_RET, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn ipv6_host_program() {
+        let words = [0x20010db8, 0, 0, 1];
+        let mut expected = vec![stmt(LDH, 12), jump(JEQ, 0x86dd, 0, 17)];
+        // -- a source word that differs moves on to the destination
+        for (i, word) in words.iter().enumerate() {
+            expected.push(stmt(LDW, 22 + 4 * i as u32));
+            expected.push(match i {
+                3 => jump(JEQ, *word, 8, 0),
+                _ => jump(JEQ, *word, 0, 6 - 2 * i as u8),
+            });
+        }
+        for (i, word) in words.iter().enumerate() {
+            expected.push(stmt(LDW, 38 + 4 * i as u32));
+            expected.push(jump(JEQ, *word, 0, 7 - 2 * i as u8));
+        }
+        expected.push(stmt(BPF_RET, ACCEPT_LEN));
+        expected.push(stmt(BPF_RET, 0));
+        assert_eq!(compile("host 2001:db8::1"), expected);
+    }
+
+    #[test]
+    fn not_arp_program() {
+        assert_eq!(
+            compile("not arp"),
+            vec![
+                stmt(LDH, 12),
+                jump(JEQ, 0x0806, 1, 0),
+                stmt(BPF_RET, ACCEPT_LEN),
+                stmt(BPF_RET, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn jumps_past_255_instructions_do_not_compile() {
+        let hosts = |n: u16| {
+            (1..=n)
+                .map(|i| format!("host 2001:db8::{:x}", i))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        };
+        // -- 18 instructions a host, the first match has to reach past all of them
+        assert!(parse(&hosts(12)).and_then(|expr| expr.compile()).is_some());
+        assert_eq!(parse(&hosts(16)).map(|expr| expr.compile()), Some(None));
+    }
+
+    #[test]
+    fn program_agrees_with_userspace_match() {
+        let packets = [
+            ("tcp4 out", tcp("192.168.1.10", 50000, "1.1.1.1", 443)),
+            ("tcp4 ssh", tcp("10.0.0.2", 50002, "10.0.0.1", 22)),
+            ("tcp6 in", tcp("2001:db8::2", 443, "2001:db8::1", 50000)),
+            ("udp4 dns", udp("192.168.1.10", 40000, "192.168.1.1", 53)),
+            ("udp6 mdns", udp("fe80::1", 5353, "ff02::fb", 5353)),
+            ("arp", arp("192.168.1.1", "192.168.1.10")),
+        ];
+        let filters = [
+            "tcp",
+            "udp",
+            "ip",
+            "ip6",
+            "arp",
+            "not arp",
+            "port 443",
+            "tcp port 443",
+            "udp port 443",
+            "src port 443",
+            "dst port 53",
+            "udp port 5353",
+            "host 192.168.1.10",
+            "dst host 192.168.1.10",
+            "src host 192.168.1.1",
+            "host 2001:db8::1",
+            "src host fe80::1",
+            "tcp and not port 22",
+            "(udp or arp) and host 192.168.1.1",
+            "ip6 or arp",
+            "not (tcp or udp)",
+        ];
+        for filter in filters {
+            let expr = parse(filter).expect(filter);
+            let program = compile(filter);
+            for (name, (frame, info)) in &packets {
+                assert_eq!(
+                    run(&program, frame) != 0,
+                    expr.matches(info),
+                    "{} on {}",
+                    filter,
+                    name
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Packet log row styles from the `packet_colors` config section.
+//!
+//! Each rule is a filter, with the same field tokens and substring terms as
+//! the filter input, and a style laid over every span of the rows it matches.
+//! Rules are compiled once from the config; one with a bad filter or color is
+//! skipped with a warning in the log so the rest still apply, the packet dump
+//! reports how many on the status line.
+
+use ratatui::style::Style;
+
+use super::field_filter::FieldFilter;
+use super::text_filter::TextFilter;
+use crate::config::{try_parse_style, PacketColorRule};
+use crate::enums::PacketsInfoTypesEnum;
+
+#[derive(Debug, Clone, PartialEq)]
+struct ColorRule {
+    fields: FieldFilter,
+    text: TextFilter,
+    style: Style,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ColorRules {
+    rules: Vec<ColorRule>,
+    skipped: usize,
+}
+
+impl ColorRules {
+    pub fn new(rules: &[PacketColorRule]) -> Self {
+        let compiled = rules
+            .iter()
+            .filter_map(|rule| match Self::compile(rule) {
+                Ok(compiled) => Some(compiled),
+                Err(e) => {
+                    log::warn!("packet_colors: rule \"{}\" skipped, {}", rule.filter, e);
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        Self {
+            skipped: rules.len() - compiled.len(),
+            rules: compiled,
+        }
+    }
+
+    /// Rules of the config left out for a bad filter or style.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    fn compile(rule: &PacketColorRule) -> Result<ColorRule, String> {
+        let style = try_parse_style(&rule.style)?;
+        let (fields, rest) = FieldFilter::parse(&rule.filter);
+        let text = TextFilter::parse(&rest);
+        if fields.is_invalid() || text.is_invalid() {
+            return Err(String::from("the filter doesn't parse"));
+        }
+        // -- an empty filter would restyle every row
+        if rule.filter.trim().is_empty() {
+            return Err(String::from("the filter is empty"));
+        }
+        Ok(ColorRule { fields, text, style })
+    }
+
+    /// Style of the first rule matching the packet.
+    pub fn style(&self, packet: &PacketsInfoTypesEnum) -> Option<Style> {
+        self.rules
+            .iter()
+            .find(|rule| rule.fields.matches(packet) && rule.text.matches(packet.raw_str()))
+            .map(|rule| rule.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(filter: &str, style: &str) -> PacketColorRule {
+        PacketColorRule {
+            filter: filter.to_string(),
+            style: style.to_string(),
+        }
+    }
+
+    #[test]
+    fn bad_rules_are_skipped() {
+        let rules = ColorRules::new(&[
+            rule("proto:arp", "bold reed"),
+            rule("port:http", "red"),
+            rule("  ", "red"),
+            rule("arp |", "red"),
+            rule("proto:arp", "bold red"),
+            rule("port:22 ssh", "dim gray12 on black"),
+        ]);
+        assert_eq!(rules.rules.len(), 2);
+        assert_eq!(rules.skipped(), 4);
+        assert_eq!(rules.rules[1].text, TextFilter::parse("ssh"));
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        use pnet::packet::arp::ArpOperations;
+        use pnet::util::MacAddr;
+        use ratatui::style::{Color, Modifier};
+
+        use crate::enums::ARPPacketInfo;
+
+        let arp = PacketsInfoTypesEnum::Arp(ARPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source_mac: MacAddr::zero(),
+            source_ip: "192.168.1.1".parse().unwrap(),
+            destination_mac: MacAddr::broadcast(),
+            destination_ip: "192.168.1.10".parse().unwrap(),
+            operation: ArpOperations::Request,
+            source_vendor: None,
+            vlan: None,
+            raw_str: "[eth0]: ARP Packet: request".to_string(),
+            raw_bytes: vec![],
+            orig_len: 42,
+        });
+        let rules = ColorRules::new(&[
+            rule("proto:tcp", "green"),
+            rule("proto:arp request", "bold red"),
+            rule("arp", "blue"),
+        ]);
+        let style = rules.style(&arp).unwrap();
+        assert_eq!(style.fg, Some(Color::Indexed(1)));
+        assert!(style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(ColorRules::new(&[rule("proto:tcp", "green")]).style(&arp), None);
+        assert_eq!(ColorRules::default().style(&arp), None);
+    }
+}
@@ -0,0 +1,253 @@
+//! Detail view of a single packet: its decoded fields and a hex/ASCII dump
+//! of the stored frame bytes.
+
+use chrono::{DateTime, Local};
+use pnet::packet::arp::ArpOperations;
+use pnet::packet::icmp::IcmpTypes;
+
+use super::{dhcp, dns, ethertypes, icmp, igmp, tcp_flags};
+use crate::enums::PacketsInfoTypesEnum;
+
+const BYTES_PER_LINE: usize = 16;
+
+/// Packet shown in the detail popup, a copy so new packets don't move it.
+pub struct PacketDetail {
+    pub time: DateTime<Local>,
+    pub packet: PacketsInfoTypesEnum,
+    pub scroll: u16,
+}
+
+impl PacketDetail {
+    pub fn new(time: DateTime<Local>, packet: PacketsInfoTypesEnum) -> Self {
+        Self {
+            time,
+            packet,
+            scroll: 0,
+        }
+    }
+
+    /// The fields and hex dump as plain text, laid out like the popup.
+    pub fn text(&self) -> String {
+        let mut lines: Vec<String> = self
+            .fields()
+            .into_iter()
+            .map(|(name, value)| format!("{:>16} {}", name, value))
+            .collect();
+        lines.push(String::new());
+        lines.extend(hex_dump(self.packet.raw_bytes()));
+        lines.join("\n")
+    }
+
+    /// Decoded fields as name and value, common ones first.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let packet = &self.packet;
+        let mut fields = vec![
+            ("time", self.time.format("%Y-%m-%d %H:%M:%S%.6f").to_string()),
+            ("interface", packet.interface_name().to_string()),
+            ("type", packet.packet_type().to_string()),
+            (
+                "length",
+                format!("{} bytes, {} stored", packet.orig_len(), packet.raw_bytes().len()),
+            ),
+        ];
+        if let Some(vlan) = packet.vlan() {
+            fields.push(("vlan", vlan.to_string()));
+        }
+        if let Some((source, destination)) = packet.addresses() {
+            fields.push(("source", source.to_string()));
+            fields.push(("destination", destination.to_string()));
+        }
+        if let Some(ip) = packet.ip_meta() {
+            fields.push(("ttl", ip.ttl.to_string()));
+            fields.push(("dscp", ip.dscp.to_string()));
+        }
+        match packet {
+            PacketsInfoTypesEnum::Tcp(tcp) => {
+                fields.push(("ports", format!("{} > {}", tcp.source_port, tcp.destination_port)));
+                fields.push(("flags", tcp_flags::names(tcp.flags)));
+                fields.push(("sequence", tcp.sequence.to_string()));
+                fields.push(("ack", tcp.acknowledgement.to_string()));
+                if let Some(sequence) = tcp.relative_sequence {
+                    fields.push(("relative seq", sequence.to_string()));
+                }
+                if let Some(acknowledgement) = tcp.relative_acknowledgement {
+                    fields.push(("relative ack", acknowledgement.to_string()));
+                }
+                fields.push(("payload", format!("{} bytes", tcp.payload_len)));
+                payload_fields(&mut fields, packet);
+                if tcp.retransmission {
+                    fields.push(("retransmission", "yes".to_string()));
+                }
+                if let Some(http) = &tcp.http_info {
+                    fields.push(("http", http.start_line.clone()));
+                    if let Some(host) = &http.host {
+                        fields.push(("host", host.clone()));
+                    }
+                }
+            }
+            PacketsInfoTypesEnum::Udp(udp) => {
+                fields.push(("ports", format!("{} > {}", udp.source_port, udp.destination_port)));
+                fields.push(("udp length", udp.length.to_string()));
+                payload_fields(&mut fields, packet);
+                if let Some(dns) = &udp.dns_info {
+                    let kind = if dns.response { "response" } else { "query" };
+                    fields.push(("dns", format!("{} id {}", kind, dns.id)));
+                    fields.push(("question", format!("{} {}", dns.qtype, dns.query)));
+                    if dns.response {
+                        fields.push(("rcode", dns::rcode_str(dns.rcode)));
+                    }
+                    for answer in &dns.answers {
+                        fields.push(("answer", answer.clone()));
+                    }
+                }
+                if let Some(dhcp) = &udp.dhcp_info {
+                    fields.push(("dhcp", dhcp::message_type_str(dhcp.message_type)));
+                    if let Some(mac) = dhcp.client_mac {
+                        fields.push(("client mac", mac.to_string()));
+                    }
+                    if let Some(ip) = dhcp.your_ip {
+                        fields.push(("your ip", ip.to_string()));
+                    }
+                    if let Some(ip) = dhcp.requested_ip {
+                        fields.push(("requested ip", ip.to_string()));
+                    }
+                    if let Some(server) = dhcp.server_id {
+                        fields.push(("server", server.to_string()));
+                    }
+                    if let Some(lease) = dhcp.lease_secs {
+                        fields.push(("lease", format!("{}s", lease)));
+                    }
+                    if let Some(hostname) = &dhcp.hostname {
+                        fields.push(("hostname", hostname.clone()));
+                    }
+                }
+                if let Some(mdns) = &udp.mdns_info {
+                    let kind = if mdns.response { "response" } else { "query" };
+                    fields.push(("mdns", kind.to_string()));
+                    for query in &mdns.queries {
+                        fields.push(("question", query.clone()));
+                    }
+                    for service in &mdns.services {
+                        fields.push(("service", service.clone()));
+                    }
+                    for (ip, name) in &mdns.hosts {
+                        fields.push(("host", format!("{} {}", name, ip)));
+                    }
+                }
+                if let Some(ssdp) = &udp.ssdp_info {
+                    fields.push(("ssdp", ssdp.method.clone()));
+                    let headers = [
+                        ("nts", &ssdp.sub_type),
+                        ("target", &ssdp.target),
+                        ("usn", &ssdp.usn),
+                        ("server", &ssdp.server),
+                        ("location", &ssdp.location),
+                    ];
+                    for (name, value) in headers {
+                        if let Some(value) = value {
+                            fields.push((name, value.clone()));
+                        }
+                    }
+                }
+            }
+            PacketsInfoTypesEnum::Arp(arp) => {
+                let operation = match arp.operation {
+                    ArpOperations::Request => "request".to_string(),
+                    ArpOperations::Reply => "reply".to_string(),
+                    other => other.0.to_string(),
+                };
+                fields.push(("operation", operation));
+                fields.push(("sender", format!("{} {}", arp.source_mac, arp.source_ip)));
+                fields.push(("target", format!("{} {}", arp.destination_mac, arp.destination_ip)));
+            }
+            PacketsInfoTypesEnum::Icmp(icmp) => {
+                fields.push((
+                    "icmp type",
+                    format!(
+                        "{} code {} ({})",
+                        icmp.icmp_type.0,
+                        icmp.code,
+                        icmp::type_str(icmp.icmp_type, icmp.code)
+                    ),
+                ));
+                if let Some(quote) = &icmp.quote {
+                    fields.push(("original", quote.to_string()));
+                }
+                if let Some(gateway) = icmp.gateway {
+                    fields.push(("gateway", gateway.to_string()));
+                }
+                if matches!(icmp.icmp_type, IcmpTypes::EchoRequest | IcmpTypes::EchoReply) {
+                    fields.push(("id", icmp.id.to_string()));
+                    fields.push(("seq", icmp.seq.to_string()));
+                }
+                if let Some(rtt) = icmp.rtt_ms {
+                    fields.push(("rtt", format!("{:.1}ms", rtt)));
+                }
+            }
+            PacketsInfoTypesEnum::Icmp6(icmp6) => {
+                fields.push(("icmp6 type", icmp6.icmp_type.0.to_string()));
+                if let Some(target) = icmp6.target {
+                    fields.push(("target", target.to_string()));
+                }
+                if let Some(mac) = icmp6.link_addr {
+                    fields.push(("link address", mac.to_string()));
+                }
+                for (prefix, len) in &icmp6.prefixes {
+                    fields.push(("prefix", format!("{}/{}", prefix, len)));
+                }
+                if let Some(lifetime) = icmp6.router_lifetime {
+                    fields.push(("router lifetime", format!("{}s", lifetime)));
+                }
+            }
+            PacketsInfoTypesEnum::Igmp(igmp_packet) => {
+                fields.push(("igmp type", igmp::type_str(igmp_packet.igmp_type)));
+                if let Some(group) = igmp_packet.group {
+                    fields.push(("group", group.to_string()));
+                }
+                for record in &igmp_packet.records {
+                    fields.push(("record", igmp::record_str(record)));
+                }
+            }
+            PacketsInfoTypesEnum::Other(other) => {
+                fields.push(("source mac", other.source_mac.to_string()));
+                fields.push(("destination mac", other.destination_mac.to_string()));
+                fields.push(("ethertype", ethertypes::format(other.ethertype)));
+                if let Some(note) = &other.note {
+                    fields.push(("note", note.clone()));
+                }
+            }
+        }
+        fields
+    }
+}
+
+// -- the payload snippet as hex, 32 bytes a line since the popup doesn't wrap
+fn payload_fields(fields: &mut Vec<(&'static str, String)>, packet: &PacketsInfoTypesEnum) {
+    let hex = packet.payload_hex();
+    for chunk in hex.as_bytes().chunks(64) {
+        fields.push(("payload hex", String::from_utf8_lossy(chunk).to_string()));
+    }
+}
+
+/// Classic dump lines: offset, 16 bytes in hex and their printable ASCII.
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(BYTES_PER_LINE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut hex = String::with_capacity(BYTES_PER_LINE * 3 + 1);
+            for (j, byte) in chunk.iter().enumerate() {
+                // -- extra space between the two halves
+                if j == BYTES_PER_LINE / 2 {
+                    hex.push(' ');
+                }
+                hex.push_str(&format!("{:02x} ", byte));
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|b| if b.is_ascii_graphic() || *b == b' ' { *b as char } else { '.' })
+                .collect();
+            format!("{:04x}  {:<49} |{}|", i * BYTES_PER_LINE, hex, ascii)
+        })
+        .collect()
+}
@@ -0,0 +1,128 @@
+//! DHCP message decoding from the BOOTP payload of UDP 67/68 packets.
+//!
+//! The fixed BOOTP header gives the client MAC and the offered address, the
+//! options the message type (53), server identifier (54), lease time (51),
+//! requested address (50) and hostname (12). An option running past the end
+//! of the payload ends the option list; a packet without the DHCP magic
+//! cookie or message type yields nothing.
+
+use std::net::Ipv4Addr;
+
+use pnet::util::MacAddr;
+
+use crate::enums::DhcpInfo;
+
+pub const DHCP_PORTS: [u16; 2] = [67, 68];
+
+const MAGIC_COOKIE: [u8; 4] = [0x63, 0x82, 0x53, 0x63];
+const OPTIONS_OFFSET: usize = 240;
+
+const OPT_PAD: u8 = 0;
+const OPT_HOSTNAME: u8 = 12;
+const OPT_REQUESTED_IP: u8 = 50;
+const OPT_LEASE_TIME: u8 = 51;
+const OPT_MESSAGE_TYPE: u8 = 53;
+const OPT_SERVER_ID: u8 = 54;
+const OPT_END: u8 = 255;
+
+fn ipv4(data: &[u8]) -> Option<Ipv4Addr> {
+    Some(Ipv4Addr::from(<[u8; 4]>::try_from(data).ok()?))
+}
+
+pub fn message_type_str(message_type: u8) -> String {
+    match message_type {
+        1 => "DISCOVER".to_string(),
+        2 => "OFFER".to_string(),
+        3 => "REQUEST".to_string(),
+        4 => "DECLINE".to_string(),
+        5 => "ACK".to_string(),
+        6 => "NAK".to_string(),
+        7 => "RELEASE".to_string(),
+        8 => "INFORM".to_string(),
+        other => format!("TYPE{}", other),
+    }
+}
+
+pub fn parse(payload: &[u8]) -> Option<DhcpInfo> {
+    if payload.get(236..OPTIONS_OFFSET)? != MAGIC_COOKIE {
+        return None;
+    }
+    // -- Ethernet hardware addresses only
+    let client_mac = match (payload[1], payload[2]) {
+        (1, 6) => Some(MacAddr::new(
+            payload[28], payload[29], payload[30], payload[31], payload[32], payload[33],
+        )),
+        _ => None,
+    };
+    let your_ip = ipv4(&payload[16..20]).filter(|ip| !ip.is_unspecified());
+
+    let mut message_type = None;
+    let mut server_id = None;
+    let mut lease_secs = None;
+    let mut requested_ip = None;
+    let mut hostname = None;
+    let mut pos = OPTIONS_OFFSET;
+    while let Some(&code) = payload.get(pos) {
+        match code {
+            OPT_PAD => {
+                pos += 1;
+                continue;
+            }
+            OPT_END => break,
+            _ => {}
+        }
+        let Some(&len) = payload.get(pos + 1) else {
+            break;
+        };
+        let Some(data) = payload.get(pos + 2..pos + 2 + len as usize) else {
+            break;
+        };
+        match code {
+            OPT_MESSAGE_TYPE => message_type = data.first().copied(),
+            OPT_SERVER_ID => server_id = ipv4(data),
+            OPT_LEASE_TIME => {
+                lease_secs = <[u8; 4]>::try_from(data).ok().map(u32::from_be_bytes)
+            }
+            OPT_REQUESTED_IP => requested_ip = ipv4(data),
+            // -- client supplied, keep control characters out of the row
+            OPT_HOSTNAME => {
+                let name = String::from_utf8_lossy(data);
+                hostname = Some(name.chars().filter(|c| !c.is_control()).collect())
+            }
+            _ => {}
+        }
+        pos += 2 + len as usize;
+    }
+
+    Some(DhcpInfo {
+        message_type: message_type?,
+        client_mac,
+        your_ip,
+        server_id,
+        lease_secs,
+        requested_ip,
+        hostname,
+    })
+}
+
+/// One line for the packet log, e.g. `DHCP OFFER 192.168.1.50 from
+/// 192.168.1.1 (lease 86400s)`, also what the text filter matches against.
+pub fn summary(dhcp: &DhcpInfo) -> String {
+    let mut line = format!("DHCP {}", message_type_str(dhcp.message_type));
+    if let Some(ip) = dhcp.your_ip.or(dhcp.requested_ip) {
+        line.push_str(&format!(" {}", ip));
+    }
+    if let Some(server) = dhcp.server_id {
+        line.push_str(&format!(" from {}", server));
+    }
+    if let Some(lease) = dhcp.lease_secs {
+        line.push_str(&format!(" (lease {}s)", lease));
+    }
+    if let Some(mac) = dhcp.client_mac {
+        line.push_str(&format!(" client {}", mac));
+    }
+    if let Some(hostname) = &dhcp.hostname {
+        line.push_str(&format!(" \"{}\"", hostname));
+    }
+    line
+}
@@ -0,0 +1,297 @@
+//! DNS query and response decoding from UDP payloads.
+//!
+//! The header, the first question and the answer records are read; authority
+//! and additional records are skipped. Names follow compression pointers with
+//! a bounded number of jumps. Anything truncated or malformed yields nothing,
+//! and the packet shows as plain UDP.
+//!
+//! Labels are arbitrary bytes on the wire. Control characters, invalid UTF-8
+//! and characters that reorder or hide text are escaped dig style (`\007`,
+//! `\u{202e}`), as are dots and backslashes inside a label, so a hostile name
+//! can't disturb the terminal or pass for another name in the log.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::enums::DnsInfo;
+
+const HEADER_LEN: usize = 12;
+// -- pointer loops in hostile packets end here
+const MAX_JUMPS: usize = 16;
+const MAX_NAME_LEN: usize = 255;
+const MAX_ANSWERS: usize = 16;
+
+pub(super) fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([*data.get(offset)?, *data.get(offset + 1)?]))
+}
+
+pub fn type_str(rtype: u16) -> String {
+    match rtype {
+        1 => "A".to_string(),
+        2 => "NS".to_string(),
+        5 => "CNAME".to_string(),
+        6 => "SOA".to_string(),
+        12 => "PTR".to_string(),
+        15 => "MX".to_string(),
+        16 => "TXT".to_string(),
+        28 => "AAAA".to_string(),
+        33 => "SRV".to_string(),
+        65 => "HTTPS".to_string(),
+        255 => "ANY".to_string(),
+        other => format!("TYPE{}", other),
+    }
+}
+
+pub fn rcode_str(rcode: u8) -> String {
+    match rcode {
+        0 => "NOERROR".to_string(),
+        1 => "FORMERR".to_string(),
+        2 => "SERVFAIL".to_string(),
+        3 => "NXDOMAIN".to_string(),
+        4 => "NOTIMP".to_string(),
+        5 => "REFUSED".to_string(),
+        other => format!("RCODE{}", other),
+    }
+}
+
+// -- bidi overrides, isolates and zero width characters
+fn is_hiding(c: char) -> bool {
+    matches!(c, '\u{200b}'..='\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+/// `bytes` safe to print, with `special` ASCII characters backslash escaped.
+fn escape(bytes: &[u8], special: &[char]) -> String {
+    let mut out = String::new();
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                c if special.contains(&c) => out.push_str(&format!("\\{}", c)),
+                c if c.is_ascii_control() => out.push_str(&format!("\\{:03}", c as u8)),
+                c if c.is_control() || is_hiding(c) => out.extend(c.escape_unicode()),
+                c => out.push(c),
+            }
+        }
+        for b in chunk.invalid() {
+            out.push_str(&format!("\\{:03}", b));
+        }
+    }
+    out
+}
+
+/// Name at `offset` and the offset right after it in the record, which is
+/// past the first pointer when the name is compressed.
+pub(super) fn read_name(data: &[u8], offset: usize) -> Option<(String, usize)> {
+    let mut name = String::new();
+    let mut wire_len = 0;
+    let mut pos = offset;
+    let mut end = None;
+    let mut jumps = 0;
+    loop {
+        let len = *data.get(pos)? as usize;
+        match len & 0xc0 {
+            0x00 if len == 0 => {
+                break;
+            }
+            0x00 => {
+                let label = data.get(pos + 1..pos + 1 + len)?;
+                if !name.is_empty() {
+                    name.push('.');
+                }
+                name.push_str(&escape(label, &['.', '\\']));
+                wire_len += len + 1;
+                // -- the root label's length byte counts too
+                if wire_len + 1 > MAX_NAME_LEN {
+                    return None;
+                }
+                pos += 1 + len;
+            }
+            0xc0 => {
+                jumps += 1;
+                if jumps > MAX_JUMPS {
+                    return None;
+                }
+                end.get_or_insert(pos + 2);
+                pos = (u16_at(data, pos)? & 0x3fff) as usize;
+            }
+            // -- extended label types were never deployed
+            _ => return None,
+        }
+    }
+    let end = end.unwrap_or(pos + 1);
+    if name.is_empty() {
+        name.push('.');
+    }
+    Some((name, end))
+}
+
+pub(super) fn rdata_str(data: &[u8], rtype: u16, offset: usize, rdata: &[u8]) -> Option<String> {
+    Some(match rtype {
+        1 => Ipv4Addr::from(<[u8; 4]>::try_from(rdata).ok()?).to_string(),
+        28 => Ipv6Addr::from(<[u8; 16]>::try_from(rdata).ok()?).to_string(),
+        2 | 5 | 12 => read_name(data, offset)?.0,
+        15 => {
+            let preference = u16_at(rdata, 0)?;
+            format!("{} {}", preference, read_name(data, offset + 2)?.0)
+        }
+        33 => {
+            let port = u16_at(rdata, 4)?;
+            format!("{}:{}", read_name(data, offset + 6)?.0, port)
+        }
+        16 => {
+            let len = *rdata.first()? as usize;
+            let text = rdata.get(1..1 + len)?;
+            format!("\"{}\"", escape(text, &['"', '\\']))
+        }
+        _ => format!("{} bytes", rdata.len()),
+    })
+}
+
+/// Resource record as found in a message, `offset` is where its data starts.
+pub(super) struct Record<'a> {
+    pub name: String,
+    pub rtype: u16,
+    pub offset: usize,
+    pub rdata: &'a [u8],
+}
+
+/// Record at `pos` and the offset of the next one.
+pub(super) fn read_record(data: &[u8], pos: usize) -> Option<(Record<'_>, usize)> {
+    let (name, after) = read_name(data, pos)?;
+    let rtype = u16_at(data, after)?;
+    let rdlen = u16_at(data, after + 8)? as usize;
+    let offset = after + 10;
+    let rdata = data.get(offset..offset + rdlen)?;
+    Some((
+        Record {
+            name,
+            rtype,
+            offset,
+            rdata,
+        },
+        offset + rdlen,
+    ))
+}
+
+pub fn parse(payload: &[u8]) -> Option<DnsInfo> {
+    if payload.len() < HEADER_LEN {
+        return None;
+    }
+    let id = u16_at(payload, 0)?;
+    let flags = u16_at(payload, 2)?;
+    let questions = u16_at(payload, 4)?;
+    let answers = u16_at(payload, 6)?;
+    // -- queries, notifies and updates all carry a question
+    let opcode = (flags >> 11) & 0x0f;
+    if questions == 0 || opcode > 5 {
+        return None;
+    }
+
+    let (query, mut pos) = read_name(payload, HEADER_LEN)?;
+    let qtype = u16_at(payload, pos)?;
+    pos += 4;
+    // -- skip any further questions, they are practically never sent
+    for _ in 1..questions {
+        pos = read_name(payload, pos)?.1 + 4;
+    }
+
+    let mut records = vec![];
+    for _ in 0..answers.min(MAX_ANSWERS as u16) {
+        let (record, next) = read_record(payload, pos)?;
+        let rtype = record.rtype;
+        records.push(format!(
+            "{} {}",
+            type_str(rtype),
+            rdata_str(payload, rtype, record.offset, record.rdata)?
+        ));
+        pos = next;
+    }
+
+    Some(DnsInfo {
+        id,
+        response: flags & 0x8000 != 0,
+        rcode: (flags & 0x000f) as u8,
+        query,
+        qtype: type_str(qtype),
+        answers: records,
+    })
+}
+
+/// One line for the packet log, also what the text filter matches against.
+pub fn summary(dns: &DnsInfo) -> String {
+    if !dns.response {
+        return format!("DNS query {} {}", dns.qtype, dns.query);
+    }
+    let mut line = format!("DNS response {} {}", dns.qtype, dns.query);
+    if dns.rcode != 0 {
+        line.push_str(&format!(" {}", rcode_str(dns.rcode)));
+    }
+    if !dns.answers.is_empty() {
+        line.push_str(&format!(" -> {}", dns.answers.join(", ")));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // -- a query for the name made of `labels`, type A
+    fn query(labels: &[&[u8]]) -> Vec<u8> {
+        let mut message = vec![0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0];
+        for label in labels {
+            message.push(label.len() as u8);
+            message.extend_from_slice(label);
+        }
+        message.extend_from_slice(&[0, 0, 1, 0, 1]);
+        message
+    }
+
+    fn query_name(labels: &[&[u8]]) -> String {
+        parse(&query(labels)).unwrap().query
+    }
+
+    #[test]
+    fn plain_names_are_unchanged() {
+        assert_eq!(query_name(&[b"www", b"example", b"com"]), "www.example.com");
+        assert_eq!(query_name(&[b"_ipp", b"_tcp", b"local"]), "_ipp._tcp.local");
+        assert_eq!(query_name(&["bücher".as_bytes(), b"de"]), "bücher.de");
+        assert_eq!(query_name(&[]), ".");
+    }
+
+    #[test]
+    fn control_and_hiding_characters_are_escaped() {
+        assert_eq!(query_name(&[b"a\x1b[2Jb", b"com"]), "a\\027[2Jb.com");
+        assert_eq!(query_name(&[b"bell\x07\x00", b"com"]), "bell\\007\\000.com");
+        assert_eq!(query_name(&[b"\xff\xfe", b"com"]), "\\255\\254.com");
+        assert_eq!(query_name(&["moc\u{202e}".as_bytes(), b"com"]), "moc\\u{202e}.com");
+        assert_eq!(query_name(&["a\u{85}".as_bytes()]), "a\\u{85}");
+    }
+
+    #[test]
+    fn dots_inside_a_label_are_escaped() {
+        assert_eq!(query_name(&[b"bank.com", b"evil", b"net"]), "bank\\.com.evil.net");
+        assert_eq!(query_name(&[b"back\\slash"]), "back\\\\slash");
+    }
+
+    #[test]
+    fn txt_data_is_escaped() {
+        let mut response = query(&[b"example", b"com"]);
+        response[2] = 0x81;
+        response[7] = 1;
+        // -- answer: pointer to the question name, TXT, IN, TTL, rdata
+        response.extend_from_slice(&[0xc0, 12, 0, 16, 0, 1, 0, 0, 0, 60, 0, 8]);
+        response.extend_from_slice(b"\x07say \"\x1b\"");
+        let dns = parse(&response).unwrap();
+        assert_eq!(dns.answers, vec!["TXT \"say \\\"\\027\\\"\""]);
+    }
+
+    #[test]
+    fn overlong_names_are_refused() {
+        // -- 255 bytes on the wire, the root label included
+        let label = [b'a'; 63];
+        assert!(parse(&query(&[&label, &label, &label, &label[..61]])).is_some());
+        assert!(parse(&query(&[&label, &label, &label, &label[..62]])).is_none());
+        // -- escaping counts for nothing
+        let control = [1u8; 63];
+        assert!(parse(&query(&[&control, &control, &control, &control[..61]])).is_some());
+    }
+}
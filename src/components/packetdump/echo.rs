@@ -0,0 +1,155 @@
+//! Round-trip times of ICMP echo exchanges.
+//!
+//! A request is remembered by its direction and `(id, seq)` until the matching
+//! reply arrives or it times out. The sequence number wraps at 65535, so
+//! requests are dropped after a few seconds rather than kept until a reused
+//! `(id, seq)` could pair with a reply it never caused. An answered request is
+//! kept until it times out too, so a repeated reply is told apart from one
+//! whose request was never seen.
+
+use std::net::IpAddr;
+
+use chrono::{DateTime, Local};
+
+use super::bounded::{BoundedMap, LastSeen};
+use crate::enums::ICMPPacketInfo;
+
+// -- (requester, responder, id, seq)
+type EchoKey = (IpAddr, IpAddr, u16, u16);
+
+struct PendingEcho {
+    sent: DateTime<Local>,
+    answered: bool,
+}
+
+impl LastSeen for PendingEcho {
+    fn last_seen(&self) -> DateTime<Local> {
+        self.sent
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EchoReply {
+    /// First reply to a known request, with its round-trip time.
+    Rtt(f64),
+    /// The request was already answered.
+    Duplicate,
+    /// No request seen, it predates the capture or timed out.
+    Unmatched,
+}
+
+pub struct EchoTracker {
+    pending: BoundedMap<EchoKey, PendingEcho>,
+}
+
+impl EchoTracker {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            pending: BoundedMap::new(capacity),
+        }
+    }
+
+    pub fn request(&mut self, time: DateTime<Local>, icmp: &ICMPPacketInfo) {
+        let key = (icmp.source, icmp.destination, icmp.id, icmp.seq);
+        self.pending.insert(
+            key,
+            PendingEcho {
+                sent: time,
+                answered: false,
+            },
+        );
+    }
+
+    pub fn reply(&mut self, time: DateTime<Local>, icmp: &ICMPPacketInfo) -> EchoReply {
+        let key = (icmp.destination, icmp.source, icmp.id, icmp.seq);
+        match self.pending.get_mut(&key) {
+            Some(pending) if pending.answered => EchoReply::Duplicate,
+            Some(pending) => {
+                pending.answered = true;
+                let rtt = time - pending.sent;
+                EchoReply::Rtt(rtt.num_microseconds().unwrap_or(0).max(0) as f64 / 1000.0)
+            }
+            None => EchoReply::Unmatched,
+        }
+    }
+
+    /// Forget requests sent longer than `timeout` ago.
+    pub fn prune(&mut self, now: DateTime<Local>, timeout: chrono::Duration) {
+        self.pending.retain(|_, pending| now - pending.sent <= timeout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+    use pnet::packet::icmp::IcmpTypes;
+
+    use super::*;
+    use crate::enums::IpMeta;
+
+    const HOST: &str = "10.0.0.2";
+    const PEER: &str = "10.0.0.1";
+
+    fn at(millis: i64) -> DateTime<Local> {
+        Local.timestamp_opt(1_700_000_000, 0).unwrap() + Duration::milliseconds(millis)
+    }
+
+    fn echo(source: &str, destination: &str, seq: u16) -> ICMPPacketInfo {
+        ICMPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source: source.parse().unwrap(),
+            destination: destination.parse().unwrap(),
+            seq,
+            id: 7,
+            icmp_type: IcmpTypes::EchoRequest,
+            rtt_ms: None,
+            duplicate: false,
+            code: 0,
+            quote: None,
+            gateway: None,
+            ip: IpMeta::default(),
+            vlan: None,
+            raw_str: String::new(),
+            raw_bytes: vec![],
+            orig_len: 98,
+        }
+    }
+
+    #[test]
+    fn reply_gets_the_round_trip_time() {
+        let mut tracker = EchoTracker::new(16);
+        tracker.request(at(0), &echo(HOST, PEER, 1));
+        tracker.request(at(1000), &echo(HOST, PEER, 2));
+        assert_eq!(tracker.reply(at(1012), &echo(PEER, HOST, 2)), EchoReply::Rtt(12.0));
+        assert_eq!(tracker.reply(at(1500), &echo(PEER, HOST, 1)), EchoReply::Rtt(1500.0));
+    }
+
+    #[test]
+    fn repeated_reply_is_a_duplicate() {
+        let mut tracker = EchoTracker::new(16);
+        tracker.request(at(0), &echo(HOST, PEER, 1));
+        assert_eq!(tracker.reply(at(5), &echo(PEER, HOST, 1)), EchoReply::Rtt(5.0));
+        assert_eq!(tracker.reply(at(6), &echo(PEER, HOST, 1)), EchoReply::Duplicate);
+    }
+
+    #[test]
+    fn reply_without_request_is_unmatched() {
+        let mut tracker = EchoTracker::new(16);
+        tracker.request(at(0), &echo(HOST, PEER, 1));
+        assert_eq!(tracker.reply(at(5), &echo(PEER, HOST, 2)), EchoReply::Unmatched);
+        // -- a reply from a host that wasn't asked
+        assert_eq!(tracker.reply(at(5), &echo("10.0.0.3", HOST, 1)), EchoReply::Unmatched);
+        // -- our own request echoed back is not a reply to it
+        assert_eq!(tracker.reply(at(5), &echo(HOST, PEER, 1)), EchoReply::Unmatched);
+    }
+
+    #[test]
+    fn requests_are_pruned_after_the_timeout() {
+        let mut tracker = EchoTracker::new(16);
+        tracker.request(at(0), &echo(HOST, PEER, 1));
+        tracker.request(at(4000), &echo(HOST, PEER, 2));
+        tracker.prune(at(5000), Duration::seconds(3));
+        assert_eq!(tracker.reply(at(5000), &echo(PEER, HOST, 1)), EchoReply::Unmatched);
+        assert_eq!(tracker.reply(at(5000), &echo(PEER, HOST, 2)), EchoReply::Rtt(1000.0));
+    }
+}
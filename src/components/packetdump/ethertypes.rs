@@ -0,0 +1,64 @@
+//! Names for link-layer frame types that have no dedicated decoder.
+//!
+//! Values up to 1500 in the EtherType field are an IEEE 802.3 length, the
+//! payload is then an LLC frame (STP, CDP, ...). Larger values are EtherTypes;
+//! well-known ones get a short name, the rest fall back to their hex value.
+
+/// Largest EtherType field value that is a payload length rather than a type.
+pub const MAX_8023_LENGTH: u16 = 1500;
+
+pub fn name(ethertype: u16) -> Option<&'static str> {
+    let name = match ethertype {
+        0..=MAX_8023_LENGTH => "802.3 LLC",
+        0x0842 => "WoL",
+        0x22f0 => "AVTP",
+        0x22f3 => "TRILL",
+        0x6002 => "DEC MOP",
+        0x8035 => "RARP",
+        0x809b => "AppleTalk",
+        0x80f3 => "AARP",
+        0x8100 => "VLAN",
+        0x8137 => "IPX",
+        // -- decoded, only leftovers like IPv6 fragments end up here
+        0x86dd => "IPv6",
+        0x8808 => "Ethernet flow control",
+        0x8809 => "Slow protocols (LACP)",
+        0x8847 => "MPLS",
+        0x8848 => "MPLS multicast",
+        0x8863 => "PPPoE discovery",
+        0x8864 => "PPPoE session",
+        0x886d => "Intel ANS",
+        0x887b => "HomePlug",
+        0x888e => "EAPOL",
+        0x8892 => "PROFINET",
+        0x889a => "HyperSCSI",
+        0x88a2 => "AoE",
+        0x88a4 => "EtherCAT",
+        0x88a8 => "Q-in-Q",
+        0x88b8 => "GOOSE",
+        0x88cc => "LLDP",
+        0x88e1 => "HomePlug AV",
+        0x88e3 => "MRP",
+        0x88e5 => "MACsec",
+        0x88e7 => "PBB",
+        0x88f7 => "PTP",
+        0x88fb => "PRP",
+        0x8902 => "CFM",
+        0x8906 => "FCoE",
+        0x8914 => "FIP",
+        0x8915 => "RoCE",
+        0x892f => "HSR",
+        0x9000 => "Loopback",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Name of the frame type with its hex value, e.g. `LLDP (0x88cc)`.
+pub fn format(ethertype: u16) -> String {
+    match name(ethertype) {
+        Some(name) if ethertype <= MAX_8023_LENGTH => name.to_string(),
+        Some(name) => format!("{} (0x{:04x})", name, ethertype),
+        None => format!("0x{:04x}", ethertype),
+    }
+}
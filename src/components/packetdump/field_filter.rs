@@ -0,0 +1,302 @@
+//! Packet filter tokens matched against decoded fields instead of the log text.
+//!
+//! - `src:ADDR` and `dst:ADDR`, an address or CIDR, IPv6 included
+//!   (`src:fe80::1`); ARP matches on sender and target
+//! - `port:N`, either TCP/UDP port
+//! - `proto:NAME`, a packet type such as `tcp`, `arp` or `icmp6`
+//! - `flags:LETTERS`, TCP segments with all of these flags set, `flags:R`
+//!   matches `[R]` and `[RA]`, `flags:SA` only `[SA]`
+//!
+//! Repeating a key accepts any of its values, `port:80 port:443`, different
+//! keys must all match. A leading `!` excludes, `!port:22`. Other `key:value`
+//! tokens, and anything else, are left to the substring filter.
+
+use std::net::IpAddr;
+
+use ipnetwork::IpNetwork;
+use strum::IntoEnumIterator;
+
+use super::tcp_flags;
+use crate::enums::{PacketTypeEnum, PacketsInfoTypesEnum};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Field {
+    Source(IpNetwork),
+    Destination(IpNetwork),
+    Port(u16),
+    Proto(PacketTypeEnum),
+    Flags(u8),
+}
+
+impl Field {
+    /// `None` for a key this module doesn't know, `Some(Err)` for a known key
+    /// with a value that doesn't parse.
+    fn parse(token: &str) -> Option<Result<Self, ()>> {
+        // -- only the first colon separates, IPv6 values keep theirs
+        let (key, value) = token.split_once(':')?;
+        let field = match key {
+            "src" => value.parse().map(Field::Source).map_err(|_| ()),
+            "dst" => value.parse().map(Field::Destination).map_err(|_| ()),
+            "port" => value.parse().map(Field::Port).map_err(|_| ()),
+            "proto" => PacketTypeEnum::iter()
+                .filter(|p| *p != PacketTypeEnum::All)
+                .find(|p| p.to_string().eq_ignore_ascii_case(value))
+                .map(Field::Proto)
+                .ok_or(()),
+            "flags" => tcp_flags::parse(value).map(Field::Flags).ok_or(()),
+            _ => return None,
+        };
+        Some(field)
+    }
+
+    fn key(&self) -> u8 {
+        match self {
+            Field::Source(_) => 0,
+            Field::Destination(_) => 1,
+            Field::Port(_) => 2,
+            Field::Proto(_) => 3,
+            Field::Flags(_) => 4,
+        }
+    }
+
+    fn matches(&self, fields: &PacketFields) -> bool {
+        match self {
+            Field::Source(net) => fields.addresses.is_some_and(|(source, _)| net.contains(source)),
+            Field::Destination(net) => {
+                fields.addresses.is_some_and(|(_, destination)| net.contains(destination))
+            }
+            Field::Port(port) => fields
+                .ports
+                .is_some_and(|(source, destination)| source == *port || destination == *port),
+            Field::Proto(packet_type) => fields.packet_type == *packet_type,
+            Field::Flags(flags) => fields.tcp_flags.is_some_and(|set| set & flags == *flags),
+        }
+    }
+}
+
+/// What the field tokens are matched against, taken from the packet once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PacketFields {
+    packet_type: PacketTypeEnum,
+    /// Source and destination, the sender and target for ARP.
+    addresses: Option<(IpAddr, IpAddr)>,
+    /// Source and destination port of TCP and UDP.
+    ports: Option<(u16, u16)>,
+    /// Control flags of TCP.
+    tcp_flags: Option<u8>,
+}
+
+impl PacketFields {
+    fn of(packet: &PacketsInfoTypesEnum) -> Self {
+        let ports = match packet {
+            PacketsInfoTypesEnum::Tcp(p) => Some((p.source_port, p.destination_port)),
+            PacketsInfoTypesEnum::Udp(p) => Some((p.source_port, p.destination_port)),
+            _ => None,
+        };
+        let tcp_flags = match packet {
+            PacketsInfoTypesEnum::Tcp(p) => Some(p.flags),
+            _ => None,
+        };
+        Self {
+            packet_type: packet.packet_type(),
+            addresses: packet.addresses(),
+            ports,
+            tcp_flags,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FieldFilter {
+    included: Vec<Field>,
+    excluded: Vec<Field>,
+    invalid: bool,
+}
+
+impl FieldFilter {
+    /// Split the field tokens out of `filter`, returning the filter and the
+    /// remaining text. Tokens with a bad value are dropped and flagged.
+    pub fn parse(filter: &str) -> (Self, String) {
+        let mut field_filter = FieldFilter::default();
+        let mut rest: Vec<&str> = vec![];
+        let mut found = false;
+        for token in filter.split_whitespace() {
+            let (field, negated) = match token.strip_prefix('!') {
+                Some(field) => (field, true),
+                None => (token, false),
+            };
+            match Field::parse(field) {
+                Some(Ok(field)) if negated => field_filter.excluded.push(field),
+                Some(Ok(field)) => field_filter.included.push(field),
+                Some(Err(())) => field_filter.invalid = true,
+                None => {
+                    rest.push(token);
+                    continue;
+                }
+            }
+            found = true;
+        }
+        if !found {
+            return (field_filter, filter.to_string());
+        }
+        (field_filter, rest.join(" "))
+    }
+
+    pub fn is_invalid(&self) -> bool {
+        self.invalid
+    }
+
+    pub fn matches(&self, packet: &PacketsInfoTypesEnum) -> bool {
+        if self.included.is_empty() && self.excluded.is_empty() {
+            return true;
+        }
+        self.matches_fields(&PacketFields::of(packet))
+    }
+
+    fn matches_fields(&self, packet: &PacketFields) -> bool {
+        let included = self.included.iter().all(|field| {
+            // -- any value of the same key will do
+            self.included.iter().filter(|f| f.key() == field.key()).any(|f| f.matches(packet))
+        });
+        included && !self.excluded.iter().any(|field| field.matches(packet))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv6Addr;
+
+    use super::*;
+
+    fn packet(
+        packet_type: PacketTypeEnum,
+        source: &str,
+        destination: &str,
+        ports: Option<(u16, u16)>,
+    ) -> PacketFields {
+        PacketFields {
+            packet_type,
+            addresses: Some((source.parse().unwrap(), destination.parse().unwrap())),
+            ports,
+            tcp_flags: None,
+        }
+    }
+
+    fn tcp(source: &str, sport: u16, destination: &str, dport: u16) -> PacketFields {
+        packet(PacketTypeEnum::Tcp, source, destination, Some((sport, dport)))
+    }
+
+    fn arp() -> PacketFields {
+        packet(PacketTypeEnum::Arp, "192.168.1.1", "192.168.1.10", None)
+    }
+
+    fn filter(text: &str) -> FieldFilter {
+        let (filter, rest) = FieldFilter::parse(text);
+        assert!(rest.is_empty(), "{} left {}", text, rest);
+        filter
+    }
+
+    #[test]
+    fn source_and_destination_are_told_apart() {
+        let out = tcp("192.168.1.10", 50000, "1.1.1.1", 443);
+        let back = tcp("1.1.1.1", 443, "192.168.1.10", 50000);
+        assert!(filter("src:192.168.1.10").matches_fields(&out));
+        assert!(!filter("src:192.168.1.10").matches_fields(&back));
+        assert!(filter("dst:192.168.1.10").matches_fields(&back));
+        assert!(filter("src:192.168.1.0/24 port:443").matches_fields(&out));
+        assert!(!filter("src:192.168.1.10 port:80").matches_fields(&out));
+    }
+
+    #[test]
+    fn ipv6_addresses_keep_their_colons() {
+        let mdns = packet(PacketTypeEnum::Udp, "fe80::1", "ff02::fb", Some((5353, 5353)));
+        assert!(filter("src:fe80::1").matches_fields(&mdns));
+        assert!(filter("dst:ff02::fb port:5353").matches_fields(&mdns));
+        assert!(filter("src:fe80::/10").matches_fields(&mdns));
+        assert!(!filter("src:fe80::2").matches_fields(&mdns));
+        assert!(!filter("dst:fe80::1").matches_fields(&mdns));
+        let (field_filter, _) = FieldFilter::parse("src:2001:db8::1");
+        let address = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        assert_eq!(field_filter.included, vec![Field::Source(IpNetwork::from(address))]);
+    }
+
+    #[test]
+    fn same_key_is_any_of() {
+        let https = tcp("10.0.0.2", 50000, "10.0.0.1", 443);
+        let http = tcp("10.0.0.2", 50001, "10.0.0.1", 80);
+        let ssh = tcp("10.0.0.2", 50002, "10.0.0.1", 22);
+        let web = filter("port:80 port:443");
+        assert!(web.matches_fields(&https));
+        assert!(web.matches_fields(&http));
+        assert!(!web.matches_fields(&ssh));
+    }
+
+    #[test]
+    fn exclusion() {
+        let ssh = tcp("10.0.0.2", 50002, "10.0.0.1", 22);
+        let https = tcp("10.0.0.2", 50000, "10.0.0.1", 443);
+        assert!(!filter("!port:22").matches_fields(&ssh));
+        assert!(filter("!port:22").matches_fields(&https));
+        assert!(filter("!port:22").matches_fields(&arp()));
+        assert!(!filter("proto:tcp !dst:10.0.0.1").matches_fields(&https));
+    }
+
+    #[test]
+    fn protocols_and_arp() {
+        assert!(filter("proto:arp").matches_fields(&arp()));
+        assert!(filter("proto:ARP").matches_fields(&arp()));
+        assert!(!filter("proto:tcp").matches_fields(&arp()));
+        assert!(filter("src:192.168.1.1 dst:192.168.1.10").matches_fields(&arp()));
+        assert!(!filter("port:53").matches_fields(&arp()));
+        let other = PacketFields {
+            packet_type: PacketTypeEnum::Other,
+            addresses: None,
+            ports: None,
+            tcp_flags: None,
+        };
+        assert!(filter("proto:other").matches_fields(&other));
+        assert!(!filter("src:10.0.0.1").matches_fields(&other));
+    }
+
+    #[test]
+    fn flags_match_on_the_bits_set() {
+        let segment = |flags| PacketFields {
+            tcp_flags: Some(flags),
+            ..tcp("10.0.0.2", 50000, "10.0.0.1", 443)
+        };
+        let (rst, rst_ack, syn_ack) = (segment(0x04), segment(0x14), segment(0x12));
+        assert!(filter("flags:R").matches_fields(&rst));
+        assert!(filter("flags:R").matches_fields(&rst_ack));
+        assert!(!filter("flags:R").matches_fields(&syn_ack));
+        assert!(filter("flags:SA").matches_fields(&syn_ack));
+        assert!(!filter("flags:SA").matches_fields(&rst_ack));
+        assert!(filter("flags:R flags:S").matches_fields(&syn_ack));
+        assert!(!filter("!flags:R").matches_fields(&rst_ack));
+        assert!(!filter("flags:R").matches_fields(&arp()));
+    }
+
+    #[test]
+    fn other_tokens_are_left_for_the_text_filter() {
+        let (field_filter, rest) = FieldFilter::parse("src:10.0.0.1 DNS foo:bar fe80::1");
+        assert!(!field_filter.is_invalid());
+        assert_eq!(rest, "DNS foo:bar fe80::1");
+        let (field_filter, rest) = FieldFilter::parse("bpf: tcp port 443");
+        assert!(!field_filter.is_invalid());
+        assert_eq!(rest, "bpf: tcp port 443");
+        let (_, rest) = FieldFilter::parse("no  fields here");
+        assert_eq!(rest, "no  fields here");
+    }
+
+    #[test]
+    fn bad_values_are_flagged() {
+        let bad = [
+            "src:nas", "port:http", "port:70000", "proto:smtp", "proto:all", "dst:", "!src:x",
+            "flags:", "flags:X",
+        ];
+        for text in bad {
+            let (field_filter, rest) = FieldFilter::parse(text);
+            assert!(field_filter.is_invalid(), "{}", text);
+            assert!(rest.is_empty(), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Filters applied from the input box, browsed with Up/Down in input mode.
+//!
+//! The newest filter is last; applying the one just before it again adds no
+//! entry. Browsing starts from the text being typed, which Down past the
+//! newest entry, or cancelling, brings back. With `ui.save_filter_history` the
+//! entries are kept in `filter_history.json` in the `.netscanner` folder, a
+//! missing or corrupt file starts an empty history.
+
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+
+use crate::utils::get_netscanner_dir;
+
+const HISTORY_FILE: &str = "filter_history.json";
+/// Filters kept, the oldest go first.
+pub const FILTER_HISTORY_SIZE: usize = 50;
+
+#[derive(Debug, Default)]
+pub struct FilterHistory {
+    entries: Vec<String>,
+    // -- entry shown while browsing, and the text typed before browsing started
+    position: Option<usize>,
+    draft: String,
+}
+
+impl FilterHistory {
+    fn path() -> PathBuf {
+        PathBuf::from(get_netscanner_dir()).join(HISTORY_FILE)
+    }
+
+    pub fn load() -> Self {
+        let mut entries: Vec<String> = std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        let excess = entries.len().saturating_sub(FILTER_HISTORY_SIZE);
+        entries.drain(..excess);
+        Self {
+            entries,
+            ..Default::default()
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.entries)?)?;
+        Ok(())
+    }
+
+    /// Add an applied filter and stop browsing. Returns whether it was added.
+    pub fn push(&mut self, filter: &str) -> bool {
+        self.position = None;
+        if filter.trim().is_empty() || self.entries.last().is_some_and(|last| last == filter) {
+            return false;
+        }
+        if self.entries.len() >= FILTER_HISTORY_SIZE {
+            self.entries.remove(0);
+        }
+        self.entries.push(filter.to_string());
+        true
+    }
+
+    /// One entry back, `typed` is kept when browsing starts. `None` when
+    /// there is nothing older.
+    pub fn previous(&mut self, typed: &str) -> Option<&str> {
+        let position = match self.position {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = typed.to_string();
+                self.entries.len() - 1
+            }
+            Some(0) => return None,
+            Some(position) => position - 1,
+        };
+        self.position = Some(position);
+        self.entries.get(position).map(String::as_str)
+    }
+
+    /// One entry forward, the typed text after the newest. `None` when not
+    /// browsing.
+    pub fn next(&mut self) -> Option<&str> {
+        let position = self.position? + 1;
+        if position < self.entries.len() {
+            self.position = Some(position);
+            return self.entries.get(position).map(String::as_str);
+        }
+        self.position = None;
+        Some(&self.draft)
+    }
+
+    /// Stop browsing, returning the text typed before it started.
+    pub fn cancel(&mut self) -> Option<String> {
+        self.position.take()?;
+        Some(std::mem::take(&mut self.draft))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(entries: &[&str]) -> FilterHistory {
+        let mut history = FilterHistory::default();
+        for entry in entries {
+            history.push(entry);
+        }
+        history
+    }
+
+    #[test]
+    fn browses_back_and_returns_to_the_typed_text() {
+        let mut history = history(&["arp", "port:53", "tcp"]);
+        assert_eq!(history.previous("ud"), Some("tcp"));
+        assert_eq!(history.previous("tcp"), Some("port:53"));
+        assert_eq!(history.previous("port:53"), Some("arp"));
+        assert_eq!(history.previous("arp"), None);
+        assert_eq!(history.next(), Some("port:53"));
+        assert_eq!(history.next(), Some("tcp"));
+        assert_eq!(history.next(), Some("ud"));
+        assert_eq!(history.next(), None);
+    }
+
+    #[test]
+    fn cancel_restores_the_typed_text() {
+        let mut history = history(&["arp", "tcp"]);
+        assert_eq!(history.cancel(), None);
+        history.previous("dns");
+        history.previous("tcp");
+        assert_eq!(history.cancel(), Some("dns".to_string()));
+        assert_eq!(history.cancel(), None);
+        assert_eq!(history.previous(""), Some("tcp"));
+    }
+
+    #[test]
+    fn repeats_and_blanks_are_not_added() {
+        let mut history = history(&["arp", "arp", "tcp", "  ", "arp"]);
+        assert_eq!(history.entries, vec!["arp", "tcp", "arp"]);
+        assert!(!history.push("arp"));
+        assert_eq!(FilterHistory::default().previous("x"), None);
+    }
+
+    #[test]
+    fn oldest_entries_go_first() {
+        let mut history = FilterHistory::default();
+        for n in 0..FILTER_HISTORY_SIZE + 2 {
+            history.push(&n.to_string());
+        }
+        assert_eq!(history.entries.len(), FILTER_HISTORY_SIZE);
+        assert_eq!(history.entries[0], "2");
+    }
+}
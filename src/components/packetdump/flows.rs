@@ -0,0 +1,217 @@
+//! TCP/UDP flow aggregation for the netstat-style flows view.
+//!
+//! Packets are grouped by their 5-tuple. Both directions of a conversation map
+//! to the same flow; the endpoints of the first packet seen are kept as the
+//! displayed source and destination, and traffic is counted per direction
+//! relative to them. The table is bounded and evicts the least recently seen
+//! flow when full; flows idle for longer than a timeout can be aged out.
+
+use std::cmp::Reverse;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Local};
+use pnet::packet::tcp::TcpFlags;
+use strum::Display;
+
+use super::bounded::{BoundedMap, LastSeen};
+use crate::enums::PacketsInfoTypesEnum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum FlowProtocol {
+    #[strum(to_string = "TCP")]
+    Tcp,
+    #[strum(to_string = "UDP")]
+    Udp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FlowKey {
+    pub protocol: FlowProtocol,
+    pub a: (IpAddr, u16),
+    pub b: (IpAddr, u16),
+}
+
+impl FlowKey {
+    fn new(protocol: FlowProtocol, src: (IpAddr, u16), dst: (IpAddr, u16)) -> Self {
+        // -- order endpoints so both directions share a key
+        let (a, b) = if src <= dst { (src, dst) } else { (dst, src) };
+        Self { protocol, a, b }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum TcpFlowState {
+    #[strum(to_string = "SYN_SENT")]
+    SynSent,
+    #[strum(to_string = "SYN_RECV")]
+    SynReceived,
+    #[strum(to_string = "ESTABLISHED")]
+    Established,
+    #[strum(to_string = "CLOSING")]
+    Closing,
+    #[strum(to_string = "CLOSED")]
+    Closed,
+    #[strum(to_string = "RESET")]
+    Reset,
+}
+
+impl TcpFlowState {
+    fn next(state: Option<TcpFlowState>, flags: u8) -> TcpFlowState {
+        if flags & TcpFlags::RST != 0 {
+            return TcpFlowState::Reset;
+        }
+        if flags & TcpFlags::FIN != 0 {
+            return match state {
+                Some(TcpFlowState::Closing) | Some(TcpFlowState::Closed) => TcpFlowState::Closed,
+                _ => TcpFlowState::Closing,
+            };
+        }
+        if flags & TcpFlags::SYN != 0 {
+            if flags & TcpFlags::ACK != 0 {
+                return TcpFlowState::SynReceived;
+            }
+            return TcpFlowState::SynSent;
+        }
+        match state {
+            // -- mid-stream pickup or handshake completion
+            None | Some(TcpFlowState::SynSent) | Some(TcpFlowState::SynReceived) => {
+                TcpFlowState::Established
+            }
+            Some(s) => s,
+        }
+    }
+}
+
+/// Order of the flows view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Display)]
+pub enum FlowOrder {
+    #[default]
+    #[strum(to_string = "recent")]
+    Recent,
+    #[strum(to_string = "bytes")]
+    Bytes,
+}
+
+impl FlowOrder {
+    pub fn next(self) -> Self {
+        match self {
+            FlowOrder::Recent => FlowOrder::Bytes,
+            FlowOrder::Bytes => FlowOrder::Recent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowStats {
+    pub protocol: FlowProtocol,
+    pub source: (IpAddr, u16),
+    pub destination: (IpAddr, u16),
+    /// Packets and bytes from source to destination.
+    pub packets_out: usize,
+    pub bytes_out: usize,
+    /// Packets and bytes from destination back to source.
+    pub packets_in: usize,
+    pub bytes_in: usize,
+    pub first_seen: DateTime<Local>,
+    pub last_seen: DateTime<Local>,
+    pub tcp_state: Option<TcpFlowState>,
+}
+
+impl LastSeen for FlowStats {
+    fn last_seen(&self) -> DateTime<Local> {
+        self.last_seen
+    }
+}
+
+impl FlowStats {
+    pub fn packets(&self) -> usize {
+        self.packets_out + self.packets_in
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes_out + self.bytes_in
+    }
+}
+
+pub struct FlowTable {
+    flows: BoundedMap<FlowKey, FlowStats>,
+}
+
+impl FlowTable {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            flows: BoundedMap::new(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.flows.len()
+    }
+
+    /// Account a captured packet to its flow. Non TCP/UDP packets are ignored.
+    pub fn update(&mut self, time: DateTime<Local>, packet: &PacketsInfoTypesEnum) {
+        let (protocol, src, dst, length, flags) = match packet {
+            PacketsInfoTypesEnum::Tcp(tcp) => (
+                FlowProtocol::Tcp,
+                (tcp.source, tcp.source_port),
+                (tcp.destination, tcp.destination_port),
+                tcp.length,
+                Some(tcp.flags),
+            ),
+            PacketsInfoTypesEnum::Udp(udp) => (
+                FlowProtocol::Udp,
+                (udp.source, udp.source_port),
+                (udp.destination, udp.destination_port),
+                udp.length,
+                None,
+            ),
+            _ => return,
+        };
+
+        let key = FlowKey::new(protocol, src, dst);
+        let stats = self.flows.get_or_insert_with(key, || FlowStats {
+            protocol,
+            source: src,
+            destination: dst,
+            packets_out: 0,
+            bytes_out: 0,
+            packets_in: 0,
+            bytes_in: 0,
+            first_seen: time,
+            last_seen: time,
+            tcp_state: None,
+        });
+        if src == stats.source {
+            stats.packets_out += 1;
+            stats.bytes_out += length;
+        } else {
+            stats.packets_in += 1;
+            stats.bytes_in += length;
+        }
+        stats.last_seen = time;
+        if let Some(flags) = flags {
+            stats.tcp_state = Some(TcpFlowState::next(stats.tcp_state, flags));
+        }
+    }
+
+    /// Drop flows idle for longer than `max_idle` before the most recent
+    /// packet of any flow, so a replayed capture ages like a live one.
+    pub fn prune(&mut self, max_idle: chrono::Duration) {
+        let Some(newest) = self.flows.values().map(|stats| stats.last_seen).max() else {
+            return;
+        };
+        self.flows.retain(|_, stats| newest - stats.last_seen <= max_idle);
+    }
+
+    /// Flows by most recent activity or by bytes in both directions, most first.
+    pub fn sorted(&self, order: FlowOrder) -> Vec<&FlowStats> {
+        let mut flows: Vec<&FlowStats> = self.flows.values().collect();
+        match order {
+            FlowOrder::Recent => flows.sort_by_key(|flow| Reverse(flow.last_seen)),
+            FlowOrder::Bytes => {
+                flows.sort_by_key(|flow| (Reverse(flow.bytes()), Reverse(flow.last_seen)))
+            }
+        }
+        flows
+    }
+}
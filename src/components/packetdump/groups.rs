@@ -0,0 +1,97 @@
+//! Multicast group membership derived from observed IGMP reports and leaves.
+//!
+//! IGMPv1/v2 reports join the sender to the reported group and v2 leaves
+//! remove it. IGMPv3 records are interpreted per RFC 3376: an EXCLUDE mode
+//! record (or an INCLUDE/ALLOW record with sources) is a join, a TO_INCLUDE
+//! record with no sources is a leave. Groups without members are dropped.
+
+use std::collections::{BTreeSet, HashMap};
+use std::net::{IpAddr, Ipv4Addr};
+
+use chrono::{DateTime, Local};
+
+use crate::enums::{IGMPPacketInfo, IgmpType};
+
+const MODE_IS_INCLUDE: u8 = 1;
+const MODE_IS_EXCLUDE: u8 = 2;
+const CHANGE_TO_INCLUDE: u8 = 3;
+const CHANGE_TO_EXCLUDE: u8 = 4;
+const ALLOW_NEW_SOURCES: u8 = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupMembership {
+    pub group: Ipv4Addr,
+    pub members: BTreeSet<IpAddr>,
+    pub first_seen: DateTime<Local>,
+    pub last_seen: DateTime<Local>,
+}
+
+#[derive(Default)]
+pub struct GroupTable {
+    groups: HashMap<Ipv4Addr, GroupMembership>,
+}
+
+impl GroupTable {
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn update(&mut self, time: DateTime<Local>, packet: &IGMPPacketInfo) {
+        match packet.igmp_type {
+            IgmpType::V1MembershipReport | IgmpType::V2MembershipReport => {
+                if let Some(group) = packet.group {
+                    self.join(time, group, packet.source);
+                }
+            }
+            IgmpType::LeaveGroup => {
+                if let Some(group) = packet.group {
+                    self.leave(group, packet.source);
+                }
+            }
+            IgmpType::V3MembershipReport => {
+                for record in &packet.records {
+                    match record.record_type {
+                        MODE_IS_EXCLUDE | CHANGE_TO_EXCLUDE => {
+                            self.join(time, record.group, packet.source)
+                        }
+                        MODE_IS_INCLUDE | ALLOW_NEW_SOURCES if record.num_sources > 0 => {
+                            self.join(time, record.group, packet.source)
+                        }
+                        CHANGE_TO_INCLUDE if record.num_sources == 0 => {
+                            self.leave(record.group, packet.source)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            IgmpType::MembershipQuery | IgmpType::Unknown(_) => {}
+        }
+    }
+
+    fn join(&mut self, time: DateTime<Local>, group: Ipv4Addr, member: IpAddr) {
+        let membership = self.groups.entry(group).or_insert_with(|| GroupMembership {
+            group,
+            members: BTreeSet::new(),
+            first_seen: time,
+            last_seen: time,
+        });
+        membership.members.insert(member);
+        membership.last_seen = time;
+    }
+
+    fn leave(&mut self, group: Ipv4Addr, member: IpAddr) {
+        if let Some(membership) = self.groups.get_mut(&group) {
+            membership.members.remove(&member);
+            if membership.members.is_empty() {
+                self.groups.remove(&group);
+            }
+        }
+    }
+
+    /// Groups ordered by address.
+    pub fn sorted(&self) -> Vec<&GroupMembership> {
+        let mut groups: Vec<&GroupMembership> = self.groups.values().collect();
+        groups.sort_by_key(|g| g.group);
+        groups
+    }
+}
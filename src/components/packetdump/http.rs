@@ -0,0 +1,62 @@
+//! Cleartext HTTP request/status line extraction from TCP payloads.
+//!
+//! Only the first segment of a message is inspected: if it starts with a known
+//! method or `HTTP/`, the first line and the `Host` header (when present in the
+//! same segment) are extracted. Request lines are shortened to method and path.
+//! A start line that does not end within the segment yields nothing.
+
+use crate::enums::HttpInfo;
+
+const HTTP_PREFIXES: &[&[u8]] = &[
+    b"GET ",
+    b"POST ",
+    b"PUT ",
+    b"DELETE ",
+    b"HEAD ",
+    b"OPTIONS ",
+    b"PATCH ",
+    b"CONNECT ",
+    b"TRACE ",
+    b"HTTP/",
+];
+
+// Guard against binary junk or huge URLs blowing up the row width
+const MAX_LINE_LEN: usize = 256;
+
+// Control characters from binary payloads would garble the terminal
+fn truncate(line: &[u8]) -> String {
+    let line = &line[..line.len().min(MAX_LINE_LEN)];
+    String::from_utf8_lossy(line)
+        .trim_end_matches('\r')
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect()
+}
+
+pub fn parse(payload: &[u8]) -> Option<HttpInfo> {
+    if !HTTP_PREFIXES.iter().any(|prefix| payload.starts_with(prefix)) {
+        return None;
+    }
+
+    // -- segmented start line, the rest is in a later packet
+    let end = payload.iter().position(|b| *b == b'\n')?;
+    let mut start_line = truncate(&payload[..end]);
+    if !payload.starts_with(b"HTTP/") {
+        if let Some((request, _version)) = start_line.rsplit_once(" HTTP/") {
+            start_line = request.to_string();
+        }
+    }
+
+    let lines = payload[end + 1..].split(|b| *b == b'\n');
+
+    let host = lines
+        .map(truncate)
+        .take_while(|line| !line.is_empty())
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("host")
+                .then(|| value.trim().to_string())
+        });
+
+    Some(HttpInfo { start_line, host })
+}
@@ -0,0 +1,108 @@
+//! ICMP error and control messages beyond echo.
+//!
+//! Destination unreachable, time exceeded, parameter problem and redirect
+//! messages quote the IP header and first eight bytes of the packet that
+//! caused them; the original destination (and port, for TCP and UDP) is read
+//! from that quote. A quote cut short yields no destination, the message type
+//! is still shown.
+
+use std::net::Ipv4Addr;
+
+use pnet::packet::icmp::{IcmpType, IcmpTypes};
+
+use crate::enums::IcmpQuote;
+
+const ICMP_HEADER_LEN: usize = 8;
+const PROTO_TCP: u8 = 6;
+const PROTO_UDP: u8 = 17;
+
+fn unreachable_str(code: u8) -> &'static str {
+    match code {
+        0 => "net",
+        1 => "host",
+        2 => "protocol",
+        3 => "port",
+        4 => "fragmentation needed",
+        5 => "source route failed",
+        6 => "net unknown",
+        7 => "host unknown",
+        9 | 10 => "administratively prohibited",
+        11 => "net for TOS",
+        12 => "host for TOS",
+        13 => "communication prohibited",
+        _ => "other",
+    }
+}
+
+fn redirect_str(code: u8) -> &'static str {
+    match code {
+        0 => "net",
+        1 => "host",
+        2 => "TOS and net",
+        3 => "TOS and host",
+        _ => "other",
+    }
+}
+
+/// Readable type and code, e.g. `dest unreachable (port)` or `time exceeded in
+/// transit`.
+pub fn type_str(icmp_type: IcmpType, code: u8) -> String {
+    match icmp_type {
+        IcmpTypes::EchoReply => "echo reply".to_string(),
+        IcmpTypes::EchoRequest => "echo request".to_string(),
+        IcmpTypes::DestinationUnreachable => {
+            format!("dest unreachable ({})", unreachable_str(code))
+        }
+        IcmpTypes::SourceQuench => "source quench".to_string(),
+        IcmpTypes::RedirectMessage => format!("redirect ({})", redirect_str(code)),
+        IcmpTypes::RouterAdvertisement => "router advertisement".to_string(),
+        IcmpTypes::RouterSolicitation => "router solicitation".to_string(),
+        IcmpTypes::TimeExceeded if code == 1 => "time exceeded in reassembly".to_string(),
+        IcmpTypes::TimeExceeded => "time exceeded in transit".to_string(),
+        IcmpTypes::ParameterProblem => "parameter problem".to_string(),
+        IcmpTypes::Timestamp => "timestamp request".to_string(),
+        IcmpTypes::TimestampReply => "timestamp reply".to_string(),
+        IcmpTypes::AddressMaskRequest => "address mask request".to_string(),
+        IcmpTypes::AddressMaskReply => "address mask reply".to_string(),
+        other => format!("type {} code {}", other.0, code),
+    }
+}
+
+/// Whether the message quotes the packet it is about.
+pub fn is_error(icmp_type: IcmpType) -> bool {
+    matches!(
+        icmp_type,
+        IcmpTypes::DestinationUnreachable
+            | IcmpTypes::SourceQuench
+            | IcmpTypes::RedirectMessage
+            | IcmpTypes::TimeExceeded
+            | IcmpTypes::ParameterProblem
+    )
+}
+
+/// Destination of the packet quoted by an error message.
+pub fn quote(message: &[u8]) -> Option<IcmpQuote> {
+    let ip = message.get(ICMP_HEADER_LEN..)?;
+    if ip.first()? >> 4 != 4 {
+        return None;
+    }
+    let header_len = (ip[0] & 0x0f) as usize * 4;
+    let protocol = *ip.get(9)?;
+    let destination = Ipv4Addr::from(<[u8; 4]>::try_from(ip.get(16..20)?).ok()?);
+    let port = match protocol {
+        PROTO_TCP | PROTO_UDP => ip
+            .get(header_len + 2..header_len + 4)
+            .map(|port| u16::from_be_bytes([port[0], port[1]])),
+        _ => None,
+    };
+    Some(IcmpQuote {
+        destination,
+        protocol,
+        port,
+    })
+}
+
+/// Gateway a redirect points to.
+pub fn redirect_gateway(message: &[u8]) -> Option<Ipv4Addr> {
+    Some(Ipv4Addr::from(<[u8; 4]>::try_from(message.get(4..8)?).ok()?))
+}
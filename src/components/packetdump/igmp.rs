@@ -0,0 +1,131 @@
+//! IGMP message parsing.
+//!
+//! pnet has no IGMP packet type, so membership queries and reports are decoded
+//! by hand here. All lengths are checked before indexing; truncated messages
+//! yield `None` and IGMPv3 group records are parsed until the data runs out.
+
+use std::net::Ipv4Addr;
+
+use crate::enums::{IgmpGroupRecord, IgmpType};
+
+const IGMP_HEADER_LEN: usize = 8;
+const IGMP_V3_RECORD_HEADER_LEN: usize = 8;
+
+pub struct IgmpMessage {
+    pub igmp_type: IgmpType,
+    pub group: Option<Ipv4Addr>,
+    pub records: Vec<IgmpGroupRecord>,
+}
+
+fn ipv4_at(data: &[u8], offset: usize) -> Option<Ipv4Addr> {
+    let b = data.get(offset..offset + 4)?;
+    Some(Ipv4Addr::new(b[0], b[1], b[2], b[3]))
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    let b = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([b[0], b[1]]))
+}
+
+pub fn parse(data: &[u8]) -> Option<IgmpMessage> {
+    if data.len() < IGMP_HEADER_LEN {
+        return None;
+    }
+    let igmp_type = IgmpType::from(data[0]);
+
+    if igmp_type != IgmpType::V3MembershipReport {
+        let group = ipv4_at(data, 4)?;
+        return Some(IgmpMessage {
+            igmp_type,
+            group: (!group.is_unspecified()).then_some(group),
+            records: vec![],
+        });
+    }
+
+    // -- IGMPv3 report: a list of group records follows the header
+    let num_records = u16_at(data, 6)?;
+    let mut records = vec![];
+    let mut offset = IGMP_HEADER_LEN;
+    for _ in 0..num_records {
+        if data.len() < offset + IGMP_V3_RECORD_HEADER_LEN {
+            break;
+        }
+        let record_type = data[offset];
+        let aux_len = data[offset + 1] as usize * 4;
+        let num_sources = u16_at(data, offset + 2)?;
+        let group = ipv4_at(data, offset + 4)?;
+        records.push(IgmpGroupRecord {
+            record_type,
+            group,
+            num_sources,
+        });
+        offset += IGMP_V3_RECORD_HEADER_LEN + num_sources as usize * 4 + aux_len;
+    }
+
+    Some(IgmpMessage {
+        igmp_type,
+        group: None,
+        records,
+    })
+}
+
+// Only the first few IGMPv3 group records are spelled out in a row
+const MAX_SUMMARY_RECORDS: usize = 3;
+
+pub fn type_str(igmp_type: IgmpType) -> String {
+    match igmp_type {
+        IgmpType::MembershipQuery => String::from("membership query"),
+        IgmpType::V1MembershipReport => String::from("v1 report"),
+        IgmpType::V2MembershipReport => String::from("v2 report"),
+        IgmpType::V3MembershipReport => String::from("v3 report"),
+        IgmpType::LeaveGroup => String::from("leave group"),
+        IgmpType::Unknown(t) => format!("type {:#04x}", t),
+    }
+}
+
+pub fn record_str(record: &IgmpGroupRecord) -> String {
+    let kind = match (record.record_type, record.num_sources) {
+        // -- CHANGE_TO_EXCLUDE with no sources is a join
+        (4, 0) => "join",
+        // -- CHANGE_TO_INCLUDE with no sources is a leave
+        (3, 0) => "leave",
+        (1, _) => "include",
+        (2, _) => "exclude",
+        (3, _) => "to-include",
+        (4, _) => "to-exclude",
+        (5, _) => "allow",
+        (6, _) => "block",
+        _ => "unknown",
+    };
+    if record.num_sources > 0 {
+        format!("{} {} ({} sources)", kind, record.group, record.num_sources)
+    } else {
+        format!("{} {}", kind, record.group)
+    }
+}
+
+/// One line description of the message, summarizing IGMPv3 group records.
+pub fn summary(message: &IgmpMessage) -> String {
+    let mut summary = type_str(message.igmp_type);
+    if let Some(group) = message.group {
+        summary.push_str(&format!(" group {}", group));
+    } else if message.igmp_type == IgmpType::MembershipQuery {
+        summary.push_str(" (general)");
+    }
+    if !message.records.is_empty() {
+        let shown: Vec<String> = message
+            .records
+            .iter()
+            .take(MAX_SUMMARY_RECORDS)
+            .map(record_str)
+            .collect();
+        summary.push_str(&format!(": {}", shown.join(", ")));
+        if message.records.len() > MAX_SUMMARY_RECORDS {
+            summary.push_str(&format!(
+                ", +{} more",
+                message.records.len() - MAX_SUMMARY_RECORDS
+            ));
+        }
+    }
+    summary
+}
@@ -0,0 +1,206 @@
+//! Duplicate IPv4 address detection from ARP senders.
+//!
+//! Each address keeps the MACs that claimed it within the detection window;
+//! a second MAC makes a conflict. A conflict between the same address and pair
+//! of MACs is reported once and then only refreshed, so a device flapping
+//! between two MACs raises a single alert until it has been quiet for a whole
+//! window. ARP probes (unspecified sender) claim nothing.
+//!
+//! A flood of random sender MACs for one address would otherwise add a
+//! conflict per pair of them, so the MACs kept per address and the conflicts
+//! kept in all are bounded, the least recently seen going first.
+
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+
+use chrono::{DateTime, Duration, Local};
+use pnet::util::MacAddr;
+
+use super::bounded::{BoundedMap, LastSeen};
+
+/// Addresses tracked at most, claims of new ones are ignored past this.
+const MAX_ADDRESSES: usize = 4096;
+/// MACs kept per address within the window.
+const MAX_CLAIMS: usize = 8;
+/// Conflicts kept, sticky ones included.
+const MAX_CONFLICTS: usize = 1024;
+
+// -- address and its two claimants, lower MAC first so both orders match
+type ConflictKey = (Ipv4Addr, MacAddr, MacAddr);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub ip: Ipv4Addr,
+    pub macs: (MacAddr, MacAddr),
+    pub first_seen: DateTime<Local>,
+    pub last_seen: DateTime<Local>,
+}
+
+impl LastSeen for Conflict {
+    fn last_seen(&self) -> DateTime<Local> {
+        self.last_seen
+    }
+}
+
+pub struct ConflictTracker {
+    claims: HashMap<Ipv4Addr, Vec<(MacAddr, DateTime<Local>)>>,
+    conflicts: BoundedMap<ConflictKey, Conflict>,
+}
+
+impl Default for ConflictTracker {
+    fn default() -> Self {
+        Self {
+            claims: HashMap::new(),
+            conflicts: BoundedMap::new(MAX_CONFLICTS),
+        }
+    }
+}
+
+impl ConflictTracker {
+    /// Record that `mac` claimed `ip`, returning the conflicts it starts.
+    pub fn claim(
+        &mut self,
+        time: DateTime<Local>,
+        ip: Ipv4Addr,
+        mac: MacAddr,
+        window: Duration,
+    ) -> Vec<Conflict> {
+        if ip.is_unspecified() || mac.is_zero() || mac.is_broadcast() {
+            return vec![];
+        }
+        if !self.claims.contains_key(&ip) && self.claims.len() >= MAX_ADDRESSES {
+            return vec![];
+        }
+        let claims = self.claims.entry(ip).or_default();
+        claims.retain(|(_, seen)| time - *seen <= window);
+
+        let mut started = vec![];
+        for (other, _) in claims.iter().filter(|(other, _)| *other != mac) {
+            let macs = if mac < *other { (mac, *other) } else { (*other, mac) };
+            match self.conflicts.get_mut(&(ip, macs.0, macs.1)) {
+                Some(conflict) => conflict.last_seen = time,
+                None => {
+                    let conflict = Conflict {
+                        ip,
+                        macs,
+                        first_seen: time,
+                        last_seen: time,
+                    };
+                    self.conflicts.insert((ip, macs.0, macs.1), conflict.clone());
+                    started.push(conflict);
+                }
+            }
+        }
+        match claims.iter_mut().find(|(claimant, _)| *claimant == mac) {
+            Some(claim) => claim.1 = time,
+            None => {
+                if claims.len() >= MAX_CLAIMS {
+                    if let Some(oldest) =
+                        claims.iter().enumerate().min_by_key(|(_, (_, seen))| *seen).map(|(i, _)| i)
+                    {
+                        claims.remove(oldest);
+                    }
+                }
+                claims.push((mac, time));
+            }
+        }
+        started
+    }
+
+    /// Conflicts newest first.
+    pub fn conflicts(&self) -> Vec<&Conflict> {
+        let mut conflicts: Vec<&Conflict> = self.conflicts.values().collect();
+        conflicts.sort_by_key(|conflict| std::cmp::Reverse(conflict.last_seen));
+        conflicts
+    }
+
+    /// Forget claims older than the window, and conflicts too unless `sticky`.
+    pub fn prune(&mut self, now: DateTime<Local>, window: Duration, sticky: bool) {
+        self.claims.retain(|_, claims| {
+            claims.retain(|(_, seen)| now - *seen <= window);
+            !claims.is_empty()
+        });
+        if !sticky {
+            self.conflicts.retain(|_, conflict| now - conflict.last_seen <= window);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IP: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 10);
+
+    fn mac(n: u8) -> MacAddr {
+        MacAddr::new(0x02, 0, 0, 0, 0, n)
+    }
+
+    fn at(secs: i64) -> DateTime<Local> {
+        DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap().with_timezone(&Local)
+    }
+
+    #[test]
+    fn repeated_claims_report_once() {
+        let mut tracker = ConflictTracker::default();
+        let window = Duration::seconds(60);
+        assert!(tracker.claim(at(0), IP, mac(1), window).is_empty());
+        assert!(tracker.claim(at(1), IP, mac(1), window).is_empty());
+        assert_eq!(tracker.claim(at(2), IP, mac(2), window).len(), 1);
+        // -- flapping back and forth only refreshes the conflict
+        assert!(tracker.claim(at(3), IP, mac(1), window).is_empty());
+        assert!(tracker.claim(at(4), IP, mac(2), window).is_empty());
+        let conflicts = tracker.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].macs, (mac(1), mac(2)));
+        assert_eq!((conflicts[0].first_seen, conflicts[0].last_seen), (at(2), at(4)));
+        // -- probes and broadcast senders claim nothing
+        assert!(tracker.claim(at(5), Ipv4Addr::UNSPECIFIED, mac(3), window).is_empty());
+        assert!(tracker.claim(at(5), IP, MacAddr::broadcast(), window).is_empty());
+    }
+
+    #[test]
+    fn conflicts_expire_after_the_window_unless_sticky() {
+        let window = Duration::seconds(60);
+        for sticky in [false, true] {
+            let mut tracker = ConflictTracker::default();
+            tracker.claim(at(0), IP, mac(1), window);
+            tracker.claim(at(10), IP, mac(2), window);
+            tracker.prune(at(70), window, sticky);
+            assert_eq!(tracker.conflicts().len(), 1);
+            tracker.prune(at(71), window, sticky);
+            assert_eq!(tracker.conflicts().len(), usize::from(sticky));
+            // -- the claims are gone either way, the old MAC starts no conflict
+            assert!(tracker.claim(at(72), IP, mac(3), window).is_empty());
+        }
+    }
+
+    #[test]
+    fn floods_are_bounded() {
+        let mut tracker = ConflictTracker::default();
+        let window = Duration::seconds(600);
+        for n in 0..=u8::MAX {
+            tracker.claim(at(n as i64), IP, mac(n), window);
+        }
+        assert_eq!(tracker.claims[&IP].len(), MAX_CLAIMS);
+        // -- the newest MACs are kept
+        assert!(tracker.claims[&IP].iter().any(|(claimant, _)| *claimant == mac(u8::MAX)));
+        assert!(!tracker.claims[&IP].iter().any(|(claimant, _)| *claimant == mac(0)));
+        assert!(tracker.conflicts.len() <= MAX_CONFLICTS);
+        assert_eq!(tracker.conflicts()[0].last_seen, at(u8::MAX as i64));
+    }
+
+    #[test]
+    fn sticky_conflicts_are_bounded() {
+        let mut tracker = ConflictTracker::default();
+        let window = Duration::seconds(1);
+        for n in 0..MAX_CONFLICTS as u32 + 10 {
+            let ip = Ipv4Addr::from(0x0a00_0000 + n);
+            tracker.claim(at(2 * n as i64), ip, mac(1), window);
+            tracker.claim(at(2 * n as i64), ip, mac(2), window);
+            tracker.prune(at(2 * n as i64 + 1), window, true);
+        }
+        assert_eq!(tracker.conflicts.len(), MAX_CONFLICTS);
+        assert!(tracker.conflicts.get(&(Ipv4Addr::from(0x0a00_0000), mac(1), mac(2))).is_none());
+    }
+}
@@ -0,0 +1,150 @@
+//! IPv6 extension header chain traversal.
+//!
+//! Hop-by-Hop, Routing, Destination Options, Fragment and Authentication
+//! headers are skipped until an upper-layer protocol is reached. Later
+//! fragments carry no upper-layer header and are reported as fragments. ESP
+//! and No Next Header end the chain like any unknown protocol. Chains longer
+//! than `MAX_HEADERS` or cut short yield nothing.
+
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+
+const MAX_HEADERS: usize = 8;
+const FRAGMENT_HEADER_LEN: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fragment {
+    pub id: u32,
+    /// Offset of the fragment data in the original packet, in bytes.
+    pub offset: u16,
+    pub more: bool,
+    /// Protocol of the reassembled packet.
+    pub next: IpNextHeaderProtocol,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Upper<'a> {
+    /// Upper-layer protocol and its header and payload.
+    Protocol(IpNextHeaderProtocol, &'a [u8]),
+    /// A fragment other than the first, only the fragment header is known.
+    Fragment(Fragment),
+}
+
+pub fn walk(mut next: IpNextHeaderProtocol, mut payload: &[u8]) -> Option<Upper<'_>> {
+    for _ in 0..MAX_HEADERS {
+        let len = match next {
+            IpNextHeaderProtocols::Hopopt
+            | IpNextHeaderProtocols::Ipv6Route
+            | IpNextHeaderProtocols::Ipv6Opts => (*payload.get(1)? as usize + 1) * 8,
+            // -- AH counts 4-byte units minus two
+            IpNextHeaderProtocols::Ah => (*payload.get(1)? as usize + 2) * 4,
+            IpNextHeaderProtocols::Ipv6Frag => {
+                let header = payload.get(..FRAGMENT_HEADER_LEN)?;
+                let offset_flags = u16::from_be_bytes([header[2], header[3]]);
+                let fragment = Fragment {
+                    id: u32::from_be_bytes([header[4], header[5], header[6], header[7]]),
+                    offset: offset_flags & 0xfff8,
+                    more: offset_flags & 1 != 0,
+                    next: IpNextHeaderProtocol::new(header[0]),
+                };
+                if fragment.offset != 0 {
+                    return Some(Upper::Fragment(fragment));
+                }
+                FRAGMENT_HEADER_LEN
+            }
+            _ => return Some(Upper::Protocol(next, payload)),
+        };
+        let header = payload.get(..len)?;
+        next = IpNextHeaderProtocol::new(header[0]);
+        payload = &payload[len..];
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TCP_HEADER: [u8; 4] = [0x04, 0xd2, 0x00, 0x50];
+
+    fn options(next: u8) -> Vec<u8> {
+        vec![next, 0, 1, 4, 0, 0, 0, 0]
+    }
+
+    fn fragment(next: u8, offset: u16, more: bool) -> Vec<u8> {
+        let mut header = vec![next, 0];
+        header.extend((offset | more as u16).to_be_bytes());
+        header.extend(0x1234_5678u32.to_be_bytes());
+        header
+    }
+
+    #[test]
+    fn test_no_extension_headers() {
+        let upper = walk(IpNextHeaderProtocols::Udp, &TCP_HEADER);
+        assert_eq!(upper, Some(Upper::Protocol(IpNextHeaderProtocols::Udp, &TCP_HEADER[..])));
+    }
+
+    #[test]
+    fn test_hop_by_hop_routing_and_destination_options() {
+        let mut packet = options(43);
+        // -- routing header of 16 bytes
+        packet.extend([60, 1, 0, 0, 0, 0, 0, 0]);
+        packet.extend([0; 8]);
+        packet.extend(options(6));
+        packet.extend(TCP_HEADER);
+        let upper = walk(IpNextHeaderProtocols::Hopopt, &packet);
+        assert_eq!(upper, Some(Upper::Protocol(IpNextHeaderProtocols::Tcp, &TCP_HEADER[..])));
+    }
+
+    #[test]
+    fn test_first_fragment_reaches_upper_layer() {
+        let mut packet = fragment(58, 0, true);
+        packet.extend([128, 0]);
+        let upper = walk(IpNextHeaderProtocols::Ipv6Frag, &packet);
+        assert_eq!(upper, Some(Upper::Protocol(IpNextHeaderProtocols::Icmpv6, &[128, 0][..])));
+    }
+
+    #[test]
+    fn test_later_fragment() {
+        let mut packet = options(44);
+        packet.extend(fragment(17, 1448, false));
+        packet.extend([0xaa; 16]);
+        let upper = walk(IpNextHeaderProtocols::Hopopt, &packet);
+        assert_eq!(
+            upper,
+            Some(Upper::Fragment(Fragment {
+                id: 0x1234_5678,
+                offset: 1448,
+                more: false,
+                next: IpNextHeaderProtocols::Udp,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_authentication_header() {
+        // -- AH with a 12-byte ICV, 24 bytes in total
+        let mut packet = vec![6, 4, 0, 0];
+        packet.extend([0; 20]);
+        packet.extend(TCP_HEADER);
+        let upper = walk(IpNextHeaderProtocols::Ah, &packet);
+        assert_eq!(upper, Some(Upper::Protocol(IpNextHeaderProtocols::Tcp, &TCP_HEADER[..])));
+    }
+
+    #[test]
+    fn test_truncated_and_endless_chains() {
+        let packet = [6, 2, 0, 0, 0, 0, 0, 0];
+        assert_eq!(walk(IpNextHeaderProtocols::Ipv6Opts, &packet), None);
+        assert_eq!(walk(IpNextHeaderProtocols::Ipv6Frag, &[17, 0, 0]), None);
+
+        let looped: Vec<u8> = (0..MAX_HEADERS + 1).flat_map(|_| options(0)).collect();
+        assert_eq!(walk(IpNextHeaderProtocols::Hopopt, &looped), None);
+    }
+
+    #[test]
+    fn test_esp_ends_the_chain() {
+        let mut packet = options(50);
+        packet.extend([0xde, 0xad]);
+        let upper = walk(IpNextHeaderProtocols::Hopopt, &packet);
+        assert_eq!(upper, Some(Upper::Protocol(IpNextHeaderProtocols::Esp, &[0xde, 0xad][..])));
+    }
+}
@@ -0,0 +1,43 @@
+//! Kernel side receive counters of the capture interface.
+//!
+//! pnet keeps the capture socket to itself, so the per-handle statistics
+//! libpcap reports are out of reach. The interface counters are the next best
+//! thing: `rx_dropped` grows when the NIC or the kernel discards frames before
+//! any socket sees them. Next to the `dropped` count of our own channel this
+//! tells an overwhelmed link apart from buffers that are too small.
+
+/// Received and dropped frames, relative to the start of the capture once
+/// passed through [`KernelStats::since`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct KernelStats {
+    pub received: u64,
+    pub dropped: u64,
+}
+
+impl KernelStats {
+    /// Current counters of the interface, `None` where the platform has no
+    /// readable counters.
+    #[cfg(target_os = "linux")]
+    pub fn read(iface: &str) -> Option<Self> {
+        let counter = |name: &str| -> Option<u64> {
+            let path = format!("/sys/class/net/{}/statistics/{}", iface, name);
+            std::fs::read_to_string(path).ok()?.trim().parse().ok()
+        };
+        Some(Self {
+            received: counter("rx_packets")?,
+            dropped: counter("rx_dropped")?,
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn read(_iface: &str) -> Option<Self> {
+        None
+    }
+
+    pub fn since(self, baseline: Self) -> Self {
+        Self {
+            received: self.received.saturating_sub(baseline.received),
+            dropped: self.dropped.saturating_sub(baseline.dropped),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Framing of packets read from the Linux loopback interface.
+//!
+//! A packet socket on `lo` normally sees an Ethernet header with zeroed MACs,
+//! but depending on how the socket and the device are set up the frame may
+//! instead start with a cooked (SLL) header or be the bare IP packet. The
+//! EtherType and the offset of the IP packet are recovered from either, so
+//! the capture loop can put a fake Ethernet header in front like the macOS
+//! loopback does.
+
+use pnet::packet::ethernet::{EtherType, EtherTypes};
+
+const ETHERNET_HEADER_LEN: usize = 14;
+const SLL_HEADER_LEN: usize = 16;
+/// `ARPHRD_LOOPBACK`, the device type in a cooked header from `lo`.
+const ARPHRD_LOOPBACK: u16 = 772;
+/// Packet types a cooked header can carry, host to outgoing.
+const SLL_MAX_PACKET_TYPE: u16 = 4;
+
+/// EtherType of the IP packet in `frame` and where it starts. `None` when the
+/// frame is neither IPv4 nor IPv6 in any of the framings.
+pub fn framing(frame: &[u8]) -> Option<(EtherType, usize)> {
+    let ethertype = |at: usize| EtherType(u16::from_be_bytes([frame[at], frame[at + 1]]));
+    // -- the usual case, an Ethernet header with zeroed addresses
+    if frame.len() > ETHERNET_HEADER_LEN && frame[..12].iter().all(|b| *b == 0) {
+        let ethertype = ethertype(12);
+        if carries(ethertype, &frame[ETHERNET_HEADER_LEN..]) {
+            return Some((ethertype, ETHERNET_HEADER_LEN));
+        }
+    }
+    if frame.len() > SLL_HEADER_LEN
+        && u16::from_be_bytes([frame[0], frame[1]]) <= SLL_MAX_PACKET_TYPE
+        && u16::from_be_bytes([frame[2], frame[3]]) == ARPHRD_LOOPBACK
+    {
+        let ethertype = ethertype(14);
+        if carries(ethertype, &frame[SLL_HEADER_LEN..]) {
+            return Some((ethertype, SLL_HEADER_LEN));
+        }
+    }
+    match ip_version(frame) {
+        Some(4) => Some((EtherTypes::Ipv4, 0)),
+        Some(6) => Some((EtherTypes::Ipv6, 0)),
+        _ => None,
+    }
+}
+
+fn ip_version(packet: &[u8]) -> Option<u8> {
+    packet.first().map(|b| b >> 4).filter(|v| *v == 4 || *v == 6)
+}
+
+// -- the EtherType is IP and agrees with the version of the packet after it
+fn carries(ethertype: EtherType, packet: &[u8]) -> bool {
+    match ethertype {
+        EtherTypes::Ipv4 => ip_version(packet) == Some(4),
+        EtherTypes::Ipv6 => ip_version(packet) == Some(6),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // -- 127.0.0.1:50000 -> 127.0.0.1:8080, SYN
+    const IPV4_SYN: [u8; 40] = [
+        0x45, 0x00, 0x00, 0x28, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06, 0x00, 0x00, 127, 0, 0, 1, 127,
+        0, 0, 1, 0xc3, 0x50, 0x1f, 0x90, 0, 0, 0, 1, 0, 0, 0, 0, 0x50, 0x02, 0xff, 0xff, 0, 0, 0,
+        0,
+    ];
+
+    // -- ::1 -> ::1, the fixed header of an empty TCP segment
+    fn ipv6_packet() -> Vec<u8> {
+        let mut packet = vec![0x60, 0, 0, 0, 0, 0, 6, 64];
+        for _ in 0..2 {
+            packet.extend_from_slice(&[0; 15]);
+            packet.push(1);
+        }
+        packet
+    }
+
+    fn ethernet(ethertype: u16, packet: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0; 12];
+        frame.extend_from_slice(&ethertype.to_be_bytes());
+        frame.extend_from_slice(packet);
+        frame
+    }
+
+    fn cooked(ethertype: u16, packet: &[u8]) -> Vec<u8> {
+        // -- outgoing, from lo, no address
+        let mut frame = vec![0, 4, 0x03, 0x04, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0];
+        frame.extend_from_slice(&ethertype.to_be_bytes());
+        frame.extend_from_slice(packet);
+        frame
+    }
+
+    #[test]
+    fn ipv4_in_every_framing() {
+        assert_eq!(framing(&ethernet(0x0800, &IPV4_SYN)), Some((EtherTypes::Ipv4, 14)));
+        assert_eq!(framing(&cooked(0x0800, &IPV4_SYN)), Some((EtherTypes::Ipv4, 16)));
+        assert_eq!(framing(&IPV4_SYN), Some((EtherTypes::Ipv4, 0)));
+    }
+
+    #[test]
+    fn ipv6_in_every_framing() {
+        let packet = ipv6_packet();
+        assert_eq!(framing(&ethernet(0x86dd, &packet)), Some((EtherTypes::Ipv6, 14)));
+        assert_eq!(framing(&cooked(0x86dd, &packet)), Some((EtherTypes::Ipv6, 16)));
+        assert_eq!(framing(&packet), Some((EtherTypes::Ipv6, 0)));
+    }
+
+    #[test]
+    fn non_ip_frames_are_rejected() {
+        // -- ARP has no IP packet to decode
+        assert_eq!(framing(&ethernet(0x0806, &[0x00, 0x01, 0x08, 0x00])), None);
+        assert_eq!(framing(&[]), None);
+        assert_eq!(framing(&[0x20, 0x00]), None);
+        // -- an EtherType that disagrees with the packet is not taken at its word
+        assert_eq!(framing(&ethernet(0x86dd, &IPV4_SYN)), None);
+    }
+}
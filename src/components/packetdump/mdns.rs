@@ -0,0 +1,112 @@
+//! Multicast DNS (UDP 5353) queries and service announcements.
+//!
+//! Messages go through the DNS decoder's name and record readers, so
+//! compressed names resolve the same way. Announcements usually carry no
+//! question and put the host addresses in the additional section, so every
+//! section is read, up to a fixed number of records. A record cut short ends
+//! the message, keeping what was read before it.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use super::dns::{read_name, read_record, u16_at};
+use crate::enums::MdnsInfo;
+
+pub const MDNS_PORT: u16 = 5353;
+
+const HEADER_LEN: usize = 12;
+const MAX_RECORDS: usize = 32;
+const MAX_QUERIES: usize = 16;
+
+const TYPE_A: u16 = 1;
+const TYPE_PTR: u16 = 12;
+const TYPE_AAAA: u16 = 28;
+const TYPE_SRV: u16 = 33;
+
+fn push_unique<T: PartialEq>(list: &mut Vec<T>, item: T) {
+    if !list.contains(&item) {
+        list.push(item);
+    }
+}
+
+pub fn parse(payload: &[u8]) -> Option<MdnsInfo> {
+    let flags = u16_at(payload, 2)?;
+    // -- only standard queries and responses are sent over mDNS
+    if (flags >> 11) & 0x0f != 0 {
+        return None;
+    }
+    let questions = u16_at(payload, 4)?;
+    let records = [6, 8, 10]
+        .iter()
+        .map(|offset| u16_at(payload, *offset).map(usize::from))
+        .sum::<Option<usize>>()?;
+
+    let mut info = MdnsInfo {
+        response: flags & 0x8000 != 0,
+        queries: vec![],
+        services: vec![],
+        hosts: vec![],
+    };
+    let mut pos = HEADER_LEN;
+    for _ in 0..questions {
+        let (name, after) = read_name(payload, pos)?;
+        if info.queries.len() < MAX_QUERIES {
+            push_unique(&mut info.queries, name);
+        }
+        pos = after + 4;
+    }
+    for _ in 0..records.min(MAX_RECORDS) {
+        let Some((record, next)) = read_record(payload, pos) else {
+            break;
+        };
+        match record.rtype {
+            TYPE_A => {
+                if let Ok(octets) = <[u8; 4]>::try_from(record.rdata) {
+                    let ip = IpAddr::V4(Ipv4Addr::from(octets));
+                    push_unique(&mut info.hosts, (ip, record.name));
+                }
+            }
+            TYPE_AAAA => {
+                if let Ok(octets) = <[u8; 16]>::try_from(record.rdata) {
+                    let ip = IpAddr::V6(Ipv6Addr::from(octets));
+                    push_unique(&mut info.hosts, (ip, record.name));
+                }
+            }
+            // -- reverse mappings name a host, not a service
+            TYPE_PTR if !record.name.ends_with(".arpa") => {
+                if let Some((instance, _)) = read_name(payload, record.offset) {
+                    push_unique(&mut info.services, instance);
+                }
+            }
+            TYPE_SRV => push_unique(&mut info.services, record.name),
+            _ => {}
+        }
+        pos = next;
+    }
+
+    if info.queries.is_empty() && info.services.is_empty() && info.hosts.is_empty() {
+        return None;
+    }
+    Some(info)
+}
+
+/// One line for the packet log, e.g. `mDNS announce Living
+/// Room._airplay._tcp.local; tv.local 192.168.1.20`, also what the text
+/// filter matches against.
+pub fn summary(mdns: &MdnsInfo) -> String {
+    if !mdns.response {
+        return format!("mDNS query {}", mdns.queries.join(", "));
+    }
+    let mut parts = vec![];
+    if !mdns.services.is_empty() {
+        parts.push(mdns.services.join(", "));
+    }
+    if !mdns.hosts.is_empty() {
+        let hosts: Vec<String> = mdns
+            .hosts
+            .iter()
+            .map(|(ip, name)| format!("{} {}", name, ip))
+            .collect();
+        parts.push(hosts.join(", "));
+    }
+    format!("mDNS announce {}", parts.join("; "))
+}
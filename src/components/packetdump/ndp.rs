@@ -0,0 +1,168 @@
+//! ICMPv6 Neighbor Discovery address extraction.
+//!
+//! Neighbor Solicitation/Advertisement and Router Advertisement messages carry
+//! the link-layer address of one side, which makes them the IPv6 counterpart
+//! of ARP:
+//!
+//! - **Solicitation**: the sender's address from the source link-layer option,
+//!   skipped for duplicate address detection (unspecified source)
+//! - **Advertisement**: the target's address from the target link-layer option
+//! - **Router Advertisement**: the router's address from the source option,
+//!   along with the advertised prefixes and the router lifetime
+
+use std::net::{IpAddr, Ipv6Addr};
+
+use pnet::packet::icmpv6::ndp::{
+    NdpOption, NdpOptionType, NdpOptionTypes, NeighborAdvertPacket, NeighborSolicitPacket,
+    RouterAdvertPacket,
+};
+use pnet::packet::icmpv6::{Icmpv6Packet, Icmpv6Types};
+use pnet::packet::Packet;
+use pnet::util::MacAddr;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NdpAddresses {
+    /// Address being solicited or advertised.
+    pub target: Option<Ipv6Addr>,
+    /// Link-layer address carried in the options.
+    pub link_addr: Option<MacAddr>,
+    /// IPv6 to MAC binding learned from the message.
+    pub neighbor: Option<(Ipv6Addr, MacAddr)>,
+    /// Prefixes and their length from Prefix Information options.
+    pub prefixes: Vec<(Ipv6Addr, u8)>,
+    /// Seconds the sender stays a default router, 0 when it is none.
+    pub router_lifetime: Option<u16>,
+}
+
+// -- Prefix Information: length, flags, two lifetimes, reserved, then the prefix
+fn prefixes(options: &[NdpOption]) -> Vec<(Ipv6Addr, u8)> {
+    options
+        .iter()
+        .filter(|o| o.option_type == NdpOptionTypes::PrefixInformation)
+        .filter_map(|o| {
+            let prefix = <[u8; 16]>::try_from(o.data.get(14..30)?).ok()?;
+            Some((Ipv6Addr::from(prefix), *o.data.first()?))
+        })
+        .collect()
+}
+
+fn link_addr(options: &[NdpOption], option_type: NdpOptionType) -> Option<MacAddr> {
+    let option = options.iter().find(|o| o.option_type == option_type)?;
+    let b = option.data.get(..6)?;
+    let mac = MacAddr::new(b[0], b[1], b[2], b[3], b[4], b[5]);
+    (!mac.is_zero()).then_some(mac)
+}
+
+pub fn parse(source: IpAddr, packet: &Icmpv6Packet) -> NdpAddresses {
+    let IpAddr::V6(source) = source else {
+        return NdpAddresses::default();
+    };
+    match packet.get_icmpv6_type() {
+        Icmpv6Types::NeighborSolicit => {
+            let Some(ns) = NeighborSolicitPacket::new(packet.packet()) else {
+                return NdpAddresses::default();
+            };
+            let link_addr = link_addr(&ns.get_options(), NdpOptionTypes::SourceLLAddr);
+            NdpAddresses {
+                target: Some(ns.get_target_addr()),
+                link_addr,
+                neighbor: link_addr
+                    .filter(|_| !source.is_unspecified())
+                    .map(|mac| (source, mac)),
+                ..Default::default()
+            }
+        }
+        Icmpv6Types::NeighborAdvert => {
+            let Some(na) = NeighborAdvertPacket::new(packet.packet()) else {
+                return NdpAddresses::default();
+            };
+            let target = na.get_target_addr();
+            let link_addr = link_addr(&na.get_options(), NdpOptionTypes::TargetLLAddr);
+            NdpAddresses {
+                target: Some(target),
+                link_addr,
+                neighbor: link_addr.map(|mac| (target, mac)),
+                ..Default::default()
+            }
+        }
+        Icmpv6Types::RouterAdvert => {
+            let Some(ra) = RouterAdvertPacket::new(packet.packet()) else {
+                return NdpAddresses::default();
+            };
+            let options = ra.get_options();
+            let link_addr = link_addr(&options, NdpOptionTypes::SourceLLAddr);
+            NdpAddresses {
+                target: None,
+                link_addr,
+                neighbor: link_addr.map(|mac| (source, mac)),
+                prefixes: prefixes(&options),
+                router_lifetime: Some(ra.get_lifetime()),
+            }
+        }
+        _ => NdpAddresses::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: Ipv6Addr = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+    const TARGET: Ipv6Addr = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 2);
+    const MAC: MacAddr = MacAddr(0x02, 0x11, 0x22, 0x33, 0x44, 0x55);
+
+    // -- type, code, checksum, reserved or flags, target, then the options
+    fn message(icmpv6_type: u8, option: Option<u8>) -> Vec<u8> {
+        let mut bytes = vec![icmpv6_type, 0, 0, 0, 0, 0, 0, 0];
+        bytes.extend_from_slice(&TARGET.octets());
+        if let Some(option_type) = option {
+            bytes.extend_from_slice(&[option_type, 1, 0x02, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        }
+        bytes
+    }
+
+    fn parse_bytes(source: Ipv6Addr, bytes: &[u8]) -> NdpAddresses {
+        parse(IpAddr::V6(source), &Icmpv6Packet::new(bytes).unwrap())
+    }
+
+    #[test]
+    fn solicitation() {
+        let ndp = parse_bytes(SOURCE, &message(135, Some(1)));
+        assert_eq!(ndp.target, Some(TARGET));
+        assert_eq!(ndp.link_addr, Some(MAC));
+        assert_eq!(ndp.neighbor, Some((SOURCE, MAC)));
+
+        let ndp = parse_bytes(SOURCE, &message(135, None));
+        assert_eq!(ndp.target, Some(TARGET));
+        assert_eq!(ndp.neighbor, None);
+
+        // -- duplicate address detection has no neighbor to learn
+        let ndp = parse_bytes(Ipv6Addr::UNSPECIFIED, &message(135, Some(1)));
+        assert_eq!(ndp.link_addr, Some(MAC));
+        assert_eq!(ndp.neighbor, None);
+    }
+
+    #[test]
+    fn advertisement() {
+        let ndp = parse_bytes(SOURCE, &message(136, Some(2)));
+        assert_eq!(ndp.target, Some(TARGET));
+        assert_eq!(ndp.neighbor, Some((TARGET, MAC)));
+
+        let ndp = parse_bytes(SOURCE, &message(136, None));
+        assert_eq!(ndp.target, Some(TARGET));
+        assert_eq!(ndp.neighbor, None);
+
+        // -- a source option is not the target's address
+        assert_eq!(parse_bytes(SOURCE, &message(136, Some(1))).neighbor, None);
+    }
+
+    #[test]
+    fn truncated() {
+        let full = message(136, Some(2));
+        for len in [4, 8, 23, 26, 30] {
+            let ndp = parse_bytes(SOURCE, &full[..len]);
+            assert_eq!(ndp.neighbor, None, "{} bytes", len);
+        }
+        assert_eq!(parse_bytes(SOURCE, &message(135, Some(1))[..12]), NdpAddresses::default());
+    }
+}
@@ -0,0 +1,252 @@
+//! Reader for classic libpcap files, replayed through the live decoders.
+//!
+//! Both byte orders and both timestamp resolutions are accepted. Only
+//! Ethernet captures can be replayed, the decoders start at layer 2; pcapng
+//! files need converting first (`editcap -F pcap`).
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::path::Path;
+
+use chrono::{DateTime, Local, TimeZone};
+
+const MAGIC_MICROS: u32 = 0xa1b2_c3d4;
+const MAGIC_NANOS: u32 = 0xa1b2_3c4d;
+const MAGIC_PCAPNG: u32 = 0x0a0d_0d0a;
+const LINKTYPE_ETHERNET: u32 = 1;
+/// Larger records are taken as a corrupt file rather than allocated.
+const MAX_RECORD_LEN: u32 = 0x0400_0000;
+
+pub struct PcapFrame {
+    pub time: DateTime<Local>,
+    pub bytes: Vec<u8>,
+    /// Length on the wire, `bytes` may be truncated to the file's snaplen.
+    pub orig_len: usize,
+}
+
+pub struct PcapReader<R = BufReader<File>> {
+    input: R,
+    big_endian: bool,
+    nanos: bool,
+}
+
+fn invalid(msg: String) -> std::io::Error {
+    std::io::Error::new(ErrorKind::InvalidData, msg)
+}
+
+impl PcapReader {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: BufRead> PcapReader<R> {
+    pub fn new(mut input: R) -> std::io::Result<Self> {
+        let mut header = [0u8; 24];
+        input.read_exact(&mut header)?;
+        let magic = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        let (big_endian, nanos) = match magic {
+            MAGIC_MICROS => (false, false),
+            MAGIC_NANOS => (false, true),
+            m if m.swap_bytes() == MAGIC_MICROS => (true, false),
+            m if m.swap_bytes() == MAGIC_NANOS => (true, true),
+            MAGIC_PCAPNG => {
+                return Err(invalid(
+                    "pcapng is not supported, convert with `editcap -F pcap`".to_string(),
+                ))
+            }
+            _ => return Err(invalid("not a pcap file".to_string())),
+        };
+        let reader = Self {
+            input,
+            big_endian,
+            nanos,
+        };
+        let linktype = reader.u32_at(&header, 20);
+        if linktype != LINKTYPE_ETHERNET {
+            return Err(invalid(format!(
+                "link type {} is not supported, only Ethernet captures can be read",
+                linktype
+            )));
+        }
+        Ok(reader)
+    }
+
+    fn read_record(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.input.read_exact(buf).map_err(|e| match e.kind() {
+            ErrorKind::UnexpectedEof => invalid("last record is truncated".to_string()),
+            _ => e,
+        })
+    }
+
+    fn u32_at(&self, bytes: &[u8], offset: usize) -> u32 {
+        let field = [bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]];
+        if self.big_endian {
+            u32::from_be_bytes(field)
+        } else {
+            u32::from_le_bytes(field)
+        }
+    }
+
+    /// Next frame, `None` at the end of the file. A record cut short by the
+    /// end of the file, as left by an aborted capture, is an error; the
+    /// frames before it were read fine.
+    pub fn next_frame(&mut self) -> std::io::Result<Option<PcapFrame>> {
+        if self.input.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        let mut header = [0u8; 16];
+        self.read_record(&mut header)?;
+        let secs = self.u32_at(&header, 0);
+        let fraction = self.u32_at(&header, 4);
+        let incl_len = self.u32_at(&header, 8);
+        let orig_len = self.u32_at(&header, 12);
+        if incl_len > MAX_RECORD_LEN {
+            return Err(invalid(format!("record of {} bytes, file is corrupt", incl_len)));
+        }
+        let mut bytes = vec![0u8; incl_len as usize];
+        self.read_record(&mut bytes)?;
+        let nanos = if self.nanos { fraction } else { fraction.saturating_mul(1000) };
+        let time = Local
+            .timestamp_opt(secs as i64, nanos.min(999_999_999))
+            .single()
+            .unwrap_or_else(Local::now);
+        Ok(Some(PcapFrame {
+            time,
+            orig_len: (orig_len as usize).max(bytes.len()),
+            bytes,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::net::Ipv4Addr;
+
+    use pnet::packet::arp::ArpOperations;
+    use pnet::util::MacAddr;
+
+    use super::*;
+    use crate::components::export::PcapWriter;
+    use crate::enums::{ARPPacketInfo, PacketsInfoTypesEnum};
+
+    fn frame(len: usize, orig_len: usize) -> PacketsInfoTypesEnum {
+        PacketsInfoTypesEnum::Arp(ARPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source_mac: MacAddr::new(2, 0, 0, 0, 0, 1),
+            source_ip: Ipv4Addr::new(10, 0, 0, 1),
+            destination_mac: MacAddr::zero(),
+            destination_ip: Ipv4Addr::new(10, 0, 0, 2),
+            operation: ArpOperations::Request,
+            source_vendor: None,
+            vlan: None,
+            raw_str: String::new(),
+            raw_bytes: (0..len).map(|i| i as u8).collect(),
+            orig_len,
+        })
+    }
+
+    // -- a file by hand: header, then (secs, fraction, bytes, orig_len) records
+    fn file(magic: u32, big_endian: bool, records: &[(u32, u32, &[u8], u32)]) -> Vec<u8> {
+        let u32_bytes = |v: u32| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+        let u16_bytes = |v: u16| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+        let mut out = vec![];
+        out.extend(u32_bytes(magic));
+        out.extend(u16_bytes(2));
+        out.extend(u16_bytes(4));
+        out.extend(u32_bytes(0));
+        out.extend(u32_bytes(0));
+        out.extend(u32_bytes(65535));
+        out.extend(u32_bytes(LINKTYPE_ETHERNET));
+        for (secs, fraction, bytes, orig_len) in records {
+            out.extend(u32_bytes(*secs));
+            out.extend(u32_bytes(*fraction));
+            out.extend(u32_bytes(bytes.len() as u32));
+            out.extend(u32_bytes(*orig_len));
+            out.extend_from_slice(bytes);
+        }
+        out
+    }
+
+    fn read_all(bytes: Vec<u8>) -> std::io::Result<Vec<PcapFrame>> {
+        let mut reader = PcapReader::new(Cursor::new(bytes))?;
+        let mut frames = vec![];
+        while let Some(frame) = reader.next_frame()? {
+            frames.push(frame);
+        }
+        Ok(frames)
+    }
+
+    #[test]
+    fn round_trip_through_the_writer() {
+        let first = Local.timestamp_opt(1_700_000_000, 123_456_000).unwrap();
+        let second = Local.timestamp_opt(1_700_000_001, 999_000).unwrap();
+        let mut out = vec![];
+        let mut writer = PcapWriter::new(&mut out, 65535).unwrap();
+        writer.write_packet(&first, &frame(42, 42)).unwrap();
+        writer.write_packet(&second, &frame(60, 1514)).unwrap();
+        writer.finish().unwrap();
+
+        let frames = read_all(out).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].time, first);
+        assert_eq!(frames[0].bytes, frame(42, 42).raw_bytes());
+        assert_eq!(frames[0].orig_len, 42);
+        assert_eq!(frames[1].time, second);
+        assert_eq!(frames[1].bytes.len(), 60);
+        assert_eq!(frames[1].orig_len, 1514);
+    }
+
+    #[test]
+    fn frames_cut_to_the_snaplen_keep_their_length() {
+        let time = Local.timestamp_opt(1_700_000_000, 0).unwrap();
+        let mut out = vec![];
+        let mut writer = PcapWriter::new(&mut out, 64).unwrap();
+        writer.write_packet(&time, &frame(100, 100)).unwrap();
+        writer.finish().unwrap();
+
+        let frames = read_all(out).unwrap();
+        assert_eq!(frames[0].bytes, frame(100, 100).raw_bytes()[..64]);
+        assert_eq!(frames[0].orig_len, 100);
+    }
+
+    #[test]
+    fn big_endian_and_nanosecond_files() {
+        let bytes = [0xaa; 14];
+        for big_endian in [false, true] {
+            let micros = file(MAGIC_MICROS, big_endian, &[(1_700_000_000, 250_000, &bytes, 60)]);
+            let frames = read_all(micros).unwrap();
+            assert_eq!(frames[0].time, Local.timestamp_opt(1_700_000_000, 250_000_000).unwrap());
+            assert_eq!((frames[0].bytes.as_slice(), frames[0].orig_len), (&bytes[..], 60));
+
+            let nanos = file(MAGIC_NANOS, big_endian, &[(1_700_000_000, 250_000_001, &bytes, 14)]);
+            let frames = read_all(nanos).unwrap();
+            assert_eq!(frames[0].time, Local.timestamp_opt(1_700_000_000, 250_000_001).unwrap());
+        }
+    }
+
+    #[test]
+    fn truncated_last_record_is_an_error() {
+        let bytes = [0xaa; 14];
+        let full = file(MAGIC_MICROS, false, &[(1, 0, &bytes, 14), (2, 0, &bytes, 14)]);
+        // -- cut in the second record's header, then in its data
+        for len in [24 + 30 + 8, full.len() - 1] {
+            let mut reader = PcapReader::new(Cursor::new(full[..len].to_vec())).unwrap();
+            assert!(reader.next_frame().unwrap().is_some());
+            let error = reader.next_frame().err().unwrap();
+            assert_eq!(error.kind(), ErrorKind::InvalidData, "{} bytes", len);
+        }
+        assert_eq!(read_all(full).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn other_files_are_refused() {
+        let pcapng = file(MAGIC_PCAPNG, false, &[]);
+        assert!(read_all(pcapng).err().unwrap().to_string().contains("pcapng"));
+        let mut raw_ip = file(MAGIC_MICROS, false, &[]);
+        raw_ip[20] = 101;
+        assert!(read_all(raw_ip).err().unwrap().to_string().contains("link type 101"));
+        assert!(read_all(vec![0; 10]).is_err());
+    }
+}
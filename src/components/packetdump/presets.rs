@@ -0,0 +1,116 @@
+//! One-key filter presets for common triage views.
+//!
+//! A preset narrows the packet log with a predicate that the plain substring
+//! filter cannot express (e.g. "either port is 53") and switches to the packet
+//! type it is most useful with. It is applied on top of the typed filter.
+
+use std::net::IpAddr;
+
+use pnet::packet::arp::ArpOperations;
+use pnet::util::MacAddr;
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use crate::enums::{PacketTypeEnum, PacketsInfoTypesEnum};
+
+/// Well-known DNS ports, `capture.port_protocols` can add more.
+pub const DNS_PORTS: &[u16] = &[53, 5353];
+const WEB_PORTS: &[u16] = &[80, 443, 8080, 8443];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Display, EnumIter)]
+pub enum FilterPreset {
+    #[default]
+    #[strum(to_string = "none")]
+    None,
+    #[strum(to_string = "DNS only")]
+    Dns,
+    #[strum(to_string = "HTTP/HTTPS")]
+    Web,
+    #[strum(to_string = "ARP storms")]
+    ArpStorm,
+    #[strum(to_string = "no broadcast/multicast")]
+    NoBroadcast,
+}
+
+fn has_port(source: u16, destination: u16, ports: &[u16]) -> bool {
+    ports.contains(&source) || ports.contains(&destination)
+}
+
+fn is_broadcast_or_multicast(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.is_broadcast() || ip.is_multicast(),
+        IpAddr::V6(ip) => ip.is_multicast(),
+    }
+}
+
+impl FilterPreset {
+    pub fn next(self) -> Self {
+        let mut iter = Self::iter().skip_while(|p| *p != self).skip(1);
+        iter.next().unwrap_or_default()
+    }
+
+    /// Packet type the view switches to when the preset is selected.
+    pub fn packet_type(self) -> PacketTypeEnum {
+        match self {
+            FilterPreset::Dns => PacketTypeEnum::Udp,
+            FilterPreset::Web => PacketTypeEnum::Tcp,
+            FilterPreset::ArpStorm => PacketTypeEnum::Arp,
+            FilterPreset::None | FilterPreset::NoBroadcast => PacketTypeEnum::All,
+        }
+    }
+
+    /// Closest BPF expression, `None` when the preset filters nothing.
+    pub fn bpf(self, dns_ports: &[u16]) -> Option<String> {
+        let ports = |ports: &[u16]| -> String {
+            let ports: Vec<String> = ports.iter().map(|p| format!("port {}", p)).collect();
+            ports.join(" or ")
+        };
+        match self {
+            FilterPreset::None => None,
+            FilterPreset::Dns => Some(ports(dns_ports)),
+            FilterPreset::Web => Some(format!("(tcp and ({})) or udp port 443", ports(WEB_PORTS))),
+            // -- ARP operation field, 1 is a request
+            FilterPreset::ArpStorm => Some("arp[6:2] = 1".to_string()),
+            FilterPreset::NoBroadcast => Some("not broadcast and not multicast".to_string()),
+        }
+    }
+
+    /// `dns_ports` are the ports the DNS preset keeps.
+    pub fn matches(self, packet: &PacketsInfoTypesEnum, dns_ports: &[u16]) -> bool {
+        match self {
+            FilterPreset::None => true,
+            FilterPreset::Dns => match packet {
+                PacketsInfoTypesEnum::Udp(p) => {
+                    has_port(p.source_port, p.destination_port, dns_ports)
+                }
+                PacketsInfoTypesEnum::Tcp(p) => {
+                    has_port(p.source_port, p.destination_port, dns_ports)
+                }
+                _ => false,
+            },
+            FilterPreset::Web => match packet {
+                PacketsInfoTypesEnum::Tcp(p) => {
+                    has_port(p.source_port, p.destination_port, WEB_PORTS)
+                }
+                // -- QUIC
+                PacketsInfoTypesEnum::Udp(p) => has_port(p.source_port, p.destination_port, &[443]),
+                _ => false,
+            },
+            // -- storms are floods of broadcast who-has requests
+            FilterPreset::ArpStorm => match packet {
+                PacketsInfoTypesEnum::Arp(p) => p.operation == ArpOperations::Request,
+                _ => false,
+            },
+            FilterPreset::NoBroadcast => match packet {
+                PacketsInfoTypesEnum::Arp(p) => p.destination_mac != MacAddr::broadcast(),
+                PacketsInfoTypesEnum::Udp(p) => !is_broadcast_or_multicast(&p.destination),
+                PacketsInfoTypesEnum::Tcp(p) => !is_broadcast_or_multicast(&p.destination),
+                PacketsInfoTypesEnum::Icmp(p) => !is_broadcast_or_multicast(&p.destination),
+                PacketsInfoTypesEnum::Icmp6(p) => !is_broadcast_or_multicast(&p.destination),
+                PacketsInfoTypesEnum::Igmp(_) => false,
+                PacketsInfoTypesEnum::Other(p) => {
+                    !p.destination_mac.is_broadcast() && !p.destination_mac.is_multicast()
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Per-second packet and byte counts for each packet type over the last minute.
+//!
+//! Every packet type has a ring of `RATE_WINDOW_SECS` buckets for each count,
+//! the last one is the second in progress. Buckets are rotated by wall clock
+//! seconds so ticks at any rate (or none during a stall) leave zero buckets
+//! for the gaps.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use chrono::{DateTime, Local};
+use strum::EnumCount;
+
+use crate::enums::PacketTypeEnum;
+
+pub const RATE_WINDOW_SECS: usize = 60;
+
+/// Counts of the last complete second.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rate {
+    pub packets: u64,
+    pub bytes: u64,
+}
+
+/// `1234` as `1.2` and `k`, whole numbers below a thousand.
+fn si(value: u64) -> (String, &'static str) {
+    let prefixes = ["", "k", "M", "G", "T"];
+    let mut scaled = value as f64;
+    let mut prefix = 0;
+    while scaled >= 1000.0 && prefix < prefixes.len() - 1 {
+        scaled /= 1000.0;
+        prefix += 1;
+    }
+    if prefix == 0 {
+        (value.to_string(), "")
+    } else {
+        (format!("{:.1}", scaled), prefixes[prefix])
+    }
+}
+
+/// Packet count in the same form as the rate, e.g. `1.2k`.
+pub fn count_str(value: u64) -> String {
+    let (count, prefix) = si(value);
+    format!("{}{}", count, prefix)
+}
+
+/// `1.2k pps / 8.4 Mbps`, bits as link speeds are given.
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (packets, packets_prefix) = si(self.packets);
+        let (bits, bits_prefix) = si(self.bytes.saturating_mul(8));
+        write!(f, "{}{} pps / {} {}bps", packets, packets_prefix, bits, bits_prefix)
+    }
+}
+
+pub struct ByteRate {
+    buckets: Vec<VecDeque<u64>>,
+    packets: Vec<VecDeque<u64>>,
+    second: i64,
+}
+
+impl Default for ByteRate {
+    fn default() -> Self {
+        Self::new(Local::now())
+    }
+}
+
+impl ByteRate {
+    pub fn new(now: DateTime<Local>) -> Self {
+        Self {
+            buckets: vec![VecDeque::from(vec![0; RATE_WINDOW_SECS]); PacketTypeEnum::COUNT],
+            packets: vec![VecDeque::from(vec![0; RATE_WINDOW_SECS]); PacketTypeEnum::COUNT],
+            second: now.timestamp(),
+        }
+    }
+
+    /// Start new buckets for every second passed since the last rotation.
+    pub fn rotate(&mut self, now: DateTime<Local>) {
+        let elapsed = now.timestamp() - self.second;
+        if elapsed <= 0 {
+            return;
+        }
+        self.second = now.timestamp();
+        let fill = (elapsed as usize).min(RATE_WINDOW_SECS);
+        for ring in self.buckets.iter_mut().chain(self.packets.iter_mut()) {
+            for _ in 0..fill {
+                ring.pop_front();
+                ring.push_back(0);
+            }
+        }
+    }
+
+    /// Count a packet for its own type and for `All`.
+    pub fn record(&mut self, time: DateTime<Local>, packet_type: PacketTypeEnum, bytes: usize) {
+        self.rotate(time);
+        self.add(packet_type, bytes);
+        if packet_type != PacketTypeEnum::All {
+            self.add(PacketTypeEnum::All, bytes);
+        }
+    }
+
+    fn add(&mut self, packet_type: PacketTypeEnum, bytes: usize) {
+        if let Some(last) = self.buckets[packet_type as usize].back_mut() {
+            *last += bytes as u64;
+        }
+        if let Some(last) = self.packets[packet_type as usize].back_mut() {
+            *last += 1;
+        }
+    }
+
+    /// Packets and bytes of the last complete second.
+    pub fn current(&self, packet_type: PacketTypeEnum) -> Rate {
+        let last_complete = |ring: &VecDeque<u64>| ring.iter().rev().nth(1).copied().unwrap_or(0);
+        Rate {
+            packets: last_complete(&self.packets[packet_type as usize]),
+            bytes: last_complete(&self.buckets[packet_type as usize]),
+        }
+    }
+
+    /// Packets per second, oldest first. The second in progress is left out
+    /// as it is still filling up.
+    pub fn history(&self, packet_type: PacketTypeEnum) -> Vec<u64> {
+        let ring = &self.packets[packet_type as usize];
+        ring.iter().take(ring.len() - 1).copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(secs: i64, millis: u32) -> DateTime<Local> {
+        Local.timestamp_opt(secs, millis * 1_000_000).unwrap()
+    }
+
+    #[test]
+    fn current_is_last_complete_second() {
+        let mut rate = ByteRate::new(at(1000, 0));
+        rate.record(at(1000, 100), PacketTypeEnum::Tcp, 1500);
+        rate.record(at(1000, 900), PacketTypeEnum::Udp, 100);
+        // -- still in progress
+        assert_eq!(rate.current(PacketTypeEnum::All), Rate::default());
+        rate.rotate(at(1001, 0));
+        assert_eq!(rate.current(PacketTypeEnum::All), Rate { packets: 2, bytes: 1600 });
+        assert_eq!(rate.current(PacketTypeEnum::Tcp), Rate { packets: 1, bytes: 1500 });
+        assert_eq!(rate.current(PacketTypeEnum::Arp), Rate::default());
+    }
+
+    #[test]
+    fn idle_seconds_leave_zero_buckets() {
+        let mut rate = ByteRate::new(at(1000, 0));
+        rate.record(at(1000, 0), PacketTypeEnum::Tcp, 60);
+        rate.rotate(at(1003, 0));
+        assert_eq!(rate.current(PacketTypeEnum::Tcp), Rate::default());
+        let history = rate.history(PacketTypeEnum::Tcp);
+        assert_eq!(history.len(), RATE_WINDOW_SECS - 1);
+        assert_eq!(&history[history.len() - 3..], &[1, 0, 0]);
+    }
+
+    #[test]
+    fn long_stall_clears_the_window() {
+        let mut rate = ByteRate::new(at(1000, 0));
+        rate.record(at(1000, 0), PacketTypeEnum::Udp, 60);
+        rate.rotate(at(1000 + 10 * RATE_WINDOW_SECS as i64, 0));
+        assert!(rate.history(PacketTypeEnum::Udp).iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn earlier_times_count_in_current_second() {
+        let mut rate = ByteRate::new(at(1000, 0));
+        rate.record(at(990, 0), PacketTypeEnum::Tcp, 40);
+        rate.rotate(at(1001, 0));
+        assert_eq!(rate.current(PacketTypeEnum::Tcp), Rate { packets: 1, bytes: 40 });
+    }
+
+    #[test]
+    fn formats_with_unit_prefixes() {
+        assert_eq!(Rate { packets: 0, bytes: 0 }.to_string(), "0 pps / 0 bps");
+        assert_eq!(Rate { packets: 12, bytes: 64 }.to_string(), "12 pps / 512 bps");
+        let rate = Rate {
+            packets: 1234,
+            bytes: 1_050_000,
+        };
+        assert_eq!(rate.to_string(), "1.2k pps / 8.4 Mbps");
+    }
+}
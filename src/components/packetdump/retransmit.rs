@@ -0,0 +1,148 @@
+//! Detection of likely TCP retransmissions.
+//!
+//! Each direction of a TCP connection keeps the recent `(sequence, payload
+//! length)` pairs it sent; a data segment repeating one of them is flagged.
+//! Segments with at most one byte of payload are never flagged, keepalives and
+//! zero-window probes repeat the same sequence number by design. Both the
+//! segments per direction and the directions tracked are bounded, the least
+//! recently seen direction is evicted when full and idle ones are pruned.
+
+use std::collections::VecDeque;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Local};
+
+use super::bounded::{BoundedMap, LastSeen};
+use crate::enums::TCPPacketInfo;
+
+/// Segments remembered per direction, enough to cover a burst in flight.
+const MAX_SEGMENTS: usize = 128;
+
+// -- directional 4-tuple, a retransmission repeats a segment the same side sent
+type SegmentKey = (IpAddr, u16, IpAddr, u16);
+
+struct SentSegments {
+    segments: VecDeque<(u32, usize)>,
+    last_seen: DateTime<Local>,
+}
+
+impl LastSeen for SentSegments {
+    fn last_seen(&self) -> DateTime<Local> {
+        self.last_seen
+    }
+}
+
+pub struct RetransmitTracker {
+    flows: BoundedMap<SegmentKey, SentSegments>,
+}
+
+impl RetransmitTracker {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            flows: BoundedMap::new(capacity),
+        }
+    }
+
+    /// Record the segment, returning whether it repeats one already seen.
+    pub fn check(&mut self, time: DateTime<Local>, tcp: &TCPPacketInfo) -> bool {
+        if tcp.payload_len <= 1 {
+            return false;
+        }
+        let key = (tcp.source, tcp.source_port, tcp.destination, tcp.destination_port);
+        let sent = self.flows.get_or_insert_with(key, || SentSegments {
+            segments: VecDeque::new(),
+            last_seen: time,
+        });
+        sent.last_seen = time;
+
+        let segment = (tcp.sequence, tcp.payload_len);
+        if sent.segments.contains(&segment) {
+            return true;
+        }
+        if sent.segments.len() >= MAX_SEGMENTS {
+            sent.segments.pop_front();
+        }
+        sent.segments.push_back(segment);
+        false
+    }
+
+    /// Forget directions idle for longer than `max_idle`.
+    pub fn prune(&mut self, now: DateTime<Local>, max_idle: chrono::Duration) {
+        self.flows.retain(|_, sent| now - sent.last_seen <= max_idle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::enums::IpMeta;
+
+    fn at(secs: i64) -> DateTime<Local> {
+        Local.timestamp_opt(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    fn segment(source_port: u16, sequence: u32, payload_len: usize) -> TCPPacketInfo {
+        TCPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source: "10.0.0.2".parse().unwrap(),
+            source_port,
+            destination: "10.0.0.1".parse().unwrap(),
+            destination_port: 443,
+            length: 20 + payload_len,
+            flags: 0x18,
+            sequence,
+            acknowledgement: 5000,
+            relative_sequence: None,
+            relative_acknowledgement: None,
+            payload_len,
+            retransmission: false,
+            http_info: None,
+            payload: vec![0; payload_len],
+            ip: IpMeta::default(),
+            vlan: None,
+            raw_str: String::new(),
+            raw_bytes: vec![],
+            orig_len: 54 + payload_len,
+        }
+    }
+
+    #[test]
+    fn repeated_data_is_a_retransmission() {
+        let mut tracker = RetransmitTracker::new(16);
+        assert!(!tracker.check(at(0), &segment(50000, 1000, 100)));
+        assert!(!tracker.check(at(0), &segment(50000, 1100, 100)));
+        assert!(tracker.check(at(1), &segment(50000, 1000, 100)));
+        // -- the same sequence number from another connection is not
+        assert!(!tracker.check(at(1), &segment(50001, 1000, 100)));
+    }
+
+    #[test]
+    fn keepalives_and_window_probes_are_not_counted() {
+        let mut tracker = RetransmitTracker::new(16);
+        tracker.check(at(0), &segment(50000, 1000, 100));
+        // -- keepalive: one byte or none at the last acknowledged byte, seq - 1
+        for _ in 0..3 {
+            assert!(!tracker.check(at(1), &segment(50000, 1099, 1)));
+            assert!(!tracker.check(at(1), &segment(50000, 1099, 0)));
+        }
+        // -- zero-window probe: the next byte, repeated until the window opens
+        for _ in 0..3 {
+            assert!(!tracker.check(at(2), &segment(50000, 1100, 1)));
+        }
+    }
+
+    #[test]
+    fn the_flow_limit_evicts_the_least_recently_seen() {
+        let mut tracker = RetransmitTracker::new(2);
+        tracker.check(at(0), &segment(50000, 1000, 100));
+        tracker.check(at(1), &segment(50001, 1000, 100));
+        tracker.check(at(2), &segment(50000, 1100, 100));
+        tracker.check(at(3), &segment(50002, 1000, 100));
+        assert_eq!(tracker.flows.len(), 2);
+        // -- 50001 was evicted, so its repeat is seen as new
+        assert!(!tracker.check(at(4), &segment(50001, 1000, 100)));
+        assert!(tracker.check(at(5), &segment(50002, 1000, 100)));
+    }
+}
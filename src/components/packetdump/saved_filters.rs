@@ -0,0 +1,63 @@
+//! Named packet filters persisted to `filters.json` in the `.netscanner` folder.
+//!
+//! The file holds a JSON object of name to filter string. A missing or corrupt
+//! file is treated as empty, it is overwritten on the next save.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+
+use crate::utils::get_netscanner_dir;
+
+const FILTERS_FILE: &str = "filters.json";
+
+#[derive(Debug, Default)]
+pub struct SavedFilters {
+    filters: BTreeMap<String, String>,
+}
+
+impl SavedFilters {
+    fn path() -> PathBuf {
+        PathBuf::from(get_netscanner_dir()).join(FILTERS_FILE)
+    }
+
+    pub fn load() -> Self {
+        let filters = std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        Self { filters }
+    }
+
+    pub fn save(&mut self, name: &str, filter: &str) -> Result<()> {
+        self.filters.insert(name.to_string(), filter.to_string());
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.filters)?)?;
+        Ok(())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.filters.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.filters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Filters ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.filters.iter()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&String> {
+        self.filters.values().nth(index)
+    }
+}
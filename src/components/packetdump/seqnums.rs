@@ -0,0 +1,191 @@
+//! Relative TCP sequence numbers.
+//!
+//! Each direction of a connection counts from the first sequence number seen
+//! for it, the ISN when the capture saw the SYN, so the SYN shows `seq=0` and
+//! the first data byte `seq=1`. Acknowledgements count from the other
+//! direction's base once that was seen. A SYN restarts the count for a reused
+//! 4-tuple. The directions tracked are bounded like the retransmission
+//! tracker's: the least recently seen is evicted when full, idle ones pruned.
+
+use std::net::IpAddr;
+
+use chrono::{DateTime, Local};
+use pnet::packet::tcp::TcpFlags;
+
+use super::bounded::{BoundedMap, LastSeen};
+use crate::enums::TCPPacketInfo;
+
+// -- directional 4-tuple, each side numbers its own bytes
+type DirectionKey = (IpAddr, u16, IpAddr, u16);
+
+struct Base {
+    sequence: u32,
+    last_seen: DateTime<Local>,
+}
+
+impl LastSeen for Base {
+    fn last_seen(&self) -> DateTime<Local> {
+        self.last_seen
+    }
+}
+
+pub struct SequenceBases {
+    bases: BoundedMap<DirectionKey, Base>,
+}
+
+impl SequenceBases {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            bases: BoundedMap::new(capacity),
+        }
+    }
+
+    /// Relative sequence number of the segment and, when it has ACK set and
+    /// the other direction was seen, its relative acknowledgement number.
+    pub fn relative(&mut self, time: DateTime<Local>, tcp: &TCPPacketInfo) -> (u32, Option<u32>) {
+        let key = (tcp.source, tcp.source_port, tcp.destination, tcp.destination_port);
+        let base = self.bases.get_or_insert_with(key, || Base {
+            sequence: tcp.sequence,
+            last_seen: time,
+        });
+        if tcp.flags & TcpFlags::SYN != 0 {
+            base.sequence = tcp.sequence;
+        }
+        base.last_seen = time;
+        let sequence = tcp.sequence.wrapping_sub(base.sequence);
+
+        let reverse = (tcp.destination, tcp.destination_port, tcp.source, tcp.source_port);
+        let acknowledgement = match self.bases.get(&reverse) {
+            Some(base) if tcp.flags & TcpFlags::ACK != 0 => {
+                Some(tcp.acknowledgement.wrapping_sub(base.sequence))
+            }
+            _ => None,
+        };
+        (sequence, acknowledgement)
+    }
+
+    /// Forget directions idle for longer than `max_idle`.
+    pub fn prune(&mut self, now: DateTime<Local>, max_idle: chrono::Duration) {
+        self.bases.retain(|_, base| now - base.last_seen <= max_idle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::enums::IpMeta;
+
+    const SYN: u8 = TcpFlags::SYN;
+    const ACK: u8 = TcpFlags::ACK;
+
+    fn at(secs: i64) -> DateTime<Local> {
+        Local.timestamp_opt(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    // -- a segment from the client 10.0.0.2:50000, or back from the server
+    fn segment(from_client: bool, flags: u8, sequence: u32, acknowledgement: u32) -> TCPPacketInfo {
+        let client = ("10.0.0.2".parse().unwrap(), 50000);
+        let server = ("10.0.0.1".parse().unwrap(), 443);
+        let (source, destination) = if from_client {
+            (client, server)
+        } else {
+            (server, client)
+        };
+        TCPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source: source.0,
+            source_port: source.1,
+            destination: destination.0,
+            destination_port: destination.1,
+            length: 20,
+            flags,
+            sequence,
+            acknowledgement,
+            relative_sequence: None,
+            relative_acknowledgement: None,
+            payload_len: 0,
+            retransmission: false,
+            http_info: None,
+            payload: vec![],
+            ip: IpMeta::default(),
+            vlan: None,
+            raw_str: String::new(),
+            raw_bytes: vec![],
+            orig_len: 54,
+        }
+    }
+
+    #[test]
+    fn handshake_counts_from_each_isn() {
+        let mut bases = SequenceBases::new(16);
+        assert_eq!(
+            bases.relative(at(0), &segment(true, SYN, 1000, 0)),
+            (0, None)
+        );
+        assert_eq!(
+            bases.relative(at(0), &segment(false, SYN | ACK, 5000, 1001)),
+            (0, Some(1))
+        );
+        assert_eq!(
+            bases.relative(at(0), &segment(true, ACK, 1001, 5001)),
+            (1, Some(1))
+        );
+        assert_eq!(
+            bases.relative(at(1), &segment(true, ACK, 1101, 5001)),
+            (101, Some(1))
+        );
+        assert_eq!(
+            bases.relative(at(1), &segment(false, ACK, 5001, 1101)),
+            (1, Some(101))
+        );
+    }
+
+    #[test]
+    fn sequence_numbers_wrap_at_32_bits() {
+        let mut bases = SequenceBases::new(16);
+        let isn = u32::MAX - 9;
+        assert_eq!(
+            bases.relative(at(0), &segment(true, SYN, isn, 0)),
+            (0, None)
+        );
+        assert_eq!(
+            bases
+                .relative(at(0), &segment(false, SYN | ACK, 7, isn.wrapping_add(1)))
+                .1,
+            Some(1)
+        );
+        assert_eq!(
+            bases.relative(at(1), &segment(true, ACK, 5, 8)),
+            (15, Some(1))
+        );
+        assert_eq!(
+            bases.relative(at(1), &segment(false, ACK, 8, 5)).1,
+            Some(15)
+        );
+    }
+
+    #[test]
+    fn a_new_syn_restarts_the_count() {
+        let mut bases = SequenceBases::new(16);
+        bases.relative(at(0), &segment(true, SYN, 1000, 0));
+        assert_eq!(bases.relative(at(1), &segment(true, ACK, 2000, 0)).0, 1000);
+        // -- the 4-tuple is reused by a new connection
+        assert_eq!(bases.relative(at(2), &segment(true, SYN, 90_000, 0)).0, 0);
+        assert_eq!(bases.relative(at(2), &segment(true, ACK, 90_001, 0)).0, 1);
+    }
+
+    #[test]
+    fn mid_stream_counts_from_the_first_segment_seen() {
+        let mut bases = SequenceBases::new(16);
+        assert_eq!(
+            bases.relative(at(0), &segment(true, ACK, 7000, 3000)),
+            (0, None)
+        );
+        assert_eq!(
+            bases.relative(at(0), &segment(false, ACK, 3000, 7100)),
+            (0, Some(100))
+        );
+    }
+}
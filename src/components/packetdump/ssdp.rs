@@ -0,0 +1,79 @@
+//! SSDP (UDP 1900) discovery messages: UPnP `NOTIFY` announcements,
+//! `M-SEARCH` queries and the unicast responses to them.
+//!
+//! The start line and the notification/search target, `USN`, `SERVER` and
+//! `LOCATION` headers are read from the first lines of the datagram.
+//! Anything else on UDP 1900 yields nothing.
+
+use crate::enums::SsdpInfo;
+
+pub const SSDP_PORT: u16 = 1900;
+
+// -- real messages have about ten headers, the rest is not looked at
+const MAX_HEADERS: usize = 32;
+const MAX_VALUE_LEN: usize = 256;
+
+fn clean(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_VALUE_LEN)
+        .collect()
+}
+
+pub fn parse(payload: &[u8]) -> Option<SsdpInfo> {
+    let text = String::from_utf8_lossy(payload);
+    let mut lines = text.split('\n').map(|line| line.trim_end_matches('\r'));
+    let start_line = lines.next()?;
+    let method = if start_line.starts_with("NOTIFY ") {
+        "NOTIFY"
+    } else if start_line.starts_with("M-SEARCH ") {
+        "M-SEARCH"
+    } else if start_line.starts_with("HTTP/") {
+        "response"
+    } else {
+        return None;
+    };
+
+    let mut info = SsdpInfo {
+        method: method.to_string(),
+        sub_type: None,
+        target: None,
+        usn: None,
+        server: None,
+        location: None,
+    };
+    for line in lines.take(MAX_HEADERS).take_while(|line| !line.is_empty()) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = Some(clean(value));
+        match name.trim().to_ascii_uppercase().as_str() {
+            "NTS" => info.sub_type = value,
+            "NT" | "ST" => info.target = value,
+            "USN" => info.usn = value,
+            "SERVER" => info.server = value,
+            "LOCATION" => info.location = value,
+            _ => {}
+        }
+    }
+    Some(info)
+}
+
+/// One line for the packet log, e.g. `SSDP NOTIFY ssdp:alive
+/// urn:schemas-upnp-org:device:MediaRenderer:1 usn uuid:...`, also what the
+/// text filter matches against.
+pub fn summary(ssdp: &SsdpInfo) -> String {
+    let mut line = format!("SSDP {}", ssdp.method);
+    for value in [&ssdp.sub_type, &ssdp.target].into_iter().flatten() {
+        line.push_str(&format!(" {}", value));
+    }
+    if let Some(usn) = &ssdp.usn {
+        line.push_str(&format!(" usn {}", usn));
+    }
+    if let Some(server) = &ssdp.server {
+        line.push_str(&format!(" ({})", server));
+    }
+    line
+}
@@ -0,0 +1,31 @@
+//! Line-delimited JSON stream of decoded packets, written by `--headless --json`.
+//!
+//! Every packet that passes the active filter becomes one JSON object on its
+//! own line, flushed right away so `jq` or a log shipper sees it as it arrives:
+//!
+//! ```text
+//! {"time":"2024-05-01T12:00:00.123+02:00","packet":{"Udp":{"source":"192.168.1.10",...}}}
+//! ```
+
+use std::io::{self, Write};
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+use crate::enums::PacketsInfoTypesEnum;
+
+#[derive(Serialize)]
+struct StreamRecord<'a> {
+    time: &'a DateTime<Local>,
+    packet: &'a PacketsInfoTypesEnum,
+}
+
+pub fn write_line(
+    out: &mut impl Write,
+    time: &DateTime<Local>,
+    packet: &PacketsInfoTypesEnum,
+) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, &StreamRecord { time, packet })?;
+    out.write_all(b"\n")?;
+    out.flush()
+}
@@ -0,0 +1,80 @@
+//! Traffic per source address for the top talkers view.
+//!
+//! Every captured packet with an IP layer (or an ARP sender) is counted to
+//! its source as it arrives, so the view never walks the packet history. The
+//! table is bounded and evicts the least recently seen source when full.
+
+use std::cmp::Reverse;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Local};
+
+use super::bounded::{BoundedMap, LastSeen};
+use crate::enums::{PacketTypeEnum, PacketsInfoTypesEnum};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TalkerStats {
+    pub ip: IpAddr,
+    pub packets: usize,
+    pub bytes: usize,
+    /// In the order of the packet type tabs.
+    pub protocols: Vec<PacketTypeEnum>,
+    pub last_seen: DateTime<Local>,
+}
+
+impl LastSeen for TalkerStats {
+    fn last_seen(&self) -> DateTime<Local> {
+        self.last_seen
+    }
+}
+
+impl TalkerStats {
+    pub fn protocols_str(&self) -> String {
+        self.protocols.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(",")
+    }
+}
+
+pub struct TalkerTable {
+    talkers: BoundedMap<IpAddr, TalkerStats>,
+}
+
+impl TalkerTable {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            talkers: BoundedMap::new(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.talkers.len()
+    }
+
+    /// Count a captured packet to its source. Frames without an address are ignored.
+    pub fn update(&mut self, time: DateTime<Local>, packet: &PacketsInfoTypesEnum) {
+        let Some((source, _)) = packet.addresses() else {
+            return;
+        };
+        let stats = self.talkers.get_or_insert_with(source, || TalkerStats {
+            ip: source,
+            packets: 0,
+            bytes: 0,
+            protocols: vec![],
+            last_seen: time,
+        });
+        stats.packets += 1;
+        stats.bytes += packet.orig_len();
+        stats.last_seen = time;
+        let packet_type = packet.packet_type();
+        if !stats.protocols.contains(&packet_type) {
+            stats.protocols.push(packet_type);
+            stats.protocols.sort_by_key(|p| *p as usize);
+        }
+    }
+
+    /// Sources by bytes sent, most first; ties by address so rows keep still.
+    pub fn sorted(&self) -> Vec<&TalkerStats> {
+        let mut talkers: Vec<&TalkerStats> = self.talkers.values().collect();
+        talkers.sort_by_key(|t| (Reverse(t.bytes), t.ip));
+        talkers
+    }
+}
@@ -0,0 +1,113 @@
+//! TCP control flags as names for the detail view and as a compact tag like
+//! `[SA]` for the packet log, where the filter can match on it.
+//!
+//! The text filter matches the tag as written, `[R]` finds bare resets but not
+//! `[RA]`. The `flags:R` field token matches every segment with RST set.
+
+use ratatui::style::Color;
+
+/// Flag bits from `FIN` upwards with their tag letter, tcpdump uses the same
+/// letters except `.` for ACK.
+const FLAGS: [(&str, char); 8] = [
+    ("FIN", 'F'),
+    ("SYN", 'S'),
+    ("RST", 'R'),
+    ("PSH", 'P'),
+    ("ACK", 'A'),
+    ("URG", 'U'),
+    ("ECE", 'E'),
+    ("CWR", 'W'),
+];
+
+// -- tag letters in connection order, SYN and RST read first
+const TAG_ORDER: [usize; 8] = [1, 0, 2, 3, 4, 5, 6, 7];
+
+fn is_set(flags: u8, bit: usize) -> bool {
+    flags & (1 << bit) != 0
+}
+
+pub fn names(flags: u8) -> String {
+    let set: Vec<&str> = FLAGS
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| is_set(flags, *bit))
+        .map(|(_, (name, _))| *name)
+        .collect();
+    if set.is_empty() {
+        "none".to_string()
+    } else {
+        set.join(" ")
+    }
+}
+
+/// `[S]`, `[SA]`, `[FA]`, `[R]`..., `[-]` for a segment without flags.
+pub fn tag(flags: u8) -> String {
+    let letters: String = TAG_ORDER
+        .iter()
+        .filter(|bit| is_set(flags, **bit))
+        .map(|bit| FLAGS[*bit].1)
+        .collect();
+    if letters.is_empty() {
+        "[-]".to_string()
+    } else {
+        format!("[{}]", letters)
+    }
+}
+
+/// Flag bits from tag letters in any order and case, `SA` or `as` for
+/// SYN+ACK. `None` for an empty set or a letter that isn't a flag.
+pub fn parse(letters: &str) -> Option<u8> {
+    if letters.is_empty() {
+        return None;
+    }
+    letters.chars().try_fold(0u8, |flags, letter| {
+        let bit = FLAGS.iter().position(|(_, tag)| tag.eq_ignore_ascii_case(&letter))?;
+        Some(flags | 1 << bit)
+    })
+}
+
+/// Resets stand out, then connection setup and teardown; plain data and
+/// ACKs stay quiet.
+pub fn color(flags: u8) -> Color {
+    if is_set(flags, 2) {
+        Color::Red
+    } else if is_set(flags, 1) {
+        Color::LightGreen
+    } else if is_set(flags, 0) {
+        Color::LightYellow
+    } else {
+        Color::Cyan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIN: u8 = 0x01;
+    const SYN: u8 = 0x02;
+    const RST: u8 = 0x04;
+    const ACK: u8 = 0x10;
+
+    #[test]
+    fn tags() {
+        assert_eq!(tag(SYN), "[S]");
+        assert_eq!(tag(SYN | ACK), "[SA]");
+        assert_eq!(tag(RST), "[R]");
+        assert_eq!(tag(RST | ACK), "[RA]");
+        assert_eq!(tag(FIN | ACK), "[FA]");
+        assert_eq!(tag(0), "[-]");
+        assert_eq!(names(RST | ACK), "RST ACK");
+    }
+
+    #[test]
+    fn letters_parse_to_flag_bits() {
+        assert_eq!(parse("R"), Some(RST));
+        assert_eq!(parse("SA"), Some(SYN | ACK));
+        assert_eq!(parse("as"), Some(SYN | ACK));
+        assert_eq!(parse("FA"), Some(FIN | ACK));
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("SX"), None);
+        assert_eq!(parse("."), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Translation of the packet log filter into an equivalent tcpdump command.
+//!
+//! The packet type, preset and `net:` subnets map directly onto BPF. The
+//! substring filter only carries over when it is an IP address (`host`), a
+//! port number (`port`) or a `bpf:` expression; time bounds and any other
+//! text are left out of the command and listed as notes next to it instead.
+//! Arguments are shell quoted, the command can be pasted as is.
+
+use std::net::IpAddr;
+
+use ipnetwork::IpNetwork;
+
+use super::presets::FilterPreset;
+use crate::enums::PacketTypeEnum;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TcpdumpCommand {
+    pub command: String,
+    /// Filter parts the command does not reproduce.
+    pub notes: Vec<String>,
+}
+
+// -- wrap alternatives so they bind tighter than the `and` joining clauses
+fn group(expr: String) -> String {
+    if expr.contains(" or ") || expr.contains("||") {
+        format!("({})", expr)
+    } else {
+        expr
+    }
+}
+
+// -- POSIX shell single quotes, a quote inside becomes '\''
+fn shell_quote(arg: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "@%+=:,./_-".contains(c);
+    if !arg.is_empty() && arg.chars().all(plain) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+fn packet_type_bpf(packet_type: PacketTypeEnum) -> Option<&'static str> {
+    match packet_type {
+        PacketTypeEnum::All => None,
+        PacketTypeEnum::Tcp => Some("tcp"),
+        PacketTypeEnum::Udp => Some("udp"),
+        PacketTypeEnum::Arp => Some("arp"),
+        PacketTypeEnum::Icmp => Some("icmp"),
+        PacketTypeEnum::Icmp6 => Some("icmp6"),
+        PacketTypeEnum::Igmp => Some("igmp"),
+        PacketTypeEnum::Other => Some("not ip and not ip6 and not arp"),
+    }
+}
+
+impl TcpdumpCommand {
+    pub fn build(
+        interface: Option<&str>,
+        packet_type: PacketTypeEnum,
+        preset: FilterPreset,
+        dns_ports: &[u16],
+        nets: &[IpNetwork],
+        text: &str,
+        time_bounded: bool,
+    ) -> Self {
+        let mut clauses: Vec<String> = vec![];
+        let mut notes: Vec<String> = vec![];
+
+        if let Some(expr) = packet_type_bpf(packet_type) {
+            clauses.push(expr.to_string());
+        }
+        if let Some(expr) = preset.bpf(dns_ports) {
+            clauses.push(group(expr));
+        }
+        if !nets.is_empty() {
+            let nets: Vec<String> = nets.iter().map(|net| format!("net {}", net)).collect();
+            clauses.push(group(nets.join(" or ")));
+        }
+
+        let text = text.trim();
+        if !text.is_empty() {
+            if let Ok(ip) = text.parse::<IpAddr>() {
+                clauses.push(format!("host {}", ip));
+            } else if let Ok(port) = text.parse::<u16>() {
+                clauses.push(format!("port {}", port));
+            } else if let Some(expr) =
+                super::bpf::strip_prefix(text).filter(|expr| super::bpf::parse(expr).is_some())
+            {
+                clauses.push(group(expr.trim().to_string()));
+            } else {
+                notes.push(format!("text filter \"{}\" has no BPF equivalent", text));
+            }
+        }
+        if time_bounded {
+            notes.push("time bounds are not applied, tcpdump captures live".to_string());
+        }
+
+        let mut command = String::from("tcpdump -n");
+        match interface {
+            Some(name) => command.push_str(&format!(" -i {}", shell_quote(name))),
+            None => notes.push("no active interface, tcpdump picks its default".to_string()),
+        }
+        if !clauses.is_empty() {
+            command.push_str(&format!(" {}", shell_quote(&clauses.join(" and "))));
+        }
+        Self { command, notes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(interface: Option<&str>, text: &str) -> TcpdumpCommand {
+        TcpdumpCommand::build(
+            interface,
+            PacketTypeEnum::All,
+            FilterPreset::default(),
+            &[53],
+            &[],
+            text,
+            false,
+        )
+    }
+
+    #[test]
+    fn plain_filter() {
+        assert_eq!(build(Some("eth0"), "").command, "tcpdump -n -i eth0");
+        assert_eq!(
+            build(Some("eth0"), "443").command,
+            "tcpdump -n -i eth0 'port 443'"
+        );
+        let command = TcpdumpCommand::build(
+            Some("eth0"),
+            PacketTypeEnum::Tcp,
+            FilterPreset::default(),
+            &[53],
+            &[
+                "10.0.0.0/8".parse().unwrap(),
+                "192.168.0.0/16".parse().unwrap(),
+            ],
+            "bpf: port 80 or port 443",
+            false,
+        );
+        assert_eq!(
+            command.command,
+            "tcpdump -n -i eth0 'tcp and (net 10.0.0.0/8 or net 192.168.0.0/16) and (port 80 or port 443)'"
+        );
+    }
+
+    #[test]
+    fn quotes_in_the_filter_are_escaped() {
+        assert_eq!(shell_quote("tcp"), "tcp");
+        assert_eq!(shell_quote("port 80"), "'port 80'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote("$(reboot)"), "'$(reboot)'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn interface_names_are_quoted() {
+        assert_eq!(
+            build(Some("my wifi"), "").command,
+            "tcpdump -n -i 'my wifi'"
+        );
+        assert_eq!(build(Some("en0;rm"), "").command, "tcpdump -n -i 'en0;rm'");
+        assert_eq!(
+            build(Some("wlp3s0.100"), "").command,
+            "tcpdump -n -i wlp3s0.100"
+        );
+        assert!(build(None, "").notes[0].contains("no active interface"));
+    }
+}
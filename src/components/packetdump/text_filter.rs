@@ -0,0 +1,233 @@
+//! Substring match of the packet filter against the packet log text.
+//!
+//! Terms separated by spaces must all match, `|` separates alternatives and
+//! binds looser than the spaces, so `dns nxdomain | icmp` is `(dns and
+//! nxdomain) or icmp`. A term starting with `!` must not match, e.g. `!:22`.
+//! Case is ignored by default, so `icmp` finds the `ICMP` rows and `AA:BB` a
+//! MAC address printed in lower case; a leading `=` matches the whole filter
+//! with exact case. The filter is compiled once when it is set and rows are
+//! compared in place, without a lowercased copy per row.
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    needle: String,
+    negated: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextFilter {
+    // -- any alternative matches when all of its terms do
+    alternatives: Vec<Vec<Term>>,
+    case_sensitive: bool,
+    invalid: bool,
+}
+
+impl TextFilter {
+    pub fn parse(filter: &str) -> Self {
+        let (filter, case_sensitive) = match filter.strip_prefix('=') {
+            Some(exact) => (exact.to_string(), true),
+            None => (filter.to_ascii_lowercase(), false),
+        };
+        let mut text_filter = Self {
+            case_sensitive,
+            ..Default::default()
+        };
+        if filter.trim().is_empty() {
+            return text_filter;
+        }
+        for alternative in filter.split('|') {
+            let mut terms = vec![];
+            for token in alternative.split_whitespace() {
+                let (needle, negated) = match token.strip_prefix('!') {
+                    Some(needle) => (needle, true),
+                    None => (token, false),
+                };
+                // -- a lone `!` negates nothing
+                if needle.is_empty() {
+                    text_filter.invalid = true;
+                }
+                terms.push(Term {
+                    needle: needle.to_string(),
+                    negated,
+                });
+            }
+            // -- `a |`, `| a` and `a | | b` leave an empty alternative
+            if terms.is_empty() {
+                text_filter.invalid = true;
+            }
+            text_filter.alternatives.push(terms);
+        }
+        if text_filter.invalid {
+            text_filter.alternatives.clear();
+        }
+        text_filter
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// The filter can't be compiled and matches every row until it is fixed.
+    pub fn is_invalid(&self) -> bool {
+        self.invalid
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        self.alternatives.is_empty()
+            || self.alternatives.iter().any(|terms| {
+                terms.iter().all(|term| self.contains(text, &term.needle) != term.negated)
+            })
+    }
+
+    /// Byte ranges of `text` matched by the terms that aren't negated, sorted
+    /// and merged where they overlap or touch.
+    pub fn match_ranges(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut ranges: Vec<(usize, usize)> = vec![];
+        for term in self.alternatives.iter().flatten().filter(|term| !term.negated) {
+            let needle = term.needle.as_bytes();
+            let mut start = 0;
+            while let Some(pos) = self.find(&text.as_bytes()[start..], needle) {
+                ranges.push((start + pos, start + pos + needle.len()));
+                start += pos + needle.len();
+            }
+        }
+        ranges.sort_unstable();
+        let mut merged: Vec<(usize, usize)> = vec![];
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        merged
+    }
+
+    fn contains(&self, text: &str, needle: &str) -> bool {
+        self.find(text.as_bytes(), needle.as_bytes()).is_some()
+    }
+
+    // -- the needle is whole characters, so a match never starts or ends
+    // -- inside a multi-byte character
+    fn find(&self, text: &[u8], needle: &[u8]) -> Option<usize> {
+        // -- parsing leaves no empty terms
+        if needle.is_empty() {
+            return None;
+        }
+        text.windows(needle.len()).position(|window| {
+            if self.case_sensitive {
+                window == needle
+            } else {
+                window.eq_ignore_ascii_case(needle)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DNS_ROW: &str = "DNS response A MyNas.Local -> 192.168.1.20";
+    const ARP_ROW: &str = "ARP request 3c:22:fb:0a:bc:de 192.168.1.1 -> ff:ff:ff:ff:ff:ff";
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        assert!(TextFilter::parse("").matches(DNS_ROW));
+        assert!(TextFilter::parse("").matches(""));
+    }
+
+    #[test]
+    fn mixed_case_hostnames() {
+        assert!(TextFilter::parse("mynas.local").matches(DNS_ROW));
+        assert!(TextFilter::parse("MYNAS").matches(DNS_ROW));
+        assert!(TextFilter::parse("response dns").matches(DNS_ROW));
+        assert!(!TextFilter::parse("mynas.lan").matches(DNS_ROW));
+    }
+
+    #[test]
+    fn hex_mac_addresses() {
+        assert!(TextFilter::parse("3C:22:FB").matches(ARP_ROW));
+        assert!(TextFilter::parse("0A:bC:De").matches(ARP_ROW));
+        assert!(TextFilter::parse("FF:FF:FF:FF:FF:FF").matches(ARP_ROW));
+        assert!(!TextFilter::parse("3C:22:FC").matches(ARP_ROW));
+    }
+
+    #[test]
+    fn equals_prefix_matches_exact_case() {
+        let filter = TextFilter::parse("=MyNas");
+        assert!(filter.is_case_sensitive());
+        assert!(filter.matches(DNS_ROW));
+        assert!(!TextFilter::parse("=mynas").matches(DNS_ROW));
+        assert!(!TextFilter::parse("=3C:22").matches(ARP_ROW));
+        assert!(!TextFilter::parse("mynas").is_case_sensitive());
+    }
+
+    #[test]
+    fn longer_than_text() {
+        assert!(!TextFilter::parse("a much longer filter").matches("ARP"));
+    }
+
+    #[test]
+    fn non_ascii_text() {
+        let row = "mDNS announce Küche._airplay._tcp.local";
+        assert!(TextFilter::parse("küche").matches(row));
+        assert!(TextFilter::parse("_AIRPLAY").matches(row));
+    }
+
+    #[test]
+    fn spaces_are_and() {
+        assert!(TextFilter::parse("dns 192.168.1.20").matches(DNS_ROW));
+        assert!(!TextFilter::parse("dns 192.168.1.1").matches(DNS_ROW));
+    }
+
+    #[test]
+    fn negation() {
+        assert!(TextFilter::parse("!arp").matches(DNS_ROW));
+        assert!(!TextFilter::parse("!arp").matches(ARP_ROW));
+        assert!(TextFilter::parse("192.168.1 !ARP").matches(DNS_ROW));
+        assert!(!TextFilter::parse("192.168.1 !ARP").matches(ARP_ROW));
+        assert!(TextFilter::parse("=!ARP").matches(DNS_ROW));
+        assert!(TextFilter::parse("=!arp").matches(ARP_ROW));
+    }
+
+    #[test]
+    fn or_binds_looser_than_and() {
+        // -- (arp and mynas) or local
+        let filter = TextFilter::parse("arp mynas | local");
+        assert!(filter.matches(DNS_ROW));
+        assert!(!filter.matches(ARP_ROW));
+        // -- arp or (nothing and local)
+        let filter = TextFilter::parse("arp | nothing local");
+        assert!(filter.matches(ARP_ROW));
+        assert!(!filter.matches(DNS_ROW));
+        // -- no space needed around the bar
+        assert!(TextFilter::parse("arp|dns").matches(DNS_ROW));
+        assert!(!TextFilter::parse("!arp|!dns").matches("ARP DNS"));
+    }
+
+    #[test]
+    fn empty_terms_are_invalid() {
+        for filter in ["arp |", "| arp", "arp | | dns", "arp ||dns", "!", "arp ! dns", "|"] {
+            let text_filter = TextFilter::parse(filter);
+            assert!(text_filter.is_invalid(), "{}", filter);
+            // -- shown as an error, not applied
+            assert!(text_filter.matches(DNS_ROW), "{}", filter);
+        }
+        assert!(!TextFilter::parse("").is_invalid());
+        assert!(!TextFilter::parse("  ").is_invalid());
+        assert!(!TextFilter::parse("=").is_invalid());
+    }
+
+    #[test]
+    fn match_ranges_cover_every_term() {
+        let row = "TCP 10.0.0.2:443 -> 10.0.0.1:50443";
+        assert_eq!(TextFilter::parse("443").match_ranges(row), vec![(13, 16), (31, 34)]);
+        // -- overlapping and touching matches merge
+        assert_eq!(TextFilter::parse("10.0 0.0.2").match_ranges(row), vec![(4, 12), (20, 24)]);
+        let ranges = TextFilter::parse("tcp | !udp 443").match_ranges(row);
+        assert_eq!(ranges, vec![(0, 3), (13, 16), (31, 34)]);
+        assert!(TextFilter::parse("!tcp").match_ranges(row).is_empty());
+        assert!(TextFilter::parse("").match_ranges(row).is_empty());
+        assert!(TextFilter::parse("=tcp").match_ranges(row).is_empty());
+    }
+}
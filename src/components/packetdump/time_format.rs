@@ -0,0 +1,102 @@
+//! How the packet log and the packet CSV export show capture times.
+//!
+//! Milliseconds by default, so a burst of packets doesn't collapse into one
+//! second; the full date for captures spanning days, and unix epoch seconds to
+//! line rows up with other tools. Rows can instead show the gap to the
+//! previous row, for spotting latency.
+
+use chrono::{DateTime, Duration, Local};
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+/// Characters of a formatted gap, e.g. `+12.345s`.
+pub const DELTA_WIDTH: usize = 9;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Display, EnumIter)]
+pub enum TimeFormat {
+    #[default]
+    #[strum(to_string = "time")]
+    Millis,
+    #[strum(to_string = "date")]
+    DateTime,
+    #[strum(to_string = "epoch")]
+    Epoch,
+}
+
+impl TimeFormat {
+    pub fn next(self) -> Self {
+        let mut iter = Self::iter().skip_while(|f| *f != self).skip(1);
+        iter.next().unwrap_or_default()
+    }
+
+    pub fn format(self, time: &DateTime<Local>) -> String {
+        match self {
+            TimeFormat::Millis => time.format("%H:%M:%S%.3f").to_string(),
+            TimeFormat::DateTime => time.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            TimeFormat::Epoch => {
+                format!("{}.{:03}", time.timestamp(), time.timestamp_subsec_millis())
+            }
+        }
+    }
+
+    /// Characters a formatted time takes, the width of the time column.
+    pub fn width(self) -> usize {
+        match self {
+            TimeFormat::Millis => 12,
+            TimeFormat::DateTime => 23,
+            // -- ten digits of seconds until the year 2286
+            TimeFormat::Epoch => 14,
+        }
+    }
+}
+
+/// Gap to the previous row, microseconds when below a millisecond. `None`
+/// for the oldest row, which has nothing to compare with.
+pub fn format_delta(delta: Option<Duration>) -> String {
+    let Some(delta) = delta else {
+        return "0.000".to_string();
+    };
+    // -- replayed captures can be out of order
+    let sign = if delta < Duration::zero() { '-' } else { '+' };
+    let micros = delta.num_microseconds().map_or(i64::MAX, i64::abs);
+    if micros < 1000 {
+        format!("{}{}µs", sign, micros)
+    } else if micros < 60_000_000 {
+        format!("{}{:.3}s", sign, micros as f64 / 1_000_000.0)
+    } else {
+        format!("{}{}s", sign, micros / 1_000_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn formats_fit_their_width() {
+        let time = Local.with_ymd_and_hms(2024, 3, 9, 7, 5, 3).unwrap()
+            + chrono::Duration::milliseconds(42);
+        for format in TimeFormat::iter() {
+            assert_eq!(format.format(&time).len(), format.width(), "{}", format);
+        }
+        assert!(TimeFormat::Millis.format(&time).ends_with("07:05:03.042"));
+        assert!(TimeFormat::Epoch.format(&time).ends_with(".042"));
+    }
+
+    #[test]
+    fn deltas() {
+        assert_eq!(format_delta(None), "0.000");
+        assert_eq!(format_delta(Some(Duration::zero())), "+0µs");
+        assert_eq!(format_delta(Some(Duration::microseconds(350))), "+350µs");
+        assert_eq!(format_delta(Some(Duration::milliseconds(4))), "+0.004s");
+        assert_eq!(format_delta(Some(Duration::milliseconds(-1500))), "-1.500s");
+        assert_eq!(format_delta(Some(Duration::seconds(125))), "+125s");
+    }
+
+    #[test]
+    fn cycles_back_to_millis() {
+        assert_eq!(TimeFormat::Millis.next(), TimeFormat::DateTime);
+        assert_eq!(TimeFormat::Epoch.next(), TimeFormat::Millis);
+    }
+}
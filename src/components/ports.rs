@@ -5,19 +5,21 @@ use futures::stream;
 use ratatui::style::Stylize;
 
 use core::str;
-use port_desc::{PortDescription, TransportProtocol};
+use serde::{Deserialize, Serialize};
 use ratatui::{prelude::*, widgets::*};
 use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 use tokio::{
     net::TcpStream,
     sync::mpsc::Sender,
+    task::JoinHandle,
 };
 
 use super::Component;
 use crate::enums::COMMON_PORTS;
 use crate::{
     action::Action,
+    services,
     config::DEFAULT_BORDER_STYLE,
     dns_cache::DnsCache,
     enums::{PortsScanState, TabsEnum},
@@ -31,7 +33,7 @@ const MAX_POOL_SIZE: usize = 128;
 const PORT_SCAN_TIMEOUT_SECS: u64 = 2;
 const SPINNER_SYMBOLS: [&str; 6] = ["⠷", "⠯", "⠟", "⠻", "⠽", "⠾"];
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScannedIpPorts {
     pub ip: String,
     state: PortsScanState,
@@ -45,9 +47,12 @@ pub struct Ports {
     ip_ports: Vec<ScannedIpPorts>,
     list_state: ListState,
     scrollbar_state: ScrollbarState,
+    // -- hosts the list showed when last drawn
+    page_rows: usize,
     spinner_index: usize,
-    port_desc: Option<PortDescription>,
     dns_cache: DnsCache,
+    // -- running scans, stopped when a session replaces the list
+    scans: Vec<JoinHandle<()>>,
 }
 
 impl Default for Ports {
@@ -58,20 +63,16 @@ impl Default for Ports {
 
 impl Ports {
     pub fn new() -> Self {
-        let mut port_desc = None;
-        if let Ok(pd) = PortDescription::default() {
-            port_desc = Some(pd);
-        }
-
         Self {
             active_tab: TabsEnum::Discovery,
             action_tx: None,
             ip_ports: Vec::new(),
             list_state: ListState::default().with_selected(Some(0)),
             scrollbar_state: ScrollbarState::new(0),
+            page_rows: 0,
             spinner_index: 0,
-            port_desc,
             dns_cache: DnsCache::new(),
+            scans: Vec::new(),
         }
     }
 
@@ -171,6 +172,29 @@ impl Ports {
         self.scrollbar_state = self.scrollbar_state.position(index);
     }
 
+    fn first_in_list(&mut self) {
+        self.list_state.select(Some(0));
+        self.scrollbar_state = self.scrollbar_state.position(0);
+    }
+
+    fn last_in_list(&mut self) {
+        let index = self.ip_ports.len().saturating_sub(1);
+        self.list_state.select(Some(index));
+        self.scrollbar_state = self.scrollbar_state.position(index);
+    }
+
+    fn page_in_list(&mut self, down: bool) {
+        let selected = self.list_state.selected().unwrap_or(0);
+        let rows = self.page_rows.max(1);
+        let index = if down {
+            (selected + rows).min(self.ip_ports.len().saturating_sub(1))
+        } else {
+            selected.saturating_sub(rows)
+        };
+        self.list_state.select(Some(index));
+        self.scrollbar_state = self.scrollbar_state.position(index);
+    }
+
     fn next_in_list(&mut self) {
         let index = match self.list_state.selected() {
             Some(index) => {
@@ -209,7 +233,8 @@ impl Ports {
         let ports_box = Box::new(COMMON_PORTS.iter());
         let pool_size = Self::get_pool_size();
 
-        tokio::spawn(async move {
+        self.scans.retain(|scan| !scan.is_finished());
+        self.scans.push(tokio::spawn(async move {
             log::debug!("Starting port scan for IP: {} with pool size {}", ip, pool_size);
             let ports = stream::iter(ports_box);
             ports
@@ -225,7 +250,7 @@ impl Ports {
                 );
             }
             log::debug!("Port scan completed for IP: {}", ip);
-        });
+        }));
     }
 
     async fn scan(tx: Sender<Action>, ip_string: String, ip: IpAddr, port: u16) {
@@ -288,9 +313,8 @@ impl Ports {
 
                     ports_spans.push(port.green());
 
-                    if let Some(pd) = &self.port_desc {
-                        let p_type = pd.get_port_service_name(p.to_owned(), TransportProtocol::Tcp);
-                        let p_type_str = format!("({})", p_type).to_string();
+                    if let Some(p_type) = services::tcp_service(*p) {
+                        let p_type_str = format!("({})", p_type);
                         ports_spans.push(p_type_str.clone().light_magenta());
                         line_size += p_type_str.len();
                     }
@@ -394,6 +418,18 @@ impl Component for Ports {
             if let Action::Up = action {
                 self.previous_in_list();
             }
+            if let Action::Home = action {
+                self.first_in_list();
+            }
+            if let Action::End = action {
+                self.last_in_list();
+            }
+            if let Action::PageUp = action {
+                self.page_in_list(false);
+            }
+            if let Action::PageDown = action {
+                self.page_in_list(true);
+            }
 
             if let Action::ScanCidr = action {
                 self.scan_selected();
@@ -416,6 +452,20 @@ impl Component for Ports {
             self.process_ip(ip);
         }
 
+        if let Action::SessionLoaded(ref session) = action {
+            for scan in self.scans.drain(..) {
+                scan.abort();
+            }
+            self.ip_ports = session.scanned_ports.clone();
+            // -- scans interrupted by the save can be started again
+            for entry in &mut self.ip_ports {
+                if entry.state == PortsScanState::Scanning {
+                    entry.state = PortsScanState::Waiting;
+                }
+            }
+            self.set_scrollbar_height();
+        }
+
         if let Action::DnsResolved(ref ip, ref hostname) = action {
             if let Some(entry) = self.ip_ports.iter_mut().find(|item| item.ip == *ip) {
                 entry.hostname = hostname.clone();
@@ -432,6 +482,8 @@ impl Component for Ports {
             let mut list_rect = layout.bottom;
             list_rect.y += 1;
             list_rect.height -= 1;
+            // -- inside the borders, a host takes at least two lines
+            self.page_rows = (list_rect.height.saturating_sub(2) / 2) as usize;
 
             let list = self.make_list(list_rect);
             f.render_stateful_widget(list, list_rect, &mut self.list_state.clone());
@@ -1,5 +1,6 @@
-
+use chrono::{DateTime, Local};
 use color_eyre::eyre::Result;
+use crossterm::event::KeyEvent;
 use ratatui::{prelude::*, widgets::*};
 use tokio::sync::mpsc::Sender;
 
@@ -9,10 +10,17 @@ use crate::{
     config::Config,
 };
 
+/// How long the last error stays on the status line unless a key dismisses it.
+const ERROR_DISPLAY_SECS: i64 = 15;
+/// How long a status message such as "copied" stays.
+const STATUS_DISPLAY_SECS: i64 = 3;
+
 #[derive(Default)]
 pub struct Title {
     command_tx: Option<Sender<Action>>,
     config: Config,
+    last_error: Option<(DateTime<Local>, String)>,
+    last_status: Option<(DateTime<Local>, String)>,
 }
 
 impl Title {
@@ -20,8 +28,25 @@ impl Title {
         Self {
             command_tx: None,
             config: Config::default(),
+            last_error: None,
+            last_status: None,
         }
     }
+
+    fn make_error_line(time: &DateTime<Local>, msg: &str) -> Line<'static> {
+        // -- multi-line errors are collapsed to their first line
+        let first_line = msg.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", time.format("%H:%M:%S")),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!("{} ", first_line.trim()),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+        ])
+    }
 }
 
 impl Component for Title {
@@ -39,11 +64,50 @@ impl Component for Title {
         Ok(())
     }
 
+    fn handle_key_events(&mut self, _key: KeyEvent) -> Result<Option<Action>> {
+        self.last_error = None;
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Error(msg) => {
+                self.last_error = Some((Local::now(), msg));
+            }
+            Action::Status(msg) => {
+                self.last_status = Some((Local::now(), msg));
+            }
+            Action::Tick => {
+                if let Some((time, _)) = &self.last_error {
+                    if (Local::now() - *time).num_seconds() >= ERROR_DISPLAY_SECS {
+                        self.last_error = None;
+                    }
+                }
+                if let Some((time, _)) = &self.last_status {
+                    if (Local::now() - *time).num_seconds() >= STATUS_DISPLAY_SECS {
+                        self.last_status = None;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
     fn draw(&mut self, f: &mut Frame<'_>, _area: Rect) -> Result<()> {
         let rect = Rect::new(0, 0, f.area().width, 1);
         let version: &str = env!("CARGO_PKG_VERSION");
         let title = format!(" Network Scanner (v{})", version);
         f.render_widget(Paragraph::new(title), rect);
+
+        // -- last error status, it hides a status message
+        if let Some((time, msg)) = &self.last_error {
+            let line = Self::make_error_line(time, msg);
+            f.render_widget(Paragraph::new(line).alignment(Alignment::Right), rect);
+        } else if let Some((_, msg)) = &self.last_status {
+            let line = Span::styled(format!("{} ", msg), Style::default().fg(Color::Green));
+            f.render_widget(Paragraph::new(line).alignment(Alignment::Right), rect);
+        }
         Ok(())
     }
 }
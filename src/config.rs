@@ -5,16 +5,25 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use derive_deref::{Deref, DerefMut};
 use ratatui::{style::{Color, Modifier, Style}, widgets::BorderType};
 use serde::{
-  de::Deserializer,
+  de::{self, Deserializer},
   Deserialize,
 };
+use strum::EnumString;
 
-use crate::{action::Action, mode::Mode};
+use crate::{action::Action, enums::PacketTypeEnum, mode::Mode};
 
 pub const DEFAULT_BORDER_STYLE: BorderType = BorderType::Rounded; 
 
 const CONFIG: &str = include_str!("../.config/config.json5");
 
+/// Largest `capture.packet_history_size`, full buffers of this size take a few
+/// hundred megabytes at the default snap length.
+pub const MAX_PACKET_HISTORY_SIZE: usize = 100_000;
+
+/// Largest `capture.payload_snippet`. TCP and UDP packets keep up to this many
+/// payload bytes on top of the frame, in every packet type buffer and in All.
+pub const MAX_PAYLOAD_SNIPPET: usize = 1024;
+
 #[derive(Clone, Debug, Deserialize, Default)]
 pub struct AppConfig {
   #[serde(default)]
@@ -31,6 +40,340 @@ pub struct Config {
   pub keybindings: KeyBindings,
   #[serde(default)]
   pub styles: Styles,
+  #[serde(default)]
+  pub export: ExportConfig,
+  #[serde(default)]
+  pub capture: CaptureConfig,
+  #[serde(default)]
+  pub ui: UiConfig,
+  #[serde(default)]
+  pub discovery: DiscoveryConfig,
+  /// Packet log rows restyled by filter, the first matching rule wins. Rows
+  /// no rule matches keep the protocol colors.
+  #[serde(default)]
+  pub packet_colors: Vec<PacketColorRule>,
+}
+
+/// Style for the packet log rows a filter matches, e.g.
+/// `{ "filter": "port:22", "style": "dim gray12" }`. The filter takes the same
+/// tokens as the filter input, `proto:arp` for a protocol.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct PacketColorRule {
+  pub filter: String,
+  pub style: String,
+}
+
+/// Tick/render rates. Command line flags take precedence over these.
+#[derive(Clone, Debug, Deserialize)]
+pub struct UiConfig {
+  /// Logic updates per second.
+  #[serde(default = "default_tick_rate")]
+  pub tick_rate: f64,
+  /// Frames rendered per second while active.
+  #[serde(default = "default_frame_rate")]
+  pub frame_rate: f64,
+  /// Drop to `idle_frame_rate` after `idle_after_secs` without input or new packets.
+  #[serde(default = "default_true")]
+  pub low_power: bool,
+  #[serde(default = "default_idle_frame_rate")]
+  pub idle_frame_rate: f64,
+  #[serde(default = "default_idle_after_secs")]
+  pub idle_after_secs: u64,
+  /// Shade every other row of the packet log.
+  #[serde(default)]
+  pub zebra_rows: bool,
+  /// Keep the filters applied from the input box across restarts.
+  #[serde(default = "default_true")]
+  pub save_filter_history: bool,
+  /// Name well-known ports in the packet log, `443(https)`.
+  #[serde(default = "default_true")]
+  pub port_names: bool,
+}
+
+impl Default for UiConfig {
+  fn default() -> Self {
+    Self {
+      tick_rate: default_tick_rate(),
+      frame_rate: default_frame_rate(),
+      low_power: true,
+      idle_frame_rate: default_idle_frame_rate(),
+      idle_after_secs: default_idle_after_secs(),
+      zebra_rows: false,
+      save_filter_history: true,
+      port_names: true,
+    }
+  }
+}
+
+fn default_tick_rate() -> f64 {
+  1.0
+}
+
+fn default_frame_rate() -> f64 {
+  10.0
+}
+
+fn default_idle_frame_rate() -> f64 {
+  2.0
+}
+
+fn default_idle_after_secs() -> u64 {
+  5
+}
+
+/// Settings for the packet capture thread.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CaptureConfig {
+  /// Number of leading bytes of each frame kept in memory.
+  #[serde(default = "default_snaplen")]
+  pub snaplen: usize,
+  /// How long a capture read blocks before returning, in milliseconds. This is
+  /// also the worst-case delay before the capture thread notices a stop request,
+  /// so longer values trade shutdown/interface-switch latency for fewer wakeups
+  /// on idle links.
+  #[serde(default = "default_read_timeout_ms")]
+  pub read_timeout_ms: u64,
+  /// How long an interface switch or shutdown waits for the capture thread to
+  /// exit, in milliseconds. Never less than two read timeouts.
+  #[serde(default = "default_join_timeout_ms")]
+  pub join_timeout_ms: u64,
+  /// Only decode frames of the packet type being viewed (ARP is always decoded).
+  /// Saves CPU when focused on one protocol, but the other packet types, the
+  /// flows view and the Traffic tab miss everything captured meanwhile.
+  #[serde(default)]
+  pub selected_type_only: bool,
+  /// Packet types never decoded or stored, e.g. `["Tcp", "Udp"]`. They also
+  /// drop out of the packet type cycle; ARP still feeds host discovery.
+  #[serde(default)]
+  pub disabled_protocols: Vec<PacketTypeEnum>,
+  /// Keep 1 in N captured frames, decided before decoding so the sample is
+  /// uniform across protocols. `1` keeps every frame.
+  #[serde(default = "default_sample_rate")]
+  pub sample_rate: u32,
+  /// Stop capturing after this many frames, `0` for no limit. Frames from
+  /// every interface captured on count together, as do their bytes.
+  #[serde(default)]
+  pub max_packets: u64,
+  /// Stop capturing after this many bytes on the wire, `0` for no limit.
+  #[serde(default)]
+  pub max_bytes: u64,
+  /// Stop capturing after this many seconds of capture, `0` for no limit.
+  /// Time spent paused doesn't count.
+  #[serde(default)]
+  pub max_duration_secs: u64,
+  /// Flows without packets for this many seconds leave the flows view, `0`
+  /// keeps them until the table is full.
+  #[serde(default = "default_flow_idle_secs")]
+  pub flow_idle_secs: u64,
+  /// Packets kept in memory per packet type, and again for the All view.
+  #[serde(default = "default_packet_history_size")]
+  pub packet_history_size: usize,
+  /// Leading payload bytes kept per TCP and UDP packet for the detail view and
+  /// the CSV export, `0` keeps none. At most [`MAX_PAYLOAD_SNIPPET`].
+  #[serde(default = "default_payload_snippet")]
+  pub payload_snippet: usize,
+  /// TCP ports whose payload is inspected for cleartext HTTP request/status lines.
+  #[serde(default = "default_http_ports")]
+  pub http_ports: Vec<u16>,
+  /// More ports per application protocol, e.g. `{ "8081": "http", "5300": "dns" }`,
+  /// on top of the well-known ones. Unknown protocol names are skipped with a warning.
+  #[serde(default)]
+  pub port_protocols: HashMap<String, String>,
+  /// `port_protocols` parsed by [`Config::new`].
+  #[serde(skip)]
+  pub port_decoders: HashMap<u16, PortProtocol>,
+}
+
+/// Application protocols a port can be mapped to in `capture.port_protocols`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum PortProtocol {
+  /// HTTP request/status line decoding.
+  Http,
+  /// Counted as DNS by the "DNS only" filter preset.
+  Dns,
+}
+
+impl CaptureConfig {
+  pub fn has_limits(&self) -> bool {
+    self.max_packets > 0 || self.max_bytes > 0 || self.max_duration_secs > 0
+  }
+
+  /// The same settings with the `max_*` limits off.
+  pub fn without_limits(&self) -> Self {
+    Self { max_packets: 0, max_bytes: 0, max_duration_secs: 0, ..self.clone() }
+  }
+
+  /// Whether a capture that has seen `packets` frames of `bytes` in total over
+  /// `secs` of capture time hit any of the limits.
+  pub fn limit_reached(&self, packets: u64, bytes: u64, secs: u64) -> bool {
+    (self.max_packets > 0 && packets >= self.max_packets)
+      || (self.max_bytes > 0 && bytes >= self.max_bytes)
+      || (self.max_duration_secs > 0 && secs >= self.max_duration_secs)
+  }
+
+  /// Ports mapped to `protocol` in `port_protocols`, sorted.
+  pub fn ports_for(&self, protocol: PortProtocol) -> Vec<u16> {
+    let mut ports: Vec<u16> =
+      self.port_decoders.iter().filter(|(_, p)| **p == protocol).map(|(port, _)| *port).collect();
+    ports.sort_unstable();
+    ports
+  }
+
+  fn parse_port_protocols(&mut self) -> Result<(), String> {
+    for (port, name) in &self.port_protocols {
+      let Ok(port) = port.parse::<u16>() else {
+        return Err(format!("capture.port_protocols: \"{}\" is not a port number", port));
+      };
+      match name.parse::<PortProtocol>() {
+        Ok(protocol) => {
+          self.port_decoders.insert(port, protocol);
+        }
+        Err(_) => log::warn!("capture.port_protocols: unknown protocol \"{}\" for port {}, ignored", name, port),
+      }
+    }
+    Ok(())
+  }
+}
+
+impl Default for CaptureConfig {
+  fn default() -> Self {
+    Self {
+      snaplen: default_snaplen(),
+      read_timeout_ms: default_read_timeout_ms(),
+      join_timeout_ms: default_join_timeout_ms(),
+      selected_type_only: false,
+      disabled_protocols: Vec::new(),
+      sample_rate: default_sample_rate(),
+      max_packets: 0,
+      max_bytes: 0,
+      max_duration_secs: 0,
+      flow_idle_secs: default_flow_idle_secs(),
+      packet_history_size: default_packet_history_size(),
+      payload_snippet: default_payload_snippet(),
+      http_ports: default_http_ports(),
+      port_protocols: HashMap::new(),
+      port_decoders: HashMap::new(),
+    }
+  }
+}
+
+fn default_snaplen() -> usize {
+  256
+}
+
+fn default_read_timeout_ms() -> u64 {
+  100
+}
+
+fn default_join_timeout_ms() -> u64 {
+  2000
+}
+
+fn default_sample_rate() -> u32 {
+  1
+}
+
+fn default_flow_idle_secs() -> u64 {
+  300
+}
+
+fn default_packet_history_size() -> usize {
+  1000
+}
+
+fn default_payload_snippet() -> usize {
+  128
+}
+
+fn default_http_ports() -> Vec<u16> {
+  vec![80, 8000, 8080]
+}
+
+/// Settings controlling the CSV export format.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExportConfig {
+  /// Collapse sequential ports into ranges (`22,80,8080-8082`) instead of
+  /// joining every port with `:`.
+  #[serde(default = "default_true")]
+  pub compact_port_ranges: bool,
+  /// Roll over to a new indexed file once a CSV grows past this many bytes.
+  #[serde(default)]
+  pub max_file_bytes: Option<u64>,
+  /// Roll over to a new indexed file once a CSV holds this many rows.
+  #[serde(default)]
+  pub max_file_rows: Option<usize>,
+  /// Also write the summary report as HTML, next to the Markdown one.
+  #[serde(default)]
+  pub report_html: bool,
+  /// Add service names to the scanned ports, `22(ssh),80(http)`.
+  #[serde(default)]
+  pub port_names: bool,
+}
+
+impl Default for ExportConfig {
+  fn default() -> Self {
+    Self {
+      compact_port_ranges: true,
+      max_file_bytes: None,
+      max_file_rows: None,
+      report_html: false,
+      port_names: false,
+    }
+  }
+}
+
+impl ExportConfig {
+  pub fn rotation_enabled(&self) -> bool {
+    self.max_file_bytes.is_some() || self.max_file_rows.is_some()
+  }
+}
+
+/// How long hosts stand out in the discovery table and how address conflicts
+/// are reported. `0` turns a marker or the detection off.
+#[derive(Clone, Debug, Deserialize)]
+pub struct DiscoveryConfig {
+  /// Highlight hosts first seen within this many seconds, fading over the interval.
+  #[serde(default = "default_new_host_secs")]
+  pub new_host_secs: u64,
+  /// Dim hosts not seen (ping reply, ARP or Neighbor Discovery) for this many seconds.
+  #[serde(default = "default_stale_host_secs")]
+  pub stale_host_secs: u64,
+  /// Two MACs sending ARP for the same IPv4 address within this many seconds
+  /// are reported as an address conflict.
+  #[serde(default = "default_ip_conflict_secs")]
+  pub ip_conflict_secs: u64,
+  /// Keep the conflict warning under the packet log after the conflict went
+  /// quiet, until the capture is cleared or the interface switched.
+  #[serde(default)]
+  pub sticky_ip_conflicts: bool,
+}
+
+impl Default for DiscoveryConfig {
+  fn default() -> Self {
+    Self {
+      new_host_secs: default_new_host_secs(),
+      stale_host_secs: default_stale_host_secs(),
+      ip_conflict_secs: default_ip_conflict_secs(),
+      sticky_ip_conflicts: false,
+    }
+  }
+}
+
+fn default_new_host_secs() -> u64 {
+  30
+}
+
+fn default_stale_host_secs() -> u64 {
+  600
+}
+
+fn default_ip_conflict_secs() -> u64 {
+  60
+}
+
+fn default_true() -> bool {
+  true
 }
 
 impl Config {
@@ -82,6 +425,52 @@ impl Config {
       }
     }
 
+    cfg.keybindings.validate().map_err(config::ConfigError::Message)?;
+
+    if cfg.capture.snaplen == 0 {
+      return Err(config::ConfigError::Message("capture.snaplen must be greater than 0".to_string()));
+    }
+    if cfg.export.max_file_bytes == Some(0) || cfg.export.max_file_rows == Some(0) {
+      return Err(config::ConfigError::Message(
+        "export.max_file_bytes and export.max_file_rows must be greater than 0".to_string(),
+      ));
+    }
+    if cfg.capture.disabled_protocols.contains(&PacketTypeEnum::All) {
+      return Err(config::ConfigError::Message("capture.disabled_protocols can't contain All".to_string()));
+    }
+    if cfg.capture.packet_history_size == 0 || cfg.capture.packet_history_size > MAX_PACKET_HISTORY_SIZE {
+      return Err(config::ConfigError::Message(format!(
+        "capture.packet_history_size must be between 1 and {}: every packet type keeps this many \
+         packets and the All view as many again, each with up to capture.snaplen bytes of frame",
+        MAX_PACKET_HISTORY_SIZE
+      )));
+    }
+    if cfg.capture.payload_snippet > MAX_PAYLOAD_SNIPPET {
+      return Err(config::ConfigError::Message(format!(
+        "capture.payload_snippet must be at most {}: every stored TCP and UDP packet keeps this many \
+         payload bytes",
+        MAX_PAYLOAD_SNIPPET
+      )));
+    }
+    if cfg.capture.sample_rate == 0 {
+      return Err(config::ConfigError::Message("capture.sample_rate must be at least 1".to_string()));
+    }
+    cfg.capture.parse_port_protocols().map_err(config::ConfigError::Message)?;
+    if cfg.capture.read_timeout_ms == 0 {
+      return Err(config::ConfigError::Message("capture.read_timeout_ms must be greater than 0".to_string()));
+    }
+    if cfg.ui.tick_rate <= 0.0 || cfg.ui.frame_rate <= 0.0 || cfg.ui.idle_frame_rate <= 0.0 {
+      return Err(config::ConfigError::Message(
+        "ui.tick_rate, ui.frame_rate and ui.idle_frame_rate must be greater than 0".to_string(),
+      ));
+    }
+
+    if cfg.discovery.stale_host_secs != 0 && cfg.discovery.stale_host_secs <= cfg.discovery.new_host_secs {
+      return Err(config::ConfigError::Message(
+        "discovery.stale_host_secs must be greater than discovery.new_host_secs".to_string(),
+      ));
+    }
+
     Ok(cfg)
   }
 }
@@ -89,6 +478,48 @@ impl Config {
 #[derive(Clone, Debug, Default, Deref, DerefMut)]
 pub struct KeyBindings(pub HashMap<Mode, HashMap<Vec<KeyEvent>, Action>>);
 
+impl KeyBindings {
+  /// Whether `key` on its own triggers an action in `mode`.
+  pub fn is_bound(&self, mode: Mode, key: &KeyEvent) -> bool {
+    self.get(&mode).is_some_and(|keymap| keymap.contains_key(&vec![*key]))
+  }
+
+  /// Key sequences bound to `action` in `mode`, formatted like the config
+  /// (`<ctrl-s>`, `<g><g>`) and sorted.
+  pub fn keys_for(&self, mode: Mode, action: &Action) -> Vec<String> {
+    let mut keys: Vec<String> = self
+      .get(&mode)
+      .map(|keymap| {
+        keymap
+          .iter()
+          .filter(|(_, a)| *a == action)
+          .map(|(keys, _)| keys.iter().map(|k| format!("<{}>", key_event_to_string(k))).collect())
+          .collect()
+      })
+      .unwrap_or_default();
+    keys.sort();
+    keys
+  }
+
+  /// Reject key sequences that can never fire because their first key is
+  /// already bound on its own.
+  pub fn validate(&self) -> Result<(), String> {
+    for (mode, keymap) in self.iter() {
+      for keys in keymap.keys() {
+        if keys.len() > 1 && keymap.contains_key(&keys[..1]) {
+          return Err(format!(
+            "Key sequence '{}' in {:?} mode is shadowed by the binding for '{}'",
+            keys.iter().map(key_event_to_string).collect::<Vec<_>>().join(" "),
+            mode,
+            key_event_to_string(&keys[0])
+          ));
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
 impl<'de> Deserialize<'de> for KeyBindings {
   fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
   where
@@ -96,24 +527,30 @@ impl<'de> Deserialize<'de> for KeyBindings {
   {
     let parsed_map = HashMap::<Mode, HashMap<String, Action>>::deserialize(deserializer)?;
 
-    let keybindings = parsed_map
-      .into_iter()
-      .map(|(mode, inner_map)| {
-        let converted_inner_map = inner_map
-          .into_iter()
-          .filter_map(|(key_str, cmd)| {
-            match parse_key_sequence(&key_str) {
-              Ok(keys) => Some((keys, cmd)),
-              Err(e) => {
-                log::warn!("Invalid key binding '{}' in config: {}", key_str, e);
-                None
-              }
-            }
-          })
-          .collect();
-        (mode, converted_inner_map)
-      })
-      .collect();
+    let mut keybindings = HashMap::new();
+    for (mode, inner_map) in parsed_map {
+      let mut converted_inner_map: HashMap<Vec<KeyEvent>, Action> = HashMap::new();
+      for (key_str, cmd) in inner_map {
+        let keys = match parse_key_sequence(&key_str) {
+          Ok(keys) => keys,
+          Err(e) => {
+            log::warn!("Invalid key binding '{}' in config: {}", key_str, e);
+            continue;
+          },
+        };
+        // -- different spellings of the same key (e.g. "<Ctrl-d>" and "<ctrl-d>") must agree
+        if let Some(existing) = converted_inner_map.get(&keys) {
+          if *existing != cmd {
+            return Err(de::Error::custom(format!(
+              "Conflicting key bindings for '{}' in {:?} mode: {:?} and {:?}",
+              key_str, mode, existing, cmd
+            )));
+          }
+        }
+        converted_inner_map.insert(keys, cmd);
+      }
+      keybindings.insert(mode, converted_inner_map);
+    }
 
     Ok(KeyBindings(keybindings))
   }
@@ -310,6 +747,18 @@ impl<'de> Deserialize<'de> for Styles {
   }
 }
 
+/// Like [`parse_style`], but a color name that doesn't parse is an error
+/// instead of being left out.
+pub fn try_parse_style(line: &str) -> Result<Style, String> {
+  let (foreground, background) = line.split_at(line.to_lowercase().find("on ").unwrap_or(line.len()));
+  for color in [process_color_string(foreground).0, process_color_string(&background.replace("on ", "")).0] {
+    if !color.trim().is_empty() && parse_color(&color).is_none() {
+      return Err(format!("unknown color \"{}\"", color.trim()));
+    }
+  }
+  Ok(parse_style(line))
+}
+
 pub fn parse_style(line: &str) -> Style {
   let (foreground, background) = line.split_at(line.to_lowercase().find("on ").unwrap_or(line.len()));
   let foreground = process_color_string(foreground);
@@ -332,7 +781,8 @@ fn process_color_string(color_str: &str) -> (String, Modifier) {
     .replace("bright ", "")
     .replace("bold ", "")
     .replace("underline ", "")
-    .replace("inverse ", "");
+    .replace("inverse ", "")
+    .replace("dim ", "");
 
   let mut modifiers = Modifier::empty();
   if color_str.contains("underline") {
@@ -344,6 +794,9 @@ fn process_color_string(color_str: &str) -> (String, Modifier) {
   if color_str.contains("inverse") {
     modifiers |= Modifier::REVERSED;
   }
+  if color_str.contains("dim") {
+    modifiers |= Modifier::DIM;
+  }
 
   (color, modifiers)
 }
@@ -410,6 +863,121 @@ mod tests {
 
   use super::*;
 
+  #[test]
+  fn test_export_config_defaults() {
+    let cfg: Config = json5::from_str("{}").unwrap();
+    assert!(cfg.export.compact_port_ranges);
+    assert!(!cfg.export.rotation_enabled());
+    assert!(!cfg.export.report_html);
+    let cfg: Config = json5::from_str(r#"{ "export": { "max_file_rows": 1000 } }"#).unwrap();
+    assert!(cfg.export.rotation_enabled());
+    let cfg: Config = json5::from_str(r#"{ "export": { "compact_port_ranges": false } }"#).unwrap();
+    assert!(!cfg.export.compact_port_ranges);
+  }
+
+  #[test]
+  fn test_capture_config_defaults() {
+    let cfg: Config = json5::from_str("{}").unwrap();
+    assert_eq!(cfg.capture.snaplen, 256);
+    assert_eq!(cfg.capture.payload_snippet, 128);
+    assert_eq!(cfg.capture.read_timeout_ms, 100);
+    assert_eq!(cfg.capture.join_timeout_ms, 2000);
+    assert!(!cfg.capture.selected_type_only);
+    assert_eq!(cfg.capture.sample_rate, 1);
+    assert!(cfg.capture.disabled_protocols.is_empty());
+    let cfg: Config = json5::from_str(r#"{ "capture": { "disabled_protocols": ["Tcp", "Icmp6"] } }"#).unwrap();
+    assert_eq!(cfg.capture.disabled_protocols, vec![PacketTypeEnum::Tcp, PacketTypeEnum::Icmp6]);
+    // -- an unknown or miscased name fails the load instead of disabling nothing
+    let unknown = r#"{ "capture": { "disabled_protocols": ["Smtp"] } }"#;
+    assert!(json5::from_str::<Config>(unknown).is_err());
+    let unknown = r#"{ "capture": { "disabled_protocols": ["tcp"] } }"#;
+    assert!(json5::from_str::<Config>(unknown).is_err());
+    assert_eq!(cfg.capture.http_ports, vec![80, 8000, 8080]);
+    assert!(cfg.capture.port_protocols.is_empty());
+    assert!(!cfg.capture.has_limits());
+    assert!(!cfg.capture.limit_reached(u64::MAX, u64::MAX, u64::MAX));
+    let cfg: Config = json5::from_str(r#"{ "capture": { "max_packets": 100, "max_duration_secs": 30 } }"#).unwrap();
+    assert!(cfg.capture.has_limits());
+    assert!(!cfg.capture.limit_reached(99, u64::MAX, 29));
+    assert!(cfg.capture.limit_reached(100, 0, 0));
+    assert!(cfg.capture.limit_reached(0, 0, 30));
+    let mut cfg: Config =
+      json5::from_str(r#"{ "capture": { "port_protocols": { "8081": "http", "5300": "DNS", "7000": "smtp" } } }"#)
+        .unwrap();
+    assert!(cfg.capture.parse_port_protocols().is_ok());
+    assert_eq!(cfg.capture.ports_for(PortProtocol::Http), vec![8081]);
+    assert_eq!(cfg.capture.ports_for(PortProtocol::Dns), vec![5300]);
+    let mut cfg: Config = json5::from_str(r#"{ "capture": { "port_protocols": { "http": "http" } } }"#).unwrap();
+    assert!(cfg.capture.parse_port_protocols().is_err());
+    let cfg: Config = json5::from_str(r#"{ "capture": { "snaplen": 64, "read_timeout_ms": 1000 } }"#).unwrap();
+    assert_eq!(cfg.capture.snaplen, 64);
+    assert_eq!(cfg.capture.read_timeout_ms, 1000);
+  }
+
+  #[test]
+  fn test_ui_config_defaults() {
+    let cfg: Config = json5::from_str("{}").unwrap();
+    assert_eq!(cfg.ui.tick_rate, 1.0);
+    assert_eq!(cfg.ui.frame_rate, 10.0);
+    assert!(cfg.ui.low_power);
+    assert_eq!(cfg.ui.idle_frame_rate, 2.0);
+    assert_eq!(cfg.ui.idle_after_secs, 5);
+    assert!(!cfg.ui.zebra_rows);
+    assert!(cfg.ui.save_filter_history);
+    assert!(cfg.ui.port_names);
+    let cfg: Config = json5::from_str(r#"{ "ui": { "frame_rate": 30, "low_power": false } }"#).unwrap();
+    assert_eq!(cfg.ui.frame_rate, 30.0);
+    assert!(!cfg.ui.low_power);
+  }
+
+  #[test]
+  fn test_discovery_config_defaults() {
+    let cfg: Config = json5::from_str("{}").unwrap();
+    assert_eq!(cfg.discovery.new_host_secs, 30);
+    assert_eq!(cfg.discovery.stale_host_secs, 600);
+    assert_eq!(cfg.discovery.ip_conflict_secs, 60);
+    assert!(!cfg.discovery.sticky_ip_conflicts);
+    let cfg: Config = json5::from_str(r#"{ "discovery": { "stale_host_secs": 0 } }"#).unwrap();
+    assert_eq!(cfg.discovery.new_host_secs, 30);
+    assert_eq!(cfg.discovery.stale_host_secs, 0);
+  }
+
+  #[test]
+  fn test_default_keybindings_are_valid() {
+    let cfg: Config = json5::from_str(CONFIG).unwrap();
+    assert!(cfg.keybindings.validate().is_ok());
+    assert!(cfg.keybindings.is_bound(Mode::Input, &KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())));
+    assert_eq!(
+      cfg.keybindings.get(&Mode::Input).unwrap().get(&parse_key_sequence("<Enter>").unwrap()),
+      Some(&Action::Submit)
+    );
+  }
+
+  #[test]
+  fn test_conflicting_keybindings() {
+    let res = json5::from_str::<Config>(r#"{ "keybindings": { "Normal": { "<Ctrl-x>": "Dump", "<ctrl-x>": "Export" } } }"#);
+    assert!(res.is_err());
+    let cfg: Config = json5::from_str(r#"{ "keybindings": { "Normal": { "<Ctrl-x>": "Dump", "<ctrl-x>": "Dump" } } }"#).unwrap();
+    assert!(cfg.keybindings.validate().is_ok());
+  }
+
+  #[test]
+  fn test_keys_for() {
+    let cfg: Config =
+      json5::from_str(r#"{ "keybindings": { "Normal": { "<q>": "Quit", "<Ctrl-d>": "Quit", "<g><x>": "Graph" } } }"#)
+        .unwrap();
+    assert_eq!(cfg.keybindings.keys_for(Mode::Normal, &Action::Quit), vec!["<ctrl-d>", "<q>"]);
+    assert_eq!(cfg.keybindings.keys_for(Mode::Normal, &Action::GraphToggle), vec!["<g><x>"]);
+    assert!(cfg.keybindings.keys_for(Mode::Input, &Action::Quit).is_empty());
+  }
+
+  #[test]
+  fn test_shadowed_key_sequence() {
+    let cfg: Config =
+      json5::from_str(r#"{ "keybindings": { "Normal": { "<g>": "Graph", "<g><g>": "Quit" } } }"#).unwrap();
+    assert!(cfg.keybindings.validate().is_err());
+  }
+
   #[test]
   fn test_parse_style_default() {
     let style = parse_style("");
@@ -435,6 +1003,18 @@ mod tests {
     assert_eq!(style.bg, Some(Color::Indexed(4)));
   }
 
+  #[test]
+  fn test_try_parse_style() {
+    let style = try_parse_style("dim gray12 on black").unwrap();
+    assert_eq!(style.fg, Some(Color::Indexed(244)));
+    assert_eq!(style.bg, Some(Color::Indexed(0)));
+    assert!(style.add_modifier.contains(Modifier::DIM));
+    assert_eq!(try_parse_style("bold red"), Ok(parse_style("bold red")));
+    assert!(try_parse_style("bold reed").is_err());
+    assert!(try_parse_style("red on purple").is_err());
+    assert_eq!(try_parse_style(""), Ok(Style::default()));
+  }
+
   #[test]
   fn test_process_color_string() {
     let (color, modifiers) = process_color_string("underline bold inverse gray");
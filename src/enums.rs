@@ -1,4 +1,8 @@
-use crate::components::{discovery::ScannedIp, ports::ScannedIpPorts};
+use crate::components::{
+    discovery::ScannedIp,
+    packetdump::{FlowStats, TimeFormat},
+    ports::ScannedIpPorts,
+};
 use chrono::{DateTime, Local};
 use pnet::{
     packet::{
@@ -8,7 +12,8 @@ use pnet::{
     },
     util::MacAddr,
 };
-use std::net::{IpAddr, Ipv4Addr};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::sync::Arc;
 use strum::{Display, EnumCount, EnumIter, FromRepr};
 
@@ -25,6 +30,19 @@ pub struct ExportData {
     pub tcp_packets: Arc<Vec<(DateTime<Local>, PacketsInfoTypesEnum)>>,
     pub icmp_packets: Arc<Vec<(DateTime<Local>, PacketsInfoTypesEnum)>>,
     pub icmp6_packets: Arc<Vec<(DateTime<Local>, PacketsInfoTypesEnum)>>,
+    pub igmp_packets: Arc<Vec<(DateTime<Local>, PacketsInfoTypesEnum)>>,
+    pub other_packets: Arc<Vec<(DateTime<Local>, PacketsInfoTypesEnum)>>,
+    pub anomalies: Arc<Vec<Anomaly>>,
+    /// Flows of the Packets tab flows view.
+    pub flows: Arc<Vec<FlowStats>>,
+    /// Interface the packets were captured on.
+    pub interface: Option<String>,
+    /// Packet times are written as the packet log shows them.
+    pub time_format: TimeFormat,
+    /// Packets lost between the capture thread and the UI.
+    pub dropped: u64,
+    /// Frames the kernel discarded, where the capture backend reports it.
+    pub kernel_dropped: Option<u64>,
 }
 
 // Manual PartialEq implementation for ExportData
@@ -38,10 +56,72 @@ impl PartialEq for ExportData {
             && self.tcp_packets.as_ref() == other.tcp_packets.as_ref()
             && self.icmp_packets.as_ref() == other.icmp_packets.as_ref()
             && self.icmp6_packets.as_ref() == other.icmp6_packets.as_ref()
+            && self.igmp_packets.as_ref() == other.igmp_packets.as_ref()
+            && self.other_packets.as_ref() == other.other_packets.as_ref()
+            && self.anomalies.as_ref() == other.anomalies.as_ref()
+            && self.flows.as_ref() == other.flows.as_ref()
+            && self.interface == other.interface
+            && self.time_format == other.time_format
+            && self.dropped == other.dropped
+            && self.kernel_dropped == other.kernel_dropped
+    }
+}
+
+// pnet header field newtypes have no serde support, these mirror them for
+// saving packets in session files
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "ArpOperation")]
+struct ArpOperationDef(pub u16);
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "IcmpType")]
+struct IcmpTypeDef(pub u8);
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Icmpv6Type")]
+struct Icmpv6TypeDef(pub u8);
+
+/// 802.1Q tag of a frame, with the outer (service) tag of a QinQ double tag.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Vlan {
+    pub id: u16,
+    pub outer: Option<u16>,
+}
+
+impl std::fmt::Display for Vlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.outer {
+            Some(outer) => write!(f, "vlan {}.{}", outer, self.id),
+            None => write!(f, "vlan {}", self.id),
+        }
+    }
+}
+
+/// IP header fields shared by all IP based packets. For IPv6 `ttl` holds the
+/// hop limit and `dscp` the upper six bits of the traffic class.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IpMeta {
+    pub ttl: u8,
+    pub dscp: u8,
+    /// TTL low enough to hint at a routing loop or a traceroute probe.
+    #[serde(default)]
+    pub low_ttl: bool,
+}
+
+impl std::fmt::Display for IpMeta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ttl: {}", self.ttl)?;
+        if self.dscp != 0 {
+            write!(f, ", dscp: {}", self.dscp)?;
+        }
+        if self.low_ttl {
+            write!(f, ", low ttl")?;
+        }
+        Ok(())
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UDPPacketInfo {
     pub interface_name: String,
     pub source: IpAddr,
@@ -49,10 +129,27 @@ pub struct UDPPacketInfo {
     pub destination: IpAddr,
     pub destination_port: u16,
     pub length: usize,
+    #[serde(default)]
+    pub dns_info: Option<DnsInfo>,
+    // -- boxed, few UDP packets carry them and they would bloat every row
+    #[serde(default)]
+    pub dhcp_info: Option<Box<DhcpInfo>>,
+    #[serde(default)]
+    pub mdns_info: Option<Box<MdnsInfo>>,
+    #[serde(default)]
+    pub ssdp_info: Option<Box<SsdpInfo>>,
+    /// Leading bytes of the datagram payload, up to `capture.payload_snippet`.
+    #[serde(default)]
+    pub payload: Vec<u8>,
+    pub ip: IpMeta,
+    #[serde(default)]
+    pub vlan: Option<Vlan>,
     pub raw_str: String,
+    pub raw_bytes: Vec<u8>,
+    pub orig_len: usize,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TCPPacketInfo {
     pub interface_name: String,
     pub source: IpAddr,
@@ -60,47 +157,409 @@ pub struct TCPPacketInfo {
     pub destination: IpAddr,
     pub destination_port: u16,
     pub length: usize,
+    pub flags: u8,
+    #[serde(default)]
+    pub sequence: u32,
+    #[serde(default)]
+    pub acknowledgement: u32,
+    /// Sequence and acknowledgement numbers relative to the start of each
+    /// direction, as far as the capture saw it.
+    #[serde(default)]
+    pub relative_sequence: Option<u32>,
+    #[serde(default)]
+    pub relative_acknowledgement: Option<u32>,
+    /// Segment length without the TCP header.
+    #[serde(default)]
+    pub payload_len: usize,
+    /// Repeats a data segment already seen on this connection.
+    #[serde(default)]
+    pub retransmission: bool,
+    pub http_info: Option<HttpInfo>,
+    /// Leading bytes of the segment payload, up to `capture.payload_snippet`.
+    #[serde(default)]
+    pub payload: Vec<u8>,
+    pub ip: IpMeta,
+    #[serde(default)]
+    pub vlan: Option<Vlan>,
     pub raw_str: String,
+    pub raw_bytes: Vec<u8>,
+    pub orig_len: usize,
+}
+
+/// First question and the answers of a DNS message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DnsInfo {
+    pub id: u16,
+    pub response: bool,
+    pub rcode: u8,
+    pub query: String,
+    pub qtype: String,
+    /// Answer records as type and data, e.g. `A 192.0.2.1`.
+    pub answers: Vec<String>,
+}
+
+/// Services and host addresses of a multicast DNS message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MdnsInfo {
+    pub response: bool,
+    /// Names asked for, usually service types like `_airplay._tcp.local`.
+    pub queries: Vec<String>,
+    /// Service instances from PTR and SRV records.
+    pub services: Vec<String>,
+    /// Addresses from A and AAAA records with the host name they belong to.
+    pub hosts: Vec<(IpAddr, String)>,
+}
+
+/// Start line and discovery headers of an SSDP message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SsdpInfo {
+    /// `NOTIFY`, `M-SEARCH` or `response`.
+    pub method: String,
+    /// `NTS` of a notification, `ssdp:alive` or `ssdp:byebye`.
+    pub sub_type: Option<String>,
+    /// Device or service type from `NT` or `ST`.
+    pub target: Option<String>,
+    pub usn: Option<String>,
+    pub server: Option<String>,
+    pub location: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Message type and addresses of a DHCP message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DhcpInfo {
+    pub message_type: u8,
+    pub client_mac: Option<MacAddr>,
+    /// Address offered or assigned by the server (`yiaddr`).
+    pub your_ip: Option<Ipv4Addr>,
+    pub server_id: Option<Ipv4Addr>,
+    pub lease_secs: Option<u32>,
+    pub requested_ip: Option<Ipv4Addr>,
+    pub hostname: Option<String>,
+}
+
+/// First line of a cleartext HTTP request or response, with its Host header
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpInfo {
+    pub start_line: String,
+    pub host: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ARPPacketInfo {
     pub interface_name: String,
     pub source_mac: MacAddr,
     pub source_ip: Ipv4Addr,
     pub destination_mac: MacAddr,
     pub destination_ip: Ipv4Addr,
+    #[serde(with = "ArpOperationDef")]
     pub operation: ArpOperation,
+    /// Company the sender MAC is registered to, from the OUI database.
+    #[serde(default)]
+    pub source_vendor: Option<String>,
+    #[serde(default)]
+    pub vlan: Option<Vlan>,
     pub raw_str: String,
+    pub raw_bytes: Vec<u8>,
+    pub orig_len: usize,
+}
+
+/// Destination of the packet quoted by an ICMP error message
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IcmpQuote {
+    pub destination: Ipv4Addr,
+    pub protocol: u8,
+    /// Destination port of a quoted TCP or UDP packet.
+    pub port: Option<u16>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl std::fmt::Display for IcmpQuote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let protocol = match self.protocol {
+            1 => "icmp".to_string(),
+            6 => "tcp".to_string(),
+            17 => "udp".to_string(),
+            other => format!("proto {}", other),
+        };
+        match self.port {
+            Some(port) => write!(f, "{} {}:{}", protocol, self.destination, port),
+            None => write!(f, "{} {}", protocol, self.destination),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ICMPPacketInfo {
     pub interface_name: String,
     pub source: IpAddr,
     pub destination: IpAddr,
     pub seq: u16,
     pub id: u16,
+    #[serde(with = "IcmpTypeDef")]
     pub icmp_type: IcmpType,
+    /// Round-trip time of an echo reply, in milliseconds.
+    #[serde(default)]
+    pub rtt_ms: Option<f64>,
+    /// Echo reply to a request that was already answered.
+    #[serde(default)]
+    pub duplicate: bool,
+    #[serde(default)]
+    pub code: u8,
+    /// Packet an error message is about, from the IP header it quotes.
+    #[serde(default)]
+    pub quote: Option<IcmpQuote>,
+    /// Better first hop named by a redirect.
+    #[serde(default)]
+    pub gateway: Option<Ipv4Addr>,
+    pub ip: IpMeta,
+    #[serde(default)]
+    pub vlan: Option<Vlan>,
     pub raw_str: String,
+    pub raw_bytes: Vec<u8>,
+    pub orig_len: usize,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ICMP6PacketInfo {
     pub interface_name: String,
     pub source: IpAddr,
     pub destination: IpAddr,
+    #[serde(with = "Icmpv6TypeDef")]
     pub icmp_type: Icmpv6Type,
+    /// Target address of a Neighbor Solicitation/Advertisement.
+    #[serde(default)]
+    pub target: Option<Ipv6Addr>,
+    /// Link-layer address from the Neighbor Discovery options.
+    #[serde(default)]
+    pub link_addr: Option<MacAddr>,
+    /// Prefixes and their length announced by a Router Advertisement.
+    #[serde(default)]
+    pub prefixes: Vec<(Ipv6Addr, u8)>,
+    /// Router lifetime of a Router Advertisement, in seconds.
+    #[serde(default)]
+    pub router_lifetime: Option<u16>,
+    pub ip: IpMeta,
+    #[serde(default)]
+    pub vlan: Option<Vlan>,
+    pub raw_str: String,
+    pub raw_bytes: Vec<u8>,
+    pub orig_len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum IgmpType {
+    MembershipQuery,
+    V1MembershipReport,
+    V2MembershipReport,
+    V3MembershipReport,
+    LeaveGroup,
+    Unknown(u8),
+}
+
+impl From<u8> for IgmpType {
+    fn from(value: u8) -> Self {
+        match value {
+            0x11 => IgmpType::MembershipQuery,
+            0x12 => IgmpType::V1MembershipReport,
+            0x16 => IgmpType::V2MembershipReport,
+            0x22 => IgmpType::V3MembershipReport,
+            0x17 => IgmpType::LeaveGroup,
+            other => IgmpType::Unknown(other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IgmpGroupRecord {
+    pub record_type: u8,
+    pub group: Ipv4Addr,
+    pub num_sources: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IGMPPacketInfo {
+    pub interface_name: String,
+    pub source: IpAddr,
+    pub destination: IpAddr,
+    pub igmp_type: IgmpType,
+    pub group: Option<Ipv4Addr>,
+    pub records: Vec<IgmpGroupRecord>,
+    pub ip: IpMeta,
+    #[serde(default)]
+    pub vlan: Option<Vlan>,
     pub raw_str: String,
+    pub raw_bytes: Vec<u8>,
+    pub orig_len: usize,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Ethernet frame of a type without a dedicated decoder (LLDP, STP, PPPoE, ...).
+/// `ethertype` holds the raw EtherType field, an 802.3 length when <= 1500.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OtherPacketInfo {
+    pub interface_name: String,
+    pub source_mac: MacAddr,
+    pub destination_mac: MacAddr,
+    pub ethertype: u16,
+    pub length: usize,
+    /// What little is known about a frame the decoders could not take
+    /// apart, e.g. an IPv6 fragment without its upper-layer header.
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub vlan: Option<Vlan>,
+    pub raw_str: String,
+    pub raw_bytes: Vec<u8>,
+    pub orig_len: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PacketsInfoTypesEnum {
     Arp(ARPPacketInfo),
     Tcp(TCPPacketInfo),
     Udp(UDPPacketInfo),
     Icmp(ICMPPacketInfo),
     Icmp6(ICMP6PacketInfo),
+    Igmp(IGMPPacketInfo),
+    Other(OtherPacketInfo),
+}
+
+impl PacketsInfoTypesEnum {
+    /// Leading bytes of the frame, limited to the configured snap length.
+    pub fn raw_bytes(&self) -> &[u8] {
+        match self {
+            PacketsInfoTypesEnum::Arp(p) => &p.raw_bytes,
+            PacketsInfoTypesEnum::Tcp(p) => &p.raw_bytes,
+            PacketsInfoTypesEnum::Udp(p) => &p.raw_bytes,
+            PacketsInfoTypesEnum::Icmp(p) => &p.raw_bytes,
+            PacketsInfoTypesEnum::Icmp6(p) => &p.raw_bytes,
+            PacketsInfoTypesEnum::Igmp(p) => &p.raw_bytes,
+            PacketsInfoTypesEnum::Other(p) => &p.raw_bytes,
+        }
+    }
+
+    /// Payload snippet of TCP and UDP packets as hex, `-` when there is none.
+    pub fn payload_hex(&self) -> String {
+        let payload = match self {
+            PacketsInfoTypesEnum::Tcp(p) => &p.payload,
+            PacketsInfoTypesEnum::Udp(p) => &p.payload,
+            _ => return String::from("-"),
+        };
+        if payload.is_empty() {
+            return String::from("-");
+        }
+        payload.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// The packet log line.
+    pub fn raw_str(&self) -> &str {
+        match self {
+            PacketsInfoTypesEnum::Arp(p) => &p.raw_str,
+            PacketsInfoTypesEnum::Tcp(p) => &p.raw_str,
+            PacketsInfoTypesEnum::Udp(p) => &p.raw_str,
+            PacketsInfoTypesEnum::Icmp(p) => &p.raw_str,
+            PacketsInfoTypesEnum::Icmp6(p) => &p.raw_str,
+            PacketsInfoTypesEnum::Igmp(p) => &p.raw_str,
+            PacketsInfoTypesEnum::Other(p) => &p.raw_str,
+        }
+    }
+
+    /// Length of the frame on the wire, before snap length truncation.
+    pub fn orig_len(&self) -> usize {
+        match self {
+            PacketsInfoTypesEnum::Arp(p) => p.orig_len,
+            PacketsInfoTypesEnum::Tcp(p) => p.orig_len,
+            PacketsInfoTypesEnum::Udp(p) => p.orig_len,
+            PacketsInfoTypesEnum::Icmp(p) => p.orig_len,
+            PacketsInfoTypesEnum::Icmp6(p) => p.orig_len,
+            PacketsInfoTypesEnum::Igmp(p) => p.orig_len,
+            PacketsInfoTypesEnum::Other(p) => p.orig_len,
+        }
+    }
+
+    /// Name of the interface the packet was captured on.
+    pub fn interface_name(&self) -> &str {
+        match self {
+            PacketsInfoTypesEnum::Arp(p) => &p.interface_name,
+            PacketsInfoTypesEnum::Tcp(p) => &p.interface_name,
+            PacketsInfoTypesEnum::Udp(p) => &p.interface_name,
+            PacketsInfoTypesEnum::Icmp(p) => &p.interface_name,
+            PacketsInfoTypesEnum::Icmp6(p) => &p.interface_name,
+            PacketsInfoTypesEnum::Igmp(p) => &p.interface_name,
+            PacketsInfoTypesEnum::Other(p) => &p.interface_name,
+        }
+    }
+
+    /// Source and destination address; the sender and target IP for ARP,
+    /// `None` for frames without an IP layer.
+    pub fn addresses(&self) -> Option<(IpAddr, IpAddr)> {
+        match self {
+            PacketsInfoTypesEnum::Arp(p) => {
+                Some((IpAddr::V4(p.source_ip), IpAddr::V4(p.destination_ip)))
+            }
+            PacketsInfoTypesEnum::Tcp(p) => Some((p.source, p.destination)),
+            PacketsInfoTypesEnum::Udp(p) => Some((p.source, p.destination)),
+            PacketsInfoTypesEnum::Icmp(p) => Some((p.source, p.destination)),
+            PacketsInfoTypesEnum::Icmp6(p) => Some((p.source, p.destination)),
+            PacketsInfoTypesEnum::Igmp(p) => Some((p.source, p.destination)),
+            PacketsInfoTypesEnum::Other(_) => None,
+        }
+    }
+
+    /// TTL/hop limit and DSCP of IP based packets, `None` for ARP and other frames.
+    pub fn ip_meta(&self) -> Option<IpMeta> {
+        match self {
+            PacketsInfoTypesEnum::Arp(_) | PacketsInfoTypesEnum::Other(_) => None,
+            PacketsInfoTypesEnum::Tcp(p) => Some(p.ip),
+            PacketsInfoTypesEnum::Udp(p) => Some(p.ip),
+            PacketsInfoTypesEnum::Icmp(p) => Some(p.ip),
+            PacketsInfoTypesEnum::Icmp6(p) => Some(p.ip),
+            PacketsInfoTypesEnum::Igmp(p) => Some(p.ip),
+        }
+    }
+
+    /// VLAN the frame was tagged with, `None` for untagged frames.
+    pub fn vlan(&self) -> Option<Vlan> {
+        match self {
+            PacketsInfoTypesEnum::Arp(p) => p.vlan,
+            PacketsInfoTypesEnum::Tcp(p) => p.vlan,
+            PacketsInfoTypesEnum::Udp(p) => p.vlan,
+            PacketsInfoTypesEnum::Icmp(p) => p.vlan,
+            PacketsInfoTypesEnum::Icmp6(p) => p.vlan,
+            PacketsInfoTypesEnum::Igmp(p) => p.vlan,
+            PacketsInfoTypesEnum::Other(p) => p.vlan,
+        }
+    }
+
+    /// Record the VLAN, also in the text the filter and the CSV export see.
+    pub fn tag_vlan(&mut self, vlan: Vlan) {
+        let (field, raw_str) = match self {
+            PacketsInfoTypesEnum::Arp(p) => (&mut p.vlan, &mut p.raw_str),
+            PacketsInfoTypesEnum::Tcp(p) => (&mut p.vlan, &mut p.raw_str),
+            PacketsInfoTypesEnum::Udp(p) => (&mut p.vlan, &mut p.raw_str),
+            PacketsInfoTypesEnum::Icmp(p) => (&mut p.vlan, &mut p.raw_str),
+            PacketsInfoTypesEnum::Icmp6(p) => (&mut p.vlan, &mut p.raw_str),
+            PacketsInfoTypesEnum::Igmp(p) => (&mut p.vlan, &mut p.raw_str),
+            PacketsInfoTypesEnum::Other(p) => (&mut p.vlan, &mut p.raw_str),
+        };
+        *field = Some(vlan);
+        raw_str.push_str(&format!("; [{}]", vlan));
+    }
+
+    /// Packet type list the packet is stored in.
+    pub fn packet_type(&self) -> PacketTypeEnum {
+        match self {
+            PacketsInfoTypesEnum::Arp(_) => PacketTypeEnum::Arp,
+            PacketsInfoTypesEnum::Tcp(_) => PacketTypeEnum::Tcp,
+            PacketsInfoTypesEnum::Udp(_) => PacketTypeEnum::Udp,
+            PacketsInfoTypesEnum::Icmp(_) => PacketTypeEnum::Icmp,
+            PacketsInfoTypesEnum::Icmp6(_) => PacketTypeEnum::Icmp6,
+            PacketsInfoTypesEnum::Igmp(_) => PacketTypeEnum::Igmp,
+            PacketsInfoTypesEnum::Other(_) => PacketTypeEnum::Other,
+        }
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.orig_len() > self.raw_bytes().len()
+    }
 }
 
 #[derive(Default, Clone, Copy, Display, FromRepr, EnumIter, EnumCount, PartialEq, Debug)]
@@ -114,9 +573,60 @@ pub enum TabsEnum {
     Ports,
     #[strum(to_string = "Traffic")]
     Traffic,
+    #[strum(to_string = "Anomalies")]
+    Anomalies,
 }
 
-#[derive(Default, Clone, Copy, Display, FromRepr, EnumIter, EnumCount, PartialEq, Debug)]
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    #[strum(to_string = "info")]
+    Info,
+    #[strum(to_string = "warning")]
+    Warning,
+    #[strum(to_string = "critical")]
+    Critical,
+}
+
+#[derive(Clone, Copy, Debug, Display, PartialEq)]
+pub enum AnomalyCategory {
+    #[strum(to_string = "ARP spoofing")]
+    ArpSpoofing,
+    #[strum(to_string = "IP conflict")]
+    IpConflict,
+    #[strum(to_string = "NDP spoofing")]
+    NdpSpoofing,
+    #[strum(to_string = "retransmission")]
+    Retransmission,
+    #[strum(to_string = "low TTL")]
+    LowTtl,
+    #[strum(to_string = "oversized frame")]
+    OversizedFrame,
+    #[strum(to_string = "drops")]
+    Drops,
+}
+
+/// Something suspicious noticed by one of the detectors, collected on the
+/// Anomalies tab.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Anomaly {
+    pub time: DateTime<Local>,
+    pub category: AnomalyCategory,
+    pub severity: Severity,
+    pub detail: String,
+}
+
+impl Anomaly {
+    pub fn new(category: AnomalyCategory, severity: Severity, detail: String) -> Self {
+        Self {
+            time: Local::now(),
+            category,
+            severity,
+            detail,
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, Display, FromRepr, EnumIter, EnumCount, PartialEq, Debug, Deserialize)]
 pub enum PacketTypeEnum {
     #[default]
     #[strum(to_string = "All")]
@@ -131,9 +641,13 @@ pub enum PacketTypeEnum {
     Icmp,
     #[strum(to_string = "ICMP6")]
     Icmp6,
+    #[strum(to_string = "IGMP")]
+    Igmp,
+    #[strum(to_string = "Other")]
+    Other,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum PortsScanState {
     Waiting,
     Scanning,
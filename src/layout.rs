@@ -43,3 +43,53 @@ pub fn get_horizontal_layout(area: Rect) -> HorizontalLayoutRects {
         right: layout[1],
     }
 }
+
+/// Input box in the top right corner of `area`, `max_width` wide but never
+/// more than half of `area`, so narrow terminals get a shorter box instead of
+/// one starting left of the screen.
+pub fn get_input_rect(area: Rect, max_width: u16) -> Rect {
+    let width = max_width.min(area.width / 2);
+    let height = area.height.saturating_sub(1).min(3);
+    // -- one cell in from the top right border, as far as `area` has one
+    let x = area.right().saturating_sub(width + 1).max(area.x);
+    let y = area.y + area.height.min(1);
+    Rect::new(x, y, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inside(rect: Rect, area: Rect) -> bool {
+        rect.x >= area.x
+            && rect.y >= area.y
+            && rect.right() <= area.right()
+            && rect.bottom() <= area.bottom()
+    }
+
+    #[test]
+    fn input_fits_a_roomy_area() {
+        let area = Rect::new(0, 0, 120, 40);
+        assert_eq!(get_input_rect(area, 30), Rect::new(89, 1, 30, 3));
+    }
+
+    #[test]
+    fn input_is_clamped_inside_narrow_and_short_areas() {
+        let areas = [
+            Rect::new(0, 0, 10, 40),
+            Rect::new(5, 2, 10, 40),
+            Rect::new(0, 0, 1, 40),
+            Rect::new(7, 0, 0, 40),
+            Rect::new(0, 0, 120, 2),
+            Rect::new(0, 3, 120, 1),
+            Rect::new(4, 6, 0, 0),
+            Rect::new(u16::MAX - 10, u16::MAX - 2, 10, 2),
+        ];
+        for area in areas {
+            let rect = get_input_rect(area, 30);
+            assert!(inside(rect, area), "{:?} outside {:?}", rect, area);
+        }
+        assert_eq!(get_input_rect(Rect::new(0, 0, 10, 40), 30), Rect::new(4, 1, 5, 3));
+        assert_eq!(get_input_rect(Rect::new(0, 0, 120, 2), 30).height, 1);
+    }
+}
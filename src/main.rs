@@ -44,6 +44,12 @@
 //!
 //! # Customize tick and frame rates
 //! sudo netscanner --tick-rate 2.0 --frame-rate 30.0
+//!
+//! # Start on the interface in a given subnet
+//! sudo netscanner --subnet 192.168.1.0/24
+//!
+//! # Stream DNS packets as JSON lines instead of showing the TUI
+//! sudo netscanner --auto --headless --json --filter ":53" | jq .
 //! ```
 //!
 //! # Error Handling
@@ -60,8 +66,11 @@ pub mod config;
 pub mod dns_cache;
 pub mod mode;
 pub mod privilege;
+pub mod services;
+pub mod session;
 pub mod tui;
 pub mod utils;
+pub mod vendors;
 pub mod enums;
 pub mod layout;
 pub mod widgets;
@@ -71,6 +80,7 @@ use cli::Cli;
 use color_eyre::eyre::Result;
 
 use crate::{
+  action::Action,
   app::App,
   utils::{initialize_logging, initialize_panic_handler},
 };
@@ -82,7 +92,7 @@ use crate::{
 /// 1. **Logging Setup**: Configures the logging system for diagnostics
 /// 2. **Panic Handler**: Installs a custom panic handler for better error reporting
 /// 3. **Privilege Check**: Warns if the application lacks network privileges (non-fatal)
-/// 4. **CLI Parsing**: Parses command-line arguments for tick/frame rates
+/// 4. **CLI Parsing**: Parses command-line arguments for tick/frame rates and interface selection
 /// 5. **Application Run**: Creates and runs the main application
 ///
 /// # Errors
@@ -101,8 +111,8 @@ async fn tokio_main() -> Result<()> {
 
   initialize_panic_handler()?;
 
-  // Warn if not running with privileges (non-fatal, operations will fail with better errors)
-  if !privilege::has_network_privileges() {
+  // Warn if packet capture is unavailable (non-fatal, capture features are disabled in the UI)
+  if !privilege::can_capture() {
     eprintln!("WARNING: Running without elevated privileges.");
     eprintln!("Some network operations may fail.");
     eprintln!("For full functionality, run with sudo or set appropriate capabilities.");
@@ -110,8 +120,25 @@ async fn tokio_main() -> Result<()> {
   }
 
   let args = Cli::parse();
-  let mut app = App::new(args.tick_rate, args.frame_rate)?;
-  app.run().await?;
+  let mut app = App::new(
+    args.tick_rate,
+    args.frame_rate,
+    args.interface_select(),
+    args.filter.clone(),
+    args.json(),
+  )?;
+  args.apply_capture_limits(&mut app.config.capture);
+  if let Some(path) = args.session.clone() {
+    app.action_tx.try_send(Action::LoadSession(path))?;
+  }
+  if let Some(path) = args.read_pcap.clone() {
+    app.action_tx.try_send(Action::ReadPcap(path))?;
+  }
+  if args.headless() {
+    app.run_headless().await?;
+  } else {
+    app.run().await?;
+  }
 
   Ok(())
 }
@@ -94,6 +94,55 @@ pub fn has_network_privileges() -> bool {
     true
 }
 
+/// Probes whether a raw packet capture handle can actually be opened.
+///
+/// Unlike [`has_network_privileges`], this also succeeds for unprivileged users
+/// that were granted `CAP_NET_RAW` (Linux) or read access to `/dev/bpf*` (macOS),
+/// and fails for root inside restricted containers.
+///
+/// # Platform Behavior
+///
+/// ## Linux
+/// Opens and immediately closes an `AF_PACKET` raw socket.
+///
+/// ## macOS / BSD
+/// Opens and immediately closes the first available `/dev/bpf*` device.
+///
+/// ## Windows
+/// Always returns `true`, see [`has_network_privileges`].
+#[cfg(target_os = "linux")]
+pub fn can_capture() -> bool {
+    let protocol = (libc::ETH_P_ALL as u16).to_be() as libc::c_int;
+    let fd = unsafe { libc::socket(libc::AF_PACKET, libc::SOCK_RAW, protocol) };
+    if fd < 0 {
+        return false;
+    }
+    unsafe { libc::close(fd) };
+    true
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn can_capture() -> bool {
+    for i in 0..256 {
+        match std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!("/dev/bpf{}", i))
+        {
+            Ok(_) => return true,
+            // -- device in use by another capture, try the next one
+            Err(e) if e.raw_os_error() == Some(libc::EBUSY) => continue,
+            Err(_) => return false,
+        }
+    }
+    false
+}
+
+#[cfg(windows)]
+pub fn can_capture() -> bool {
+    has_network_privileges()
+}
+
 /// Generates a platform-specific error message for privilege-related failures.
 ///
 /// This provides users with clear, actionable instructions for running the
@@ -0,0 +1,57 @@
+//! Service names of well-known ports, shared by the packet log, the Ports tab
+//! and the export.
+//!
+//! The IANA service name registry embedded in `port_desc` is parsed into hash
+//! maps once, on first use, so every lookup after that is a map access.
+
+use lazy_static::lazy_static;
+use port_desc::{PortDescription, TransportProtocol};
+
+lazy_static! {
+    static ref PORT_DESCRIPTION: Option<PortDescription> = match PortDescription::default() {
+        Ok(description) => Some(description),
+        Err(e) => {
+            log::warn!("Port service names unavailable: {}", e);
+            None
+        }
+    };
+}
+
+fn service_name(port: u16, protocol: TransportProtocol) -> Option<&'static str> {
+    let name = PORT_DESCRIPTION.as_ref()?.get_port_service_name(port, protocol);
+    (!name.is_empty()).then_some(name)
+}
+
+/// Registered name of a TCP port, e.g. `https` for 443.
+pub fn tcp_service(port: u16) -> Option<&'static str> {
+    service_name(port, TransportProtocol::Tcp)
+}
+
+/// Registered name of a UDP port, e.g. `mdns` for 5353.
+pub fn udp_service(port: u16) -> Option<&'static str> {
+    service_name(port, TransportProtocol::Udp)
+}
+
+/// `443(https)` for a port with a service name, just the port otherwise.
+pub fn port_label(port: u16, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{}({})", port, name),
+        None => port.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn well_known_ports() {
+        assert_eq!(tcp_service(443), Some("https"));
+        assert_eq!(tcp_service(22), Some("ssh"));
+        assert_eq!(udp_service(53), Some("domain"));
+        assert_eq!(udp_service(5353), Some("mdns"));
+        assert_eq!(tcp_service(0), None);
+        assert_eq!(port_label(443, tcp_service(443)), "443(https)");
+        assert_eq!(port_label(0, tcp_service(0)), "0");
+    }
+}
@@ -0,0 +1,146 @@
+//! Session snapshots for offline inspection.
+//!
+//! A [`Session`] holds everything captured and scanned so far: discovered hosts,
+//! port scan results and the packets of every protocol. It is written as a
+//! single JSON file and can be loaded back later:
+//!
+//! - **Save**: `Action::SaveSession(path)` collects the data from the Discovery,
+//!   Ports and PacketDump components, like export does
+//! - **Load**: `Action::LoadSession(path)` reads the file and sends
+//!   `Action::SessionLoaded`, the components replace their data with it,
+//!   running host and port scans are stopped and packet capture stays paused
+//!   until the user resumes it
+//!
+//! Without an explicit path both use `session.json` in the `.netscanner` folder.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{discovery::ScannedIp, ports::ScannedIpPorts},
+    enums::PacketsInfoTypesEnum,
+    utils::get_netscanner_dir,
+};
+
+/// Bumped whenever the file layout changes incompatibly.
+pub const SESSION_VERSION: u32 = 1;
+
+const SESSION_FILE: &str = "session.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub version: u32,
+    pub saved_at: DateTime<Local>,
+    /// Interface the packets were captured on.
+    pub interface: Option<String>,
+    pub scanned_ips: Vec<ScannedIp>,
+    pub scanned_ports: Vec<ScannedIpPorts>,
+    /// Packets of all protocols, oldest first.
+    pub packets: Vec<(DateTime<Local>, PacketsInfoTypesEnum)>,
+}
+
+impl Session {
+    pub fn default_path() -> PathBuf {
+        PathBuf::from(get_netscanner_dir()).join(SESSION_FILE)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        serde_json::to_writer(file, self)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        let session: Session = serde_json::from_reader(file)?;
+        if session.version != SESSION_VERSION {
+            return Err(eyre!(
+                "Unsupported session file version {} in {}",
+                session.version,
+                path.display()
+            ));
+        }
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use chrono::TimeZone;
+    use pnet::packet::arp::ArpOperations;
+    use pnet::util::MacAddr;
+
+    use super::*;
+    use crate::enums::ARPPacketInfo;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("netscanner-{}-{}.json", name, std::process::id()))
+    }
+
+    fn session() -> Session {
+        let time = Local.timestamp_opt(1_700_000_000, 123_456_000).unwrap();
+        let arp = PacketsInfoTypesEnum::Arp(ARPPacketInfo {
+            interface_name: "eth0".to_string(),
+            source_mac: MacAddr::new(2, 0, 0, 0, 0, 1),
+            source_ip: Ipv4Addr::new(192, 168, 1, 1),
+            destination_mac: MacAddr::zero(),
+            destination_ip: Ipv4Addr::new(192, 168, 1, 10),
+            operation: ArpOperations::Reply,
+            source_vendor: Some("Acme".to_string()),
+            vlan: None,
+            raw_str: "ARP reply".to_string(),
+            raw_bytes: vec![0xff; 42],
+            orig_len: 60,
+        });
+        Session {
+            version: SESSION_VERSION,
+            saved_at: time,
+            interface: Some("eth0".to_string()),
+            scanned_ips: vec![ScannedIp {
+                ip: "192.168.1.1".to_string(),
+                ip_addr: "192.168.1.1".parse().unwrap(),
+                mac: "02:00:00:00:00:01".to_string(),
+                hostname: "router".to_string(),
+                vendor: "Acme".to_string(),
+                role: None,
+                first_seen: time,
+                last_seen: time,
+            }],
+            scanned_ports: vec![serde_json::from_str(
+                r#"{"ip": "192.168.1.1", "state": "Done", "hostname": "router", "ports": [22, 443]}"#,
+            )
+            .unwrap()],
+            packets: vec![(time, arp)],
+        }
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = temp_path("round-trip");
+        session().save(&path).unwrap();
+        let loaded = Session::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), session());
+    }
+
+    #[test]
+    fn other_versions_are_refused() {
+        let path = temp_path("version");
+        let session = Session {
+            version: SESSION_VERSION + 1,
+            ..session()
+        };
+        session.save(&path).unwrap();
+        let loaded = Session::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(loaded.unwrap_err().to_string().contains("Unsupported session file version"));
+    }
+}
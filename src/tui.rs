@@ -10,7 +10,8 @@
 //! It runs two concurrent loops:
 //!
 //! 1. **Event Collection Loop**: Captures keyboard, mouse, and resize events
-//! 2. **Timer Loops**: Generate Tick (logic updates) and Render (draw) events
+//! 2. **Timer Loops**: Generate Tick (logic updates) and Render (draw) events; the
+//!    render rate drops to `idle_frame_rate` while the app reports it is idle
 //!
 //! ```text
 //! ┌────────────────────────────────────────────────────┐
@@ -51,7 +52,8 @@
 //!
 //! The TUI uses a **bounded channel with capacity 100** for events. This prevents
 //! memory exhaustion during event bursts (e.g., window resize storms). If the
-//! buffer fills, events are silently dropped using `try_send`.
+//! buffer fills, events are dropped and counted; the app reads the count with
+//! [`Tui::take_dropped`] and warns when the UI falls behind.
 //!
 //! # Graceful Shutdown
 //!
@@ -62,7 +64,13 @@
 //! - Handles cleanup errors gracefully
 
 use std::{
+  io::Write,
   ops::{Deref, DerefMut},
+  process::{Command, Stdio},
+  sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc,
+  },
   time::Duration,
 };
 
@@ -73,13 +81,14 @@ use crossterm::{
     DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event as CrosstermEvent,
     KeyEvent, KeyEventKind, MouseEvent,
   },
+  style::Print,
   terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 };
 use futures::{FutureExt, StreamExt};
 use ratatui::backend::CrosstermBackend as Backend;
 use serde::{Deserialize, Serialize};
 use tokio::{
-  sync::mpsc::{self, Receiver, Sender},
+  sync::mpsc::{self, error::TrySendError, Receiver, Sender},
   task::JoinHandle,
 };
 use tokio_util::sync::CancellationToken;
@@ -140,6 +149,8 @@ pub enum Event {
 /// * `event_rx` - Receiver for terminal events
 /// * `event_tx` - Sender for terminal events (cloned to task)
 /// * `frame_rate` - Render updates per second
+/// * `idle_frame_rate` - Render updates per second while idle
+/// * `idle` - Set by the app when there has been no recent activity
 /// * `tick_rate` - Logic updates per second
 /// * `mouse` - Whether mouse capture is enabled
 /// * `paste` - Whether bracketed paste is enabled
@@ -150,11 +161,21 @@ pub struct Tui {
   pub event_rx: Receiver<Event>,
   pub event_tx: Sender<Event>,
   pub frame_rate: f64,
+  pub idle_frame_rate: f64,
+  pub idle: Arc<AtomicBool>,
+  pub dropped: Arc<AtomicU64>,
   pub tick_rate: f64,
   pub mouse: bool,
   pub paste: bool,
 }
 
+// -- a full channel drops the event, counted so the app can warn about it
+fn send_event(event_tx: &Sender<Event>, dropped: &AtomicU64, event: Event) {
+  if let Err(TrySendError::Full(_)) = event_tx.try_send(event) {
+    dropped.fetch_add(1, Ordering::Relaxed);
+  }
+}
+
 impl Tui {
   pub fn new() -> Result<Self> {
     let tick_rate = 4.0;
@@ -167,7 +188,20 @@ impl Tui {
     let task = tokio::spawn(async {});
     let mouse = false;
     let paste = false;
-    Ok(Self { terminal, task, cancellation_token, event_rx, event_tx, frame_rate, tick_rate, mouse, paste })
+    Ok(Self {
+      terminal,
+      task,
+      cancellation_token,
+      event_rx,
+      event_tx,
+      frame_rate,
+      idle_frame_rate: frame_rate,
+      idle: Arc::new(AtomicBool::new(false)),
+      dropped: Arc::new(AtomicU64::new(0)),
+      tick_rate,
+      mouse,
+      paste,
+    })
   }
 
   pub fn tick_rate(mut self, tick_rate: f64) -> Self {
@@ -180,6 +214,23 @@ impl Tui {
     self
   }
 
+  /// Frame rate used while [`Tui::set_idle`] is on. Defaults to `frame_rate`.
+  pub fn idle_frame_rate(mut self, idle_frame_rate: f64) -> Self {
+    self.idle_frame_rate = idle_frame_rate;
+    self
+  }
+
+  /// Switch between the normal and the idle frame rate. Leaving idle takes
+  /// effect at the next event or idle frame.
+  pub fn set_idle(&self, idle: bool) {
+    self.idle.store(idle, Ordering::Relaxed);
+  }
+
+  /// Events lost to a full channel since the last call.
+  pub fn take_dropped(&self) -> u64 {
+    self.dropped.swap(0, Ordering::Relaxed)
+  }
+
   pub fn mouse(mut self, mouse: bool) -> Self {
     self.mouse = mouse;
     self
@@ -193,6 +244,9 @@ impl Tui {
   pub fn start(&mut self) {
     let tick_delay = std::time::Duration::from_secs_f64(1.0 / self.tick_rate);
     let render_delay = std::time::Duration::from_secs_f64(1.0 / self.frame_rate);
+    let idle_render_delay = std::time::Duration::from_secs_f64(1.0 / self.idle_frame_rate);
+    let idle = self.idle.clone();
+    let dropped = self.dropped.clone();
     self.cancel();
     self.cancellation_token = CancellationToken::new();
     let _cancellation_token = self.cancellation_token.clone();
@@ -201,11 +255,18 @@ impl Tui {
       let mut reader = crossterm::event::EventStream::new();
       let mut tick_interval = tokio::time::interval(tick_delay);
       let mut render_interval = tokio::time::interval(render_delay);
+      let mut current_render_delay = render_delay;
       // Send init event; if this fails, the receiver is already dropped
       if _event_tx.try_send(Event::Init).is_err() {
         return;
       }
       loop {
+        // -- adapt the render rate to activity
+        let wanted_render_delay = if idle.load(Ordering::Relaxed) { idle_render_delay } else { render_delay };
+        if wanted_render_delay != current_render_delay {
+          current_render_delay = wanted_render_delay;
+          render_interval = tokio::time::interval(current_render_delay);
+        }
         let tick_delay = tick_interval.tick();
         let render_delay = render_interval.tick();
         let crossterm_event = reader.next().fuse();
@@ -219,38 +280,37 @@ impl Tui {
                 match evt {
                   CrosstermEvent::Key(key) => {
                     if key.kind == KeyEventKind::Press {
-                      // Ignore send errors - channel may be full or receiver dropped
-                      let _ = _event_tx.try_send(Event::Key(key));
+                      send_event(&_event_tx, &dropped, Event::Key(key));
                     }
                   },
                   CrosstermEvent::Mouse(mouse) => {
-                    let _ = _event_tx.try_send(Event::Mouse(mouse));
+                    send_event(&_event_tx, &dropped, Event::Mouse(mouse));
                   },
                   CrosstermEvent::Resize(x, y) => {
-                    let _ = _event_tx.try_send(Event::Resize(x, y));
+                    send_event(&_event_tx, &dropped, Event::Resize(x, y));
                   },
                   CrosstermEvent::FocusLost => {
-                    let _ = _event_tx.try_send(Event::FocusLost);
+                    send_event(&_event_tx, &dropped, Event::FocusLost);
                   },
                   CrosstermEvent::FocusGained => {
-                    let _ = _event_tx.try_send(Event::FocusGained);
+                    send_event(&_event_tx, &dropped, Event::FocusGained);
                   },
                   CrosstermEvent::Paste(s) => {
-                    let _ = _event_tx.try_send(Event::Paste(s));
+                    send_event(&_event_tx, &dropped, Event::Paste(s));
                   },
                 }
               }
               Some(Err(_)) => {
-                let _ = _event_tx.try_send(Event::Error);
+                send_event(&_event_tx, &dropped, Event::Error);
               }
               None => {},
             }
           },
           _ = tick_delay => {
-              let _ = _event_tx.try_send(Event::Tick);
+              send_event(&_event_tx, &dropped, Event::Tick);
           },
           _ = render_delay => {
-              let _ = _event_tx.try_send(Event::Render);
+              send_event(&_event_tx, &dropped, Event::Render);
           },
         }
       }
@@ -294,14 +354,7 @@ impl Tui {
     self.stop()?;
     if crossterm::terminal::is_raw_mode_enabled()? {
       self.flush()?;
-      if self.paste {
-        crossterm::execute!(io(), DisableBracketedPaste)?;
-      }
-      if self.mouse {
-        crossterm::execute!(io(), DisableMouseCapture)?;
-      }
-      crossterm::execute!(io(), LeaveAlternateScreen, cursor::Show)?;
-      crossterm::terminal::disable_raw_mode()?;
+      restore()?;
     }
     Ok(())
   }
@@ -322,11 +375,59 @@ impl Tui {
     Ok(())
   }
 
+  /// Put `text` on the clipboard with an OSC 52 sequence. The terminal does
+  /// the copying, so it works over SSH too, if the terminal supports it.
+  /// Terminals without OSC 52 ignore the sequence, so on a local desktop the
+  /// text also goes to its clipboard tool; returns the tool that took it.
+  pub fn copy(&mut self, text: &str) -> Result<Option<&'static str>> {
+    crossterm::execute!(io(), Print(osc52(text)))?;
+    Ok(clipboard_tool(text))
+  }
+
   pub async fn next(&mut self) -> Option<Event> {
     self.event_rx.recv().await
   }
 }
 
+// -- tried in order, each only when its desktop is there
+fn clipboard_tool(text: &str) -> Option<&'static str> {
+  let x11 = std::env::var_os("DISPLAY").is_some();
+  let tools: [(bool, &'static str, &[&str]); 4] = [
+    (cfg!(target_os = "macos"), "pbcopy", &[]),
+    (std::env::var_os("WAYLAND_DISPLAY").is_some(), "wl-copy", &[]),
+    (x11, "xclip", &["-selection", "clipboard"]),
+    (x11, "xsel", &["--clipboard", "--input"]),
+  ];
+  tools.into_iter().filter(|(available, ..)| *available).find_map(|(_, tool, args)| {
+    let mut child = Command::new(tool)
+      .args(args)
+      .stdin(Stdio::piped())
+      .stdout(Stdio::null())
+      .stderr(Stdio::null())
+      .spawn()
+      .ok()?;
+    child.stdin.take()?.write_all(text.as_bytes()).ok()?;
+    child.wait().ok()?.success().then_some(tool)
+  })
+}
+
+// -- OSC 52 takes the text base64 encoded
+fn osc52(text: &str) -> String {
+  const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+  let mut encoded = String::new();
+  for chunk in text.as_bytes().chunks(3) {
+    let n = chunk.iter().enumerate().fold(0u32, |n, (i, b)| n | ((*b as u32) << (16 - 8 * i)));
+    for i in 0..4 {
+      if i <= chunk.len() {
+        encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+      } else {
+        encoded.push('=');
+      }
+    }
+  }
+  format!("\x1b]52;c;{}\x07", encoded)
+}
+
 impl Deref for Tui {
   type Target = ratatui::Terminal<Backend<IO>>;
 
@@ -341,6 +442,24 @@ impl DerefMut for Tui {
   }
 }
 
+/// Put the terminal back into cooked mode and leave the alternate screen.
+///
+/// Unlike [`Tui::exit`] this needs no `Tui` instance (and no tokio runtime),
+/// so the panic hook can call it from any thread before printing the report.
+pub fn restore() -> Result<()> {
+  if crossterm::terminal::is_raw_mode_enabled()? {
+    crossterm::execute!(
+      io(),
+      DisableBracketedPaste,
+      DisableMouseCapture,
+      LeaveAlternateScreen,
+      cursor::Show
+    )?;
+    crossterm::terminal::disable_raw_mode()?;
+  }
+  Ok(())
+}
+
 impl Drop for Tui {
   fn drop(&mut self) {
     if let Err(e) = self.exit() {
@@ -348,3 +467,25 @@ impl Drop for Tui {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn osc52_encodes_rfc_4648_vectors() {
+    let vectors = [
+      ("", ""),
+      ("f", "Zg=="),
+      ("fo", "Zm8="),
+      ("foo", "Zm9v"),
+      ("foob", "Zm9vYg=="),
+      ("fooba", "Zm9vYmE="),
+      ("foobar", "Zm9vYmFy"),
+    ];
+    for (text, encoded) in vectors {
+      assert_eq!(osc52(text), format!("\x1b]52;c;{}\x07", encoded), "{:?}", text);
+    }
+    assert_eq!(osc52("\u{ff}\u{fe}"), "\x1b]52;c;w7/Dvg==\x07");
+  }
+}
@@ -7,6 +7,7 @@ use color_eyre::eyre::Result;
 use directories::ProjectDirs;
 use ipnetwork::Ipv6Network;
 use lazy_static::lazy_static;
+use ratatui::{buffer::Buffer, style::Color};
 use std::net::{Ipv4Addr, Ipv6Addr};
 use tracing::error;
 use tracing_error::ErrorLayer;
@@ -30,6 +31,12 @@ lazy_static! {
             .map(PathBuf::from);
     pub static ref LOG_ENV: String = format!("{}_LOGLEVEL", PROJECT_NAME.clone());
     pub static ref LOG_FILE: String = format!("{}.log", env!("CARGO_PKG_NAME"));
+    /// Colors are off when `NO_COLOR` is set (https://no-color.org) or the terminal is dumb.
+    pub static ref COLOR_ENABLED: bool = {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        let dumb_term = std::env::var("TERM").is_ok_and(|t| t == "dumb");
+        !no_color && !dumb_term
+    };
 }
 
 fn project_directory() -> Option<ProjectDirs> {
@@ -107,6 +114,24 @@ impl<T> MaxSizeVec<T> {
         self.deque.push_front(item);
     }
 
+    /// Change the maximum length, dropping the oldest items if it shrinks.
+    pub fn resize(&mut self, max_len: usize) {
+        self.deque.truncate(max_len);
+        self.deque.shrink_to(max_len);
+        self.max_len = max_len;
+    }
+
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.deque.retain(f);
+    }
+
     pub fn get_deque(&self) -> &VecDeque<T> {
         &self.deque
     }
@@ -150,10 +175,9 @@ pub fn initialize_panic_handler() -> Result<()> {
         .into_hooks();
     eyre_hook.install()?;
     std::panic::set_hook(Box::new(move |panic_info| {
-        if let Ok(mut t) = crate::tui::Tui::new() {
-            if let Err(r) = t.exit() {
-                error!("Unable to exit Terminal: {:?}", r);
-            }
+        // -- restore the shell before anything is printed
+        if let Err(r) = crate::tui::restore() {
+            error!("Unable to restore Terminal: {:?}", r);
         }
 
         #[cfg(not(debug_assertions))]
@@ -186,6 +210,64 @@ pub fn initialize_panic_handler() -> Result<()> {
     Ok(())
 }
 
+/// Reset foreground and background colors of a rendered frame, keeping text
+/// modifiers (bold, reversed, ...) so highlighted keys stay distinguishable.
+pub fn strip_colors(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        cell.set_fg(Color::Reset).set_bg(Color::Reset);
+    }
+}
+
+/// The `.netscanner` folder in the invoking user's home, also under sudo.
+/// Exports and saved filters live here.
+#[cfg(target_os = "linux")]
+pub fn get_netscanner_dir() -> String {
+    let mut home_dir = String::from("/root");
+    if let Some(h_dir) = std::env::var_os("HOME") {
+        if let Some(dir_str) = h_dir.to_str() {
+            home_dir = String::from(dir_str);
+        }
+    }
+    if let Some(sudo_user) = std::env::var_os("SUDO_USER") {
+        if let Some(user_str) = sudo_user.to_str() {
+            home_dir = format!("/home/{}", user_str);
+        }
+    }
+    format!("{}/.netscanner", home_dir)
+}
+
+#[cfg(target_os = "macos")]
+pub fn get_netscanner_dir() -> String {
+    let mut home_dir = String::from("/root");
+    if let Some(h_dir) = std::env::var_os("HOME") {
+        if let Some(dir_str) = h_dir.to_str() {
+            home_dir = String::from(dir_str);
+        }
+    }
+    if let Some(sudo_user) = std::env::var_os("SUDO_USER") {
+        if let Some(user_str) = sudo_user.to_str() {
+            home_dir = format!("/Users/{}", user_str);
+        }
+    }
+    format!("{}/.netscanner", home_dir)
+}
+
+#[cfg(target_os = "windows")]
+pub fn get_netscanner_dir() -> String {
+    let mut home_dir = String::from("C:\\Users\\Administrator");
+    if let Some(h_dir) = std::env::var_os("USERPROFILE") {
+        if let Some(dir_str) = h_dir.to_str() {
+            home_dir = String::from(dir_str);
+        }
+    }
+    if let Some(sudo_user) = std::env::var_os("SUDO_USER") {
+        if let Some(user_str) = sudo_user.to_str() {
+            home_dir = format!("C:\\Users\\{}", user_str);
+        }
+    }
+    format!("{}\\.netscanner", home_dir)
+}
+
 pub fn get_data_dir() -> PathBuf {
     let directory = if let Some(s) = DATA_FOLDER.clone() {
         s
@@ -0,0 +1,45 @@
+//! Vendors of MAC addresses from the OUI database, shared by the Discovery
+//! tab and the packet log so the database is in memory once.
+//!
+//! The database embedded in `mac_oui` is parsed on first use; [`load`] does
+//! it up front so the first lookup from the capture thread doesn't pay for it.
+
+use lazy_static::lazy_static;
+use mac_oui::Oui;
+
+lazy_static! {
+    static ref OUI: Option<Oui> = match Oui::default() {
+        Ok(oui) => Some(oui),
+        Err(e) => {
+            log::warn!("MAC vendor database unavailable: {}", e);
+            None
+        }
+    };
+}
+
+/// Parse the database now rather than on the first lookup.
+pub fn load() {
+    lazy_static::initialize(&OUI);
+}
+
+/// Company the MAC address block of `mac` is registered to, `None` for
+/// unknown blocks and addresses that don't parse.
+pub fn vendor(mac: &str) -> Option<&'static str> {
+    match OUI.as_ref()?.lookup_by_mac(mac) {
+        Ok(Some(entry)) if !entry.company_name.is_empty() => Some(entry.company_name.as_str()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_and_unknown_blocks() {
+        assert!(vendor("3c:22:fb:0a:bc:de").is_some_and(|v| v.contains("Apple")));
+        assert_eq!(vendor("00:00:00:00:00:00"), vendor("00:00:00:11:22:33"));
+        assert_eq!(vendor("02:00:00:00:00:01"), None);
+        assert_eq!(vendor("not a mac"), None);
+    }
+}